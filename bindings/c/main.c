@@ -470,7 +470,7 @@ cleanup6:
       packet_payloads_t response = get_indexed_payload(message_returns, i);
       printf("  (%zu) public: '%s'\n", i, response.public_payload.ptr);
       printf("  (%zu) masked: '%s'\n", i, response.masked_payload.ptr);
-      //`get_indexed_payload` does not allocate, no need to drop `response`
+      drop_payloads(response);
     }
 
 cleanup7:
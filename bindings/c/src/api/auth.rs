@@ -1,8 +1,6 @@
-use iota_streams::app_channels::api::tangle::futures::TryStreamExt;
-
 use super::*;
 
-pub type Author = iota_streams::app_channels::api::tangle::Author<TransportWrap>;
+pub type Author = blocking::Author<TransportWrap, RuntimeExecutor>;
 
 /// Generate a new Author Instance
 #[no_mangle]
@@ -20,7 +18,7 @@ pub unsafe extern "C" fn auth_new(
     CStr::from_ptr(c_seed).to_str().map_or(Err::BadArgument, |seed| {
         transport.as_ref().map_or(Err::NullArgument, |tsp| {
             c_author.as_mut().map_or(Err::NullArgument, |author| {
-                let user = Author::new(seed, channel_impl, tsp.clone());
+                let user = Author::with_executor(tangle::Author::new(seed, channel_impl, tsp.clone()), RuntimeExecutor);
                 *author = safe_into_mut_ptr(user);
                 Err::Ok
             })
@@ -45,10 +43,13 @@ pub unsafe extern "C" fn auth_recover(
         c_ann_address.as_ref().map_or(Err::NullArgument, |addr| {
             transport.as_ref().map_or(Err::NullArgument, |tsp| {
                 c_author.as_mut().map_or(Err::NullArgument, |author| {
-                    run_async(Author::recover(seed, addr, channel_impl, tsp.clone())).map_or(Err::OperationFailed, |user| {
-                        *author = safe_into_mut_ptr(user);
-                        Err::Ok
-                    })
+                    Author::recover_with_executor(seed, addr, channel_impl, tsp.clone(), RuntimeExecutor).map_or(
+                        Err::OperationFailed,
+                        |user| {
+                            *author = safe_into_mut_ptr(user);
+                            Err::Ok
+                        },
+                    )
                 })
             })
         })
@@ -67,17 +68,22 @@ pub unsafe extern "C" fn auth_import(
         return Err::NullArgument;
     }
 
-    CStr::from_ptr(c_password).to_str().map_or(Err::BadArgument, |password| {
-        transport.as_ref().map_or(Err::NullArgument, |tsp| {
-            c_author.as_mut().map_or(Err::NullArgument, |author| {
-                let bytes_vec: Vec<_> = buffer.into();
-                run_async(Author::import(&bytes_vec, password, tsp.clone())).map_or(Err::OperationFailed, |user| {
-                    *author = safe_into_mut_ptr(user);
-                    Err::Ok
+    CStr::from_ptr(c_password)
+        .to_str()
+        .map_or(Err::BadArgument, |password| {
+            transport.as_ref().map_or(Err::NullArgument, |tsp| {
+                c_author.as_mut().map_or(Err::NullArgument, |author| {
+                    let bytes_vec: Vec<_> = buffer.into();
+                    Author::import_with_executor(&bytes_vec, password, tsp.clone(), RuntimeExecutor).map_or(
+                        Err::OperationFailed,
+                        |user| {
+                            *author = safe_into_mut_ptr(user);
+                            Err::Ok
+                        },
+                    )
                 })
             })
         })
-    })
 }
 
 #[no_mangle]
@@ -86,16 +92,18 @@ pub unsafe extern "C" fn auth_export(buf: *mut Buffer, c_author: *mut Author, c_
         return Err::NullArgument;
     }
 
-    CStr::from_ptr(c_password).to_str().map_or(Err::BadArgument, |password| {
-        c_author.as_ref().map_or(Err::NullArgument, |user| {
-            buf.as_mut().map_or(Err::NullArgument, |buf| {
-                run_async(user.export(password)).map_or(Err::OperationFailed, |bytes| {
-                    *buf = bytes.into();
-                    Err::Ok
+    CStr::from_ptr(c_password)
+        .to_str()
+        .map_or(Err::BadArgument, |password| {
+            c_author.as_ref().map_or(Err::NullArgument, |user| {
+                buf.as_mut().map_or(Err::NullArgument, |buf| {
+                    user.export(password).map_or(Err::OperationFailed, |bytes| {
+                        *buf = bytes.into();
+                        Err::Ok
+                    })
                 })
             })
         })
-    })
 }
 
 #[no_mangle]
@@ -154,7 +162,7 @@ pub unsafe extern "C" fn auth_get_public_key(pk: *mut *const PublicKey, user: *c
 pub unsafe extern "C" fn auth_send_announce(addr: *mut *const Address, user: *mut Author) -> Err {
     user.as_mut().map_or(Err::NullArgument, |user| {
         addr.as_mut().map_or(Err::NullArgument, |addr| {
-            run_async(user.send_announce()).map_or(Err::OperationFailed, |a| {
+            user.send_announce().map_or(Err::OperationFailed, |a| {
                 *addr = safe_into_ptr(a);
                 Err::Ok
             })
@@ -167,7 +175,7 @@ pub unsafe extern "C" fn auth_send_announce(addr: *mut *const Address, user: *mu
 pub unsafe extern "C" fn auth_receive_subscribe(user: *mut Author, link: *const Address) -> Err {
     user.as_mut().map_or(Err::NullArgument, |user| {
         link.as_ref().map_or(Err::NullArgument, |link| {
-            run_async(user.receive_subscribe(link)).map_or(Err::OperationFailed, |_| Err::Ok)
+            user.receive_subscribe(link).map_or(Err::OperationFailed, |_| Err::Ok)
         })
     })
 }
@@ -177,7 +185,7 @@ pub unsafe extern "C" fn auth_receive_subscribe(user: *mut Author, link: *const
 pub unsafe extern "C" fn auth_receive_unsubscribe(user: *mut Author, link: *const Address) -> Err {
     user.as_mut().map_or(Err::NullArgument, |user| {
         link.as_ref().map_or(Err::NullArgument, |link| {
-            run_async(user.receive_unsubscribe(link)).map_or(Err::OperationFailed, |_| Err::Ok)
+            user.receive_unsubscribe(link).map_or(Err::OperationFailed, |_| Err::Ok)
         })
     })
 }
@@ -199,7 +207,7 @@ pub unsafe extern "C" fn auth_send_keyload(
                         let pks = ke_pks.into_iter().copied().map(Into::<Identifier>::into);
                         let psks = psk_ids.into_iter().copied().map(Into::<Identifier>::into);
                         let identifiers: Vec<Identifier> = pks.chain(psks).collect();
-                        run_async(user.send_keyload(link_to, &identifiers))
+                        user.send_keyload(link_to, &identifiers)
                             .map_or(Err::OperationFailed, |response| {
                                 *r = response.into();
                                 Err::Ok
@@ -221,7 +229,7 @@ pub unsafe extern "C" fn auth_send_keyload_for_everyone(
     r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             link_to.as_ref().map_or(Err::NullArgument, |link_to| {
-                run_async(user.send_keyload_for_everyone(link_to))
+                user.send_keyload_for_everyone(link_to)
                     .map_or(Err::OperationFailed, |response| {
                         *r = response.into();
                         Err::Ok
@@ -247,24 +255,24 @@ pub unsafe extern "C" fn auth_send_tagged_packet(
             link_to
                 .into_seq_link(user.is_multi_branching())
                 .map_or(Err::NullArgument, |link_to| {
-                    let public_payload = Bytes(Vec::from_raw_parts(
+                    let public_payload = Bytes::from(Vec::from_raw_parts(
                         public_payload_ptr as *mut u8,
                         public_payload_size,
                         public_payload_size,
                     ));
-                    let masked_payload = Bytes(Vec::from_raw_parts(
+                    let masked_payload = Bytes::from(Vec::from_raw_parts(
                         masked_payload_ptr as *mut u8,
                         masked_payload_size,
                         masked_payload_size,
                     ));
-                    let e = run_async(user
-                        .send_tagged_packet(link_to, &public_payload, &masked_payload))
+                    let e = user
+                        .send_tagged_packet(link_to, &public_payload, &masked_payload)
                         .map_or(Err::OperationFailed, |response| {
                             *r = response.into();
                             Err::Ok
                         });
-                    let _ = core::mem::ManuallyDrop::new(public_payload.0);
-                    let _ = core::mem::ManuallyDrop::new(masked_payload.0);
+                    let _ = core::mem::ManuallyDrop::new(public_payload);
+                    let _ = core::mem::ManuallyDrop::new(masked_payload);
                     e
                 })
         })
@@ -281,7 +289,7 @@ pub unsafe extern "C" fn auth_receive_tagged_packet(
     r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             link.as_ref().map_or(Err::NullArgument, |link| {
-                run_async(user.receive_tagged_packet(link))
+                user.receive_tagged_packet(link)
                     .map_or(Err::OperationFailed, |tagged_payloads| {
                         *r = tagged_payloads.into();
                         Err::Ok
@@ -307,24 +315,24 @@ pub unsafe extern "C" fn auth_send_signed_packet(
             link_to
                 .into_seq_link(user.is_multi_branching())
                 .map_or(Err::NullArgument, |link_to| {
-                    let public_payload = Bytes(Vec::from_raw_parts(
+                    let public_payload = Bytes::from(Vec::from_raw_parts(
                         public_payload_ptr as *mut u8,
                         public_payload_size,
                         public_payload_size,
                     ));
-                    let masked_payload = Bytes(Vec::from_raw_parts(
+                    let masked_payload = Bytes::from(Vec::from_raw_parts(
                         masked_payload_ptr as *mut u8,
                         masked_payload_size,
                         masked_payload_size,
                     ));
-                    let e = run_async(user
-                        .send_signed_packet(link_to, &public_payload, &masked_payload))
+                    let e = user
+                        .send_signed_packet(link_to, &public_payload, &masked_payload)
                         .map_or(Err::OperationFailed, |response| {
                             *r = response.into();
                             Err::Ok
                         });
-                    let _ = core::mem::ManuallyDrop::new(public_payload.0);
-                    let _ = core::mem::ManuallyDrop::new(masked_payload.0);
+                    let _ = core::mem::ManuallyDrop::new(public_payload);
+                    let _ = core::mem::ManuallyDrop::new(masked_payload);
                     e
                 })
         })
@@ -338,10 +346,11 @@ pub unsafe extern "C" fn auth_receive_signed_packet(
     user: *mut Author,
     link: *const Address,
 ) -> Err {
-     r.as_mut().map_or(Err::NullArgument, |r| {
+    r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
-            link.as_ref().map_or(Err::NullArgument, move |link|{
-                run_async(user.receive_signed_packet(link)).map_or(Err::OperationFailed, |signed_payloads| {
+            link.as_ref().map_or(Err::NullArgument, move |link| {
+                user.receive_signed_packet(link)
+                    .map_or(Err::OperationFailed, |signed_payloads| {
                         *r = signed_payloads.into();
                         Err::Ok
                     })
@@ -355,7 +364,7 @@ pub unsafe extern "C" fn auth_receive_sequence(r: *mut *const Address, user: *mu
     r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             link.as_ref().map_or(Err::NullArgument, |link| {
-                run_async(user.receive_sequence(link)).map_or(Err::OperationFailed, |seq_link| {
+                user.receive_sequence(link).map_or(Err::OperationFailed, |seq_link| {
                     *r = safe_into_ptr(seq_link);
                     Err::Ok
                 })
@@ -384,7 +393,7 @@ pub unsafe extern "C" fn auth_receive_msg(
     r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             link.as_ref().map_or(Err::NullArgument, |link| {
-                run_async(user.receive_msg(link)).map_or(Err::OperationFailed, |u| {
+                user.receive_msg(link).map_or(Err::OperationFailed, |u| {
                     *r = safe_into_ptr(u);
                     Err::Ok
                 })
@@ -403,10 +412,11 @@ pub unsafe extern "C" fn auth_receive_msg_by_sequence_number(
     r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             anchor_link.as_ref().map_or(Err::NullArgument, |link| {
-                run_async(user.receive_msg_by_sequence_number(link, msg_num as u32)).map_or(Err::OperationFailed, |u| {
-                    *r = safe_into_ptr(u);
-                    Err::Ok
-                })
+                user.receive_msg_by_sequence_number(link, msg_num as u32)
+                    .map_or(Err::OperationFailed, |u| {
+                        *r = safe_into_ptr(u);
+                        Err::Ok
+                    })
             })
         })
     })
@@ -416,7 +426,7 @@ pub unsafe extern "C" fn auth_receive_msg_by_sequence_number(
 pub unsafe extern "C" fn auth_fetch_next_msg(umsg: *mut *const UnwrappedMessage, user: *mut Author) -> Err {
     user.as_mut().map_or(Err::NullArgument, |user| {
         umsg.as_mut().map_or(Err::NullArgument, |umsg| {
-            run_async(user.messages().try_next()).map_or(Err::OperationFailed, |m| {
+            user.messages().next().transpose().map_or(Err::OperationFailed, |m| {
                 *umsg = m.map_or_else(null, safe_into_ptr);
                 Err::Ok
             })
@@ -428,7 +438,7 @@ pub unsafe extern "C" fn auth_fetch_next_msg(umsg: *mut *const UnwrappedMessage,
 pub unsafe extern "C" fn auth_fetch_next_msgs(umsgs: *mut *const UnwrappedMessages, user: *mut Author) -> Err {
     user.as_mut().map_or(Err::NullArgument, |user| {
         umsgs.as_mut().map_or(Err::NullArgument, |umsgs| {
-            run_async(user.fetch_next_msgs()).map_or(Err::OperationFailed, |m| {
+            user.fetch_next_msgs().map_or(Err::OperationFailed, |m| {
                 *umsgs = safe_into_ptr(m);
                 Err::Ok
             })
@@ -437,11 +447,15 @@ pub unsafe extern "C" fn auth_fetch_next_msgs(umsgs: *mut *const UnwrappedMessag
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn auth_fetch_prev_msg(m: *mut *const UnwrappedMessage, user: *mut Author, address: *const Address) -> Err {
+pub unsafe extern "C" fn auth_fetch_prev_msg(
+    m: *mut *const UnwrappedMessage,
+    user: *mut Author,
+    address: *const Address,
+) -> Err {
     m.as_mut().map_or(Err::NullArgument, |m| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             address.as_ref().map_or(Err::NullArgument, |addr| {
-                run_async(user.fetch_prev_msg(addr)).map_or(Err::OperationFailed, |msg| {
+                user.fetch_prev_msg(addr).map_or(Err::OperationFailed, |msg| {
                     *m = safe_into_ptr(msg);
                     Err::Ok
                 })
@@ -451,14 +465,20 @@ pub unsafe extern "C" fn auth_fetch_prev_msg(m: *mut *const UnwrappedMessage, us
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn auth_fetch_prev_msgs(umsgs: *mut *const UnwrappedMessages, user: *mut Author, address: *const Address, num_msgs: size_t) -> Err {
+pub unsafe extern "C" fn auth_fetch_prev_msgs(
+    umsgs: *mut *const UnwrappedMessages,
+    user: *mut Author,
+    address: *const Address,
+    num_msgs: size_t,
+) -> Err {
     umsgs.as_mut().map_or(Err::NullArgument, |umsgs| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             address.as_ref().map_or(Err::NullArgument, |addr| {
-                run_async(user.fetch_prev_msgs(addr, num_msgs)).map_or(Err::OperationFailed, |msgs| {
-                    *umsgs = safe_into_ptr(msgs);
-                    Err::Ok
-                })
+                user.fetch_prev_msgs(addr, num_msgs)
+                    .map_or(Err::OperationFailed, |msgs| {
+                        *umsgs = safe_into_ptr(msgs);
+                        Err::Ok
+                    })
             })
         })
     })
@@ -468,7 +488,7 @@ pub unsafe extern "C" fn auth_fetch_prev_msgs(umsgs: *mut *const UnwrappedMessag
 pub unsafe extern "C" fn auth_sync_state(user: *mut Author) -> Err {
     // TODO: return message count
     user.as_mut().map_or(Err::NullArgument, |user| {
-        run_async(user.sync_state()).map_or(Err::OperationFailed, |_| Err::Ok)
+        user.sync_state().map_or(Err::OperationFailed, |_| Err::Ok)
     })
 }
 
@@ -492,23 +512,29 @@ pub unsafe extern "C" fn auth_reset_state(user: *mut Author) -> Err {
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn auth_store_psk(c_pskid: *mut *const PskId, c_user: *mut Author, c_psk_seed: *const c_char) -> Err {
+pub unsafe extern "C" fn auth_store_psk(
+    c_pskid: *mut *const PskId,
+    c_user: *mut Author,
+    c_psk_seed: *const c_char,
+) -> Err {
     if c_psk_seed == null() {
         return Err::NullArgument;
     }
 
-    CStr::from_ptr(c_psk_seed).to_str().map_or(Err::BadArgument, |psk_seed| {
-        c_user.as_mut().map_or(Err::NullArgument, |user| {
-            c_pskid.as_mut().map_or(Err::NullArgument, |pskid| {
-                let psk = psk_from_seed(psk_seed.as_ref());
-                let id = pskid_from_psk(&psk);
-                user.store_psk(id, psk).map_or(Err::OperationFailed, |_| {
-                    *pskid = safe_into_ptr(id);
-                    Err::Ok
+    CStr::from_ptr(c_psk_seed)
+        .to_str()
+        .map_or(Err::BadArgument, |psk_seed| {
+            c_user.as_mut().map_or(Err::NullArgument, |user| {
+                c_pskid.as_mut().map_or(Err::NullArgument, |pskid| {
+                    let psk = psk_from_seed(psk_seed.as_ref());
+                    let id = pskid_from_psk(&psk);
+                    user.store_psk(id, psk).map_or(Err::OperationFailed, |_| {
+                        *pskid = safe_into_ptr(id);
+                        Err::Ok
+                    })
                 })
             })
         })
-    })
 }
 
 #[no_mangle]
@@ -536,4 +562,4 @@ pub unsafe extern "C" fn auth_remove_subscriber(c_user: *mut Author, c_pk: *cons
             user.remove_subscriber(*pk).map_or(Err::OperationFailed, |_| Err::Ok)
         })
     })
-}
\ No newline at end of file
+}
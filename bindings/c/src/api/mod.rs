@@ -16,7 +16,10 @@ use iota_streams::{
     app_channels::api::{
         psk_from_seed,
         pskid_from_psk,
-        tangle::*,
+        tangle::{
+            self,
+            *,
+        },
     },
     core::{
         prelude::*,
@@ -24,13 +27,19 @@ use iota_streams::{
     },
 };
 
-use tokio::runtime::Runtime;
+#[cfg(feature = "client")]
+use core::future::Future;
+#[cfg(feature = "client")]
 use once_cell::sync::OnceCell;
+#[cfg(feature = "client")]
+use tokio::runtime::Runtime;
 
 use core::ptr::{
     null,
     null_mut,
 };
+#[cfg(not(feature = "client"))]
+use core::cell::RefCell;
 
 pub fn get_channel_type(channel_type: uint8_t) -> ChannelType {
     match channel_type {
@@ -164,13 +173,26 @@ pub type TransportWrap = iota_streams::app::transport::tangle::client::Client;
 #[cfg(not(feature = "client"))]
 pub type TransportWrap = Rc<RefCell<BucketTransport>>;
 
+#[cfg(feature = "client")]
 static INSTANCE: OnceCell<Runtime> = OnceCell::new();
 
-pub fn run_async<C: Future>(cb: C) -> C::Output {
-    let runtime = INSTANCE.get_or_init(|| Runtime::new().unwrap());
-    runtime.block_on(cb)
+/// Executor driving Author and Subscriber operations, backed by the tokio runtime required by the client
+#[cfg(feature = "client")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RuntimeExecutor;
+
+#[cfg(feature = "client")]
+impl blocking::Executor for RuntimeExecutor {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let runtime = INSTANCE.get_or_init(|| Runtime::new().unwrap());
+        runtime.block_on(future)
+    }
 }
 
+/// Executor driving Author and Subscriber operations, no async runtime is needed for the bucket transport
+#[cfg(not(feature = "client"))]
+pub use blocking::DefaultExecutor as RuntimeExecutor;
+
 #[no_mangle]
 pub extern "C" fn transport_new() -> *mut TransportWrap {
     safe_into_mut_ptr(TransportWrap::default())
@@ -191,6 +213,7 @@ pub unsafe extern "C" fn transport_client_new_from_url(c_url: *const c_char) ->
 #[cfg(feature = "client")]
 mod client_details {
     use super::*;
+    use iota_streams::app_channels::api::tangle::blocking::Executor as _;
     use iota_streams::app::transport::{
         tangle::client::{
             iota_client::{
@@ -353,7 +376,7 @@ mod client_details {
         r.as_mut().map_or(Err::NullArgument, |r| {
             tsp.as_mut().map_or(Err::NullArgument, |tsp| {
                 link.as_ref().map_or(Err::NullArgument, |link| {
-                    run_async(tsp.get_link_details(link)).map_or(Err::OperationFailed, |d| {
+                    RuntimeExecutor.block_on(tsp.get_link_details(link)).map_or(Err::OperationFailed, |d| {
                         *r = d.into();
                         Err::Ok
                     })
@@ -449,7 +472,7 @@ impl Default for Buffer {
 
 impl From<Bytes> for Buffer {
     fn from(b: Bytes) -> Self {
-        let p = core::mem::ManuallyDrop::new(b.0);
+        let p = core::mem::ManuallyDrop::new(b.into_vec());
         Self {
             ptr: p.as_ptr(),
             size: p.len(),
@@ -460,7 +483,7 @@ impl From<Bytes> for Buffer {
 
 impl From<Buffer> for Bytes {
     fn from(b: Buffer) -> Self {
-        unsafe { Self(Vec::from_raw_parts(b.ptr as *mut u8, b.size, b.cap)) }
+        unsafe { Self::from(Vec::from_raw_parts(b.ptr as *mut u8, b.size, b.cap)) }
     }
 }
 
@@ -470,20 +493,17 @@ impl From<Buffer> for Vec<u8> {
     }
 }
 
+// The contents of `Bytes` may be shared with the message they were unwrapped from, so they are copied
+// into a buffer owned by the caller, which can then be released with `drop_buffer`
 impl<'a> From<&'a Bytes> for Buffer {
     fn from(b: &Bytes) -> Self {
-        let p = &b.0;
-        Self {
-            ptr: p.as_ptr(),
-            size: p.len(),
-            cap: p.capacity(),
-        }
+        Self::from(b.as_slice().to_vec())
     }
 }
 
 impl Buffer {
     pub fn new(size: usize) -> Self {
-        Bytes(Vec::with_capacity(size)).into()
+        Bytes::from(Vec::with_capacity(size)).into()
     }
     pub fn drop(self) {
         let _b: Bytes = self.into();
@@ -627,4 +647,3 @@ pub use auth::*;
 
 mod sub;
 pub use sub::*;
-use core::future::Future;
@@ -1,8 +1,6 @@
-use iota_streams::app_channels::api::tangle::futures::TryStreamExt;
-
 use super::*;
 
-pub type Subscriber = iota_streams::app_channels::api::tangle::Subscriber<TransportWrap>;
+pub type Subscriber = blocking::Subscriber<TransportWrap, RuntimeExecutor>;
 
 /// Create a new subscriber
 #[no_mangle]
@@ -18,7 +16,7 @@ pub unsafe extern "C" fn sub_new(
     CStr::from_ptr(c_seed).to_str().map_or(Err::BadArgument, |seed| {
         transport.as_ref().map_or(Err::NullArgument, |tsp| {
             c_sub.as_mut().map_or(Err::NullArgument, |sub| {
-                let user = Subscriber::new(seed, tsp.clone());
+                let user = Subscriber::with_executor(tangle::Subscriber::new(seed, tsp.clone()), RuntimeExecutor);
                 *sub = safe_into_mut_ptr(user);
                 Err::Ok
             })
@@ -38,17 +36,22 @@ pub unsafe extern "C" fn sub_import(
         return Err::NullArgument;
     }
 
-    CStr::from_ptr(c_password).to_str().map_or(Err::BadArgument, |password| {
-        transport.as_ref().map_or(Err::NullArgument, |tsp| {
-            c_sub.as_mut().map_or(Err::NullArgument, |sub| {
-                let bytes_vec: Vec<_> = buffer.into();
-                run_async(Subscriber::import(&bytes_vec, password, tsp.clone())).map_or(Err::OperationFailed, |user| {
-                    *sub = safe_into_mut_ptr(user);
-                    Err::Ok
+    CStr::from_ptr(c_password)
+        .to_str()
+        .map_or(Err::BadArgument, |password| {
+            transport.as_ref().map_or(Err::NullArgument, |tsp| {
+                c_sub.as_mut().map_or(Err::NullArgument, |sub| {
+                    let bytes_vec: Vec<_> = buffer.into();
+                    Subscriber::import_with_executor(&bytes_vec, password, tsp.clone(), RuntimeExecutor).map_or(
+                        Err::OperationFailed,
+                        |user| {
+                            *sub = safe_into_mut_ptr(user);
+                            Err::Ok
+                        },
+                    )
                 })
             })
         })
-    })
 }
 
 #[no_mangle]
@@ -57,16 +60,18 @@ pub unsafe extern "C" fn sub_export(buf: *mut Buffer, c_sub: *mut Subscriber, c_
         return Err::NullArgument;
     }
 
-    CStr::from_ptr(c_password).to_str().map_or(Err::BadArgument, |password| {
-        c_sub.as_ref().map_or(Err::NullArgument, |user| {
-            buf.as_mut().map_or(Err::NullArgument, |buf| {
-                run_async(user.export(password)).map_or(Err::OperationFailed, |bytes| {
-                    *buf = bytes.into();
-                    Err::Ok
+    CStr::from_ptr(c_password)
+        .to_str()
+        .map_or(Err::BadArgument, |password| {
+            c_sub.as_ref().map_or(Err::NullArgument, |user| {
+                buf.as_mut().map_or(Err::NullArgument, |buf| {
+                    user.export(password).map_or(Err::OperationFailed, |bytes| {
+                        *buf = bytes.into();
+                        Err::Ok
+                    })
                 })
             })
         })
-    })
 }
 
 #[no_mangle]
@@ -149,7 +154,7 @@ pub unsafe extern "C" fn sub_unregister(user: *mut Subscriber) {
 pub unsafe extern "C" fn sub_receive_announce(user: *mut Subscriber, link: *const Address) -> Err {
     user.as_mut().map_or(Err::NullArgument, |user| {
         link.as_ref().map_or(Err::NullArgument, |link| {
-            run_async(user.receive_announcement(link))
+            user.receive_announcement(link)
                 .map_or(Err::OperationFailed, |_| Err::Ok)
         })
     })
@@ -167,7 +172,7 @@ pub unsafe extern "C" fn sub_send_subscribe(
             announcement_link
                 .as_ref()
                 .map_or(Err::NullArgument, |announcement_link| -> Err {
-                    run_async(user.send_subscribe(announcement_link))
+                    user.send_subscribe(announcement_link)
                         .map_or(Err::OperationFailed, |link| -> Err {
                             *r = safe_into_ptr(link);
                             Err::Ok
@@ -186,20 +191,17 @@ pub unsafe extern "C" fn sub_send_unsubscribe(
 ) -> Err {
     r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
-            subscription_link
-                .as_ref()
-                .map_or(Err::NullArgument, |sub_link| -> Err {
-                    run_async(user.send_unsubscribe(sub_link))
-                        .map_or(Err::OperationFailed, |link| -> Err {
-                            *r = safe_into_ptr(link);
-                            Err::Ok
-                        })
-                })
+            subscription_link.as_ref().map_or(Err::NullArgument, |sub_link| -> Err {
+                user.send_unsubscribe(sub_link)
+                    .map_or(Err::OperationFailed, |link| -> Err {
+                        *r = safe_into_ptr(link);
+                        Err::Ok
+                    })
+            })
         })
     })
 }
 
-
 #[no_mangle]
 pub unsafe extern "C" fn sub_send_tagged_packet(
     r: *mut MessageLinks,
@@ -215,24 +217,24 @@ pub unsafe extern "C" fn sub_send_tagged_packet(
             link_to
                 .into_seq_link(user.is_multi_branching())
                 .map_or(Err::NullArgument, |link_to| {
-                    let public_payload = Bytes(Vec::from_raw_parts(
+                    let public_payload = Bytes::from(Vec::from_raw_parts(
                         public_payload_ptr as *mut u8,
                         public_payload_size,
                         public_payload_size,
                     ));
-                    let masked_payload = Bytes(Vec::from_raw_parts(
+                    let masked_payload = Bytes::from(Vec::from_raw_parts(
                         masked_payload_ptr as *mut u8,
                         masked_payload_size,
                         masked_payload_size,
                     ));
-                    let e = run_async(user
-                        .send_tagged_packet(link_to, &public_payload, &masked_payload))
+                    let e = user
+                        .send_tagged_packet(link_to, &public_payload, &masked_payload)
                         .map_or(Err::OperationFailed, |response| {
                             *r = response.into();
                             Err::Ok
                         });
-                    let _ = core::mem::ManuallyDrop::new(public_payload.0);
-                    let _ = core::mem::ManuallyDrop::new(masked_payload.0);
+                    let _ = core::mem::ManuallyDrop::new(public_payload);
+                    let _ = core::mem::ManuallyDrop::new(masked_payload);
                     e
                 })
         })
@@ -254,24 +256,24 @@ pub unsafe extern "C" fn sub_send_signed_packet(
             link_to
                 .into_seq_link(user.is_multi_branching())
                 .map_or(Err::NullArgument, |link_to| {
-                    let public_payload = Bytes(Vec::from_raw_parts(
+                    let public_payload = Bytes::from(Vec::from_raw_parts(
                         public_payload_ptr as *mut u8,
                         public_payload_size,
                         public_payload_size,
                     ));
-                    let masked_payload = Bytes(Vec::from_raw_parts(
+                    let masked_payload = Bytes::from(Vec::from_raw_parts(
                         masked_payload_ptr as *mut u8,
                         masked_payload_size,
                         masked_payload_size,
                     ));
-                    let e = run_async(user
-                        .send_signed_packet(link_to, &public_payload, &masked_payload))
+                    let e = user
+                        .send_signed_packet(link_to, &public_payload, &masked_payload)
                         .map_or(Err::OperationFailed, |response| {
                             *r = response.into();
                             Err::Ok
                         });
-                    let _ = core::mem::ManuallyDrop::new(public_payload.0);
-                    let _ = core::mem::ManuallyDrop::new(masked_payload.0);
+                    let _ = core::mem::ManuallyDrop::new(public_payload);
+                    let _ = core::mem::ManuallyDrop::new(masked_payload);
                     e
                 })
         })
@@ -280,12 +282,20 @@ pub unsafe extern "C" fn sub_send_signed_packet(
 
 /// Process a keyload message
 #[no_mangle]
-pub unsafe extern "C" fn sub_receive_keyload(access: *mut *const uint8_t, user: *mut Subscriber, link: *const Address) -> Err {
+pub unsafe extern "C" fn sub_receive_keyload(
+    access: *mut *const uint8_t,
+    user: *mut Subscriber,
+    link: *const Address,
+) -> Err {
     access.as_mut().map_or(Err::NullArgument, |a| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             link.as_ref().map_or(Err::NullArgument, |link| {
-                run_async(user.receive_keyload(link)).map_or(Err::OperationFailed, |access| {
-                    if access { *a = safe_into_ptr(1) } else { *a = safe_into_ptr(0) }
+                user.receive_keyload(link).map_or(Err::OperationFailed, |access| {
+                    if access {
+                        *a = safe_into_ptr(1)
+                    } else {
+                        *a = safe_into_ptr(0)
+                    }
                     Err::Ok
                 })
             })
@@ -302,7 +312,7 @@ pub unsafe extern "C" fn sub_receive_sequence(
     r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             link.as_ref().map_or(Err::NullArgument, |link| {
-                run_async(user.receive_sequence(link)).map_or(Err::OperationFailed, |seq_link| {
+                user.receive_sequence(link).map_or(Err::OperationFailed, |seq_link| {
                     *r = safe_into_ptr(seq_link);
                     Err::Ok
                 })
@@ -321,7 +331,7 @@ pub unsafe extern "C" fn sub_receive_tagged_packet(
     r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             link.as_ref().map_or(Err::NullArgument, |link| {
-                run_async(user.receive_tagged_packet(link))
+                user.receive_tagged_packet(link)
                     .map_or(Err::OperationFailed, |tagged_payloads| {
                         *r = tagged_payloads.into();
                         Err::Ok
@@ -341,7 +351,7 @@ pub unsafe extern "C" fn sub_receive_signed_packet(
     r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             link.as_ref().map_or(Err::NullArgument, |link| {
-                run_async(user.receive_signed_packet(link))
+                user.receive_signed_packet(link)
                     .map_or(Err::OperationFailed, |signed_payloads| {
                         *r = signed_payloads.into();
                         Err::Ok
@@ -372,8 +382,8 @@ pub unsafe extern "C" fn sub_receive_keyload_from_ids(
         user.as_mut().map_or(Err::NullArgument, |user| {
             next_msg_ids.as_ref().map_or(Err::NullArgument, |ids| {
                 for (_pk, cursor) in ids {
-                    if let Ok(keyload_link) = run_async(user.receive_sequence(&cursor.link)) {
-                        match run_async(user.receive_keyload(&keyload_link)) {
+                    if let Ok(keyload_link) = user.receive_sequence(&cursor.link) {
+                        match user.receive_keyload(&keyload_link) {
                             Ok(true) => {
                                 *r = (cursor.link.clone(), Some(keyload_link)).into();
                                 return Err::Ok;
@@ -398,7 +408,7 @@ pub unsafe extern "C" fn sub_receive_msg(
     r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             link.as_ref().map_or(Err::NullArgument, |link| {
-                run_async(user.receive_msg(link)).map_or(Err::OperationFailed, |u| {
+                user.receive_msg(link).map_or(Err::OperationFailed, |u| {
                     *r = safe_into_ptr(u);
                     Err::Ok
                 })
@@ -417,10 +427,11 @@ pub unsafe extern "C" fn sub_receive_msg_by_sequence_number(
     r.as_mut().map_or(Err::NullArgument, |r| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             anchor_link.as_ref().map_or(Err::NullArgument, |link| {
-                run_async(user.receive_msg_by_sequence_number(link, msg_num as u32)).map_or(Err::OperationFailed, |u| {
-                    *r = safe_into_ptr(u);
-                    Err::Ok
-                })
+                user.receive_msg_by_sequence_number(link, msg_num as u32)
+                    .map_or(Err::OperationFailed, |u| {
+                        *r = safe_into_ptr(u);
+                        Err::Ok
+                    })
             })
         })
     })
@@ -430,7 +441,7 @@ pub unsafe extern "C" fn sub_receive_msg_by_sequence_number(
 pub unsafe extern "C" fn sub_fetch_next_msg(umsg: *mut *const UnwrappedMessage, user: *mut Subscriber) -> Err {
     user.as_mut().map_or(Err::NullArgument, |user| {
         umsg.as_mut().map_or(Err::NullArgument, |umsg| {
-            run_async(user.messages().try_next()).map_or(Err::OperationFailed, |m| {
+            user.messages().next().transpose().map_or(Err::OperationFailed, |m| {
                 *umsg = m.map_or_else(null, safe_into_ptr);
                 Err::Ok
             })
@@ -442,7 +453,7 @@ pub unsafe extern "C" fn sub_fetch_next_msg(umsg: *mut *const UnwrappedMessage,
 pub unsafe extern "C" fn sub_fetch_next_msgs(umsgs: *mut *const UnwrappedMessages, user: *mut Subscriber) -> Err {
     user.as_mut().map_or(Err::NullArgument, |user| {
         umsgs.as_mut().map_or(Err::NullArgument, |umsgs| {
-            run_async(user.fetch_next_msgs()).map_or(Err::OperationFailed, |m| {
+            user.fetch_next_msgs().map_or(Err::OperationFailed, |m| {
                 *umsgs = safe_into_ptr(m);
                 Err::Ok
             })
@@ -451,11 +462,15 @@ pub unsafe extern "C" fn sub_fetch_next_msgs(umsgs: *mut *const UnwrappedMessage
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn sub_fetch_prev_msg(m: *mut *const UnwrappedMessage, user: *mut Subscriber, address: *const Address) -> Err {
+pub unsafe extern "C" fn sub_fetch_prev_msg(
+    m: *mut *const UnwrappedMessage,
+    user: *mut Subscriber,
+    address: *const Address,
+) -> Err {
     m.as_mut().map_or(Err::NullArgument, |m| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             address.as_ref().map_or(Err::NullArgument, |addr| {
-                run_async(user.fetch_prev_msg(addr)).map_or(Err::OperationFailed, |msg| {
+                user.fetch_prev_msg(addr).map_or(Err::OperationFailed, |msg| {
                     *m = safe_into_ptr(msg);
                     Err::Ok
                 })
@@ -465,25 +480,30 @@ pub unsafe extern "C" fn sub_fetch_prev_msg(m: *mut *const UnwrappedMessage, use
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn sub_fetch_prev_msgs(umsgs: *mut *const UnwrappedMessages, user: *mut Subscriber, address: *const Address, num_msgs: size_t) -> Err {
+pub unsafe extern "C" fn sub_fetch_prev_msgs(
+    umsgs: *mut *const UnwrappedMessages,
+    user: *mut Subscriber,
+    address: *const Address,
+    num_msgs: size_t,
+) -> Err {
     umsgs.as_mut().map_or(Err::NullArgument, |umsgs| {
         user.as_mut().map_or(Err::NullArgument, |user| {
             address.as_ref().map_or(Err::NullArgument, |addr| {
-                run_async(user.fetch_prev_msgs(addr, num_msgs)).map_or(Err::OperationFailed, |msgs| {
-                    *umsgs = safe_into_ptr(msgs);
-                    Err::Ok
-                })
+                user.fetch_prev_msgs(addr, num_msgs)
+                    .map_or(Err::OperationFailed, |msgs| {
+                        *umsgs = safe_into_ptr(msgs);
+                        Err::Ok
+                    })
             })
         })
     })
 }
 
-
 #[no_mangle]
 pub unsafe extern "C" fn sub_sync_state(user: *mut Subscriber) -> Err {
     // TODO: return message count
     user.as_mut().map_or(Err::NullArgument, |user| {
-        run_async(user.sync_state()).map_or(Err::OperationFailed, |_| Err::Ok)
+        user.sync_state().map_or(Err::OperationFailed, |_| Err::Ok)
     })
 }
 
@@ -507,23 +527,29 @@ pub unsafe extern "C" fn sub_reset_state(user: *mut Subscriber) -> Err {
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn sub_store_psk(c_pskid: *mut *const PskId, c_user: *mut Subscriber, c_psk_seed: *const c_char) -> Err {
+pub unsafe extern "C" fn sub_store_psk(
+    c_pskid: *mut *const PskId,
+    c_user: *mut Subscriber,
+    c_psk_seed: *const c_char,
+) -> Err {
     if c_psk_seed == null() {
         return Err::NullArgument;
     }
 
-    CStr::from_ptr(c_psk_seed).to_str().map_or(Err::BadArgument, |psk_seed| {
-        c_user.as_mut().map_or(Err::NullArgument, |user| {
-            c_pskid.as_mut().map_or(Err::NullArgument, |pskid| {
-                let psk = psk_from_seed(psk_seed.as_ref());
-                let id = pskid_from_psk(&psk);
-                user.store_psk(id, psk).map_or(Err::OperationFailed, |_| {
-                    *pskid = safe_into_ptr(id);
-                    Err::Ok
+    CStr::from_ptr(c_psk_seed)
+        .to_str()
+        .map_or(Err::BadArgument, |psk_seed| {
+            c_user.as_mut().map_or(Err::NullArgument, |user| {
+                c_pskid.as_mut().map_or(Err::NullArgument, |pskid| {
+                    let psk = psk_from_seed(psk_seed.as_ref());
+                    let id = pskid_from_psk(&psk);
+                    user.store_psk(id, psk).map_or(Err::OperationFailed, |_| {
+                        *pskid = safe_into_ptr(id);
+                        Err::Ok
+                    })
                 })
             })
         })
-    })
 }
 
 #[no_mangle]
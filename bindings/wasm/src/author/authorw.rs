@@ -193,8 +193,8 @@ impl Author {
             .borrow_mut()
             .send_tagged_packet(
                 link.as_inner(),
-                &Bytes(public_payload.clone()),
-                &Bytes(masked_payload.clone()),
+                &Bytes::from(public_payload.clone()),
+                &Bytes::from(masked_payload.clone()),
             )
             .await
             .map(|(link, seq_link)| UserResponse::new(link.into(), seq_link.map(Into::into), None))
@@ -210,7 +210,11 @@ impl Author {
     ) -> Result<UserResponse> {
         self.author
             .borrow_mut()
-            .send_signed_packet(link.as_inner(), &Bytes(public_payload), &Bytes(masked_payload))
+            .send_signed_packet(
+                link.as_inner(),
+                &Bytes::from(public_payload),
+                &Bytes::from(masked_payload),
+            )
             .await
             .map(|(link, seq_link)| UserResponse::new(link.into(), seq_link.map(Into::into), None))
             .into_js_result()
@@ -241,7 +245,7 @@ impl Author {
             .receive_tagged_packet(link.as_inner())
             .await
             .map(|(pub_bytes, masked_bytes)| {
-                UserResponse::new(link, None, Some(Message::new(None, pub_bytes.0, masked_bytes.0)))
+                UserResponse::new(link, None, Some(Message::new(None, pub_bytes.into_vec(), masked_bytes.into_vec())))
             })
             .into_js_result()
     }
@@ -258,8 +262,8 @@ impl Author {
                     None,
                     Some(Message::new(
                         Some(public_key_to_string(&pk)),
-                        pub_bytes.0,
-                        masked_bytes.0,
+                        pub_bytes.into_vec(),
+                        masked_bytes.into_vec(),
                     )),
                 )
             })
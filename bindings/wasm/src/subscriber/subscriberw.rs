@@ -165,7 +165,7 @@ impl Subscriber {
             .receive_tagged_packet(link.as_inner())
             .await
             .map(|(pub_bytes, masked_bytes)| {
-                UserResponse::new(link, None, Some(Message::new(None, pub_bytes.0, masked_bytes.0)))
+                UserResponse::new(link, None, Some(Message::new(None, pub_bytes.into_vec(), masked_bytes.into_vec())))
             })
             .into_js_result()
     }
@@ -182,8 +182,8 @@ impl Subscriber {
                     None,
                     Some(Message::new(
                         Some(public_key_to_string(&pk)),
-                        pub_bytes.0,
-                        masked_bytes.0,
+                        pub_bytes.into_vec(),
+                        masked_bytes.into_vec(),
                     )),
                 )
             })
@@ -247,7 +247,11 @@ impl Subscriber {
     ) -> Result<UserResponse> {
         self.subscriber
             .borrow_mut()
-            .send_tagged_packet(link.as_inner(), &Bytes(public_payload), &Bytes(masked_payload))
+            .send_tagged_packet(
+                link.as_inner(),
+                &Bytes::from(public_payload),
+                &Bytes::from(masked_payload),
+            )
             .await
             .map(|(link, seq_link)| UserResponse::new(link.into(), seq_link.map(Into::into), None))
             .into_js_result()
@@ -262,7 +266,11 @@ impl Subscriber {
     ) -> Result<UserResponse> {
         self.subscriber
             .borrow_mut()
-            .send_signed_packet(link.as_inner(), &Bytes(public_payload), &Bytes(masked_payload))
+            .send_signed_packet(
+                link.as_inner(),
+                &Bytes::from(public_payload),
+                &Bytes::from(masked_payload),
+            )
             .await
             .map(|(link, seq_link)| UserResponse::new(link.into(), seq_link.map(Into::into), None))
             .into_js_result()
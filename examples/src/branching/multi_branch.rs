@@ -31,8 +31,8 @@ pub async fn example<T: Transport>(transport: T, channel_type: ChannelType, seed
     let mut subscriberB = Subscriber::new("SUBSCRIBERB9SEED", transport.clone());
     let mut subscriberC = Subscriber::new("SUBSCRIBERC9SEED", transport);
 
-    let public_payload = Bytes("PUBLICPAYLOAD".as_bytes().to_vec());
-    let masked_payload = Bytes("MASKEDPAYLOAD".as_bytes().to_vec());
+    let public_payload = Bytes::from("PUBLICPAYLOAD".as_bytes().to_vec());
+    let masked_payload = Bytes::from("MASKEDPAYLOAD".as_bytes().to_vec());
 
     println!("\nAnnounce Channel");
     let announcement_link = {
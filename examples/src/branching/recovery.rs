@@ -25,8 +25,8 @@ pub async fn example<T: Transport>(transport: T, channel_type: ChannelType, seed
 
     let mut subscriberA = Subscriber::new("SUBSCRIBERA9SEED", transport.clone());
 
-    let public_payload = Bytes("PUBLICPAYLOAD".as_bytes().to_vec());
-    let masked_payload = Bytes("MASKEDPAYLOAD".as_bytes().to_vec());
+    let public_payload = Bytes::from("PUBLICPAYLOAD".as_bytes().to_vec());
+    let masked_payload = Bytes::from("MASKEDPAYLOAD".as_bytes().to_vec());
 
     println!("\nAnnounce Channel");
     let announcement_link = {
@@ -30,7 +30,7 @@ pub async fn example<T: Transport>(transport: T, channel_impl: ChannelType, seed
     let mut subscriberB = Subscriber::new("SUBSCRIBERB9SEED", transport.clone());
     let mut subscriberC = Subscriber::new("SUBSCRIBERC9SEED", transport);
 
-    let empty_payload = Bytes(Vec::new());
+    let empty_payload = Bytes::new();
 
     println!("\nAnnounce Channel");
     let announcement_link = {
@@ -109,7 +109,7 @@ pub async fn example<T: Transport>(transport: T, channel_impl: ChannelType, seed
     for i in 0..10 {
         let mut message = String::from("Message ");
         message.push_str(&i.to_string());
-        let masked_payload = Bytes(message.as_bytes().to_vec());
+        let masked_payload = Bytes::from(message.as_bytes().to_vec());
         let (msg, seq) = author
             .send_signed_packet(&anchor_msg_link, &empty_payload, &masked_payload)
             .await?;
@@ -132,7 +132,7 @@ pub async fn example<T: Transport>(transport: T, channel_impl: ChannelType, seed
             println!(
                 "  Msg => <{}>: {}",
                 msg.link.msgid,
-                String::from_utf8(masked_payload.0.to_vec())?
+                String::from_utf8(masked_payload.as_slice().to_vec())?
             );
         } else {
             panic!("Packet found was not a signed packet from author")
@@ -151,9 +151,9 @@ pub async fn example<T: Transport>(transport: T, channel_impl: ChannelType, seed
         println!(
             "  Msg => <{}>: {}",
             msg.link.msgid,
-            String::from_utf8(masked_payload.0.to_vec())?
+            String::from_utf8(masked_payload.as_slice().to_vec())?
         );
-        assert_eq!(masked_payload.0, "Message 4".as_bytes().to_vec());
+        assert_eq!(masked_payload.as_slice(), "Message 4".as_bytes());
     } else {
         panic!("Packet found was not a signed packet from author")
     }
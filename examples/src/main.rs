@@ -6,6 +6,8 @@ use std::env;
 
 use rand::Rng;
 
+#[cfg(feature = "mock-node")]
+use iota_streams::app::transport::tangle::mock_node::MockNode;
 use iota_streams::{
     app::transport::tangle::client::Client,
     app_channels::api::tangle::{
@@ -86,14 +88,11 @@ async fn main_pure() {
     println!("#######################################");
 }
 
-async fn main_client() {
-    // Parse env vars with a fallback
-    let node_url = env::var("URL").unwrap_or_else(|_| "https://chrysalis-nodes.iota.org".to_string());
-
-    let transport = Client::new_from_url(&node_url);
+async fn main_client(node_url: &str) {
+    let transport = Client::new_from_url(node_url);
 
     println!("#######################################");
-    println!("Running tests accessing Tangle via node {}", &node_url);
+    println!("Running tests accessing Tangle via node {}", node_url);
     println!("#######################################");
     println!("\n");
 
@@ -102,7 +101,7 @@ async fn main_client() {
     run_multi_branch_test(transport.clone(), &new_seed()).await;
     run_recovery_single_branch_test(transport.clone(), &new_seed()).await;
     run_recovery_multi_branch_test(transport.clone(), &new_seed()).await;
-    println!("Done running tests accessing Tangle via node {}", &node_url);
+    println!("Done running tests accessing Tangle via node {}", node_url);
     println!("#######################################");
 }
 
@@ -121,7 +120,17 @@ async fn main() {
     };
 
     match env::var("TRANSPORT").ok().as_deref() {
-        Some("tangle") => main_client().await,
+        Some("tangle") => {
+            // Parse env vars with a fallback
+            let node_url = env::var("URL").unwrap_or_else(|_| "https://chrysalis-nodes.iota.org".to_string());
+            main_client(&node_url).await
+        }
+        #[cfg(feature = "mock-node")]
+        Some("mock") => {
+            // In-process stand-in for a node, running the client without network access
+            let node = MockNode::spawn().expect("failed to start mock node");
+            main_client(node.url()).await
+        }
         Some("bucket") | None => main_pure().await,
         Some(other) => panic!("Unexpected TRANSPORT '{}'", other),
     }
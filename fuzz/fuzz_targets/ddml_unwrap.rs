@@ -0,0 +1,113 @@
+//! Run arbitrary programs of DDML unwrap commands over arbitrary bytes.
+
+#![no_main]
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+use iota_streams::{
+    app_channels::api::DefaultF,
+    core::Result,
+    core_edsig::{
+        key_exchange::x25519,
+        signature::ed25519,
+    },
+    ddml::{
+        command::{
+            unwrap,
+            Absorb,
+            Commit,
+            Mask,
+            Repeated,
+            Skip,
+            Squeeze,
+        },
+        types::*,
+    },
+};
+
+type Context<'a> = unwrap::Context<DefaultF, &'a [u8]>;
+
+#[derive(Arbitrary, Debug)]
+enum Command {
+    AbsorbUint8,
+    AbsorbUint64,
+    AbsorbSize,
+    AbsorbBytes,
+    AbsorbStr,
+    AbsorbNBytes,
+    AbsorbVaruint,
+    AbsorbVarint,
+    AbsorbOption,
+    AbsorbMap,
+    AbsorbEd25519PublicKey,
+    AbsorbX25519PublicKey,
+    MaskUint16,
+    MaskSize,
+    MaskBytes,
+    MaskStr,
+    MaskNBytes,
+    SkipUint32,
+    SkipSize,
+    SkipBytes,
+    Squeeze(u8),
+    SqueezeExternal,
+    Commit,
+    Drop(u8),
+    Repeated(Vec<Command>),
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    max_bytes_len: u16,
+    max_repeated: u8,
+    max_nesting: u8,
+    program: Vec<Command>,
+    stream: Vec<u8>,
+}
+
+fn run<'c, 'a>(ctx: &'c mut Context<'a>, program: &[Command]) -> Result<&'c mut Context<'a>> {
+    for command in program {
+        match command {
+            Command::AbsorbUint8 => ctx.absorb(&mut Uint8(0))?,
+            Command::AbsorbUint64 => ctx.absorb(&mut Uint64(0))?,
+            Command::AbsorbSize => ctx.absorb(&mut Size(0))?,
+            Command::AbsorbBytes => ctx.absorb(&mut Bytes::default())?,
+            Command::AbsorbStr => ctx.absorb(&mut Str::default())?,
+            Command::AbsorbNBytes => ctx.absorb(&mut NBytes::<U32>::default())?,
+            Command::AbsorbVaruint => ctx.absorb(&mut Varuint::default())?,
+            Command::AbsorbVarint => ctx.absorb(&mut Varint::default())?,
+            Command::AbsorbOption => ctx.absorb(&mut Option::<Uint32>::None)?,
+            Command::AbsorbMap => ctx.absorb(&mut Map::<Size, Bytes>::new())?,
+            Command::AbsorbEd25519PublicKey => ctx.absorb(&mut ed25519::PublicKey::default())?,
+            Command::AbsorbX25519PublicKey => ctx.absorb(&mut x25519::PublicKey::from([0_u8; 32]))?,
+            Command::MaskUint16 => ctx.mask(&mut Uint16(0))?,
+            Command::MaskSize => ctx.mask(&mut Size(0))?,
+            Command::MaskBytes => ctx.mask(&mut Bytes::default())?,
+            Command::MaskStr => ctx.mask(&mut Str::default())?,
+            Command::MaskNBytes => ctx.mask(&mut NBytes::<U32>::default())?,
+            Command::SkipUint32 => ctx.skip(&mut Uint32(0))?,
+            Command::SkipSize => ctx.skip(&mut Size(0))?,
+            Command::SkipBytes => ctx.skip(&mut Bytes::default())?,
+            Command::Squeeze(n) => ctx.squeeze(Mac(*n as usize))?,
+            Command::SqueezeExternal => ctx.squeeze(&mut External(NBytes::<U64>::default()))?,
+            Command::Commit => ctx.commit()?,
+            Command::Drop(n) => ctx.drop(Size(*n as usize))?,
+            Command::Repeated(body) => {
+                let mut n = Size(0);
+                ctx.absorb(&mut n)?.repeated(n, |ctx| run(ctx, body))?
+            }
+        };
+    }
+    Ok(ctx)
+}
+
+fuzz_target!(|input: Input| {
+    // Keep limits small, otherwise a large repeated count makes a single input run for ages.
+    let limits = unwrap::Limits::default()
+        .with_max_bytes_len(input.max_bytes_len as usize)
+        .with_max_allocation(1 << 20)
+        .with_max_repeated(input.max_repeated as usize)
+        .with_max_nesting(input.max_nesting as usize % 8);
+    let mut ctx = Context::new(&input.stream[..]).with_limits(limits);
+    let _ = run(&mut ctx, &input.program);
+});
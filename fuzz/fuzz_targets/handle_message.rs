@@ -0,0 +1,156 @@
+//! Handle mutations of valid channel messages of every content type.
+//!
+//! A channel is set up with an author and a subscriber, then the message of the generated kind
+//! and content is sent, mutated and handed to the receiving users in place of the original.
+
+#![no_main]
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+use std::{
+    cell::RefCell,
+    rc::Rc,
+};
+
+use iota_streams::{
+    app::transport::Transport,
+    app_channels::api::tangle::{
+        Address,
+        Author,
+        BinaryMessage,
+        BucketTransport,
+        Bytes,
+        ChannelType,
+        Subscriber,
+        UnwrapLimits,
+        User,
+    },
+    core::Result,
+};
+use iota_streams_fuzz::{
+    block_on,
+    mutate,
+    Mutation,
+};
+
+type Trans = Rc<RefCell<BucketTransport>>;
+
+#[derive(Arbitrary, Debug)]
+enum Kind {
+    Announce,
+    Subscribe,
+    Unsubscribe,
+    Keyload,
+    SignedPacket { public: Vec<u8>, masked: Vec<u8> },
+    TaggedPacket { public: Vec<u8>, masked: Vec<u8> },
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    kind: Kind,
+    multi_branch: bool,
+    /// Keep the original message next to the mutated one under the same link.
+    duplicate: bool,
+    mutations: Vec<Mutation>,
+}
+
+/// Send the message of the given kind, returning its link and the transport state before it was sent.
+async fn send(
+    input: &Input,
+    transport: &Trans,
+    author: &mut Author<Trans>,
+    subscriber: &mut Subscriber<Trans>,
+) -> Result<(Address, BucketTransport)> {
+    if let Kind::Announce = input.kind {
+        let snapshot = transport.borrow().clone();
+        return Ok((author.send_announce().await?, snapshot));
+    }
+    let announcement_link = author.send_announce().await?;
+    subscriber.receive_announcement(&announcement_link).await?;
+    let snapshot = transport.borrow().clone();
+    let subscribe_link = subscriber.send_subscribe(&announcement_link).await?;
+    if let Kind::Subscribe = input.kind {
+        return Ok((subscribe_link, snapshot));
+    }
+    author.receive_subscribe(&subscribe_link).await?;
+    let snapshot = transport.borrow().clone();
+    let link = match &input.kind {
+        Kind::Unsubscribe => subscriber.send_unsubscribe(&subscribe_link).await?,
+        Kind::Keyload => author.send_keyload_for_everyone(&announcement_link).await?.0,
+        Kind::SignedPacket { public, masked } => {
+            author
+                .send_signed_packet(
+                    &announcement_link,
+                    &Bytes::from(public.clone()),
+                    &Bytes::from(masked.clone()),
+                )
+                .await?
+                .0
+        }
+        Kind::TaggedPacket { public, masked } => {
+            author
+                .send_tagged_packet(
+                    &announcement_link,
+                    &Bytes::from(public.clone()),
+                    &Bytes::from(masked.clone()),
+                )
+                .await?
+                .0
+        }
+        Kind::Announce | Kind::Subscribe => unreachable!(),
+    };
+    Ok((link, snapshot))
+}
+
+async fn run(input: Input) -> Result<()> {
+    let channel_type = if input.multi_branch {
+        ChannelType::MultiBranch
+    } else {
+        ChannelType::SingleBranch
+    };
+    let transport = Rc::new(RefCell::new(BucketTransport::new()));
+    let mut author = Author::new("AUTHOR9SEED", channel_type.clone(), transport.clone());
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport.clone());
+    let mut reader = User::new("READER9SEED", channel_type, transport.clone());
+    // Bound allocations so that mutated sizes don't make the harness run out of memory.
+    let limits = UnwrapLimits::default()
+        .with_max_allocation(1 << 20)
+        .with_max_repeated(1 << 10);
+    author.set_unwrap_limits(limits);
+    subscriber.set_unwrap_limits(limits);
+    reader.set_unwrap_limits(limits);
+
+    let (link, mut snapshot) = send(&input, &transport, &mut author, &mut subscriber).await?;
+    let mut current = transport.borrow().clone();
+    let original = current.recv_message(&link).await?;
+    let mut body = original.body.as_bytes().to_vec();
+    mutate(&mut body, &input.mutations);
+    let mutated = BinaryMessage::new(original.link, original.prev_link, body.into());
+    if input.duplicate {
+        snapshot.send_message(&original).await?;
+    }
+    snapshot.send_message(&mutated).await?;
+    *transport.borrow_mut() = snapshot;
+
+    let pristine = input.mutations.is_empty() && !input.duplicate;
+    let handled = match input.kind {
+        Kind::Announce => subscriber.receive_announcement(&link).await,
+        Kind::Subscribe => author.receive_subscribe(&link).await,
+        Kind::Unsubscribe => author.receive_unsubscribe(&link).await,
+        Kind::Keyload => subscriber.receive_keyload(&link).await.map(|_| ()),
+        Kind::SignedPacket { .. } => subscriber.receive_signed_packet(&link).await.map(|_| ()),
+        Kind::TaggedPacket { .. } => subscriber.receive_tagged_packet(&link).await.map(|_| ()),
+    };
+    assert!(!pristine || handled.is_ok(), "valid message rejected: {:?}", handled);
+
+    if let Some(announcement_link) = author.announcement_link() {
+        let _ = reader.receive_announcement(announcement_link).await;
+    }
+    let _ = reader.handle_message(&mutated, false).await;
+    Ok(())
+}
+
+fuzz_target!(|input: Input| {
+    // Setting up the channel doesn't depend on the input and must not fail.
+    block_on(run(input)).expect("channel setup");
+});
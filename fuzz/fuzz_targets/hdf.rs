@@ -0,0 +1,113 @@
+//! Round trip structurally generated message headers, then parse mutated encodings.
+
+#![no_main]
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+use iota_streams::{
+    app::{
+        identifier::Identifier,
+        message::{
+            ContentSizeof,
+            ContentWrap,
+            HasLink,
+            HDF,
+        },
+    },
+    app_channels::api::{
+        tangle::{
+            Address,
+            BinaryMessage,
+            Preparsed,
+            UnwrapLimits,
+        },
+        DefaultF,
+    },
+    core::psk::PskId,
+    core_edsig::signature::ed25519,
+    ddml::{
+        command::{
+            sizeof,
+            wrap,
+        },
+        link_store::EmptyLinkStore,
+        types::Bytes,
+    },
+};
+use iota_streams_fuzz::{
+    address,
+    block_on,
+    mutate,
+    Mutation,
+    ADDRESS_SIZE,
+};
+
+#[derive(Arbitrary, Debug)]
+enum Sender {
+    EdPubKey([u8; ed25519::SECRET_KEY_LENGTH]),
+    PskId([u8; 16]),
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    link: [u8; ADDRESS_SIZE],
+    prev_link: [u8; ADDRESS_SIZE],
+    content_type: u8,
+    payload_length: u16,
+    payload_frame_count: u32,
+    seq_num: u64,
+    sender: Sender,
+    mutations: Vec<Mutation>,
+}
+
+fn encode(header: &HDF<Address>) -> Vec<u8> {
+    let store = EmptyLinkStore::<DefaultF, Address, ()>::default();
+    let mut ctx = sizeof::Context::<DefaultF>::new();
+    block_on(header.sizeof(&mut ctx)).expect("header size");
+    let mut buf = vec![0_u8; ctx.get_size()];
+    let mut ctx = wrap::Context::<DefaultF, _>::new(&mut buf[..]);
+    block_on(header.wrap(&store, &mut ctx)).expect("header wrap");
+    assert!(ctx.stream.is_empty());
+    buf
+}
+
+fuzz_target!(|input: Input| {
+    let sender = match input.sender {
+        Sender::EdPubKey(secret) => {
+            let secret = ed25519::SecretKey::from_bytes(&secret).unwrap();
+            Identifier::from(ed25519::PublicKey::from(&secret))
+        }
+        Sender::PskId(id) => Identifier::from(PskId::clone_from_slice(&id)),
+    };
+    let link = address(&input.link);
+    let prev_link = address(&input.prev_link);
+    let header = match HDF::new_with_fields(
+        link,
+        Bytes::from(prev_link.to_bytes()),
+        input.content_type,
+        input.payload_length as usize,
+        input.seq_num,
+        &sender,
+    )
+    .and_then(|h| h.with_payload_frame_count(input.payload_frame_count))
+    {
+        Ok(header) => header,
+        Err(_) => return,
+    };
+
+    let mut body = encode(&header);
+    let msg = BinaryMessage::new(link, prev_link, body.clone().into());
+    let preparsed: Preparsed = block_on(msg.parse_header()).expect("valid header must parse");
+    let parsed = preparsed.header;
+    assert_eq!(parsed.get_content_type(), header.get_content_type());
+    assert_eq!(parsed.get_payload_length(), header.get_payload_length());
+    assert_eq!(parsed.get_payload_frame_count(), header.get_payload_frame_count());
+    assert_eq!(parsed.get_seq_num(), header.get_seq_num());
+    assert_eq!(parsed.get_previous_msg_link(), header.get_previous_msg_link());
+    assert_eq!(parsed.get_identifier(), header.get_identifier());
+
+    mutate(&mut body, &input.mutations);
+    let msg = BinaryMessage::new(link, prev_link, body.into());
+    let limits = UnwrapLimits::default().with_max_allocation(1 << 20);
+    let _ = block_on(msg.parse_header_with_limits::<DefaultF>(limits));
+});
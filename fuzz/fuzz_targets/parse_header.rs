@@ -0,0 +1,38 @@
+//! Parse the header of arbitrary binary messages.
+
+#![no_main]
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+use iota_streams::app_channels::api::tangle::{
+    BinaryMessage,
+    Preparsed,
+    UnwrapLimits,
+};
+use iota_streams_fuzz::{
+    address,
+    block_on,
+    ADDRESS_SIZE,
+};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    link: [u8; ADDRESS_SIZE],
+    prev_link: [u8; ADDRESS_SIZE],
+    max_bytes_len: Option<u16>,
+    body: Vec<u8>,
+}
+
+fuzz_target!(|input: Input| {
+    let msg = BinaryMessage::new(address(&input.link), address(&input.prev_link), input.body.into());
+    // Keep allocations below the default limits so that a single input stays cheap.
+    let limits = UnwrapLimits::default().with_max_allocation(1 << 20);
+    let limits = match input.max_bytes_len {
+        Some(n) => limits.with_max_bytes_len(n as usize),
+        None => limits,
+    };
+    if let Ok(preparsed) = block_on(msg.parse_header_with_limits(limits)) {
+        let preparsed: Preparsed = preparsed;
+        let _ = format!("{:?} {:?}", preparsed.header, msg.body);
+    }
+});
@@ -0,0 +1,63 @@
+//! Shared generators for the fuzz targets in `fuzz_targets`.
+//!
+//! Run a target with `cargo fuzz run <target>` from this directory, eg. `cargo fuzz run handle_message`.
+
+use arbitrary::Arbitrary;
+pub use futures::executor::block_on;
+
+use iota_streams::{
+    app::message::HasLink,
+    app_channels::api::tangle::Address,
+};
+
+/// Size of a binary Tangle address: appinst followed by msgid.
+pub const ADDRESS_SIZE: usize = 40 + 12;
+
+/// Construct a Tangle address from arbitrary bytes.
+pub fn address(bytes: &[u8; ADDRESS_SIZE]) -> Address {
+    Address::try_from_bytes(&bytes[..]).expect("address of the right size")
+}
+
+/// A single mutation of a valid encoded message.
+#[derive(Arbitrary, Clone, Debug)]
+pub enum Mutation {
+    /// Flip a bit of the byte at the given position.
+    Flip { at: usize, bit: u8 },
+    /// Overwrite the byte at the given position.
+    Set { at: usize, byte: u8 },
+    /// Insert bytes before the given position.
+    Insert { at: usize, bytes: Vec<u8> },
+    /// Remove up to `len` bytes starting at the given position.
+    Remove { at: usize, len: usize },
+    /// Truncate to the given length.
+    Truncate { len: usize },
+}
+
+impl Mutation {
+    /// Apply mutation to `bytes`, positions are taken modulo length of `bytes`.
+    pub fn apply(&self, bytes: &mut Vec<u8>) {
+        let n = bytes.len();
+        match self {
+            Mutation::Flip { at, bit } if n != 0 => bytes[at % n] ^= 1 << (bit % 8),
+            Mutation::Set { at, byte } if n != 0 => bytes[at % n] = *byte,
+            Mutation::Insert { at, bytes: inserted } => {
+                let at = at % (n + 1);
+                bytes.splice(at..at, inserted.iter().cloned());
+            }
+            Mutation::Remove { at, len } if n != 0 => {
+                let at = at % n;
+                let end = at + core::cmp::min(*len, n - at);
+                bytes.drain(at..end);
+            }
+            Mutation::Truncate { len } => bytes.truncate(len % (n + 1)),
+            _ => {}
+        }
+    }
+}
+
+/// Apply all `mutations` to `bytes` in order.
+pub fn mutate(bytes: &mut Vec<u8>, mutations: &[Mutation]) {
+    for m in mutations {
+        m.apply(bytes);
+    }
+}
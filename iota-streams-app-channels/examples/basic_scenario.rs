@@ -1,7 +1,12 @@
+use std::env;
+
 use iota_streams_app::transport::{
-    tangle::client::{
-        Client,
-        SendOptions,
+    tangle::{
+        client::{
+            Client,
+            SendOptions,
+        },
+        mock_node::MockNode,
     },
     TransportOptions,
 };
@@ -9,8 +14,16 @@ use iota_streams_app_channels::api::tangle::test::example;
 
 #[tokio::main]
 async fn main() {
-    let send_opt = SendOptions::default();
+    let mut send_opt = SendOptions::default();
     let mut tsp = Client::new_from_url("https://nodes.devnet.iota.org:443");
+
+    // TRANSPORT=mock runs against an in-process stand-in for a node instead
+    let mock_node;
+    if env::var("TRANSPORT").ok().as_deref() == Some("mock") {
+        mock_node = MockNode::spawn().expect("failed to start mock node");
+        send_opt.url = mock_node.url().to_string();
+        tsp = Client::new_from_url(mock_node.url());
+    }
     tsp.set_send_options(send_opt);
     assert!(dbg!(example(tsp).await).is_ok());
 }
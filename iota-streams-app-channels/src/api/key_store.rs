@@ -27,6 +27,12 @@ pub trait KeyStore<Info, F: PRP>: Default {
     fn get_psk(&self, id: &Identifier) -> Option<Psk>;
     fn contains(&self, id: &Identifier) -> bool;
     fn insert_cursor(&mut self, id: Identifier, info: Info) -> Result<()>;
+    /// Replace the x25519 key exchange public key of an already stored publisher
+    fn insert_ke_pk(&mut self, id: Identifier, ke_pk: x25519::PublicKey) -> Result<()>;
+    /// Rotation number of the key exchange key of a stored publisher, if one was announced in a Subscribe message
+    fn get_ke_rotation(&self, id: &Identifier) -> Option<u32>;
+    /// Record the rotation number of the key exchange key of an already stored publisher
+    fn insert_ke_rotation(&mut self, id: Identifier, rotation: u32) -> Result<()>;
     fn insert_psk(&mut self, id: Identifier, psk: Option<Psk>, info: Info) -> Result<()>;
     fn get_next_pskid(&self) -> Option<&Identifier>;
     fn keys(&self) -> Vec<(&Identifier, Vec<u8>)>;
@@ -37,8 +43,11 @@ pub trait KeyStore<Info, F: PRP>: Default {
 
 pub struct KeyMap<Info> {
     /// Map from user identity -- ed25519 pk -- to
-    /// a precalculated corresponding x25519 pk and some additional info.
+    /// its x25519 pk and some additional info. The x25519 pk is precalculated from
+    /// the ed25519 pk unless the user announced a separate one.
     ke_pks: HashMap<Identifier, (x25519::PublicKey, Info)>,
+    /// Rotation numbers of the x25519 pks, only known for the ones announced in Subscribe messages
+    ke_rotations: HashMap<Identifier, u32>,
     psks: HashMap<Identifier, (Option<Psk>, Info)>,
 }
 
@@ -46,6 +55,7 @@ impl<Info> KeyMap<Info> {
     pub fn new() -> Self {
         Self {
             ke_pks: HashMap::new(),
+            ke_rotations: HashMap::new(),
             psks: HashMap::new(),
         }
     }
@@ -126,8 +136,13 @@ impl<Info, F: PRP> KeyStore<Info, F> for KeyMap<Info> {
     fn insert_cursor(&mut self, id: Identifier, info: Info) -> Result<()> {
         match &id {
             Identifier::EdPubKey(pk) => {
-                let store_id = x25519::public_from_ed25519(&pk.0)?;
-                self.ke_pks.insert(id, (store_id, info));
+                // Keep the key exchange key of known publishers, it might not be the derived one
+                if let Some((_x, i)) = self.ke_pks.get_mut(&id) {
+                    *i = info;
+                } else {
+                    let store_id = x25519::public_from_ed25519(&pk.0)?;
+                    self.ke_pks.insert(id, (store_id, info));
+                }
                 Ok(())
             }
             Identifier::PskId(_id) => {
@@ -137,6 +152,29 @@ impl<Info, F: PRP> KeyStore<Info, F> for KeyMap<Info> {
         }
     }
 
+    fn insert_ke_pk(&mut self, id: Identifier, ke_pk: x25519::PublicKey) -> Result<()> {
+        match self.ke_pks.get_mut(&id) {
+            Some((x, _i)) => {
+                *x = ke_pk;
+                Ok(())
+            }
+            None => err(BadIdentifier),
+        }
+    }
+
+    fn get_ke_rotation(&self, id: &Identifier) -> Option<u32> {
+        self.ke_rotations.get(id).copied()
+    }
+
+    fn insert_ke_rotation(&mut self, id: Identifier, rotation: u32) -> Result<()> {
+        if self.ke_pks.contains_key(&id) {
+            self.ke_rotations.insert(id, rotation);
+            Ok(())
+        } else {
+            err(BadIdentifier)
+        }
+    }
+
     fn insert_psk(&mut self, id: Identifier, psk: Option<Psk>, info: Info) -> Result<()> {
         match &id {
             Identifier::PskId(_id) => {
@@ -183,6 +221,7 @@ impl<Info, F: PRP> KeyStore<Info, F> for KeyMap<Info> {
 
     fn remove(&mut self, id: &Identifier) {
         self.ke_pks.borrow_mut().remove(id);
+        self.ke_rotations.remove(id);
         self.psks.borrow_mut().remove(id);
     }
 }
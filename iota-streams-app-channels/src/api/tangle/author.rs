@@ -16,15 +16,20 @@ use iota_streams_app::identifier::Identifier;
 
 use iota_streams_core::{
     prelude::{
+        Box,
         String,
         Vec,
     },
+    prng,
     psk::{
         Psk,
         PskId,
     },
 };
-use iota_streams_core_edsig::signature::ed25519;
+use iota_streams_core_edsig::{
+    key_exchange::x25519,
+    signature::ed25519,
+};
 
 /// Author Object. Contains User API.
 pub struct Author<Trans> {
@@ -47,6 +52,27 @@ impl<Trans> Author<Trans> {
         Self { user }
     }
 
+    /// Create a new Author instance with the given keys.
+    ///
+    /// The keys may be held outside of memory (eg. by an HSM), in which case the author state can not be exported.
+    ///
+    /// # Arguments
+    /// * `sig_kp` - Ed25519 signing key of the author
+    /// * `ke_kp` - X25519 key exchange key of the author
+    /// * `channel_type` - Implementation type: [0: Single Branch, 1: Multi Branch , 2: Single Depth]
+    /// * `transport` - Transport object used for sending and receiving
+    pub fn new_with_keys(
+        sig_kp: Box<dyn ed25519::Signer + Send + Sync>,
+        ke_kp: Box<dyn x25519::KeyAgreement + Send + Sync>,
+        channel_type: ChannelType,
+        transport: Trans,
+    ) -> Self {
+        let mut user = User::new_with_keys(sig_kp, ke_kp, channel_type, transport);
+        let channel_idx = 0_u64;
+        let _ = user.user.create_channel(channel_idx);
+        Self { user }
+    }
+
     /// Returns a clone of the transport object
     pub fn get_transport(&self) -> &Trans {
         self.user.get_transport()
@@ -77,6 +103,29 @@ impl<Trans> Author<Trans> {
         self.user.get_public_key()
     }
 
+    /// Generate a new x25519 key exchange key pair, independent from the ed25519 key pair
+    ///
+    /// Must be called before sending the announcement, subscribers only learn the key of the author from it. Fails
+    /// once the channel is announced.
+    ///
+    /// # Arguments
+    /// * `seed` - A string slice representing the seed of the key exchange key pair
+    pub fn gen_exchange_key(&mut self, seed: &str) -> Result<()> {
+        self.user.gen_exchange_key(seed)
+    }
+
+    /// Replace the random number generator used for session keys, nonces and ephemeral keys, see
+    /// [`User::set_rng`](crate::api::user::User::set_rng)
+    pub fn set_rng(&mut self, rng: Box<dyn prng::CryptoRngCore + Send + Sync>) {
+        self.user.set_rng(rng)
+    }
+
+    /// Set resource limits enforced while unwrapping received messages, see
+    /// [`User::set_unwrap_limits`](crate::api::user::User::set_unwrap_limits)
+    pub fn set_unwrap_limits(&mut self, limits: UnwrapLimits) {
+        self.user.set_unwrap_limits(limits)
+    }
+
     /// Store a PSK in the user instance
     ///
     ///   # Arguments
@@ -162,6 +211,28 @@ impl<Trans> Author<Trans> {
         self.user.reset_state()
     }
 
+    /// Messages rejected since the last call to `take_rejected_messages`, see
+    /// [`User::rejected_messages`](crate::api::tangle::User::rejected_messages)
+    pub fn rejected_messages(&self) -> &[RejectedMessage] {
+        self.user.rejected_messages()
+    }
+
+    /// Take the rejected messages, clearing the record
+    pub fn take_rejected_messages(&mut self) -> Vec<RejectedMessage> {
+        self.user.take_rejected_messages()
+    }
+
+    /// Equivocations detected since the last call to `take_equivocations`, see
+    /// [`User::equivocations`](crate::api::tangle::User::equivocations)
+    pub fn equivocations(&self) -> &[Equivocation] {
+        self.user.equivocations()
+    }
+
+    /// Take the detected equivocations, clearing the record
+    pub fn take_equivocations(&mut self) -> Vec<Equivocation> {
+        self.user.take_equivocations()
+    }
+
     /// Serialize user state and encrypt it with password.
     ///
     ///   # Arguments
@@ -377,7 +448,7 @@ impl<Trans: Clone> fmt::Display for Author<Trans> {
         write!(
             f,
             "<{}>\n{}",
-            hex::encode(self.user.user.sig_kp.public.as_bytes()),
+            hex::encode(self.user.user.sig_kp.public_key().as_bytes()),
             self.user.user.key_store
         )
     }
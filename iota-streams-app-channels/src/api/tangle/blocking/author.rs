@@ -0,0 +1,467 @@
+//! Blocking Author over the Tangle.
+
+use core::fmt;
+
+use iota_streams_app::identifier::Identifier;
+use iota_streams_core::{
+    prelude::{
+        Box,
+        String,
+        Vec,
+    },
+    prng,
+    psk::{
+        Psk,
+        PskId,
+    },
+    Result,
+};
+use iota_streams_core_edsig::{
+    key_exchange::x25519,
+    signature::ed25519,
+};
+
+use super::{
+    DefaultExecutor,
+    Executor,
+    Messages,
+};
+use crate::api::tangle::{
+    Address,
+    Bytes,
+    ChannelAddress,
+    ChannelType,
+    Cursor,
+    Equivocation,
+    PublicKey,
+    RejectedMessage,
+    Transport,
+    UnwrapLimits,
+    UnwrappedMessage,
+};
+
+/// Author Object with a blocking API. Contains an async [`Author`](crate::api::tangle::Author) and the
+/// [`Executor`] used to drive it.
+pub struct Author<Trans, E = DefaultExecutor> {
+    author: crate::api::tangle::Author<Trans>,
+    executor: E,
+}
+
+impl<Trans> Author<Trans> {
+    /// Create a new Author instance, generate new Ed25519 key pair.
+    ///
+    /// # Arguments
+    /// * `seed` - A string slice representing the seed of the user [Characters: A-Z, 9]
+    /// * `channel_type` - Implementation type: [0: Single Branch, 1: Multi Branch , 2: Single Depth]
+    /// * `transport` - Transport object used for sending and receiving
+    pub fn new(seed: &str, channel_type: ChannelType, transport: Trans) -> Self {
+        Self::with_executor(
+            crate::api::tangle::Author::new(seed, channel_type, transport),
+            DefaultExecutor,
+        )
+    }
+
+    /// Create a new Author instance with the given keys.
+    ///
+    /// # Arguments
+    /// * `sig_kp` - Ed25519 signing key of the author
+    /// * `ke_kp` - X25519 key exchange key of the author
+    /// * `channel_type` - Implementation type: [0: Single Branch, 1: Multi Branch , 2: Single Depth]
+    /// * `transport` - Transport object used for sending and receiving
+    pub fn new_with_keys(
+        sig_kp: Box<dyn ed25519::Signer + Send + Sync>,
+        ke_kp: Box<dyn x25519::KeyAgreement + Send + Sync>,
+        channel_type: ChannelType,
+        transport: Trans,
+    ) -> Self {
+        Self::with_executor(
+            crate::api::tangle::Author::new_with_keys(sig_kp, ke_kp, channel_type, transport),
+            DefaultExecutor,
+        )
+    }
+
+    /// Deserialize user state and decrypt it with password.
+    ///
+    ///   # Arguments
+    ///   * `bytes` - Encrypted serialized user state
+    ///   * `pwd` - Encryption password
+    ///   * `tsp` - Transport object
+    pub fn import(bytes: &[u8], pwd: &str, tsp: Trans) -> Result<Self> {
+        Self::import_with_executor(bytes, pwd, tsp, DefaultExecutor)
+    }
+}
+
+impl<Trans: Transport + Clone> Author<Trans> {
+    /// Generates a new Author implementation from input, see
+    /// [`Author::recover`](crate::api::tangle::Author::recover).
+    ///
+    ///  # Arguments
+    /// * `seed` - A string slice representing the seed of the user [Characters: A-Z, 9]
+    /// * `announcement` - An existing announcement message link for validation of ownership
+    /// * `channel_type` - Implementation type: [0: Single Branch, 1: Multi Branch , 2: Single Depth]
+    /// * `transport` - Transport object used for sending and receiving
+    pub fn recover(seed: &str, announcement: &Address, channel_type: ChannelType, transport: Trans) -> Result<Self> {
+        Self::recover_with_executor(seed, announcement, channel_type, transport, DefaultExecutor)
+    }
+}
+
+impl<Trans, E: Executor> Author<Trans, E> {
+    /// Wrap an async Author, driving its operations with `executor`.
+    pub fn with_executor(author: crate::api::tangle::Author<Trans>, executor: E) -> Self {
+        Self { author, executor }
+    }
+
+    /// Deserialize user state and decrypt it with password, driving the operations with `executor`.
+    ///
+    ///   # Arguments
+    ///   * `bytes` - Encrypted serialized user state
+    ///   * `pwd` - Encryption password
+    ///   * `tsp` - Transport object
+    ///   * `executor` - Executor used to drive the operations
+    pub fn import_with_executor(bytes: &[u8], pwd: &str, tsp: Trans, executor: E) -> Result<Self> {
+        let author = executor.block_on(crate::api::tangle::Author::import(bytes, pwd, tsp))?;
+        Ok(Self::with_executor(author, executor))
+    }
+
+    /// Unwrap the async Author.
+    pub fn into_inner(self) -> crate::api::tangle::Author<Trans> {
+        self.author
+    }
+
+    /// Executor driving the operations of this Author.
+    pub fn executor(&self) -> &E {
+        &self.executor
+    }
+
+    /// Returns a clone of the transport object
+    pub fn get_transport(&self) -> &Trans {
+        self.author.get_transport()
+    }
+
+    /// Return boolean representing the sequencing nature of the channel
+    pub fn is_multi_branching(&self) -> bool {
+        self.author.is_multi_branching()
+    }
+
+    /// Return boolean representing whether the implementation type is single depth
+    pub fn is_single_depth(&self) -> bool {
+        self.author.is_single_depth()
+    }
+
+    /// Fetch the Address (application instance) of the channel.
+    pub fn channel_address(&self) -> Option<&ChannelAddress> {
+        self.author.channel_address()
+    }
+
+    /// Fetch the Announcement Link of the channel.
+    pub fn announcement_link(&self) -> &Option<Address> {
+        self.author.announcement_link()
+    }
+
+    /// Fetch the user ed25519 public key
+    pub fn get_public_key(&self) -> &ed25519::PublicKey {
+        self.author.get_public_key()
+    }
+
+    /// Generate a new x25519 key exchange key pair, independent from the ed25519 key pair
+    ///
+    /// Must be called before sending the announcement, see
+    /// [`Author::gen_exchange_key`](crate::api::tangle::Author::gen_exchange_key).
+    ///
+    /// # Arguments
+    /// * `seed` - A string slice representing the seed of the key exchange key pair
+    pub fn gen_exchange_key(&mut self, seed: &str) -> Result<()> {
+        self.author.gen_exchange_key(seed)
+    }
+
+    /// Replace the random number generator used for session keys, nonces and ephemeral keys, see
+    /// [`User::set_rng`](crate::api::user::User::set_rng)
+    pub fn set_rng(&mut self, rng: Box<dyn prng::CryptoRngCore + Send + Sync>) {
+        self.author.set_rng(rng)
+    }
+
+    /// Set resource limits enforced while unwrapping received messages, see
+    /// [`User::set_unwrap_limits`](crate::api::user::User::set_unwrap_limits)
+    pub fn set_unwrap_limits(&mut self, limits: UnwrapLimits) {
+        self.author.set_unwrap_limits(limits)
+    }
+
+    /// Store a PSK in the user instance
+    ///
+    ///   # Arguments
+    ///   * `pskid` - An identifier representing a pre shared key
+    ///   * `psk` - A pre shared key
+    pub fn store_psk(&mut self, pskid: PskId, psk: Psk) -> Result<()> {
+        self.author.store_psk(pskid, psk)
+    }
+
+    /// Remove a PSK from the user instance
+    ///
+    ///   # Arguments
+    ///   * `pskid` - An identifier representing a pre shared key
+    pub fn remove_psk(&mut self, pskid: PskId) -> Result<()> {
+        self.author.remove_psk(pskid)
+    }
+
+    /// Store a predefined Subscriber by their public key
+    ///
+    ///   # Arguments
+    ///   * `pk` - ed25519 public key of known subscriber
+    pub fn store_new_subscriber(&mut self, pk: PublicKey) -> Result<()> {
+        self.author.store_new_subscriber(pk)
+    }
+
+    /// Remove a Subscriber from the user instance
+    ///
+    ///   # Arguments
+    ///   * `pk` - ed25519 public key of known subscriber
+    pub fn remove_subscriber(&mut self, pk: PublicKey) -> Result<()> {
+        self.author.remove_subscriber(pk)
+    }
+
+    /// Generate the next batch of message [`Address`] to poll
+    ///
+    /// Prefer [`Author::messages()`] to traverse the channel, see
+    /// [`Author::gen_next_msg_addresses`](crate::api::tangle::Author::gen_next_msg_addresses).
+    pub fn gen_next_msg_addresses(&self) -> Vec<(Identifier, Cursor<Address>)> {
+        self.author.gen_next_msg_addresses()
+    }
+
+    /// Stores the provided link to the internal sequencing state for the provided participant
+    /// [Used for multi-branching sequence state updates]
+    ///
+    ///   # Arguments
+    ///   * `pk` - ed25519 Public Key of the sender of the message
+    ///   * `link` - Address link to be stored in internal sequence state mapping
+    pub fn store_state(&mut self, pk: Identifier, link: &Address) -> Result<()> {
+        self.author.store_state(pk, link)
+    }
+
+    /// Stores the provided link and sequence number to the internal sequencing state for all participants
+    /// [Used for single-branching sequence state updates]
+    ///
+    ///   # Arguments
+    ///   * `link` - Address link to be stored in internal sequence state mapping
+    ///   * `seq_num` - New sequence state to be stored in internal sequence state mapping
+    pub fn store_state_for_all(&mut self, link: &Address, seq_num: u32) -> Result<()> {
+        self.author.store_state_for_all(link, seq_num)
+    }
+
+    /// Fetches the latest PublicKey -> Cursor state mapping from the implementation, allowing the
+    /// user to see the latest messages present from each publisher
+    pub fn fetch_state(&self) -> Result<Vec<(String, Cursor<Address>)>> {
+        self.author.fetch_state()
+    }
+
+    /// Resets the cursor state storage to allow an Author to retrieve all messages in a channel
+    /// from scratch
+    pub fn reset_state(&mut self) -> Result<()> {
+        self.author.reset_state()
+    }
+
+    /// Messages rejected since the last call to `take_rejected_messages`, see
+    /// [`User::rejected_messages`](crate::api::tangle::User::rejected_messages)
+    pub fn rejected_messages(&self) -> &[RejectedMessage] {
+        self.author.rejected_messages()
+    }
+
+    /// Take the rejected messages, clearing the record
+    pub fn take_rejected_messages(&mut self) -> Vec<RejectedMessage> {
+        self.author.take_rejected_messages()
+    }
+
+    /// Equivocations detected since the last call to `take_equivocations`, see
+    /// [`User::equivocations`](crate::api::tangle::User::equivocations)
+    pub fn equivocations(&self) -> &[Equivocation] {
+        self.author.equivocations()
+    }
+
+    /// Take the detected equivocations, clearing the record
+    pub fn take_equivocations(&mut self) -> Vec<Equivocation> {
+        self.author.take_equivocations()
+    }
+
+    /// Serialize user state and encrypt it with password.
+    ///
+    ///   # Arguments
+    ///   * `pwd` - Encryption password
+    pub fn export(&self, pwd: &str) -> Result<Vec<u8>> {
+        self.executor.block_on(self.author.export(pwd))
+    }
+}
+
+impl<Trans: Transport + Clone, E: Executor> Author<Trans, E> {
+    /// Generates a new Author implementation from input, driving the operations with `executor`.
+    ///
+    ///  # Arguments
+    /// * `seed` - A string slice representing the seed of the user [Characters: A-Z, 9]
+    /// * `announcement` - An existing announcement message link for validation of ownership
+    /// * `channel_type` - Implementation type: [0: Single Branch, 1: Multi Branch , 2: Single Depth]
+    /// * `transport` - Transport object used for sending and receiving
+    /// * `executor` - Executor used to drive the operations
+    pub fn recover_with_executor(
+        seed: &str,
+        announcement: &Address,
+        channel_type: ChannelType,
+        transport: Trans,
+        executor: E,
+    ) -> Result<Self> {
+        let author = executor.block_on(crate::api::tangle::Author::recover(
+            seed,
+            announcement,
+            channel_type,
+            transport,
+        ))?;
+        Ok(Self::with_executor(author, executor))
+    }
+
+    /// Send an announcement message, generating a channel.
+    pub fn send_announce(&mut self) -> Result<Address> {
+        self.executor.block_on(self.author.send_announce())
+    }
+
+    /// Create and send a new keyload for a list of subscribers.
+    ///
+    ///  # Arguments
+    ///  * `link_to` - Address of the message the keyload will be attached to
+    ///  * `keys`  - Iterable of [`Identifier`] to be included in message
+    pub fn send_keyload<'a, I>(&mut self, link_to: &Address, keys: I) -> Result<(Address, Option<Address>)>
+    where
+        I: IntoIterator<Item = &'a Identifier>,
+    {
+        self.executor.block_on(self.author.send_keyload(link_to, keys))
+    }
+
+    /// Create and send keyload for all subscribed subscribers.
+    ///
+    ///  # Arguments
+    ///  * `link_to` - Address of the message the keyload will be attached to
+    pub fn send_keyload_for_everyone(&mut self, link_to: &Address) -> Result<(Address, Option<Address>)> {
+        self.executor.block_on(self.author.send_keyload_for_everyone(link_to))
+    }
+
+    /// Create and send a signed packet.
+    ///
+    ///  # Arguments
+    ///  * `link_to` - Address of the message the keyload will be attached to
+    ///  * `public_payload` - Wrapped vector of Bytes to have public access
+    ///  * `masked_payload` - Wrapped vector of Bytes to have masked access
+    pub fn send_signed_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)> {
+        self.executor
+            .block_on(self.author.send_signed_packet(link_to, public_payload, masked_payload))
+    }
+
+    /// Create and send a tagged packet.
+    ///
+    ///  # Arguments
+    ///  * `link_to` - Address of the message the keyload will be attached to
+    ///  * `public_payload` - Wrapped vector of Bytes to have public access
+    ///  * `masked_payload` - Wrapped vector of Bytes to have masked access
+    pub fn send_tagged_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)> {
+        self.executor
+            .block_on(self.author.send_tagged_packet(link_to, public_payload, masked_payload))
+    }
+
+    /// Receive and process a subscribe message.
+    ///
+    ///  # Arguments
+    ///  * `link` - Address of the message to be processed
+    pub fn receive_subscribe(&mut self, link: &Address) -> Result<()> {
+        self.executor.block_on(self.author.receive_subscribe(link))
+    }
+
+    /// Receive and process an unsubscribe message.
+    ///
+    ///  # Arguments
+    ///  * `link` - Address of the message to be processed
+    pub fn receive_unsubscribe(&mut self, link: &Address) -> Result<()> {
+        self.executor.block_on(self.author.receive_unsubscribe(link))
+    }
+
+    /// Receive and process a signed packet message.
+    ///
+    ///  # Arguments
+    ///  * `link` - Address of the message to be processed
+    pub fn receive_signed_packet(&mut self, link: &Address) -> Result<(ed25519::PublicKey, Bytes, Bytes)> {
+        self.executor.block_on(self.author.receive_signed_packet(link))
+    }
+
+    /// Receive and process a tagged packet message.
+    ///
+    ///  # Arguments
+    ///  * `link` - Address of the message to be processed
+    pub fn receive_tagged_packet(&mut self, link: &Address) -> Result<(Bytes, Bytes)> {
+        self.executor.block_on(self.author.receive_tagged_packet(link))
+    }
+
+    /// Receive and process a sequence message.
+    ///
+    ///  # Arguments
+    ///  * `link` - Address of the message to be processed
+    pub fn receive_sequence(&mut self, link: &Address) -> Result<Address> {
+        self.executor.block_on(self.author.receive_sequence(link))
+    }
+
+    /// Start a blocking [`Messages`] iterator to traverse the channel messages
+    pub fn messages(&mut self) -> Messages<'_, Trans, E> {
+        Messages::new(self.author.messages(), &self.executor)
+    }
+
+    /// Iteratively fetches all the pending messages from the transport
+    pub fn fetch_next_msgs(&mut self) -> Result<Vec<UnwrappedMessage>> {
+        self.executor.block_on(self.author.fetch_next_msgs())
+    }
+
+    /// Retrieves the previous message from the message specified (provided the user has access to it)
+    pub fn fetch_prev_msg(&mut self, link: &Address) -> Result<UnwrappedMessage> {
+        self.executor.block_on(self.author.fetch_prev_msg(link))
+    }
+
+    /// Retrieves a specified number of previous messages from an original specified messsage link
+    pub fn fetch_prev_msgs(&mut self, link: &Address, max: usize) -> Result<Vec<UnwrappedMessage>> {
+        self.executor.block_on(self.author.fetch_prev_msgs(link, max))
+    }
+
+    /// Iteratively fetches all the next messages until internal state has caught up
+    ///
+    /// If succeeded, returns the number of messages advanced.
+    pub fn sync_state(&mut self) -> Result<usize> {
+        self.executor.block_on(self.author.sync_state())
+    }
+
+    /// Receive and process a message of unknown type. Message will be handled appropriately and
+    /// the unwrapped contents returned
+    ///
+    ///   # Arguments
+    ///   * `link` - Address of the message to be processed
+    pub fn receive_msg(&mut self, link: &Address) -> Result<UnwrappedMessage> {
+        self.executor.block_on(self.author.receive_msg(link))
+    }
+
+    /// Receive and process a message with a known anchor link and message number. This can only
+    /// be used if the channel is a single depth channel.
+    ///
+    ///   # Arguments
+    ///   * `anchor_link` - Address of the anchor message for the channel
+    ///   * `msg_num` - Sequence of sent message (not counting announce or any keyloads)
+    pub fn receive_msg_by_sequence_number(&mut self, anchor_link: &Address, msg_num: u32) -> Result<UnwrappedMessage> {
+        self.executor
+            .block_on(self.author.receive_msg_by_sequence_number(anchor_link, msg_num))
+    }
+}
+
+impl<Trans: Clone, E> fmt::Display for Author<Trans, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.author, f)
+    }
+}
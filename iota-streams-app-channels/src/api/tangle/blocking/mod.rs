@@ -0,0 +1,174 @@
+//! Blocking facade over the Tangle-specific [`Author`](super::Author) and [`Subscriber`](super::Subscriber).
+//!
+//! Every operation that involves the transport is driven to completion on the calling thread by an
+//! [`Executor`]. The built-in [`DefaultExecutor`] needs no async runtime; an executor backed by an existing
+//! runtime can be provided instead when the transport depends on one (eg. a Tangle client running on tokio).
+
+use core::{
+    future::Future,
+    task::{
+        Context,
+        Poll,
+        Waker,
+    },
+};
+
+use futures::pin_mut;
+
+use iota_streams_core::Result;
+
+use super::{
+    Transport,
+    UnwrappedMessage,
+};
+
+mod author;
+pub use author::Author;
+
+mod subscriber;
+pub use subscriber::Subscriber;
+
+/// Runs futures to completion, blocking the calling thread.
+pub trait Executor {
+    /// Block the calling thread until `future` completes and return its output.
+    fn block_on<F: Future>(&self, future: F) -> F::Output;
+}
+
+impl<E: Executor + ?Sized> Executor for &E {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        (**self).block_on(future)
+    }
+}
+
+/// Minimal built-in [`Executor`].
+///
+/// With `std` the calling thread is parked until the future is woken. Without `std` the future is polled in a
+/// busy loop, which suits transports that complete without waiting for I/O, such as
+/// [`BucketTransport`](super::BucketTransport).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultExecutor;
+
+impl Executor for DefaultExecutor {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        pin_mut!(future);
+        let waker = waker();
+        let mut ctx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut ctx) {
+                return output;
+            }
+            wait();
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+struct ThreadWaker(std::thread::Thread);
+
+#[cfg(feature = "std")]
+impl std::task::Wake for ThreadWaker {
+    fn wake(self: std::sync::Arc<Self>) {
+        self.0.unpark()
+    }
+
+    fn wake_by_ref(self: &std::sync::Arc<Self>) {
+        self.0.unpark()
+    }
+}
+
+#[cfg(feature = "std")]
+fn waker() -> Waker {
+    Waker::from(std::sync::Arc::new(ThreadWaker(std::thread::current())))
+}
+
+#[cfg(feature = "std")]
+fn wait() {
+    std::thread::park()
+}
+
+#[cfg(not(feature = "std"))]
+fn waker() -> Waker {
+    futures::task::noop_waker()
+}
+
+#[cfg(not(feature = "std"))]
+fn wait() {
+    core::hint::spin_loop()
+}
+
+/// Blocking iterator over the channel messages.
+///
+/// Each call to [`Iterator::next`] blocks on the next item of the underlying [`Messages`](super::Messages) stream.
+pub struct Messages<'a, Trans, E> {
+    messages: super::Messages<'a, Trans>,
+    executor: &'a E,
+}
+
+impl<'a, Trans, E> Messages<'a, Trans, E> {
+    fn new(messages: super::Messages<'a, Trans>, executor: &'a E) -> Self {
+        Self { messages, executor }
+    }
+}
+
+impl<'a, Trans, E> Iterator for Messages<'a, Trans, E>
+where
+    Trans: Transport,
+    E: Executor,
+{
+    type Item = Result<UnwrappedMessage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.executor.block_on(self.messages.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::RefCell;
+
+    use iota_streams_core::prelude::{
+        Rc,
+        Vec,
+    };
+
+    use super::{
+        Author,
+        Subscriber,
+    };
+    #[cfg(not(feature = "std"))]
+    use crate::api::tangle::test::test_rng;
+    use crate::api::tangle::{
+        BucketTransport,
+        ChannelType,
+        MessageContent,
+    };
+
+    #[test]
+    fn blocking_author_and_subscriber() -> iota_streams_core::Result<()> {
+        let transport = Rc::new(RefCell::new(BucketTransport::new()));
+        let mut author = Author::new("blocking author seed", ChannelType::SingleBranch, transport.clone());
+        let mut subscriber = Subscriber::new("blocking subscriber seed", transport);
+        #[cfg(not(feature = "std"))]
+        author.set_rng(test_rng("blocking author rng"));
+        #[cfg(not(feature = "std"))]
+        subscriber.set_rng(test_rng("blocking subscriber rng"));
+
+        let announcement_link = author.send_announce()?;
+        subscriber.receive_announcement(&announcement_link)?;
+        let subscription_link = subscriber.send_subscribe(&announcement_link)?;
+        author.receive_subscribe(&subscription_link)?;
+        let (keyload_link, _) = author.send_keyload_for_everyone(&announcement_link)?;
+        let (packet_link, _) = author.send_signed_packet(&keyload_link, &b"public".into(), &b"masked".into())?;
+
+        let messages = subscriber.messages().collect::<iota_streams_core::Result<Vec<_>>>()?;
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].link, keyload_link);
+        assert_eq!(messages[1].link, packet_link);
+        assert_eq!(
+            messages[1].body,
+            MessageContent::new_signed_packet(*author.get_public_key(), b"public", b"masked")
+        );
+        assert!(subscriber.messages().next().is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,415 @@
+//! Blocking Subscriber over the Tangle.
+
+use core::fmt;
+
+use iota_streams_app::identifier::Identifier;
+use iota_streams_core::{
+    prelude::{
+        Box,
+        String,
+        Vec,
+    },
+    prng,
+    psk::{
+        Psk,
+        PskId,
+    },
+    Result,
+};
+use iota_streams_core_edsig::{
+    key_exchange::x25519,
+    signature::ed25519,
+};
+
+use super::{
+    DefaultExecutor,
+    Executor,
+    Messages,
+};
+use crate::api::tangle::{
+    Address,
+    Bytes,
+    ChannelAddress,
+    Cursor,
+    Equivocation,
+    RejectedMessage,
+    Transport,
+    UnwrapLimits,
+    UnwrappedMessage,
+};
+
+/// Subscriber Object with a blocking API. Contains an async [`Subscriber`](crate::api::tangle::Subscriber) and
+/// the [`Executor`] used to drive it.
+pub struct Subscriber<Trans, E = DefaultExecutor> {
+    subscriber: crate::api::tangle::Subscriber<Trans>,
+    executor: E,
+}
+
+impl<Trans> Subscriber<Trans> {
+    /// Create a new Subscriber instance, generate new Ed25519 key pair.
+    ///
+    /// # Arguments
+    /// * `seed` - A string slice representing the seed of the user [Characters: A-Z, 9]
+    /// * `transport` - Transport object used for sending and receiving
+    pub fn new(seed: &str, transport: Trans) -> Self {
+        Self::with_executor(crate::api::tangle::Subscriber::new(seed, transport), DefaultExecutor)
+    }
+
+    /// Create a new Subscriber instance with the given keys.
+    ///
+    /// # Arguments
+    /// * `sig_kp` - Ed25519 signing key of the subscriber
+    /// * `ke_kp` - X25519 key exchange key of the subscriber
+    /// * `transport` - Transport object used for sending and receiving
+    pub fn new_with_keys(
+        sig_kp: Box<dyn ed25519::Signer + Send + Sync>,
+        ke_kp: Box<dyn x25519::KeyAgreement + Send + Sync>,
+        transport: Trans,
+    ) -> Self {
+        Self::with_executor(
+            crate::api::tangle::Subscriber::new_with_keys(sig_kp, ke_kp, transport),
+            DefaultExecutor,
+        )
+    }
+
+    /// Deserialize user state and decrypt it with password.
+    ///
+    ///   # Arguments
+    ///   * `bytes` - Encrypted serialized user state
+    ///   * `pwd` - Encryption password
+    ///   * `tsp` - Transport object
+    pub fn import(bytes: &[u8], pwd: &str, tsp: Trans) -> Result<Self> {
+        Self::import_with_executor(bytes, pwd, tsp, DefaultExecutor)
+    }
+}
+
+impl<Trans, E: Executor> Subscriber<Trans, E> {
+    /// Wrap an async Subscriber, driving its operations with `executor`.
+    pub fn with_executor(subscriber: crate::api::tangle::Subscriber<Trans>, executor: E) -> Self {
+        Self { subscriber, executor }
+    }
+
+    /// Deserialize user state and decrypt it with password, driving the operations with `executor`.
+    ///
+    ///   # Arguments
+    ///   * `bytes` - Encrypted serialized user state
+    ///   * `pwd` - Encryption password
+    ///   * `tsp` - Transport object
+    ///   * `executor` - Executor used to drive the operations
+    pub fn import_with_executor(bytes: &[u8], pwd: &str, tsp: Trans, executor: E) -> Result<Self> {
+        let subscriber = executor.block_on(crate::api::tangle::Subscriber::import(bytes, pwd, tsp))?;
+        Ok(Self::with_executor(subscriber, executor))
+    }
+
+    /// Unwrap the async Subscriber.
+    pub fn into_inner(self) -> crate::api::tangle::Subscriber<Trans> {
+        self.subscriber
+    }
+
+    /// Executor driving the operations of this Subscriber.
+    pub fn executor(&self) -> &E {
+        &self.executor
+    }
+
+    /// Returns a clone of the transport object
+    pub fn get_transport(&self) -> &Trans {
+        self.subscriber.get_transport()
+    }
+
+    /// Returns a boolean representing whether an Announcement message has been processed
+    pub fn is_registered(&self) -> bool {
+        self.subscriber.is_registered()
+    }
+
+    /// Clears inner state except for own keys and link store.
+    pub fn unregister(&mut self) {
+        self.subscriber.unregister()
+    }
+
+    /// Fetch the user ed25519 public key
+    pub fn get_public_key(&self) -> &ed25519::PublicKey {
+        self.subscriber.get_public_key()
+    }
+
+    /// Generate a new x25519 key exchange key pair, independent from the ed25519 key pair
+    ///
+    /// # Arguments
+    /// * `seed` - A string slice representing the seed of the key exchange key pair
+    pub fn gen_exchange_key(&mut self, seed: &str) -> Result<()> {
+        self.subscriber.gen_exchange_key(seed)
+    }
+
+    /// Go back to the x25519 key exchange key pair derived from the ed25519 key pair
+    pub fn reset_exchange_key(&mut self) -> Result<()> {
+        self.subscriber.reset_exchange_key()
+    }
+
+    /// Replace the random number generator used for session keys, nonces and ephemeral keys, see
+    /// [`User::set_rng`](crate::api::user::User::set_rng)
+    pub fn set_rng(&mut self, rng: Box<dyn prng::CryptoRngCore + Send + Sync>) {
+        self.subscriber.set_rng(rng)
+    }
+
+    /// Set resource limits enforced while unwrapping received messages, see
+    /// [`User::set_unwrap_limits`](crate::api::user::User::set_unwrap_limits)
+    pub fn set_unwrap_limits(&mut self, limits: UnwrapLimits) {
+        self.subscriber.set_unwrap_limits(limits)
+    }
+
+    /// Channel Author's signature public key
+    pub fn author_public_key(&self) -> Option<&ed25519::PublicKey> {
+        self.subscriber.author_public_key()
+    }
+
+    /// Store a PSK in the user instance
+    ///
+    ///   # Arguments
+    ///   * `pskid` - An identifier representing a pre shared key
+    ///   * `psk` - A pre shared key
+    pub fn store_psk(&mut self, pskid: PskId, psk: Psk) -> Result<()> {
+        self.subscriber.store_psk(pskid, psk)
+    }
+
+    /// Remove a PSK from the user instance
+    ///
+    ///   # Arguments
+    ///   * `pskid` - An identifier representing a pre shared key
+    pub fn remove_psk(&mut self, pskid: PskId) -> Result<()> {
+        self.subscriber.remove_psk(pskid)
+    }
+
+    /// Fetch the Address (application instance) of the channel.
+    pub fn channel_address(&self) -> Option<&ChannelAddress> {
+        self.subscriber.channel_address()
+    }
+
+    /// Fetch the Announcement Link of the channel.
+    pub fn announcement_link(&self) -> &Option<Address> {
+        self.subscriber.announcement_link()
+    }
+
+    /// Return boolean representing the sequencing nature of the channel
+    pub fn is_multi_branching(&self) -> bool {
+        self.subscriber.is_multi_branching()
+    }
+
+    /// Return boolean representing whether the implementation type is single depth
+    pub fn is_single_depth(&self) -> bool {
+        self.subscriber.is_single_depth()
+    }
+
+    /// Stores the provided link to the internal sequencing state for the provided participant
+    /// [Used for multi-branching sequence state updates]
+    ///
+    ///   # Arguments
+    ///   * `id` - Identifier of the sender of the message
+    ///   * `link` - Address link to be stored in internal sequence state mapping
+    pub fn store_state(&mut self, id: Identifier, link: &Address) -> Result<()> {
+        self.subscriber.store_state(id, link)
+    }
+
+    /// Stores the provided link and sequence number to the internal sequencing state for all participants
+    /// [Used for single-branching sequence state updates]
+    ///
+    ///   # Arguments
+    ///   * `link` - Address link to be stored in internal sequence state mapping
+    ///   * `seq_num` - New sequence state to be stored in internal sequence state mapping
+    pub fn store_state_for_all(&mut self, link: &Address, seq_num: u32) -> Result<()> {
+        self.subscriber.store_state_for_all(link, seq_num)
+    }
+
+    /// Fetches the latest PublicKey -> Cursor state mapping from the implementation, allowing the
+    /// user to see the latest messages present from each publisher
+    pub fn fetch_state(&self) -> Result<Vec<(String, Cursor<Address>)>> {
+        self.subscriber.fetch_state()
+    }
+
+    /// Resets the cursor state storage to allow a Subscriber to retrieve all messages in a channel
+    /// from scratch
+    pub fn reset_state(&mut self) -> Result<()> {
+        self.subscriber.reset_state()
+    }
+
+    /// Messages rejected since the last call to `take_rejected_messages`, see
+    /// [`User::rejected_messages`](crate::api::tangle::User::rejected_messages)
+    pub fn rejected_messages(&self) -> &[RejectedMessage] {
+        self.subscriber.rejected_messages()
+    }
+
+    /// Take the rejected messages, clearing the record
+    pub fn take_rejected_messages(&mut self) -> Vec<RejectedMessage> {
+        self.subscriber.take_rejected_messages()
+    }
+
+    /// Equivocations detected since the last call to `take_equivocations`, see
+    /// [`User::equivocations`](crate::api::tangle::User::equivocations)
+    pub fn equivocations(&self) -> &[Equivocation] {
+        self.subscriber.equivocations()
+    }
+
+    /// Take the detected equivocations, clearing the record
+    pub fn take_equivocations(&mut self) -> Vec<Equivocation> {
+        self.subscriber.take_equivocations()
+    }
+
+    /// Generate the next batch of message [`Address`] to poll
+    ///
+    /// Prefer [`Subscriber::messages()`] to traverse the channel, see
+    /// [`Subscriber::gen_next_msg_addresses`](crate::api::tangle::Subscriber::gen_next_msg_addresses).
+    pub fn gen_next_msg_addresses(&self) -> Vec<(Identifier, Cursor<Address>)> {
+        self.subscriber.gen_next_msg_addresses()
+    }
+
+    /// Serialize user state and encrypt it with password.
+    ///
+    ///   # Arguments
+    ///   * `pwd` - Encryption password
+    pub fn export(&self, pwd: &str) -> Result<Vec<u8>> {
+        self.executor.block_on(self.subscriber.export(pwd))
+    }
+}
+
+impl<Trans: Transport + Clone, E: Executor> Subscriber<Trans, E> {
+    /// Create and Send a Subscribe message to a Channel app instance.
+    ///
+    /// # Arguments
+    /// * `link_to` - Address of the Channel Announcement message
+    pub fn send_subscribe(&mut self, link_to: &Address) -> Result<Address> {
+        self.executor.block_on(self.subscriber.send_subscribe(link_to))
+    }
+
+    /// Create and send a tagged packet.
+    ///
+    ///  # Arguments
+    ///  * `link_to` - Address of the message the keyload will be attached to
+    ///  * `public_payload` - Wrapped vector of Bytes to have public access
+    ///  * `masked_payload` - Wrapped vector of Bytes to have masked access
+    pub fn send_tagged_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)> {
+        self.executor.block_on(
+            self.subscriber
+                .send_tagged_packet(link_to, public_payload, masked_payload),
+        )
+    }
+
+    /// Create and send a signed packet.
+    ///
+    ///  # Arguments
+    ///  * `link_to` - Address of the message the keyload will be attached to
+    ///  * `public_payload` - Wrapped vector of Bytes to have public access
+    ///  * `masked_payload` - Wrapped vector of Bytes to have masked access
+    pub fn send_signed_packet(
+        &mut self,
+        link_to: &Address,
+        public_payload: &Bytes,
+        masked_payload: &Bytes,
+    ) -> Result<(Address, Option<Address>)> {
+        self.executor.block_on(
+            self.subscriber
+                .send_signed_packet(link_to, public_payload, masked_payload),
+        )
+    }
+
+    /// Send an Unsubscribe message to inform the Author that you would like to be removed
+    /// from the channel instance.
+    pub fn send_unsubscribe(&mut self, link_to: &Address) -> Result<Address> {
+        self.executor.block_on(self.subscriber.send_unsubscribe(link_to))
+    }
+
+    /// Receive and Process an announcement message.
+    ///
+    /// # Arguments
+    /// * `link` - Address of the Channel Announcement message
+    pub fn receive_announcement(&mut self, link: &Address) -> Result<()> {
+        self.executor.block_on(self.subscriber.receive_announcement(link))
+    }
+
+    /// Receive and process a keyload message.
+    ///
+    ///  # Arguments
+    ///  * `link` - Address of the message to be processed
+    pub fn receive_keyload(&mut self, link: &Address) -> Result<bool> {
+        self.executor.block_on(self.subscriber.receive_keyload(link))
+    }
+
+    /// Receive and process a signed packet message.
+    ///
+    ///  # Arguments
+    ///  * `link` - Address of the message to be processed
+    pub fn receive_signed_packet(&mut self, link: &Address) -> Result<(ed25519::PublicKey, Bytes, Bytes)> {
+        self.executor.block_on(self.subscriber.receive_signed_packet(link))
+    }
+
+    /// Receive and process a tagged packet message.
+    ///
+    ///  # Arguments
+    ///  * `link` - Address of the message to be processed
+    pub fn receive_tagged_packet(&mut self, link: &Address) -> Result<(Bytes, Bytes)> {
+        self.executor.block_on(self.subscriber.receive_tagged_packet(link))
+    }
+
+    /// Receive and process a sequence message.
+    ///
+    ///  # Arguments
+    ///  * `link` - Address of the message to be processed
+    pub fn receive_sequence(&mut self, link: &Address) -> Result<Address> {
+        self.executor.block_on(self.subscriber.receive_sequence(link))
+    }
+
+    /// Start a blocking [`Messages`] iterator to traverse the channel messages
+    pub fn messages(&mut self) -> Messages<'_, Trans, E> {
+        Messages::new(self.subscriber.messages(), &self.executor)
+    }
+
+    /// Retrieves the previous message from the message specified (provided the user has access to it)
+    pub fn fetch_prev_msg(&mut self, link: &Address) -> Result<UnwrappedMessage> {
+        self.executor.block_on(self.subscriber.fetch_prev_msg(link))
+    }
+
+    /// Retrieves a specified number of previous messages from an original specified messsage link
+    pub fn fetch_prev_msgs(&mut self, link: &Address, max: usize) -> Result<Vec<UnwrappedMessage>> {
+        self.executor.block_on(self.subscriber.fetch_prev_msgs(link, max))
+    }
+
+    /// Iteratively fetches all the pending messages from the transport
+    pub fn fetch_next_msgs(&mut self) -> Result<Vec<UnwrappedMessage>> {
+        self.executor.block_on(self.subscriber.fetch_next_msgs())
+    }
+
+    /// Iteratively fetches all the next messages until internal state has caught up
+    ///
+    /// If succeeded, returns the number of messages advanced.
+    pub fn sync_state(&mut self) -> Result<usize> {
+        self.executor.block_on(self.subscriber.sync_state())
+    }
+
+    /// Receive and process a message of unknown type. Message will be handled appropriately and
+    /// the unwrapped contents returned
+    ///
+    ///   # Arguments
+    ///   * `link` - Address of the message to be processed
+    pub fn receive_msg(&mut self, link: &Address) -> Result<UnwrappedMessage> {
+        self.executor.block_on(self.subscriber.receive_msg(link))
+    }
+
+    /// Receive and process a message with a known anchor link and message number. This can only
+    /// be used if the channel is a single depth channel.
+    ///
+    ///   # Arguments
+    ///   * `anchor_link` - Address of the anchor message for the channel
+    ///   * `msg_num` - Sequence of sent message (not counting announce or any keyloads)
+    pub fn receive_msg_by_sequence_number(&mut self, anchor_link: &Address, msg_num: u32) -> Result<UnwrappedMessage> {
+        self.executor
+            .block_on(self.subscriber.receive_msg_by_sequence_number(anchor_link, msg_num))
+    }
+}
+
+impl<Trans: Transport + Clone, E> fmt::Display for Subscriber<Trans, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.subscriber, f)
+    }
+}
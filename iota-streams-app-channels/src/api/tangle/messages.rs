@@ -14,10 +14,7 @@ use futures::{
     StreamExt,
     TryStreamExt,
 };
-use iota_streams_app::{
-    identifier::Identifier,
-    message::LinkedMessage,
-};
+use iota_streams_app::message::LinkedMessage;
 use iota_streams_core::{
     prelude::{
         Box,
@@ -29,6 +26,7 @@ use iota_streams_core::{
 };
 
 use super::{
+    user::handle_candidates,
     Address,
     BinaryMessage,
     Cursor,
@@ -564,7 +562,8 @@ type PinBoxFut<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
 
 pub struct MessagesState<'a, Trans> {
     user: &'a mut User<Trans>,
-    ids_stack: Vec<(Identifier, Cursor<Address>)>,
+    // Next link of each publisher fetched in the current round, along with the messages found
+    fetched: Vec<(Address, Result<Vec<BinaryMessage>>)>,
     msg_queue: HashMap<Address, VecDeque<BinaryMessage>>,
     stage: VecDeque<BinaryMessage>,
     successful_round: bool,
@@ -574,7 +573,7 @@ impl<'a, Trans> MessagesState<'a, Trans> {
     pub fn new(user: &'a mut User<Trans>) -> Self {
         Self {
             user,
-            ids_stack: Vec::new(),
+            fetched: Vec::new(),
             msg_queue: HashMap::new(),
             stage: VecDeque::new(),
             successful_round: false,
@@ -592,79 +591,54 @@ impl<'a, Trans> MessagesState<'a, Trans> {
         if let Some(binary_msg) = self.stage.pop_front() {
             // Drain stage if not empty...
             match self.user.handle_message(&binary_msg, true).await {
-                Ok(UnwrappedMessage {
-                    body: MessageContent::Unreadable(unreadable_binary),
-                    prev_link,
-                    link,
-                }) => {
-                    // The message might be unreadable because it's predecessor might still be pending
-                    // to be retrieved from the Tangle. We could defensively check if the predecessor
-                    // is already present in the state, but we don't want to couple this iterator to
-                    // a memory-intensive storage. Instead, we take the optimistic approach and store
-                    // the msg for later if the handling has failed.
-                    self.msg_queue
-                        .entry(prev_link)
-                        .or_default()
-                        .push_back(unreadable_binary);
-
-                    // If the handled message is a sequence_message, unreadable_binary is its referenced msg,
-                    // not the sequence msg itself. However, messages can be linked to either. The sequence
-                    // message has already been read successfuly, thus we need to awake any messages linked to it.
-                    // Currently inferring it's a sequence message by checking if the original_link
-                    // is different from resulting readable_msg link:
-                    if *binary_msg.link() != link {
-                        if let Some(msgs) = self.msg_queue.remove(binary_msg.link()) {
-                            self.stage.extend(msgs);
-                        }
-                    }
-
-                    self.next().await
-                }
-                Ok(readable_msg) => {
-                    // Check if message has descendants pending to process and stage them for processing
-                    if let Some(msgs) = self.msg_queue.remove(readable_msg.link()) {
-                        self.stage.extend(msgs);
-                    }
-
-                    // If the handled message is a sequence_message, readable_msg is its referenced msg,
-                    // not the sequence msg itself. However, messages can be linked to either.
-                    // Currently inferring it's a sequence message by checking if the original_link
-                    // is different from resulting readable_msg link:
-                    if binary_msg.link() != readable_msg.link() {
-                        if let Some(msgs) = self.msg_queue.remove(binary_msg.link()) {
-                            self.stage.extend(msgs);
-                        }
-                    }
-
-                    Some(Ok(readable_msg))
-                }
+                Ok(handled) => match self.process(*binary_msg.link(), handled) {
+                    Some(readable_msg) => Some(Ok(readable_msg)),
+                    None => self.next().await,
+                },
                 // message-Handling errors are a normal execution path, just skip them
-                Err(_e) => self.next().await,
+                Err(_) => self.next().await,
             }
         } else {
             // Stage is empty, populate it with some more messages
-            let (_id, Cursor { link, .. }) = match self.ids_stack.pop() {
-                Some(id_cursor) => id_cursor,
+            let (link, fetched) = match self.fetched.pop() {
+                Some(fetched) => fetched,
                 None => {
-                    let mut id_cursors = self.user.gen_next_msg_addresses();
-                    let last = id_cursors.pop()?;
-                    self.ids_stack = id_cursors;
-                    self.successful_round = false; // new round
-                    last
+                    // New round, the next links of all the publishers are fetched in one batch
+                    let links: Vec<Address> = self
+                        .user
+                        .gen_next_msg_addresses()
+                        .into_iter()
+                        .map(|(_id, Cursor { link, .. })| link)
+                        .collect();
+                    if links.is_empty() {
+                        return None;
+                    }
+                    let results = self.user.transport.recv_messages_many(&links).await;
+                    self.fetched = links.into_iter().zip(results).collect();
+                    self.successful_round = false;
+                    self.fetched.pop()?
                 }
             };
-            match self.user.transport.recv_message(&link).await {
-                Ok(msg) => {
-                    self.stage.push_back(msg);
+            match fetched {
+                Ok(msgs) if !msgs.is_empty() => {
                     self.successful_round = true;
-                    self.next().await
+                    // Anyone can publish at a link, try all messages until one is accepted
+                    let user = &mut *self.user;
+                    match handle_candidates!(user, &link, msgs, |msg| user.handle_message(&msg, true).await) {
+                        Ok(handled) => match self.process(link, handled) {
+                            Some(readable_msg) => Some(Ok(readable_msg)),
+                            None => self.next().await,
+                        },
+                        // message-Handling errors are a normal execution path, just skip them
+                        Err(_) => self.next().await,
+                    }
                 }
-                Err(_e) => {
+                _ => {
                     // Message not found or network error. Right now we are not distinguishing
                     // between each case, so we must assume it's message not found.
                     // When we introduce typed error handling and are able to distinguish,
                     // Return Err(e) if error is network-related or any other transient error
-                    if self.ids_stack.is_empty() && !self.successful_round {
+                    if self.fetched.is_empty() && !self.successful_round {
                         // After trying all ids, none has produced an existing link, end of stream (for now...)
                         None
                     } else {
@@ -675,6 +649,59 @@ impl<'a, Trans> MessagesState<'a, Trans> {
             }
         }
     }
+
+    /// Queue or stage the messages depending on a message handled at a link, evaluating to the message
+    /// if it is readable.
+    fn process(&mut self, link: Address, handled: UnwrappedMessage) -> Option<UnwrappedMessage> {
+        match handled {
+            UnwrappedMessage {
+                body: MessageContent::Unreadable(unreadable_binary),
+                prev_link,
+                link: unreadable_link,
+            } => {
+                // The message might be unreadable because it's predecessor might still be pending
+                // to be retrieved from the Tangle. We could defensively check if the predecessor
+                // is already present in the state, but we don't want to couple this iterator to
+                // a memory-intensive storage. Instead, we take the optimistic approach and store
+                // the msg for later if the handling has failed.
+                self.msg_queue
+                    .entry(prev_link)
+                    .or_default()
+                    .push_back(unreadable_binary);
+
+                // If the handled message is a sequence_message, unreadable_binary is its referenced msg,
+                // not the sequence msg itself. However, messages can be linked to either. The sequence
+                // message has already been read successfuly, thus we need to awake any messages linked to it.
+                // Currently inferring it's a sequence message by checking if the original_link
+                // is different from resulting readable_msg link:
+                if link != unreadable_link {
+                    if let Some(msgs) = self.msg_queue.remove(&link) {
+                        self.stage.extend(msgs);
+                    }
+                }
+
+                None
+            }
+            readable_msg => {
+                // Check if message has descendants pending to process and stage them for processing
+                if let Some(msgs) = self.msg_queue.remove(readable_msg.link()) {
+                    self.stage.extend(msgs);
+                }
+
+                // If the handled message is a sequence_message, readable_msg is its referenced msg,
+                // not the sequence msg itself. However, messages can be linked to either.
+                // Currently inferring it's a sequence message by checking if the original_link
+                // is different from resulting readable_msg link:
+                if link != *readable_msg.link() {
+                    if let Some(msgs) = self.msg_queue.remove(&link) {
+                        self.stage.extend(msgs);
+                    }
+                }
+
+                Some(readable_msg)
+            }
+        }
+    }
 }
 
 impl<'a, Trans> Messages<'a, Trans>
@@ -755,11 +782,10 @@ where
 #[cfg(test)]
 mod tests {
 
-    use std::{
-        cell::RefCell,
-        rc::Rc,
-    };
+    use core::cell::RefCell;
 
+    #[cfg(not(feature = "std"))]
+    use crate::api::tangle::test::test_rng;
     use crate::{
         api::tangle::BucketTransport,
         Address,
@@ -767,7 +793,10 @@ mod tests {
         ChannelType,
         Subscriber,
     };
-    use iota_streams_core::Result;
+    use iota_streams_core::{
+        prelude::Rc,
+        Result,
+    };
 
     type Transport = Rc<RefCell<BucketTransport>>;
 
@@ -833,6 +862,8 @@ mod tests {
     async fn author_subscriber_fixture() -> Result<(Author<Transport>, Subscriber<Transport>, Address, Transport)> {
         let transport = Rc::new(RefCell::new(BucketTransport::new()));
         let mut author = Author::new("author", ChannelType::MultiBranch, transport.clone());
+        #[cfg(not(feature = "std"))]
+        author.set_rng(test_rng("author"));
         let announcement_link = author.send_announce().await?;
         let subscriber = subscriber_fixture("subscriber", &mut author, &announcement_link, transport.clone()).await?;
         Ok((author, subscriber, announcement_link, transport))
@@ -845,6 +876,8 @@ mod tests {
         transport: Transport,
     ) -> Result<Subscriber<Transport>> {
         let mut subscriber = Subscriber::new(seed, transport);
+        #[cfg(not(feature = "std"))]
+        subscriber.set_rng(test_rng(seed));
         subscriber.receive_announcement(announcement_link).await?;
         let subscription = subscriber.send_subscribe(announcement_link).await?;
         author.receive_subscribe(&subscription).await?;
@@ -29,9 +29,17 @@ pub use message::LinkGenerator;
 
 pub use super::ChannelType;
 use super::DefaultF;
-use iota_streams_core::psk;
+use iota_streams_core::{
+    prelude::{
+        Box,
+        String,
+    },
+    psk,
+};
 use iota_streams_ddml::link_store::DefaultLinkStore;
 pub use iota_streams_ddml::types::Bytes;
+/// Resource limits enforced while unwrapping received messages
+pub use iota_streams_ddml::command::unwrap::Limits as UnwrapLimits;
 
 use iota_streams_core_edsig::signature::ed25519;
 
@@ -80,6 +88,9 @@ pub type BucketTransport = transport::BucketTransport<Address, Message>;
 pub trait Transport: transport::Transport<Address, Message> + Clone {}
 impl<T> Transport for T where T: transport::Transport<Address, Message> + Clone {}
 
+/// Transport picked at runtime, e.g. `Box::new(BucketTransport::new()) as BoxedTransport`.
+pub type BoxedTransport = Box<dyn transport::DynTransport<Address, Message>>;
+
 mod msginfo;
 pub use msginfo::MsgInfo;
 
@@ -210,6 +221,39 @@ pub type UnwrappedMessage = message::GenericMessage<Address, MessageContent>;
 /// Generic binary message type for sequence handling
 pub type BinaryMessage = message::GenericMessage<Address, BinaryBody>;
 
+/// Max number of rejected messages a user keeps, the oldest being dropped first.
+pub const MAX_REJECTED_MESSAGES: usize = 64;
+
+/// Max number of messages handled at a link, the others being ignored.
+///
+/// Anyone can publish at any link, the authentic message is expected among the first ones found.
+pub const MAX_CANDIDATES_PER_LINK: usize = 16;
+
+/// Message found at a link along with other messages, that could not be handled.
+///
+/// Anyone can publish at any link, such messages are likely spam shadowing the authentic one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RejectedMessage {
+    pub message: BinaryMessage,
+    /// Error raised while handling the message.
+    pub reason: String,
+}
+
+/// Two different messages unwrapped at the same link.
+///
+/// Both messages passed the checks of their type, which does not make both of them the publisher's.
+/// Only signed messages (announcements, signed packets, subscriptions and unsubscriptions) prove
+/// that the publisher forked its history or that its keys are compromised. Keyloads, tagged packets
+/// and sequence messages are not signed, anyone holding the keys they depend on could publish either.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Equivocation {
+    pub link: Address,
+    /// Message handled first, kept in the state.
+    pub first: BinaryMessage,
+    /// Conflicting message.
+    pub second: BinaryMessage,
+}
+
 mod user;
 /// User object storing the Auth/Sub implementation as well as the transport instance
 pub use user::User;
@@ -228,4 +272,7 @@ mod subscriber;
 /// Tangle-specific Channel Subscriber type.
 pub use subscriber::Subscriber;
 
+/// Blocking Author and Subscriber API.
+pub mod blocking;
+
 pub mod test;
@@ -17,16 +17,21 @@ use crate::api::tangle::{
 use iota_streams_app::identifier::Identifier;
 use iota_streams_core::{
     prelude::{
+        Box,
         String,
         Vec,
     },
+    prng,
     psk::{
         Psk,
         PskId,
     },
     Errors::SingleDepthOperationFailure,
 };
-use iota_streams_core_edsig::signature::ed25519;
+use iota_streams_core_edsig::{
+    key_exchange::x25519,
+    signature::ed25519,
+};
 
 /// Subscriber Object. Contains User API.
 pub struct Subscriber<T> {
@@ -44,6 +49,24 @@ impl<Trans> Subscriber<Trans> {
         Self { user }
     }
 
+    /// Create a new Subscriber instance with the given keys.
+    ///
+    /// The keys may be held outside of memory (eg. by an HSM), in which case the subscriber state can not be
+    /// exported.
+    ///
+    /// # Arguments
+    /// * `sig_kp` - Ed25519 signing key of the subscriber
+    /// * `ke_kp` - X25519 key exchange key of the subscriber
+    /// * `transport` - Transport object used for sending and receiving
+    pub fn new_with_keys(
+        sig_kp: Box<dyn ed25519::Signer + Send + Sync>,
+        ke_kp: Box<dyn x25519::KeyAgreement + Send + Sync>,
+        transport: Trans,
+    ) -> Self {
+        let user = User::new_with_keys(sig_kp, ke_kp, SingleBranch, transport);
+        Self { user }
+    }
+
     /// Returns a clone of the transport object
     pub fn get_transport(&self) -> &Trans {
         self.user.get_transport()
@@ -64,6 +87,37 @@ impl<Trans> Subscriber<Trans> {
         self.user.get_public_key()
     }
 
+    /// Generate a new x25519 key exchange key pair, independent from the ed25519 key pair
+    ///
+    /// The new key is shared with the Author in the next Subscribe message. Subscribing again
+    /// after generating a new key rotates the key used by the Author in subsequent keyloads.
+    ///
+    /// # Arguments
+    /// * `seed` - A string slice representing the seed of the key exchange key pair
+    pub fn gen_exchange_key(&mut self, seed: &str) -> Result<()> {
+        self.user.gen_exchange_key(seed)
+    }
+
+    /// Go back to the x25519 key exchange key pair derived from the ed25519 key pair
+    ///
+    /// Subscribing again afterwards rotates the key used by the Author in subsequent keyloads back
+    /// to the derived one.
+    pub fn reset_exchange_key(&mut self) -> Result<()> {
+        self.user.reset_exchange_key()
+    }
+
+    /// Replace the random number generator used for session keys, nonces and ephemeral keys, see
+    /// [`User::set_rng`](crate::api::user::User::set_rng)
+    pub fn set_rng(&mut self, rng: Box<dyn prng::CryptoRngCore + Send + Sync>) {
+        self.user.set_rng(rng)
+    }
+
+    /// Set resource limits enforced while unwrapping received messages, see
+    /// [`User::set_unwrap_limits`](crate::api::user::User::set_unwrap_limits)
+    pub fn set_unwrap_limits(&mut self, limits: UnwrapLimits) {
+        self.user.set_unwrap_limits(limits)
+    }
+
     /// Channel Author's signature public key
     pub fn author_public_key(&self) -> Option<&ed25519::PublicKey> {
         self.user.author_public_key()
@@ -145,6 +199,28 @@ impl<Trans> Subscriber<Trans> {
         self.user.reset_state()
     }
 
+    /// Messages rejected since the last call to `take_rejected_messages`, see
+    /// [`User::rejected_messages`](crate::api::tangle::User::rejected_messages)
+    pub fn rejected_messages(&self) -> &[RejectedMessage] {
+        self.user.rejected_messages()
+    }
+
+    /// Take the rejected messages, clearing the record
+    pub fn take_rejected_messages(&mut self) -> Vec<RejectedMessage> {
+        self.user.take_rejected_messages()
+    }
+
+    /// Equivocations detected since the last call to `take_equivocations`, see
+    /// [`User::equivocations`](crate::api::tangle::User::equivocations)
+    pub fn equivocations(&self) -> &[Equivocation] {
+        self.user.equivocations()
+    }
+
+    /// Take the detected equivocations, clearing the record
+    pub fn take_equivocations(&mut self) -> Vec<Equivocation> {
+        self.user.take_equivocations()
+    }
+
     /// Generate the next batch of message [`Address`] to poll
     ///
     /// Given the set of users registered as participants of the channel and their current registered
@@ -339,7 +415,7 @@ impl<T: Transport + Clone> fmt::Display for Subscriber<T> {
         write!(
             f,
             "<{}>\n{}",
-            hex::encode(self.user.user.sig_kp.public.as_bytes()),
+            hex::encode(self.user.user.sig_kp.public_key().as_bytes()),
             self.user.user.key_store
         )
     }
@@ -16,18 +16,54 @@ use iota_streams_core::{
     println,
     Result,
 };
+#[cfg(any(test, feature = "test-rng", not(feature = "std")))]
+use iota_streams_core::{
+    prelude::Box,
+    prng,
+};
 
 use super::*;
 
+/// Deterministic RNG for users in tests and test vectors.
+#[cfg(any(test, feature = "test-rng"))]
+pub fn test_rng(seed: &str) -> Box<dyn prng::CryptoRngCore + Send + Sync> {
+    seeded_rng(seed)
+}
+
+// Without `std` there is no default RNG, the example users are given deterministic ones
+#[cfg(any(test, feature = "test-rng", not(feature = "std")))]
+fn seeded_rng(seed: &str) -> Box<dyn prng::CryptoRngCore + Send + Sync> {
+    Box::new(prng::Rng::new(
+        prng::dbg_init_str::<DefaultF>(seed),
+        seed.as_bytes().to_vec(),
+    ))
+}
+
+/// Author of a channel announced over `transport`, along with the link of the announcement.
+#[cfg(test)]
+async fn announced<T: Transport>(channel_type: ChannelType, transport: T) -> Result<(Author<T>, Address)> {
+    let mut author = Author::new("AUTHOR9SEED", channel_type, transport);
+    #[cfg(not(feature = "std"))]
+    author.set_rng(test_rng("AUTHOR9RNG"));
+    let announcement_link = author.send_announce().await?;
+    Ok((author, announcement_link))
+}
+
 pub async fn example<T: Transport + Clone>(transport: T) -> Result<()> {
     let mut author = Author::new("AUTHOR9SEED", ChannelType::SingleBranch, transport.clone());
+    #[cfg(not(feature = "std"))]
+    author.set_rng(seeded_rng("AUTHOR9RNG"));
 
     let mut subscriberA = Subscriber::new("SUBSCRIBERA9SEED", transport.clone());
+    #[cfg(not(feature = "std"))]
+    subscriberA.set_rng(seeded_rng("SUBSCRIBERA9RNG"));
 
     let mut subscriberB = Subscriber::new("SUBSCRIBERB9SEED", transport.clone());
+    #[cfg(not(feature = "std"))]
+    subscriberB.set_rng(seeded_rng("SUBSCRIBERB9RNG"));
 
-    let public_payload = Bytes("PUBLICPAYLOAD".as_bytes().to_vec());
-    let masked_payload = Bytes("MASKEDPAYLOAD".as_bytes().to_vec());
+    let public_payload = Bytes::from("PUBLICPAYLOAD".as_bytes().to_vec());
+    let masked_payload = Bytes::from("MASKEDPAYLOAD".as_bytes().to_vec());
 
     println!("announce");
     let msg = &author.send_announce().await?;
@@ -151,3 +187,996 @@ async fn run_basic_scenario() {
     let transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
     example(transport).await.unwrap();
 }
+
+#[cfg(test)]
+#[tokio::test]
+async fn keyload_with_separate_exchange_keys() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_core::prelude::Rc;
+
+    let transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    let mut author = Author::new("AUTHOR9SEED", ChannelType::SingleBranch, transport.clone());
+    #[cfg(not(feature = "std"))]
+    author.set_rng(test_rng("AUTHOR9RNG"));
+    author.gen_exchange_key("AUTHOR9KE9SEED")?;
+    let announcement_link = author.send_announce().await?;
+
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport.clone());
+    #[cfg(not(feature = "std"))]
+    subscriber.set_rng(test_rng("SUBSCRIBER9RNG"));
+    subscriber.gen_exchange_key("SUBSCRIBER9KE9SEED")?;
+    subscriber.receive_announcement(&announcement_link).await?;
+    let subscribe_link = subscriber.send_subscribe(&announcement_link).await?;
+    author.receive_subscribe(&subscribe_link).await?;
+
+    let (keyload_link, _) = author.send_keyload_for_everyone(&announcement_link).await?;
+    let subscriber_dump = subscriber.export("pwdSub").await?;
+    ensure!(
+        subscriber.receive_keyload(&keyload_link).await?,
+        "subscriber failed to unwrap keyload with separate exchange key"
+    );
+
+    let mut subscriber2 = Subscriber::import(&subscriber_dump, "pwdSub", transport).await?;
+    ensure!(
+        subscriber2.receive_keyload(&keyload_link).await?,
+        "imported subscriber failed to unwrap keyload with separate exchange key"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn announce_with_derived_exchange_key_is_unchanged() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_app::transport::Transport;
+    use iota_streams_core::prelude::Rc;
+
+    // Announce of the same author before separate key exchange keys were supported
+    const ANNOUNCE_V0: &str = "000000010400000000000000000000000000657f9751cb972555032119bdff0ba4a675c82ff55aee3a552ae6aeccad27e0240e000001657f9751cb972555032119bdff0ba4a675c82ff55aee3a552ae6aeccad27e02400ef747f44ed3bc3968fce3c14e8772413c0a2ea163ee33749451b34454a1c8a0a1fbe648a5c3e76b1048b496c6bc1ab0a375921bc4e3464ea69bab38d3daa3b07";
+
+    let mut transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    let mut author = Author::new("AUTHOR9SEED", ChannelType::SingleBranch, transport.clone());
+    let announcement_link = author.send_announce().await?;
+    let announcement = transport.recv_message(&announcement_link).await?;
+    ensure!(
+        hex::encode(announcement.body.as_bytes()) == ANNOUNCE_V0,
+        "announce with derived exchange key changed"
+    );
+
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport.clone());
+    subscriber.receive_announcement(&announcement_link).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn subscriber_rotates_back_to_derived_exchange_key() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_core::prelude::Rc;
+
+    let transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    let (mut author, announcement_link) = announced(ChannelType::SingleBranch, transport.clone()).await?;
+
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport.clone());
+    #[cfg(not(feature = "std"))]
+    subscriber.set_rng(test_rng("SUBSCRIBER9RNG"));
+    subscriber.receive_announcement(&announcement_link).await?;
+    subscriber.gen_exchange_key("SUBSCRIBER9KE9SEED")?;
+    let subscribe_link = subscriber.send_subscribe(&announcement_link).await?;
+    author.receive_subscribe(&subscribe_link).await?;
+
+    subscriber.reset_exchange_key()?;
+    let subscribe_link = subscriber.send_subscribe(&announcement_link).await?;
+    author.receive_subscribe(&subscribe_link).await?;
+    let (keyload_link, _) = author.send_keyload_for_everyone(&announcement_link).await?;
+    ensure!(
+        subscriber.receive_keyload(&keyload_link).await?,
+        "subscriber failed to unwrap keyload after rotating back to the derived exchange key"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn older_subscribe_does_not_roll_exchange_key_back() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_core::{
+        prelude::Rc,
+        Errors,
+    };
+
+    let transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    let (mut author, announcement_link) = announced(ChannelType::SingleBranch, transport.clone()).await?;
+
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport.clone());
+    #[cfg(not(feature = "std"))]
+    subscriber.set_rng(test_rng("SUBSCRIBER9RNG"));
+    subscriber.receive_announcement(&announcement_link).await?;
+    let first_link = subscriber.send_subscribe(&announcement_link).await?;
+    author.receive_subscribe(&first_link).await?;
+    subscriber.gen_exchange_key("SUBSCRIBER9KE9SEED")?;
+    let rotation_link = subscriber.send_subscribe(&announcement_link).await?;
+    ensure!(
+        rotation_link != first_link,
+        "rotation published at the link of the first subscription"
+    );
+    author.receive_subscribe(&rotation_link).await?;
+
+    // The first Subscribe is still authentic, the author has to remember it is older, even once imported
+    let author_dump = author.export("pwdAuthor").await?;
+    let mut author = Author::import(&author_dump, "pwdAuthor", transport).await?;
+    #[cfg(not(feature = "std"))]
+    author.set_rng(test_rng("AUTHOR9RNG"));
+    let e = author.receive_subscribe(&first_link).await.unwrap_err();
+    ensure!(
+        matches!(e.downcast_ref::<Errors>(), Some(StaleKeyExchangeKey(0, 1))),
+        "unexpected error: {}",
+        e
+    );
+    author.receive_subscribe(&rotation_link).await?;
+    let (keyload_link, _) = author.send_keyload_for_everyone(&announcement_link).await?;
+    ensure!(
+        subscriber.receive_keyload(&keyload_link).await?,
+        "keyload not wrapped for the rotated exchange key"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn author_exchange_key_is_kept_once_announced() -> Result<()> {
+    use iota_streams_core::Errors;
+
+    let mut author = Author::new("AUTHOR9SEED", ChannelType::SingleBranch, BucketTransport::new());
+    author.gen_exchange_key("AUTHOR9KE9SEED")?;
+    author.send_announce().await?;
+    let e = author.gen_exchange_key("AUTHOR9KE9SEED2").unwrap_err();
+    ensure!(
+        matches!(e.downcast_ref::<Errors>(), Some(AuthorExchangeKeyAnnounced)),
+        "unexpected error: {}",
+        e
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn users_exported_before_separate_exchange_keys_are_imported() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_core::{
+        anyhow,
+        prelude::Rc,
+    };
+
+    use crate::api::tangle::BucketTransport;
+
+    // Version 0 exports of an author after its announcement and of a subscriber having received it
+    const AUTHOR_V0: &str = "0000468eadb36cb38f5a22bd69f25e4c5efa09419cfa849d36850cff111d64f6845a0001057574662d380000000000007d0001657f9751cb972555032119bdff0ba4a675c82ff55aee3a552ae6aeccad27e0240000000000000000196753aa70924e009b6c4def01657f9751cb972555032119bdff0ba4a675c82ff55aee3a552ae6aeccad27e0240101196753aa70924e009b6c4deffcbdb43ae4945f0d9ea243814dfe6842020c7df9def6610883daca4688465c9000010104449ec0e3d980eb9b85f6b736702b3f196bc5d99b1bde9fdf78f0c34bc7d340bc196753aa70924e009b6c4def0000000000000002bb532551137db5d027fca81ec012fd7edf59fac1f36ef305a15b9626d0ce6a02";
+    const SUBSCRIBER_V0: &str = "0001b65951711df25cf34e93b58b0ca1bad976a4cd7d83c37ee6a3959b96577b80500001057574662d380000000000007d0001657f9751cb972555032119bdff0ba4a675c82ff55aee3a552ae6aeccad27e0240000000000000000196753aa70924e009b6c4def01657f9751cb972555032119bdff0ba4a675c82ff55aee3a552ae6aeccad27e0240101196753aa70924e009b6c4def135abdf2dc42be34bdf564673a2f58f956c44e9de6c99ff38c1861f6803c5547000102e438e7b4b63c2ade887daeb6f9dd196df27ea76852fae72eb77b6b97073edcb62a196753aa70924e009b6c4def0000000000000002dae3ccc11abe835fba4f2abd2133e69df10a28f11272cee400bd702dc92a9711d6196753aa70924e009b6c4def0000000000000002850abdd8f3c5d8e2cbb41547b61d62c03e61b7bc353259f2e1dea9251f0a4ecc";
+
+    let transport = Rc::new(RefCell::new(BucketTransport::new()));
+    let mut author = Author::import(&hex::decode(AUTHOR_V0).unwrap(), "pwd", transport.clone()).await?;
+    let mut subscriber = Subscriber::import(&hex::decode(SUBSCRIBER_V0).unwrap(), "pwd", transport).await?;
+    #[cfg(not(feature = "std"))]
+    author.set_rng(test_rng("AUTHOR9RNG"));
+    #[cfg(not(feature = "std"))]
+    subscriber.set_rng(test_rng("SUBSCRIBER9RNG"));
+    ensure!(
+        author.get_public_key()
+            == Author::new("AUTHOR9SEED", ChannelType::SingleBranch, BucketTransport::new()).get_public_key(),
+        "author identity not recovered"
+    );
+
+    // Key exchange keys are derived from the Ed25519 identities
+    let announcement_link = (*author.announcement_link()).ok_or_else(|| anyhow!("announcement link not recovered"))?;
+    let subscribe_link = subscriber.send_subscribe(&announcement_link).await?;
+    author.receive_subscribe(&subscribe_link).await?;
+    let (keyload_link, _) = author.send_keyload_for_everyone(&announcement_link).await?;
+    ensure!(
+        subscriber.receive_keyload(&keyload_link).await?,
+        "imported subscriber failed to unwrap keyload"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn keyload_with_external_keys() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_core::prelude::{
+        Box,
+        Rc,
+    };
+    use iota_streams_core_edsig::{
+        key_exchange::x25519,
+        signature::ed25519,
+    };
+
+    // Keys that are not exposed to the user, as if they were held by an HSM
+    struct ExternalSigner(ed25519::Keypair);
+    impl ed25519::Signer for ExternalSigner {
+        fn public_key(&self) -> &ed25519::PublicKey {
+            &self.0.public
+        }
+        fn sign_prehashed(&self, prehashed: ed25519::Prehashed, context: &[u8]) -> Result<ed25519::Signature> {
+            ed25519::Signer::sign_prehashed(&self.0, prehashed, context)
+        }
+    }
+    struct ExternalKeyAgreement(x25519::StaticSecret);
+    impl x25519::KeyAgreement for ExternalKeyAgreement {
+        fn public_key(&self) -> x25519::PublicKey {
+            x25519::PublicKey::from(&self.0)
+        }
+        fn diffie_hellman(&self, their_public: &x25519::PublicKey) -> Result<[u8; 32]> {
+            Ok(self.0.diffie_hellman(their_public).to_bytes())
+        }
+    }
+    fn external_keys(
+        seed: u8,
+    ) -> (
+        Box<dyn ed25519::Signer + Send + Sync>,
+        Box<dyn x25519::KeyAgreement + Send + Sync>,
+    ) {
+        let secret = ed25519::SecretKey::from_bytes(&[seed; ed25519::SECRET_KEY_LENGTH]).unwrap();
+        let public = ed25519::PublicKey::from(&secret);
+        let ke_sk = x25519::StaticSecret::from([seed.wrapping_add(1); 32]);
+        (
+            Box::new(ExternalSigner(ed25519::Keypair { secret, public })),
+            Box::new(ExternalKeyAgreement(ke_sk)),
+        )
+    }
+
+    let transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    let (sig_kp, ke_kp) = external_keys(3);
+    let mut author = Author::new_with_keys(sig_kp, ke_kp, ChannelType::SingleBranch, transport.clone());
+    #[cfg(not(feature = "std"))]
+    author.set_rng(test_rng("AUTHOR9RNG"));
+    let announcement_link = author.send_announce().await?;
+
+    let (sig_kp, ke_kp) = external_keys(7);
+    let mut subscriber = Subscriber::new_with_keys(sig_kp, ke_kp, transport);
+    #[cfg(not(feature = "std"))]
+    subscriber.set_rng(test_rng("SUBSCRIBER9RNG"));
+    subscriber.receive_announcement(&announcement_link).await?;
+    let subscribe_link = subscriber.send_subscribe(&announcement_link).await?;
+    author.receive_subscribe(&subscribe_link).await?;
+
+    let (keyload_link, _) = author.send_keyload_for_everyone(&announcement_link).await?;
+    ensure!(
+        subscriber.receive_keyload(&keyload_link).await?,
+        "subscriber failed to unwrap keyload with external keys"
+    );
+
+    ensure!(
+        author.export("pwdAut").await.is_err(),
+        "author with external keys should not be exportable"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn messages_are_reproducible_with_injected_rng() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_app::transport::Transport as _;
+    use iota_streams_core::prelude::{
+        Rc,
+        Vec,
+    };
+
+    async fn subscribe_and_keyload_bodies() -> Result<(Vec<u8>, Vec<u8>)> {
+        let mut transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+        let mut author = Author::new("AUTHOR9SEED", ChannelType::SingleBranch, transport.clone());
+        author.set_rng(test_rng("AUTHOR9RNG"));
+        let announcement_link = author.send_announce().await?;
+
+        let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport.clone());
+        subscriber.set_rng(test_rng("SUBSCRIBER9RNG"));
+        subscriber.receive_announcement(&announcement_link).await?;
+        let subscribe_link = subscriber.send_subscribe(&announcement_link).await?;
+        author.receive_subscribe(&subscribe_link).await?;
+
+        let (keyload_link, _) = author.send_keyload_for_everyone(&announcement_link).await?;
+        ensure!(
+            subscriber.receive_keyload(&keyload_link).await?,
+            "subscriber failed to unwrap keyload"
+        );
+        let subscribe_body = transport.recv_message(&subscribe_link).await?.body.as_bytes().to_vec();
+        let keyload_body = transport.recv_message(&keyload_link).await?.body.as_bytes().to_vec();
+        Ok((subscribe_body, keyload_body))
+    }
+
+    ensure!(
+        subscribe_and_keyload_bodies().await? == subscribe_and_keyload_bodies().await?,
+        "messages generated with the same RNG differ"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn users_are_send_and_sync() {
+    use crate::api::tangle::BucketTransport;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Author<BucketTransport>>();
+    assert_send_sync::<Subscriber<BucketTransport>>();
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn unwrap_limits_reject_oversized_fields() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_core::prelude::Rc;
+
+    let transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    let mut author = Author::new("AUTHOR9SEED", ChannelType::SingleBranch, transport.clone());
+    #[cfg(not(feature = "std"))]
+    author.set_rng(test_rng("AUTHOR9RNG"));
+    let announcement_link = author.send_announce().await?;
+    let public_payload = Bytes::from(vec![1; 100]);
+    let (packet_link, _) = author
+        .send_signed_packet(&announcement_link, &public_payload, &Bytes::default())
+        .await?;
+
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport);
+    #[cfg(not(feature = "std"))]
+    subscriber.set_rng(test_rng("SUBSCRIBER9RNG"));
+    subscriber.receive_announcement(&announcement_link).await?;
+
+    subscriber.set_unwrap_limits(UnwrapLimits::default().with_max_bytes_len(64));
+    ensure!(
+        subscriber.receive_signed_packet(&packet_link).await.is_err(),
+        "subscriber unwrapped a field longer than the limit"
+    );
+
+    subscriber.set_unwrap_limits(UnwrapLimits::default().with_max_bytes_len(100));
+    let (_, unwrapped_public, _) = subscriber.receive_signed_packet(&packet_link).await?;
+    ensure!(unwrapped_public == public_payload, "public payload mismatch");
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn unwrap_limits_bound_keyload_recipients() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_core::prelude::{
+        Rc,
+        Vec,
+    };
+
+    let transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    let mut author = Author::new("AUTHOR9SEED", ChannelType::SingleBranch, transport.clone());
+    #[cfg(not(feature = "std"))]
+    author.set_rng(test_rng("AUTHOR9RNG"));
+    let announcement_link = author.send_announce().await?;
+
+    let mut subscribers = Vec::new();
+    for &seed in &["SUBSCRIBERA9SEED", "SUBSCRIBERB9SEED"] {
+        let mut subscriber = Subscriber::new(seed, transport.clone());
+        #[cfg(not(feature = "std"))]
+        subscriber.set_rng(test_rng(seed));
+        subscriber.receive_announcement(&announcement_link).await?;
+        let subscribe_link = subscriber.send_subscribe(&announcement_link).await?;
+        author.receive_subscribe(&subscribe_link).await?;
+        subscribers.push(subscriber);
+    }
+    let (keyload_link, _) = author.send_keyload_for_everyone(&announcement_link).await?;
+
+    // The keyload lists the author and both subscribers
+    let subscriber = &mut subscribers[0];
+    subscriber.set_unwrap_limits(UnwrapLimits::default().with_max_repeated(2));
+    ensure!(
+        subscriber.receive_keyload(&keyload_link).await.is_err(),
+        "subscriber unwrapped more keyload recipients than the limit"
+    );
+    subscriber.set_unwrap_limits(UnwrapLimits::default().with_max_repeated(3));
+    ensure!(
+        subscriber.receive_keyload(&keyload_link).await?,
+        "subscriber failed to unwrap keyload within the limit"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn traces_locate_where_unwrap_diverges_from_wrap() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_core::prelude::{
+        Rc,
+        Vec,
+    };
+
+    use crate::{
+        api::tangle::User,
+        message::tagged_packet,
+    };
+
+    let transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    let mut author = User::new("AUTHOR9SEED", ChannelType::SingleBranch, transport.clone());
+    author.user.create_channel(0)?;
+    let announcement_link = author.send_announce().await?;
+    let mut subscriber = User::new("SUBSCRIBER9SEED", ChannelType::SingleBranch, transport);
+    subscriber.receive_announcement(&announcement_link).await?;
+
+    let public_payload = Bytes::from(vec![1; 10]);
+    let masked_payload = Bytes::from(vec![2; 10]);
+    let (wrapped, wrap_trace) = author
+        .user
+        .prepare_tagged_packet(&announcement_link, &public_payload, &masked_payload)?
+        .wrap_traced(&author.user.link_store)
+        .await?;
+    let fields = wrap_trace
+        .entries()
+        .iter()
+        .map(|entry| entry.field.as_str())
+        .collect::<Vec<_>>();
+    for field in &["header", "link", "public_payload", "masked_payload"] {
+        ensure!(fields.contains(field), "field {} not labelled", field);
+    }
+
+    let (unwrapped, unwrap_trace) = wrapped
+        .message
+        .parse_header_traced(UnwrapLimits::default())
+        .await?
+        .unwrap_traced(
+            &subscriber.user.link_store,
+            tagged_packet::ContentUnwrap::<DefaultF, Address>::default(),
+        )
+        .await;
+    unwrapped?;
+    ensure!(
+        wrap_trace.diverges_at(&unwrap_trace).is_none(),
+        "traces of the same message differ"
+    );
+
+    // Altering the last masked byte makes the unwrap diverge at the masked payload and fail the MAC check
+    let masked = wrap_trace
+        .entries()
+        .iter()
+        .position(|entry| entry.field == "masked_payload")
+        .expect("masked payload traced");
+    let entry = &wrap_trace.entries()[masked];
+    let mut message = wrapped.message.clone();
+    message.body.to_mut()[entry.offset + entry.length - 1] ^= 1;
+    let (unwrapped, unwrap_trace) = message
+        .parse_header_traced(UnwrapLimits::default())
+        .await?
+        .unwrap_traced(
+            &subscriber.user.link_store,
+            tagged_packet::ContentUnwrap::<DefaultF, Address>::default(),
+        )
+        .await;
+    ensure!(unwrapped.is_err(), "altered message unwrapped");
+    ensure!(
+        wrap_trace.diverges_at(&unwrap_trace) == Some(masked),
+        "traces diverge before the altered field"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn spam_does_not_shadow_authentic_messages() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_app::transport::Transport as _;
+    use iota_streams_core::prelude::{
+        Rc,
+        Vec,
+    };
+
+    let mut author_transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    let mut author = Author::new("AUTHOR9SEED", ChannelType::SingleBranch, author_transport.clone());
+    #[cfg(not(feature = "std"))]
+    author.set_rng(test_rng("AUTHOR9RNG"));
+    let announcement_link = author.send_announce().await?;
+    let (packet_link, _) = author
+        .send_signed_packet(&announcement_link, &Bytes::from(vec![1]), &Bytes::from(vec![2]))
+        .await?;
+    let announcement = author_transport.recv_message(&announcement_link).await?;
+    let packet = author_transport.recv_message(&packet_link).await?;
+
+    // The forged packet is published at the same link before the authentic one.
+    let mut forged_body = packet.body.to_bytes();
+    *forged_body.last_mut().unwrap() ^= 1;
+    let forged = BinaryMessage::new(packet_link, packet.prev_link, forged_body.into());
+    let mut transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    for msg in [&announcement, &forged, &packet] {
+        transport.send_message(msg).await?;
+    }
+
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport.clone());
+    subscriber.receive_announcement(&announcement_link).await?;
+    let (_, public, masked) = subscriber.receive_signed_packet(&packet_link).await?;
+    ensure!(public.as_slice() == [1] && masked.as_slice() == [2], "payload mismatch");
+    let rejected = subscriber.take_rejected_messages();
+    ensure!(
+        rejected.len() == 1 && rejected[0].message == forged,
+        "forged packet not rejected"
+    );
+    ensure!(subscriber.rejected_messages().is_empty(), "rejected messages not taken");
+
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport.clone());
+    subscriber.receive_announcement(&announcement_link).await?;
+    let msgs = subscriber.fetch_next_msgs().await?;
+    ensure!(
+        msgs.len() == 1 && msgs[0].link == packet_link && msgs[0].body.is_signed_packet(),
+        "authentic packet not fetched"
+    );
+    ensure!(subscriber.rejected_messages().len() == 1, "forged packet not rejected");
+
+    // Fails with the only message at the link.
+    let mut transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    for msg in [&announcement, &forged] {
+        transport.send_message(msg).await?;
+    }
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport);
+    subscriber.receive_announcement(&announcement_link).await?;
+    ensure!(
+        subscriber.receive_signed_packet(&packet_link).await.is_err(),
+        "forged packet accepted"
+    );
+    ensure!(
+        subscriber.rejected_messages().is_empty(),
+        "single message recorded as rejected"
+    );
+
+    // Spam at a link is recorded up to a bound, keeping the last messages.
+    let mut transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    transport.send_message(&announcement).await?;
+    transport.send_message(&packet).await?;
+    let mut spam = Vec::new();
+    for i in 1..MAX_CANDIDATES_PER_LINK {
+        let mut body = packet.body.to_bytes();
+        *body.last_mut().unwrap() ^= i as u8;
+        let forged = BinaryMessage::new(packet_link, packet.prev_link, body.into());
+        transport.send_message(&forged).await?;
+        spam.push(forged);
+    }
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport);
+    subscriber.receive_announcement(&announcement_link).await?;
+    subscriber.receive_signed_packet(&packet_link).await?;
+    for _ in 0..MAX_REJECTED_MESSAGES / spam.len() + 1 {
+        let _ = subscriber.receive_signed_packet(&packet_link).await;
+    }
+    ensure!(
+        subscriber.rejected_messages().len() == MAX_REJECTED_MESSAGES
+            && subscriber.rejected_messages().last().map(|rejected| &rejected.message) == spam.last(),
+        "rejected messages not bounded"
+    );
+
+    // No more messages than the bound are handled at a link.
+    let mut body = packet.body.to_bytes();
+    *body.last_mut().unwrap() ^= MAX_CANDIDATES_PER_LINK as u8;
+    spam.push(BinaryMessage::new(packet_link, packet.prev_link, body.into()));
+    let mut transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    transport.send_message(&announcement).await?;
+    for msg in spam.iter().chain([&packet]) {
+        transport.send_message(msg).await?;
+    }
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport);
+    subscriber.receive_announcement(&announcement_link).await?;
+    ensure!(
+        subscriber.receive_signed_packet(&packet_link).await.is_err(),
+        "messages past the bound handled"
+    );
+    ensure!(
+        subscriber.rejected_messages().len() == MAX_CANDIDATES_PER_LINK,
+        "messages past the bound rejected"
+    );
+
+    // Previous messages are fetched despite spam at the link and at the previous link.
+    let (last_link, _) = author
+        .send_signed_packet(&packet_link, &Bytes::from(vec![3]), &Bytes::from(vec![4]))
+        .await?;
+    let last = author_transport.recv_message(&last_link).await?;
+    let unlinked = BinaryMessage::new(last_link, announcement.prev_link, announcement.body.clone());
+    let mut transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    for msg in [&announcement, &forged, &packet, &unlinked, &last] {
+        transport.send_message(msg).await?;
+    }
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport);
+    subscriber.receive_announcement(&announcement_link).await?;
+    let prev_msg = subscriber.fetch_prev_msg(&last_link).await?;
+    ensure!(
+        prev_msg.link == packet_link && prev_msg.body.is_signed_packet(),
+        "previous message shadowed by spam"
+    );
+    let prev_msgs = subscriber.fetch_prev_msgs(&last_link, 2).await?;
+    ensure!(
+        prev_msgs.len() == 1 && prev_msgs[0].link == packet_link,
+        "previous messages shadowed by spam"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn equivocating_messages_are_detected() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_app::transport::Transport as _;
+    use iota_streams_core::prelude::{
+        Rc,
+        Vec,
+    };
+
+    // Two instances of the same author publish different packets after the same announcement.
+    let mut messages = Vec::new();
+    for masked_payload in [1, 2] {
+        let mut author_transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+        let mut author = Author::new("AUTHOR9SEED", ChannelType::SingleBranch, author_transport.clone());
+        #[cfg(not(feature = "std"))]
+        author.set_rng(test_rng("AUTHOR9RNG"));
+        let announcement_link = author.send_announce().await?;
+        let (packet_link, _) = author
+            .send_signed_packet(
+                &announcement_link,
+                &Bytes::default(),
+                &Bytes::from(vec![masked_payload]),
+            )
+            .await?;
+        messages.push((
+            author_transport.recv_message(&announcement_link).await?,
+            author_transport.recv_message(&packet_link).await?,
+        ));
+    }
+    let (announcement, first) = messages[0].clone();
+    let second = messages[1].1.clone();
+    ensure!(
+        first.link == second.link && first != second,
+        "packets are not equivocating"
+    );
+
+    let mut transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    for msg in [&announcement, &first, &second] {
+        transport.send_message(msg).await?;
+    }
+
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport.clone());
+    subscriber.receive_announcement(&announcement.link).await?;
+    let (_, _, masked) = subscriber.receive_signed_packet(&first.link).await?;
+    ensure!(masked.as_slice() == [1], "first packet not kept");
+    let equivocations = subscriber.take_equivocations();
+    ensure!(
+        equivocations.len() == 1 && equivocations[0].first == first && equivocations[0].second == second,
+        "equivocation not detected"
+    );
+    ensure!(
+        subscriber.rejected_messages().is_empty(),
+        "equivocation recorded as rejected"
+    );
+
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", transport.clone());
+    subscriber.receive_announcement(&announcement.link).await?;
+    let msgs = subscriber.fetch_next_msgs().await?;
+    ensure!(
+        msgs.len() == 1 && msgs[0].body.masked_payload().map(|p| p.as_slice()) == Some(&[1][..]),
+        "first packet not fetched"
+    );
+    ensure!(
+        subscriber.equivocations().len() == 1 && subscriber.equivocations()[0].second == second,
+        "equivocation not detected"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn receive_recovers_from_transport_faults() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_app::transport::{
+        Fault,
+        Faults,
+        FaultyTransport,
+    };
+    use iota_streams_core::prelude::Rc;
+
+    let transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    let (mut author, announcement_link) = announced(ChannelType::SingleBranch, transport.clone()).await?;
+    let (packet_link, _) = author
+        .send_signed_packet(&announcement_link, &Bytes::default(), &Bytes::from(vec![1]))
+        .await?;
+
+    let faulty = FaultyTransport::new(transport, Faults::default(), 0).with_recv_script([
+        Fault::Fail,
+        Fault::None,
+        Fault::Corrupt,
+        Fault::Fail,
+    ]);
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", Rc::new(RefCell::new(faulty)));
+    ensure!(
+        subscriber.receive_announcement(&announcement_link).await.is_err(),
+        "injected failure ignored"
+    );
+    subscriber.receive_announcement(&announcement_link).await?;
+    ensure!(
+        subscriber.receive_signed_packet(&packet_link).await.is_err(),
+        "corrupted packet accepted"
+    );
+    ensure!(
+        subscriber.receive_signed_packet(&packet_link).await.is_err(),
+        "injected failure ignored"
+    );
+    let (_, _, masked) = subscriber.receive_signed_packet(&packet_link).await?;
+    ensure!(masked.as_slice() == [1], "payload mismatch");
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn sync_state_catches_up_despite_transport_faults() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_app::transport::{
+        Fault,
+        Faults,
+        FaultyTransport,
+    };
+    use iota_streams_core::prelude::{
+        Rc,
+        Vec,
+    };
+
+    const PACKETS: u8 = 10;
+
+    let transport = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    // Messages are duplicated, reordered and delayed on the way, the announcement is sent straight
+    let author_transport = FaultyTransport::new(
+        transport.clone(),
+        Faults::default()
+            .with_duplicate(0.3)
+            .with_reorder(0.3)
+            .with_delay(0.3, 4),
+        1,
+    )
+    .with_send_script([Fault::None]);
+    let author_transport = Rc::new(RefCell::new(author_transport));
+    let (mut author, announcement_link) = announced(ChannelType::MultiBranch, author_transport.clone()).await?;
+    let mut link = announcement_link;
+    for i in 0..PACKETS {
+        link = author
+            .send_signed_packet(&link, &Bytes::default(), &Bytes::from(vec![i]))
+            .await?
+            .0;
+    }
+    drop(author);
+    let mut author_transport = Rc::try_unwrap(author_transport)
+        .map_err(|_| iota_streams_core::anyhow!("author transport still shared"))?
+        .into_inner();
+    author_transport.flush().await?;
+    ensure!(author_transport.injected() > 0, "no faults injected");
+
+    let subscriber_transport = FaultyTransport::new(transport, Faults::default().with_fail_recv(0.3), 2);
+    let subscriber_transport = Rc::new(RefCell::new(subscriber_transport));
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", subscriber_transport.clone());
+    let mut received = Vec::new();
+    for _ in 0..100 {
+        if subscriber.receive_announcement(&announcement_link).await.is_ok() {
+            break;
+        }
+    }
+    for _ in 0..100 {
+        for msg in subscriber.fetch_next_msgs().await? {
+            received.extend(msg.body.masked_payload().map(|p| p.as_slice()[0]));
+        }
+        if received.len() == PACKETS as usize {
+            break;
+        }
+    }
+    ensure!(subscriber_transport.borrow().injected() > 0, "no faults injected");
+    ensure!(
+        received == (0..PACKETS).collect::<Vec<_>>(),
+        "packets fetched out of order: {:?}",
+        received
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn sequence_messages_follow_sent_messages_and_lookups_are_batched() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_app::transport::{
+        Fault,
+        Faults,
+        FaultyTransport,
+        Transport,
+        TransportDetails,
+        TransportOptions,
+    };
+    use iota_streams_core::{
+        async_trait,
+        prelude::{
+            Rc,
+            Vec,
+        },
+        Errors,
+    };
+
+    // Transport counting the lookup batches reaching it and keeping the links of the sent messages
+    struct CountingTransport<Tsp> {
+        inner: Tsp,
+        sent: Vec<Address>,
+        recv_batches: usize,
+    }
+
+    impl<Tsp: TransportOptions> TransportOptions for CountingTransport<Tsp> {
+        type SendOptions = <Tsp as TransportOptions>::SendOptions;
+        fn get_send_options(&self) -> Self::SendOptions {
+            self.inner.get_send_options()
+        }
+        fn set_send_options(&mut self, opt: Self::SendOptions) {
+            self.inner.set_send_options(opt)
+        }
+
+        type RecvOptions = <Tsp as TransportOptions>::RecvOptions;
+        fn get_recv_options(&self) -> Self::RecvOptions {
+            self.inner.get_recv_options()
+        }
+        fn set_recv_options(&mut self, opt: Self::RecvOptions) {
+            self.inner.set_recv_options(opt)
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl<Tsp> TransportDetails<Address> for CountingTransport<Tsp> {
+        type Details = ();
+        async fn get_link_details(&mut self, _link: &Address) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl<Tsp: Transport<Address, Message>> Transport<Address, Message> for CountingTransport<Tsp> {
+        async fn send_message(&mut self, msg: &Message) -> Result<()> {
+            self.sent.push(msg.link);
+            self.inner.send_message(msg).await
+        }
+
+        async fn recv_messages(&mut self, link: &Address) -> Result<Vec<Message>> {
+            self.inner.recv_messages(link).await
+        }
+
+        async fn recv_message(&mut self, link: &Address) -> Result<Message> {
+            self.inner.recv_message(link).await
+        }
+
+        async fn recv_messages_many(&mut self, links: &[Address]) -> Vec<Result<Vec<Message>>> {
+            self.recv_batches += 1;
+            self.inner.recv_messages_many(links).await
+        }
+    }
+
+    fn counting<Tsp>(inner: Tsp) -> CountingTransport<Tsp> {
+        CountingTransport {
+            inner,
+            sent: Vec::new(),
+            recv_batches: 0,
+        }
+    }
+
+    let bucket = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    // The second packet is lost, the sequence message of the third one is
+    let faulty = FaultyTransport::new(counting(bucket.clone()), Faults::default(), 0).with_send_script([
+        Fault::None,
+        Fault::None,
+        Fault::None,
+        Fault::Fail,
+        Fault::None,
+        Fault::Fail,
+    ]);
+    let faulty = Rc::new(RefCell::new(faulty));
+    let (mut author, announcement_link) = announced(ChannelType::MultiBranch, faulty.clone()).await?;
+
+    let (packet_link, seq_link) = author
+        .send_signed_packet(&announcement_link, &Bytes::default(), &Bytes::from(vec![1]))
+        .await?;
+    ensure!(seq_link.is_some(), "no sequence message sent");
+
+    // Subscribers look the next messages up in batches
+    let subscriber_transport = Rc::new(RefCell::new(counting(bucket)));
+    let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", subscriber_transport.clone());
+    subscriber.receive_announcement(&announcement_link).await?;
+    ensure!(subscriber.sync_state().await? == 1, "packet not synced");
+    ensure!(
+        subscriber_transport.borrow().recv_batches > 0,
+        "next messages not looked up in a batch"
+    );
+
+    // No sequence message is sent for a packet which failed to be sent
+    let next_links = |author: &Author<_>| {
+        author
+            .gen_next_msg_addresses()
+            .into_iter()
+            .map(|(_, cursor)| cursor.link)
+            .collect::<Vec<_>>()
+    };
+    let next = next_links(&author);
+    let e = author
+        .send_signed_packet(&packet_link, &Bytes::default(), &Bytes::from(vec![2]))
+        .await
+        .unwrap_err();
+    ensure!(
+        matches!(e.downcast_ref::<Errors>(), Some(InjectedTransportFailure(_))),
+        "unexpected error: {}",
+        e
+    );
+    ensure!(next_links(&author) == next, "unsent packet committed");
+    ensure!(
+        faulty.borrow().inner().sent.len() == 3,
+        "sequence message sent without its packet"
+    );
+
+    // The packet sent without its sequence message is committed, so that messages can be linked to it
+    let next = next_links(&author);
+    ensure!(author
+        .send_signed_packet(&packet_link, &Bytes::default(), &Bytes::from(vec![3]))
+        .await
+        .is_err());
+    ensure!(next_links(&author) == next, "unsent sequence message committed");
+    let sent_packet_link = faulty.borrow().inner().sent.last().cloned().unwrap();
+    author
+        .send_signed_packet(&sent_packet_link, &Bytes::default(), &Bytes::from(vec![4]))
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn boxed_transport_is_picked_at_runtime() -> Result<()> {
+    use core::cell::RefCell;
+
+    use iota_streams_app::transport::{
+        Faults,
+        FaultyTransport,
+        TransportDetails,
+    };
+    use iota_streams_core::prelude::Rc;
+
+    use crate::api::tangle::BoxedTransport;
+
+    let bucket = Rc::new(RefCell::new(crate::api::tangle::BucketTransport::new()));
+    let from_config = |kind: &str| -> BoxedTransport {
+        match kind {
+            "bucket" => Box::new(bucket.clone()),
+            _ => Box::new(Rc::new(RefCell::new(FaultyTransport::new(
+                bucket.clone(),
+                Faults::default(),
+                0,
+            )))),
+        }
+    };
+
+    let (mut author, announcement_link) = announced(ChannelType::SingleBranch, from_config("faulty")).await?;
+    author
+        .send_signed_packet(&announcement_link, &Bytes::default(), &Bytes::from(vec![1]))
+        .await?;
+
+    for kind in ["bucket", "faulty"] {
+        let mut subscriber = Subscriber::new("SUBSCRIBER9SEED", from_config(kind));
+        subscriber.receive_announcement(&announcement_link).await?;
+        ensure!(
+            subscriber.fetch_next_msgs().await?.len() == 1,
+            "packet not fetched from {}",
+            kind
+        );
+    }
+
+    let details = from_config("bucket").get_link_details(&announcement_link).await?;
+    ensure!(details.downcast_ref::<()>().is_some(), "details lost through the box");
+    Ok(())
+}
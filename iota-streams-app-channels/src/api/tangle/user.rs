@@ -1,7 +1,7 @@
 use async_recursion::async_recursion;
+use core::mem;
 use futures::{
     future,
-    TryFutureExt,
     TryStreamExt,
 };
 
@@ -22,8 +22,9 @@ use iota_streams_core::{
         PskId,
     },
     try_or,
-    unwrap_or_break,
+    Error,
     Errors::{
+        self,
         ChannelDuplication,
         ChannelNotSingleDepth,
         NoPreviousMessage,
@@ -33,6 +34,11 @@ use iota_streams_core::{
     Result,
 };
 
+use iota_streams_core_edsig::{
+    key_exchange::x25519,
+    signature::ed25519,
+};
+
 use super::*;
 use crate::{
     api,
@@ -44,10 +50,111 @@ type UserImp = api::user::User<DefaultF, Address, LinkGen, LinkStore, KeyStore>;
 const ENCODING: &str = "utf-8";
 const PAYLOAD_LENGTH: usize = 32_000;
 
+/// Handle the messages found at a link in turn, evaluating to the result of the first one accepted.
+///
+/// Anyone can publish at any link, so a message failing to be handled must not shadow the authentic
+/// one. If several messages are found, those failing are recorded as rejected, and those conflicting
+/// with the accepted one as equivocations.
+macro_rules! first_accepted {
+    ($self:ident, $link:expr, |$msg:ident| $handle:expr) => {{
+        let link: &Address = $link;
+        let candidates = $self.transport.recv_messages(link).await?;
+        handle_candidates!($self, link, candidates, |$msg| $handle).map_err(|(e, _)| e)
+    }};
+}
+
+/// Handle the messages already fetched at a link in turn, see [`first_accepted`].
+///
+/// Once a message is accepted, the remaining ones are only checked for conflicts with it, without
+/// changing the state. No more than [`MAX_CANDIDATES_PER_LINK`] messages are handled. If none is
+/// accepted, evaluates to the error along with the first message which failed for another reason
+/// than an equivocation.
+macro_rules! handle_candidates {
+    ($self:ident, $link:expr, $candidates:expr, |$msg:ident| $handle:expr) => {{
+        use iota_streams_core::{
+            err,
+            prelude::{
+                ToString,
+                Vec,
+            },
+            Errors::NoMessageAccepted,
+        };
+        use $crate::api::tangle::{
+            user::is_equivocation,
+            Address,
+            Message,
+            MAX_CANDIDATES_PER_LINK,
+        };
+        let link: &Address = $link;
+        let candidates: Vec<Message> = $candidates;
+        let count = candidates.len();
+        let mut handled_msgs: Vec<Message> = Vec::new();
+        let mut accepted = None;
+        let mut failed = Vec::new();
+        for $msg in candidates {
+            if handled_msgs.len() == MAX_CANDIDATES_PER_LINK {
+                break;
+            }
+            if handled_msgs.contains(&$msg) {
+                continue;
+            }
+            if accepted.is_some() {
+                // The other messages must not touch the state, they are only checked for conflicts
+                let checked = $self.user.check_conflict(&$msg).await;
+                handled_msgs.push($msg.clone());
+                if let Err(e) = checked {
+                    failed.push(($msg, e));
+                }
+                continue;
+            }
+            let handled = $handle;
+            handled_msgs.push($msg.clone());
+            match handled {
+                Ok(unwrapped) => accepted = Some(($msg, unwrapped)),
+                Err(e) => failed.push(($msg, e)),
+            }
+        }
+        let mut conflicting = Vec::new();
+        let mut error = None;
+        let mut unreadable = None;
+        for (msg, e) in failed {
+            if count > 1 && is_equivocation(&e) {
+                conflicting.push(msg);
+                error = Some(e);
+            } else {
+                if count > 1 {
+                    $self.reject(msg.clone(), &e);
+                } else {
+                    error = Some(e);
+                }
+                unreadable.get_or_insert(msg);
+            }
+        }
+        match (accepted, error) {
+            (Some((first, unwrapped)), _) => {
+                for second in conflicting {
+                    $self.record_equivocation(first.clone(), second);
+                }
+                Ok(unwrapped)
+            }
+            (None, Some(e)) => Err((e, unreadable)),
+            (None, None) => err(NoMessageAccepted(count, link.to_string())).map_err(|e| (e, unreadable)),
+        }
+    }};
+}
+
+pub(crate) use handle_candidates;
+
+pub(crate) fn is_equivocation(e: &Error) -> bool {
+    matches!(e.downcast_ref::<Errors>(), Some(Errors::Equivocation))
+}
+
 /// Baseline User api object. Contains the api user implementation as well as the transport object
 pub struct User<Trans> {
     pub user: UserImp,
     pub transport: Trans,
+    rejected: Vec<RejectedMessage>,
+    equivocations: Vec<Equivocation>,
 }
 
 impl<Trans> User<Trans> {
@@ -66,7 +173,40 @@ impl<Trans> User<Trans> {
             ENCODING.as_bytes().to_vec(),
             PAYLOAD_LENGTH,
         );
-        Self { user, transport }
+        Self {
+            user,
+            transport,
+            rejected: Vec::new(),
+            equivocations: Vec::new(),
+        }
+    }
+
+    /// Create a new User instance with the given keys.
+    ///
+    /// # Arguments
+    /// * `sig_kp` - Ed25519 signing key of the user, possibly held by an external signer
+    /// * `ke_kp` - X25519 key exchange key of the user, possibly held outside of memory
+    /// * `channel_type` - Implementation type: [0: Single Branch, 1: Multi Branch , 2: Single Depth]
+    /// * `transport` - Transport object used for sending and receiving
+    pub fn new_with_keys(
+        sig_kp: Box<dyn ed25519::Signer + Send + Sync>,
+        ke_kp: Box<dyn x25519::KeyAgreement + Send + Sync>,
+        channel_type: ChannelType,
+        transport: Trans,
+    ) -> Self {
+        let user = UserImp::new_with_keys(
+            sig_kp,
+            ke_kp,
+            channel_type,
+            ENCODING.as_bytes().to_vec(),
+            PAYLOAD_LENGTH,
+        );
+        Self {
+            user,
+            transport,
+            rejected: Vec::new(),
+            equivocations: Vec::new(),
+        }
     }
 
     pub fn get_transport(&self) -> &Trans {
@@ -102,7 +242,41 @@ impl<Trans> User<Trans> {
 
     /// Fetch the user ed25519 public key
     pub fn get_public_key(&self) -> &PublicKey {
-        &self.user.sig_kp.public
+        self.user.sig_kp.public_key()
+    }
+
+    /// Generate a new x25519 key exchange key pair, independent from the ed25519 key pair
+    ///
+    /// The new public key is shared with the other participants in the next Announce or Subscribe message.
+    ///
+    /// # Arguments
+    /// * `seed` - A string slice representing the seed of the key exchange key pair
+    pub fn gen_exchange_key(&mut self, seed: &str) -> Result<()> {
+        let nonce = "TANGLEUSERNONCE".as_bytes().to_vec();
+        let ke_sk = x25519::StaticSecret::new(prng::Rng::new(
+            prng::from_seed::<DefaultF>("IOTA Streams Channels user ke keypair", seed),
+            nonce,
+        ));
+        self.user.set_ke_kp(Box::new(ke_sk))
+    }
+
+    /// Go back to the x25519 key exchange key pair derived from the ed25519 key pair
+    ///
+    /// The derived key is used by the other participants after the next Announce or Subscribe message.
+    pub fn reset_exchange_key(&mut self) -> Result<()> {
+        self.user.reset_ke_kp()
+    }
+
+    /// Replace the random number generator used for session keys, nonces and ephemeral keys, see
+    /// [`User::set_rng`](crate::api::user::User::set_rng)
+    pub fn set_rng(&mut self, rng: Box<dyn prng::CryptoRngCore + Send + Sync>) {
+        self.user.set_rng(rng)
+    }
+
+    /// Set resource limits enforced while unwrapping received messages, see
+    /// [`User::set_unwrap_limits`](crate::api::user::User::set_unwrap_limits)
+    pub fn set_unwrap_limits(&mut self, limits: UnwrapLimits) {
+        self.user.set_unwrap_limits(limits)
     }
 
     pub fn is_registered(&self) -> bool {
@@ -186,6 +360,8 @@ impl<Trans> User<Trans> {
         UserImp::import(bytes, flag, pwd).await.map(|u| Self {
             user: u,
             transport: tsp,
+            rejected: Vec::new(),
+            equivocations: Vec::new(),
         })
     }
 
@@ -222,15 +398,62 @@ impl<Trans> User<Trans> {
         self.user.remove_subscriber(pk)
     }
 
-    /// Consume a binary sequence message and return the derived message link
-    async fn process_sequence(&mut self, msg: &BinaryMessage, store: bool) -> Result<Address> {
-        let unwrapped = self.user.handle_sequence(msg, MsgInfo::Sequence, store).await?;
+    /// Messages rejected since the last call to [`User::take_rejected_messages`].
+    ///
+    /// A message is rejected when it could not be handled while other messages were found at its link.
+    /// Since anyone can publish such messages, only the last [`MAX_REJECTED_MESSAGES`] are kept.
+    pub fn rejected_messages(&self) -> &[RejectedMessage] {
+        &self.rejected
+    }
+
+    /// Take the rejected messages, clearing the record.
+    pub fn take_rejected_messages(&mut self) -> Vec<RejectedMessage> {
+        mem::take(&mut self.rejected)
+    }
+
+    pub(crate) fn reject(&mut self, message: Message, reason: &impl ToString) {
+        if self.rejected.len() == MAX_REJECTED_MESSAGES {
+            self.rejected.remove(0);
+        }
+        self.rejected.push(RejectedMessage {
+            message,
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Equivocations detected since the last call to [`User::take_equivocations`].
+    ///
+    /// An equivocation is detected when a message unwraps at a link where another message was
+    /// already handled. The first message is kept, the conflicting one is not handled. See
+    /// [`Equivocation`] for what a detected conflict does and does not prove.
+    pub fn equivocations(&self) -> &[Equivocation] {
+        &self.equivocations
+    }
+
+    /// Take the detected equivocations, clearing the record.
+    pub fn take_equivocations(&mut self) -> Vec<Equivocation> {
+        mem::take(&mut self.equivocations)
+    }
+
+    pub(crate) fn record_equivocation(&mut self, first: Message, second: Message) {
+        self.equivocations.push(Equivocation {
+            link: first.link,
+            first,
+            second,
+        });
+    }
+
+    /// Consume a binary sequence message and return the publisher along with the derived message link
+    ///
+    /// The cursor of the publisher is left as is, see `handle_message`.
+    async fn process_sequence(&mut self, msg: &BinaryMessage) -> Result<(Identifier, Address)> {
+        let unwrapped = self.user.handle_sequence(msg, MsgInfo::Sequence, false).await?;
         let msg_cursor = self.user.gen_link(
             unwrapped.body.id,
             &unwrapped.body.ref_link,
             unwrapped.body.seq_num.0 as u32,
         );
-        Ok(msg_cursor.link)
+        Ok((unwrapped.body.id, msg_cursor.link))
     }
 }
 
@@ -272,6 +495,9 @@ impl<Trans: Transport + Clone> User<Trans> {
 
     /// Send a message using sequencing logic.
     ///
+    /// The sequence message is only sent once the message was sent and committed, so that readers are
+    /// never pointed to a message which is not published.
+    ///
     /// # Arguments
     /// * `msg` - Wrapped Message ready for sending
     /// * `ref_link` - Reference link to be included in sequence message
@@ -383,16 +609,18 @@ impl<Trans: Transport + Clone> User<Trans> {
     ///  # Arguments
     ///  * `link` - Address of the message to be processed
     pub async fn receive_sequence(&mut self, link: &Address) -> Result<Address> {
-        let msg = self.transport.recv_message(link).await?;
-        if let Some(_addr) = &self.user.appinst {
-            let seq_msg = self.user.handle_sequence(&msg, MsgInfo::Sequence, true).await?.body;
-            let msg_cursor = self
-                .user
-                .gen_link(seq_msg.id, &seq_msg.ref_link, seq_msg.seq_num.0 as u32);
-            Ok(msg_cursor.link)
-        } else {
-            err!(UserNotRegistered)
+        if self.user.appinst.is_none() {
+            return err!(UserNotRegistered);
         }
+        let seq_msg = first_accepted!(self, link, |msg| self
+            .user
+            .handle_sequence(&msg, MsgInfo::Sequence, true)
+            .await)?
+        .body;
+        let msg_cursor = self
+            .user
+            .gen_link(seq_msg.id, &seq_msg.ref_link, seq_msg.seq_num.0 as u32);
+        Ok(msg_cursor.link)
     }
 
     /// Receive and process a signed packet message [Author, Subscriber].
@@ -400,8 +628,10 @@ impl<Trans: Transport + Clone> User<Trans> {
     ///  # Arguments
     ///  * `link` - Address of the message to be processed
     pub async fn receive_signed_packet(&mut self, link: &Address) -> Result<(PublicKey, Bytes, Bytes)> {
-        let msg = self.transport.recv_message(link).await?;
-        let m = self.user.handle_signed_packet(&msg, MsgInfo::SignedPacket).await?;
+        let m = first_accepted!(self, link, |msg| self
+            .user
+            .handle_signed_packet(&msg, MsgInfo::SignedPacket)
+            .await)?;
         Ok(m.body)
     }
 
@@ -410,8 +640,10 @@ impl<Trans: Transport + Clone> User<Trans> {
     ///  # Arguments
     ///  * `link` - Address of the message to be processed
     pub async fn receive_tagged_packet(&mut self, link: &Address) -> Result<(Bytes, Bytes)> {
-        let msg = self.transport.recv_message(link).await?;
-        let m = self.user.handle_tagged_packet(&msg, MsgInfo::TaggedPacket).await?;
+        let m = first_accepted!(self, link, |msg| self
+            .user
+            .handle_tagged_packet(&msg, MsgInfo::TaggedPacket)
+            .await)?;
         Ok(m.body)
     }
 
@@ -420,8 +652,10 @@ impl<Trans: Transport + Clone> User<Trans> {
     ///  # Arguments
     ///  * `link` - Address of the message to be processed
     pub async fn receive_subscribe(&mut self, link: &Address) -> Result<()> {
-        let msg = self.transport.recv_message(link).await?;
-        self.user.handle_subscribe(&msg, MsgInfo::Subscribe).await
+        first_accepted!(self, link, |msg| self
+            .user
+            .handle_subscribe(&msg, MsgInfo::Subscribe)
+            .await)
     }
 
     /// Receive and process an unsubscribe message [Author].
@@ -429,8 +663,10 @@ impl<Trans: Transport + Clone> User<Trans> {
     ///  # Arguments
     ///  * `link` - Address of the message to be processed
     pub async fn receive_unsubscribe(&mut self, link: &Address) -> Result<()> {
-        let msg = self.transport.recv_message(link).await?;
-        self.user.handle_unsubscribe(msg, MsgInfo::Unsubscribe).await
+        first_accepted!(self, link, |msg| self
+            .user
+            .handle_unsubscribe(msg.clone(), MsgInfo::Unsubscribe)
+            .await)
     }
 
     /// Receive and Process an announcement message [Subscriber].
@@ -438,8 +674,10 @@ impl<Trans: Transport + Clone> User<Trans> {
     /// # Arguments
     /// * `link_to` - Address of the Channel Announcement message
     pub async fn receive_announcement(&mut self, link: &Address) -> Result<()> {
-        let msg = self.transport.recv_message(link).await?;
-        self.user.handle_announcement(&msg, MsgInfo::Announce).await
+        first_accepted!(self, link, |msg| self
+            .user
+            .handle_announcement(&msg, MsgInfo::Announce)
+            .await)
     }
 
     /// Receive and process a keyload message [Subscriber].
@@ -447,8 +685,7 @@ impl<Trans: Transport + Clone> User<Trans> {
     ///  # Argument&s
     ///  * `link` - Address of the message to be processed
     pub async fn receive_keyload(&mut self, link: &Address) -> Result<bool> {
-        let msg = self.transport.recv_message(link).await?;
-        let m = self.user.handle_keyload(&msg, MsgInfo::Keyload).await?;
+        let m = first_accepted!(self, link, |msg| self.user.handle_keyload(&msg, MsgInfo::Keyload).await)?;
         Ok(m.body)
     }
 
@@ -459,8 +696,7 @@ impl<Trans: Transport + Clone> User<Trans> {
     ///   * `link` - Address of the message to be processed
     ///   * `pk` - Optional ed25519 Public Key of the sending participant. None if unknown
     pub async fn receive_message(&mut self, link: &Address) -> Result<UnwrappedMessage> {
-        let msg = self.transport.recv_message(link).await?;
-        self.handle_message(msg, true).await
+        first_accepted!(self, link, |msg| self.handle_message(&msg, true).await)
     }
 
     /// Start a [`Messages`] stream to traverse the channel messages
@@ -493,14 +729,25 @@ impl<Trans: Transport + Clone> User<Trans> {
     /// # Arguments
     /// * `link` - Address of message to act as root of previous message fetching
     pub async fn fetch_prev_msg(&mut self, link: &Address) -> Result<UnwrappedMessage> {
-        let msg = self.transport.recv_message(link).await?;
-        let preparsed: Preparsed = msg.parse_header().await?;
-        let header = preparsed.header;
-        let prev_msg_link = Address::try_from_bytes(&header.previous_msg_link.0)
-            .or_else(|_| err!(NoPreviousMessage(link.to_string())))?;
-        let prev_msg = self.transport.recv_message(&prev_msg_link).await?;
-        let unwrapped = self.handle_message(prev_msg, false).await?;
-        Ok(unwrapped)
+        // Anyone can publish at the link, each message found is followed until a previous message is accepted
+        let mut result = err(NoPreviousMessage(link.to_string()));
+        for prev_msg_link in self.prev_msg_links(link).await? {
+            let candidates = match self.transport.recv_messages(&prev_msg_link).await {
+                Ok(candidates) => candidates,
+                Err(e) => {
+                    result = Err(e);
+                    continue;
+                }
+            };
+            result = handle_candidates!(self, &prev_msg_link, candidates, |prev_msg| self
+                .handle_message(&prev_msg, false)
+                .await)
+            .map_err(|(e, _)| e);
+            if result.is_ok() {
+                break;
+            }
+        }
+        result
     }
 
     /// Retrieves a specified number of previous messages from an original specified messsage link [Author, Subscriber]
@@ -508,25 +755,20 @@ impl<Trans: Transport + Clone> User<Trans> {
     /// * `link` - Address of message to act as root of previous message fetching
     /// * `max` - The number of msgs to try and parse
     pub async fn fetch_prev_msgs(&mut self, link: &Address, max: usize) -> Result<Vec<UnwrappedMessage>> {
-        let mut msg_info: (Address, u8, Message) = self.parse_msg_info(link).await?;
-        let mut to_process = Vec::new();
+        let mut link = *link;
         let mut msgs = Vec::new();
 
-        for _ in 0..max {
-            msg_info = unwrap_or_break!(self.parse_msg_info(&msg_info.0).await);
-            if msg_info.1 == message::SEQUENCE {
-                let msg_link = self.process_sequence(&msg_info.2, false).await?;
-                msg_info = self.parse_msg_info(&msg_link).await?;
-            }
-            to_process.push(msg_info.2);
-        }
-
-        to_process.reverse();
-        for msg in to_process {
-            let unwrapped = self.handle_message(msg, false).await?;
-            msgs.push(unwrapped);
+        for i in 0..max {
+            let prev_msg = match self.fetch_prev_msg(&link).await {
+                Ok(prev_msg) => prev_msg,
+                Err(e) if i == 0 => return Err(e),
+                Err(_) => break,
+            };
+            link = prev_msg.link;
+            msgs.push(prev_msg);
         }
 
+        msgs.reverse();
         Ok(msgs)
     }
 
@@ -541,7 +783,7 @@ impl<Trans: Transport + Clone> User<Trans> {
         M: AsRef<BinaryMessage>,
     {
         let msg = msg.as_ref();
-        let preparsed: Preparsed = msg.parse_header().await?;
+        let preparsed: Preparsed = msg.parse_header_with_limits(self.user.unwrap_limits).await?;
         match preparsed.header.content_type {
             message::SIGNED_PACKET => Ok(self
                 .user
@@ -565,39 +807,60 @@ impl<Trans: Transport + Clone> User<Trans> {
                     .map(|_allowed| MessageContent::new_keyload()))
             }
             message::SEQUENCE => {
-                let msg_link = self.process_sequence(msg, store).await?;
-                let sequenced_msg = self.transport.recv_message(&msg_link).await?;
-                let unwrapped_msg = self.handle_message(&sequenced_msg, store).await;
-                // future::ready artificiality is needed to be able to move sequence_msg into the async closure
-                future::ready(unwrapped_msg)
-                    .or_else(|_| async {
-                        // As long as the sequence message is readable, we return Ok even if the referenced msg is not.
-                        // This lets subscribers advance the cursor of the publisher even if they don't have access
-                        // to this particular msg
-                        let preparsed: Preparsed<'_> = sequenced_msg.parse_header().await?;
-                        let link = preparsed.header.link;
-                        let prev_link = TangleAddress::try_from_bytes(&preparsed.header.previous_msg_link.0)?;
-                        Ok(UnwrappedMessage::new(
-                            link,
-                            prev_link,
-                            MessageContent::unreadable(sequenced_msg),
-                        ))
-                    })
-                    .await
+                let (publisher, msg_link) = self.process_sequence(msg).await?;
+                let candidates = self.transport.recv_messages(&msg_link).await?;
+                // The cursor only moves past the sequence message once the referenced message is fetched,
+                // so that the sequence message is polled again after a transport failure
+                if store {
+                    self.user.store_state(publisher, *msg.link.rel())?;
+                }
+                let sequenced_msg = match handle_candidates!(self, &msg_link, candidates, |sequenced_msg| self
+                    .handle_message(&sequenced_msg, store)
+                    .await)
+                {
+                    Ok(unwrapped_msg) => return Ok(unwrapped_msg),
+                    Err((_, Some(sequenced_msg))) => sequenced_msg,
+                    Err((e, None)) => return Err(e),
+                };
+                // As long as the sequence message is readable, we return Ok even if the referenced msg is not.
+                // This lets subscribers advance the cursor of the publisher even if they don't have access
+                // to this particular msg
+                let preparsed: Preparsed<'_> = sequenced_msg.parse_header_with_limits(self.user.unwrap_limits).await?;
+                let link = preparsed.header.link;
+                let prev_link = TangleAddress::try_from_bytes(preparsed.header.previous_msg_link.as_slice())?;
+                Ok(UnwrappedMessage::new(
+                    link,
+                    prev_link,
+                    MessageContent::unreadable(sequenced_msg),
+                ))
             }
             unknown_content => err!(UnknownMsgType(unknown_content)),
         }
     }
 
-    /// Get the previous msg link and msg type from header of message and return in a tuple alongside
-    /// the message itself
-    async fn parse_msg_info(&mut self, link: &Address) -> Result<(Address, u8, Message)> {
-        let msg = self.transport.recv_message(link).await?;
-        let preparsed: Preparsed = msg.parse_header().await?;
-        let header = preparsed.header;
-        let link = Address::try_from_bytes(&header.previous_msg_link.0)
-            .or_else(|_| err!(NoPreviousMessage(link.to_string())))?;
-        Ok((link, header.content_type, msg))
+    /// Get the distinct previous msg links from the headers of the messages found at a link, no more than
+    /// [`MAX_CANDIDATES_PER_LINK`] messages being parsed
+    async fn prev_msg_links(&mut self, link: &Address) -> Result<Vec<Address>> {
+        let mut prev_msg_links = Vec::new();
+        for msg in self
+            .transport
+            .recv_messages(link)
+            .await?
+            .iter()
+            .take(MAX_CANDIDATES_PER_LINK)
+        {
+            // Messages which can not be parsed are not authentic, they are skipped
+            let preparsed: Preparsed = match msg.parse_header_with_limits(self.user.unwrap_limits).await {
+                Ok(preparsed) => preparsed,
+                Err(_) => continue,
+            };
+            if let Ok(prev_msg_link) = Address::try_from_bytes(preparsed.header.previous_msg_link.as_slice()) {
+                if !prev_msg_links.contains(&prev_msg_link) {
+                    prev_msg_links.push(prev_msg_link);
+                }
+            }
+        }
+        Ok(prev_msg_links)
     }
 
     /// Receive and process a message with a known anchor link and message number. This can only
@@ -618,8 +881,7 @@ impl<Trans: Transport + Clone> User<Trans> {
             Some(pk) => {
                 let seq_no = self.user.fetch_anchor()?.seq_no;
                 let msg_cursor = self.user.gen_link(pk, anchor_link.rel(), seq_no + msg_num);
-                let msg = self.transport.recv_message(&msg_cursor.link).await?;
-                self.handle_message(msg, false).await
+                first_accepted!(self, &msg_cursor.link, |msg| self.handle_message(&msg, false).await)
             }
             None => err(UserNotRegistered),
         }
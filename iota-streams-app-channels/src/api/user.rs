@@ -67,8 +67,19 @@ use core::borrow::BorrowMut;
 const ANN_MESSAGE_NUM: u32 = 0; // Announcement is always the first message of authors
 const SUB_MESSAGE_NUM: u32 = 0; // Subscribe is always the first message of subscribers
 const SEQ_MESSAGE_NUM: u32 = 1; // Reserved for sequence messages
+const EXPORT_VERSION: u8 = 2; // Version 1 adds separate key exchange keys, version 2 their rotation numbers
 const INIT_MESSAGE_NUM: u32 = 2; // First non-reserved message number
 
+/// Unsubscribe key and ephemeral seed of a subscribe message, in the order they are drawn.
+fn subscribe_secrets(rng: &mut dyn prng::CryptoRngCore) -> (prng::Key, prng::Key) {
+    (prng::random_key(rng), prng::random_key(rng))
+}
+
+/// Nonce, session key and ephemeral seed of a keyload message, in the order they are drawn.
+fn keyload_secrets(rng: &mut dyn prng::CryptoRngCore) -> (prng::Nonce, prng::Key, prng::Key) {
+    (prng::random_nonce(rng), prng::random_key(rng), prng::random_key(rng))
+}
+
 /// Sequence wrapping object
 ///
 /// When using multibranch mode, this wrapping object contains the (wrapped) sequence message ([`WrappedMessage`]) to be
@@ -116,11 +127,14 @@ where
     // pub(crate) prng: prng::Prng<F>,
     _phantom: PhantomData<F>,
 
-    /// Own Ed25519 private key.
-    pub(crate) sig_kp: ed25519::Keypair,
+    /// Own Ed25519 signing key, either held in memory or by an external signer.
+    pub(crate) sig_kp: Box<dyn ed25519::Signer + Send + Sync>,
+
+    /// Own X25519 key, either derived from Ed25519 keypair or separate from it.
+    pub(crate) ke_kp: Box<dyn x25519::KeyAgreement + Send + Sync>,
 
-    /// Own x25519 key pair corresponding to Ed25519 keypair.
-    pub(crate) ke_kp: (x25519::StaticSecret, x25519::PublicKey),
+    /// RNG used for session keys, nonces and ephemeral keys. None to use the default RNG of the platform.
+    pub(crate) rng: Option<Box<dyn prng::CryptoRngCore + Send + Sync>>,
 
     /// Users' trusted public keys together with additional sequencing info: (msgid, seq_no).
     pub(crate) key_store: Keys,
@@ -149,6 +163,9 @@ where
 
     /// Anchor message for the channel (can either be an announcement or keyload) - For single depth
     pub anchor: Option<Cursor<Link>>,
+
+    /// Resource limits enforced while unwrapping received messages.
+    pub(crate) unwrap_limits: unwrap::Limits,
 }
 
 impl<F, Link, LG, LS, Keys> Default for User<F, Link, LG, LS, Keys>
@@ -164,12 +181,13 @@ where
             secret: ed25519::SecretKey::from_bytes(&[0; ed25519::SECRET_KEY_LENGTH]).unwrap(),
             public: ed25519::PublicKey::default(),
         };
-        let ke_kp = x25519::keypair_from_ed25519(&sig_kp);
+        let (ke_sk, _) = x25519::keypair_from_ed25519(&sig_kp);
 
         Self {
             _phantom: PhantomData,
-            sig_kp,
-            ke_kp,
+            sig_kp: Box::new(sig_kp),
+            ke_kp: Box::new(ke_sk),
+            rng: None,
 
             key_store: Keys::default(),
             author_sig_pk: None,
@@ -181,6 +199,7 @@ where
             uniform_payload_length: 0,
             use_psk: false,
             anchor: None,
+            unwrap_limits: unwrap::Limits::default(),
         }
     }
 }
@@ -204,8 +223,27 @@ where
         uniform_payload_length: usize,
     ) -> Self {
         let sig_kp = ed25519::Keypair::generate(&mut prng::Rng::new(prng, nonce));
-        let ke_kp = x25519::keypair_from_ed25519(&sig_kp);
+        let (ke_sk, _) = x25519::keypair_from_ed25519(&sig_kp);
+        Self::new_with_keys(
+            Box::new(sig_kp),
+            Box::new(ke_sk),
+            channel_type,
+            message_encoding,
+            uniform_payload_length,
+        )
+    }
 
+    /// Create a new User with the given signing and key exchange keys.
+    ///
+    /// The keys may be held outside of the process (eg. in an HSM), in which case the user
+    /// state can not be exported.
+    pub fn new_with_keys(
+        sig_kp: Box<dyn ed25519::Signer + Send + Sync>,
+        ke_kp: Box<dyn x25519::KeyAgreement + Send + Sync>,
+        channel_type: ChannelType,
+        message_encoding: Vec<u8>,
+        uniform_payload_length: usize,
+    ) -> Self {
         let flags: u8 = match channel_type {
             ChannelType::SingleBranch => 0,
             ChannelType::MultiBranch => 1,
@@ -216,6 +254,7 @@ where
             _phantom: PhantomData,
             sig_kp,
             ke_kp,
+            rng: None,
 
             key_store: Keys::default(),
             author_sig_pk: None,
@@ -227,6 +266,7 @@ where
             uniform_payload_length,
             use_psk: false,
             anchor: None,
+            unwrap_limits: unwrap::Limits::default(),
         }
     }
 
@@ -237,13 +277,14 @@ where
                 self.appinst.as_ref().unwrap().base().to_string()
             ));
         }
-        self.link_gen.gen(&self.sig_kp.public, channel_idx);
+        self.link_gen.gen(self.sig_kp.public_key(), channel_idx);
         let appinst = self.link_gen.get();
 
-        let identifier = self.sig_kp.public.into();
+        let identifier = (*self.sig_kp.public_key()).into();
         self.key_store
             .insert_cursor(identifier, Cursor::new_at(appinst.rel().clone(), 0, INIT_MESSAGE_NUM))?;
-        self.author_sig_pk = Some(self.sig_kp.public);
+        self.key_store.insert_ke_pk(identifier, self.ke_kp.public_key())?;
+        self.author_sig_pk = Some(*self.sig_kp.public_key());
         self.anchor = Some(Cursor::new_at(appinst.clone(), 0, INIT_MESSAGE_NUM));
         self.appinst = Some(appinst);
         Ok(())
@@ -254,6 +295,82 @@ where
         self.author_sig_pk.as_ref()
     }
 
+    /// Replace own X25519 key with one that is not derived from the Ed25519 key pair.
+    ///
+    /// The new public key is announced to the other participants in subsequent Announce and
+    /// Subscribe messages. Once the user is part of a channel, every replacement increments the
+    /// rotation number sent along with the key in Subscribe messages.
+    ///
+    /// Subscribers only learn the key of the author from the announcement, so the author can't
+    /// replace its key once the channel is announced. Rotating it takes a new channel.
+    pub fn set_ke_kp(&mut self, ke_kp: Box<dyn x25519::KeyAgreement + Send + Sync>) -> Result<()> {
+        if let (Some(author_sig_pk), Some(appinst)) = (&self.author_sig_pk, &self.appinst) {
+            let announced = self.link_store.lookup(appinst.rel()).is_ok();
+            try_or!(
+                !announced || author_sig_pk != self.sig_kp.public_key(),
+                AuthorExchangeKeyAnnounced
+            )?;
+        }
+        let identifier = (*self.sig_kp.public_key()).into();
+        if self.key_store.contains(&identifier) {
+            self.key_store.insert_ke_pk(identifier, ke_kp.public_key())?;
+            let rotation = self
+                .key_store
+                .get_ke_rotation(&identifier)
+                .map_or(1, |rotation| rotation + 1);
+            self.key_store.insert_ke_rotation(identifier, rotation)?;
+        }
+        self.ke_kp = ke_kp;
+        Ok(())
+    }
+
+    /// Replace own X25519 key with the one derived from the Ed25519 key pair, undoing [`User::set_ke_kp`].
+    pub fn reset_ke_kp(&mut self) -> Result<()> {
+        match self.sig_kp.keypair() {
+            Some(sig_kp) => {
+                let (ke_sk, _) = x25519::keypair_from_ed25519(sig_kp);
+                self.set_ke_kp(Box::new(ke_sk))
+            }
+            None => err(SigKeyNotInMemory),
+        }
+    }
+
+    /// Replace the RNG used for session keys, nonces and ephemeral keys.
+    ///
+    /// Without an RNG set, the thread-local RNG is used on `std` targets. `no_std` targets have no
+    /// global source of randomness, so sending subscribe and keyload messages fails until an RNG is set.
+    /// The RNG is not part of the exported state and has to be set again after import.
+    pub fn set_rng(&mut self, rng: Box<dyn prng::CryptoRngCore + Send + Sync>) {
+        self.rng = Some(rng);
+    }
+
+    /// Set resource limits enforced while unwrapping received messages.
+    ///
+    /// Sizes and counts of fields in received messages are not trusted beyond these limits.
+    /// The limits are not part of the exported state and have to be set again after import.
+    pub fn set_unwrap_limits(&mut self, limits: unwrap::Limits) {
+        self.unwrap_limits = limits;
+    }
+
+    fn with_rng<T>(&mut self, f: impl FnOnce(&mut dyn prng::CryptoRngCore) -> T) -> Result<T> {
+        match self.rng.as_mut() {
+            Some(rng) => Ok(f(&mut **rng)),
+            #[cfg(feature = "std")]
+            None => Ok(f(&mut prng::rng())),
+            #[cfg(not(feature = "std"))]
+            None => err(NoStdRngMissing),
+        }
+    }
+
+    /// Own X25519 public key, if it is not derived from the Ed25519 public key.
+    pub fn separate_ke_pk(&self) -> Option<x25519::PublicKey> {
+        let ke_pk = self.ke_kp.public_key();
+        match x25519::public_from_ed25519(self.sig_kp.public_key()) {
+            Ok(derived_ke_pk) if derived_ke_pk == ke_pk => None,
+            _ => Some(ke_pk),
+        }
+    }
+
     /// Reset link store and key store to original state
     pub fn reset_state(&mut self) -> Result<()> {
         match &self.appinst {
@@ -261,6 +378,12 @@ where
                 let mut key_store = Keys::default();
                 for (id, _cursor) in self.key_store.iter() {
                     key_store.insert_cursor(*id, Cursor::new_at(appinst.rel().clone(), 0, INIT_MESSAGE_NUM))?;
+                    if let Some(ke_pk) = self.key_store.get_ke_pk(id) {
+                        key_store.insert_ke_pk(*id, *ke_pk)?;
+                    }
+                    if let Some(rotation) = self.key_store.get_ke_rotation(id) {
+                        key_store.insert_ke_rotation(*id, rotation)?;
+                    }
                 }
                 self.key_store = key_store;
 
@@ -289,8 +412,8 @@ where
             .with_content_type(ANNOUNCE)?
             .with_payload_length(1)?
             .with_seq_num(ANN_MESSAGE_NUM)
-            .with_identifier(&self.sig_kp.public.into());
-        let content = announce::ContentWrap::new(&self.sig_kp, self.flags);
+            .with_identifier(&(*self.sig_kp.public_key()).into());
+        let content = announce::ContentWrap::new(&*self.sig_kp, self.separate_ke_pk(), self.flags);
         Ok(PreparedMessage::new(header, content))
     }
 
@@ -306,7 +429,7 @@ where
         if let Some(appinst) = &self.appinst {
             try_or!(
                 appinst == &preparsed.header.link,
-                UserAlreadyRegistered(hex::encode(self.sig_kp.public), appinst.base().to_string())
+                UserAlreadyRegistered(hex::encode(self.sig_kp.public_key()), appinst.base().to_string())
             )?;
         }
 
@@ -318,7 +441,7 @@ where
     /// Bind Subscriber (or anonymously subscribe) to the channel announced
     /// in the message.
     pub async fn handle_announcement(&mut self, msg: &BinaryMessage<Link>, info: LS::Info) -> Result<()> {
-        let preparsed = msg.parse_header().await?;
+        let preparsed = msg.parse_header_with_limits(self.unwrap_limits).await?;
         try_or!(
             preparsed.content_type() == ANNOUNCE,
             NotAnnouncement(preparsed.content_type())
@@ -336,10 +459,12 @@ where
         // At the moment the Author is free to choose any address, not tied to PK.
 
         let cursor = Cursor::new_at(link.rel().clone(), 0, INIT_MESSAGE_NUM);
-        self.key_store
-            .insert_cursor(Identifier::EdPubKey(content.sig_pk.into()), cursor.clone())?;
-        self.key_store
-            .insert_cursor(Identifier::EdPubKey(self.sig_kp.public.into()), cursor)?;
+        let author_id = Identifier::EdPubKey(content.sig_pk.into());
+        let own_id = Identifier::EdPubKey((*self.sig_kp.public_key()).into());
+        self.key_store.insert_cursor(author_id, cursor.clone())?;
+        self.key_store.insert_ke_pk(author_id, content.ke_pk)?;
+        self.key_store.insert_cursor(own_id, cursor)?;
+        self.key_store.insert_ke_pk(own_id, self.ke_kp.public_key())?;
         // Reset link_gen
         self.link_gen.reset(link.clone());
         self.anchor = Some(Cursor::new_at(link.clone(), 0, INIT_MESSAGE_NUM));
@@ -349,26 +474,52 @@ where
         Ok(())
     }
 
-    /// Prepare Subscribe message.
+    /// Prepare Subscribe message with an unsubscribe key and ephemeral seed drawn from `rng`.
     pub fn prepare_subscribe<'a>(
         &'a self,
         link_to: &'a Link,
+        rng: &mut dyn prng::CryptoRngCore,
+    ) -> Result<PreparedMessage<F, Link, subscribe::ContentWrap<'a, F, Link>>> {
+        self.do_prepare_subscribe(link_to, subscribe_secrets(rng))
+    }
+
+    fn do_prepare_subscribe<'a>(
+        &'a self,
+        link_to: &'a Link,
+        (unsubscribe_key, ephemeral_seed): (prng::Key, prng::Key),
     ) -> Result<PreparedMessage<F, Link, subscribe::ContentWrap<'a, F, Link>>> {
         if let Some(author_sig_pk) = &self.author_sig_pk {
             let identifier = Identifier::EdPubKey(ed25519::PublicKeyWrap(*author_sig_pk));
             if let Some(author_ke_pk) = self.key_store.get_ke_pk(&identifier) {
-                let msg_cursor = self.gen_link(&self.sig_kp.public, link_to.rel(), SUB_MESSAGE_NUM);
-                let header = HDF::new(msg_cursor.link)
-                    .with_previous_msg_link(Bytes(link_to.to_bytes()))
-                    .with_content_type(SUBSCRIBE)?
+                let own_id = Identifier::EdPubKey((*self.sig_kp.public_key()).into());
+                let ke_rotation = self.key_store.get_ke_rotation(&own_id).unwrap_or(0);
+                // Every rotation is published at a link of its own, numbered like branches of the Subscribe,
+                // so that the first Subscribe keeps its link
+                let msg_link = self.link_gen.link_from(
+                    self.sig_kp.public_key(),
+                    Cursor::new_at(link_to.rel(), ke_rotation, SUB_MESSAGE_NUM),
+                );
+                // Rotating back to the derived key exchange key still has to announce the rotation
+                let subscriber_ke_pk = self
+                    .separate_ke_pk()
+                    .or_else(|| (ke_rotation > 0).then(|| self.ke_kp.public_key()));
+                let content_type = match subscriber_ke_pk {
+                    Some(_) => SUBSCRIBE_KE_PK,
+                    None => SUBSCRIBE,
+                };
+                let header = HDF::new(msg_link)
+                    .with_previous_msg_link(Bytes::from(link_to.to_bytes()))
+                    .with_content_type(content_type)?
                     .with_payload_length(1)?
-                    .with_seq_num(msg_cursor.seq_no)
-                    .with_identifier(&self.sig_kp.public.into());
-                let unsubscribe_key = NBytes::from(prng::random_key());
+                    .with_seq_num(SUB_MESSAGE_NUM)
+                    .with_identifier(&(*self.sig_kp.public_key()).into());
                 let content = subscribe::ContentWrap {
                     link: link_to.rel(),
-                    unsubscribe_key,
-                    subscriber_sig_kp: &self.sig_kp,
+                    unsubscribe_key: NBytes::from(unsubscribe_key),
+                    ephemeral_seed,
+                    subscriber_sig_kp: &*self.sig_kp,
+                    subscriber_ke_pk,
+                    ke_rotation,
                     author_ke_pk,
                     _phantom: PhantomData,
                 };
@@ -382,36 +533,74 @@ where
     }
 
     /// Subscribe to the channel.
-    pub async fn subscribe(&self, link_to: &Link) -> Result<WrappedMessage<F, Link>> {
-        self.prepare_subscribe(link_to)?.wrap(&self.link_store).await
+    pub async fn subscribe(&mut self, link_to: &Link) -> Result<WrappedMessage<F, Link>> {
+        let secrets = self.with_rng(subscribe_secrets)?;
+        self.do_prepare_subscribe(link_to, secrets)?
+            .wrap(&self.link_store)
+            .await
     }
 
     #[allow(clippy::needless_lifetimes)] // See https://github.com/rust-lang/rust-clippy/issues/7271
     pub async fn unwrap_subscribe<'a>(
         &self,
         preparsed: PreparsedMessage<'_, F, Link>,
-        author_ke_pk: &'a x25519::StaticSecret,
+        author_ke_pk: &'a dyn x25519::KeyAgreement,
     ) -> Result<UnwrappedMessage<F, Link, subscribe::ContentUnwrap<'a, F, Link>>> {
         self.ensure_appinst(&preparsed)?;
-        let content = subscribe::ContentUnwrap::new(author_ke_pk)?;
+        let mut content = subscribe::ContentUnwrap::new(author_ke_pk)?;
+        content.has_ke_pk = preparsed.content_type() == SUBSCRIBE_KE_PK;
         preparsed.unwrap(&self.link_store, content).await
     }
 
     /// Get public payload, decrypt masked payload and verify MAC.
     pub async fn handle_subscribe(&mut self, msg: &BinaryMessage<Link>, info: LS::Info) -> Result<()> {
-        let preparsed = msg.parse_header().await?;
+        let preparsed = msg.parse_header_with_limits(self.unwrap_limits).await?;
         // TODO: check content type
 
-        let content = self
-            // We need to borrow self.ke_kp.0 at this scope
+        let unwrapped = self
+            // We need to borrow self.ke_kp at this scope
             // to leverage https://doc.rust-lang.org/nomicon/borrow-splitting.html
-            .unwrap_subscribe(preparsed, &self.ke_kp.0)
-            .await?
-            .commit(&mut self.link_store, info)?;
+            .unwrap_subscribe(preparsed, &*self.ke_kp)
+            .await?;
+        let subscriber_sig_pk = unwrapped.pcf.content.subscriber_sig_pk;
+        let subscriber_id = subscriber_sig_pk.into();
+        let ke_rotation = unwrapped.pcf.content.ke_rotation;
+        let derived_ke_pk = x25519::public_from_ed25519(&subscriber_sig_pk)?;
+        // Subscribe messages are signed but can be published again by anyone, an older one must not
+        // roll the key exchange key of the subscriber back
+        if let Some(known_rotation) = self.key_store.get_ke_rotation(&subscriber_id) {
+            let ke_pk = unwrapped.pcf.content.subscriber_ke_pk.unwrap_or(derived_ke_pk);
+            let same_key = self.key_store.get_ke_pk(&subscriber_id) == Some(&ke_pk);
+            try_or!(
+                ke_rotation > known_rotation || (ke_rotation == known_rotation && same_key),
+                StaleKeyExchangeKey(ke_rotation, known_rotation)
+            )?;
+        }
+        // Subscribing again replaces the previous subscription, it is how subscribers rotate their key exchange key
+        let content = if self.key_store.contains(&subscriber_id) {
+            unwrapped.commit_replacing(&mut self.link_store, info)?
+        } else {
+            unwrapped.commit(&mut self.link_store, info)?
+        };
         // TODO: trust content.subscriber_sig_pk
         // TODO: remove unused unsubscribe_key because it is unnecessary for verification anymore
-        let subscriber_sig_pk = content.subscriber_sig_pk;
-        self.insert_subscriber(subscriber_sig_pk)
+        match content.subscriber_ke_pk {
+            // A known subscriber subscribing again with a separate key is rotating its key exchange key
+            Some(ke_pk) if self.key_store.contains(&subscriber_id) => {
+                self.key_store.insert_ke_pk(subscriber_id, ke_pk)?
+            }
+            Some(ke_pk) => {
+                self.insert_subscriber(subscriber_sig_pk)?;
+                self.key_store.insert_ke_pk(subscriber_id, ke_pk)?
+            }
+            None => match self.key_store.get_ke_pk(&subscriber_id) {
+                // A known subscriber with a separate key subscribing again without it, from before rotations
+                // were numbered, is rotating back to the key exchange key derived from its signature key
+                Some(ke_pk) if *ke_pk != derived_ke_pk => self.key_store.insert_ke_pk(subscriber_id, derived_ke_pk)?,
+                _ => self.insert_subscriber(subscriber_sig_pk)?,
+            },
+        }
+        self.key_store.insert_ke_rotation(subscriber_id, ke_rotation)
     }
 
     pub fn insert_subscriber(&mut self, pk: ed25519::PublicKey) -> Result<()> {
@@ -436,16 +625,16 @@ where
             Some(seq_no) => {
                 let msg_link = self
                     .link_gen
-                    .link_from(self.sig_kp.public, Cursor::new_at(link_to.rel(), 0, seq_no));
+                    .link_from(*self.sig_kp.public_key(), Cursor::new_at(link_to.rel(), 0, seq_no));
                 let header = HDF::new(msg_link)
-                    .with_previous_msg_link(Bytes(link_to.to_bytes()))
+                    .with_previous_msg_link(Bytes::from(link_to.to_bytes()))
                     .with_content_type(UNSUBSCRIBE)?
                     .with_payload_length(1)?
                     .with_seq_num(seq_no)
-                    .with_identifier(&self.sig_kp.public.into());
+                    .with_identifier(&(*self.sig_kp.public_key()).into());
                 let content = unsubscribe::ContentWrap {
                     link: link_to.rel(),
-                    sig_kp: &self.sig_kp,
+                    sig_kp: &*self.sig_kp,
                     _phantom: PhantomData,
                 };
                 Ok(PreparedMessage::new(header, content))
@@ -470,7 +659,7 @@ where
 
     /// Confirm unsubscription request ownership and remove subscriber.
     pub async fn handle_unsubscribe(&mut self, msg: BinaryMessage<Link>, info: LS::Info) -> Result<()> {
-        let preparsed = msg.parse_header().await?;
+        let preparsed = msg.parse_header_with_limits(self.unwrap_limits).await?;
         let content = self
             .unwrap_unsubscribe(preparsed)
             .await?
@@ -493,60 +682,86 @@ where
         &'a self,
         header: HDF<Link>,
         link_to: &'a Link::Rel,
-        keys: Vec<(&'a Identifier, Vec<u8>)>,
+        mut keys: Vec<(&'a Identifier, Vec<u8>)>,
+        (nonce, key, ephemeral_seed): (prng::Nonce, prng::Key, prng::Key),
     ) -> Result<PreparedMessage<F, Link, keyload::ContentWrap<'a, F, Link>>> {
-        let nonce = NBytes::from(prng::random_nonce());
-        let key = NBytes::from(prng::random_key());
+        // Key store iteration order is arbitrary, sort recipients so that the keyload only depends on the RNG
+        keys.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
         let content = keyload::ContentWrap {
             link: link_to,
-            nonce,
-            key,
+            nonce: NBytes::from(nonce),
+            key: NBytes::from(key),
+            ephemeral_seed,
             keys,
-            sig_kp: &self.sig_kp,
+            sig_kp: &*self.sig_kp,
             _phantom: PhantomData,
         };
         Ok(PreparedMessage::new(header, content))
     }
 
+    /// Prepare Keyload message with a session key, nonce and ephemeral seed drawn from `rng`.
     pub fn prepare_keyload<'a, 'b, I>(
         &'a self,
         link_to: &'a Link,
         keys: I,
+        rng: &mut dyn prng::CryptoRngCore,
+    ) -> Result<PreparedMessage<F, Link, keyload::ContentWrap<'a, F, Link>>>
+    where
+        I: IntoIterator<Item = &'b Identifier>,
+    {
+        self.prepare_keyload_with(link_to, keys, keyload_secrets(rng))
+    }
+
+    fn prepare_keyload_with<'a, 'b, I>(
+        &'a self,
+        link_to: &'a Link,
+        keys: I,
+        secrets: (prng::Nonce, prng::Key, prng::Key),
     ) -> Result<PreparedMessage<F, Link, keyload::ContentWrap<'a, F, Link>>>
     where
         I: IntoIterator<Item = &'b Identifier>,
     {
         match self.get_seq_no() {
             Some(seq_no) => {
-                let msg_cursor = self.gen_link(self.sig_kp.public, link_to.rel(), seq_no);
+                let msg_cursor = self.gen_link(*self.sig_kp.public_key(), link_to.rel(), seq_no);
                 let header = HDF::new(msg_cursor.link)
-                    .with_previous_msg_link(Bytes(link_to.to_bytes()))
+                    .with_previous_msg_link(Bytes::from(link_to.to_bytes()))
                     .with_content_type(KEYLOAD)?
                     .with_payload_length(1)?
                     .with_seq_num(msg_cursor.seq_no)
-                    .with_identifier(&self.sig_kp.public.into());
+                    .with_identifier(&(*self.sig_kp.public_key()).into());
                 let filtered_keys = self.key_store.filter(keys);
-                self.do_prepare_keyload(header, link_to.rel(), filtered_keys)
+                self.do_prepare_keyload(header, link_to.rel(), filtered_keys, secrets)
             }
             None => err!(SeqNumRetrievalFailure),
         }
     }
 
+    /// Prepare Keyload message for all known subscribers with secrets drawn from `rng`.
     pub fn prepare_keyload_for_everyone<'a>(
         &'a self,
         link_to: &'a Link,
+        rng: &mut dyn prng::CryptoRngCore,
+    ) -> Result<PreparedMessage<F, Link, keyload::ContentWrap<'a, F, Link>>> {
+        self.prepare_keyload_for_everyone_with(link_to, keyload_secrets(rng))
+    }
+
+    fn prepare_keyload_for_everyone_with<'a>(
+        &'a self,
+        link_to: &'a Link,
+        secrets: (prng::Nonce, prng::Key, prng::Key),
     ) -> Result<PreparedMessage<F, Link, keyload::ContentWrap<'a, F, Link>>> {
         match self.get_seq_no() {
             Some(seq_no) => {
-                let msg_cursor = self.gen_link(self.sig_kp.public, link_to.rel(), seq_no);
+                let msg_cursor = self.gen_link(*self.sig_kp.public_key(), link_to.rel(), seq_no);
                 let header = hdf::HDF::new(msg_cursor.link)
-                    .with_previous_msg_link(Bytes(link_to.to_bytes()))
+                    .with_previous_msg_link(Bytes::from(link_to.to_bytes()))
                     .with_content_type(KEYLOAD)?
                     .with_payload_length(1)?
                     .with_seq_num(msg_cursor.seq_no)
-                    .with_identifier(&self.sig_kp.public.into());
+                    .with_identifier(&(*self.sig_kp.public_key()).into());
                 let keys = self.key_store.keys();
-                self.do_prepare_keyload(header, link_to.rel(), keys)
+                self.do_prepare_keyload(header, link_to.rel(), keys, secrets)
             }
             None => err!(SeqNumRetrievalFailure),
         }
@@ -558,13 +773,19 @@ where
     where
         I: IntoIterator<Item = &'a Identifier>,
     {
-        self.prepare_keyload(link_to, keys)?.wrap(&self.link_store).await
+        let secrets = self.with_rng(keyload_secrets)?;
+        self.prepare_keyload_with(link_to, keys, secrets)?
+            .wrap(&self.link_store)
+            .await
     }
 
     /// Create keyload message with a new session key shared with all Subscribers
     /// known to Author.
     pub async fn share_keyload_for_everyone(&mut self, link_to: &Link) -> Result<WrappedMessage<F, Link>> {
-        self.prepare_keyload_for_everyone(link_to)?.wrap(&self.link_store).await
+        let secrets = self.with_rng(keyload_secrets)?;
+        self.prepare_keyload_for_everyone_with(link_to, secrets)?
+            .wrap(&self.link_store)
+            .await
     }
 
     pub async fn unwrap_keyload<'a>(
@@ -591,13 +812,13 @@ where
         msg: &BinaryMessage<Link>,
         info: LS::Info,
     ) -> Result<GenericMessage<Link, bool>> {
-        let preparsed = msg.parse_header().await?;
-        let prev_link = Link::try_from_bytes(&preparsed.header.previous_msg_link.0)?;
+        let preparsed = msg.parse_header_with_limits(self.unwrap_limits).await?;
+        let prev_link = Link::try_from_bytes(preparsed.header.previous_msg_link.as_slice())?;
         let seq_no = preparsed.header.seq_num;
         // We need to borrow self.key_store, self.sig_kp and self.ke_kp at this scope
         // to leverage https://doc.rust-lang.org/nomicon/borrow-splitting.html
         let keys_lookup = KeysLookup::new(&self.key_store);
-        let own_keys = OwnKeys(&self.sig_kp, &self.ke_kp);
+        let own_keys = OwnKeys(&*self.sig_kp, &*self.ke_kp);
         let unwrapped = self
             .unwrap_keyload(preparsed, keys_lookup, own_keys, self.author_sig_pk.as_ref())
             .await?;
@@ -652,18 +873,18 @@ where
         }
         match self.get_seq_no() {
             Some(seq_no) => {
-                let msg_cursor = self.gen_link(self.sig_kp.public, link_to.rel(), seq_no);
+                let msg_cursor = self.gen_link(*self.sig_kp.public_key(), link_to.rel(), seq_no);
                 let header = HDF::new(msg_cursor.link)
-                    .with_previous_msg_link(Bytes(link_to.to_bytes()))
+                    .with_previous_msg_link(Bytes::from(link_to.to_bytes()))
                     .with_content_type(SIGNED_PACKET)?
                     .with_payload_length(1)?
                     .with_seq_num(msg_cursor.seq_no)
-                    .with_identifier(&self.sig_kp.public.into());
+                    .with_identifier(&(*self.sig_kp.public_key()).into());
                 let content = signed_packet::ContentWrap {
                     link: link_to.rel(),
                     public_payload,
                     masked_payload,
-                    sig_kp: &self.sig_kp,
+                    sig_kp: &*self.sig_kp,
                     _phantom: PhantomData,
                 };
                 Ok(PreparedMessage::new(header, content))
@@ -700,8 +921,8 @@ where
         info: LS::Info,
     ) -> Result<GenericMessage<Link, (ed25519::PublicKey, Bytes, Bytes)>> {
         // TODO: pass author_pk to unwrap
-        let preparsed = msg.parse_header().await?;
-        let prev_link = Link::try_from_bytes(&preparsed.header.previous_msg_link.0)?;
+        let preparsed = msg.parse_header_with_limits(self.unwrap_limits).await?;
+        let prev_link = Link::try_from_bytes(preparsed.header.previous_msg_link.as_slice())?;
         let seq_no = preparsed.header.seq_num;
         let content = self
             .unwrap_signed_packet(preparsed)
@@ -732,7 +953,7 @@ where
             Some(seq_no) => {
                 let msg_cursor = self.gen_link(identifier, link_to.rel(), seq_no);
                 let header = HDF::new(msg_cursor.link)
-                    .with_previous_msg_link(Bytes(link_to.to_bytes()))
+                    .with_previous_msg_link(Bytes::from(link_to.to_bytes()))
                     .with_content_type(TAGGED_PACKET)?
                     .with_payload_length(1)?
                     .with_seq_num(msg_cursor.seq_no)
@@ -756,7 +977,7 @@ where
                 None => err(MessageBuildFailure),
             }
         } else {
-            Ok(self.sig_kp.public.into())
+            Ok((*self.sig_kp.public_key()).into())
         }
     }
 
@@ -788,8 +1009,8 @@ where
         msg: &BinaryMessage<Link>,
         info: LS::Info,
     ) -> Result<GenericMessage<Link, (Bytes, Bytes)>> {
-        let preparsed = msg.parse_header().await?;
-        let prev_link = Link::try_from_bytes(&preparsed.header.previous_msg_link.0)?;
+        let preparsed = msg.parse_header_with_limits(self.unwrap_limits).await?;
+        let prev_link = Link::try_from_bytes(preparsed.header.previous_msg_link.as_slice())?;
         let seq_no = preparsed.header.seq_num;
         let content = self
             .unwrap_tagged_packet(preparsed)
@@ -817,7 +1038,7 @@ where
                         Link::from_base_rel(self.appinst.as_ref().unwrap().base(), &original_cursor.link);
                     let seq_msg_cursor = self.gen_seq_link(identifier, &original_cursor.link);
                     let header = HDF::new(seq_msg_cursor.link)
-                        .with_previous_msg_link(Bytes(previous_msg_link.to_bytes()))
+                        .with_previous_msg_link(Bytes::from(previous_msg_link.to_bytes()))
                         .with_content_type(SEQUENCE)?
                         .with_payload_length(1)?
                         .with_seq_num(seq_msg_cursor.seq_no)
@@ -839,7 +1060,7 @@ where
                 } else if self.is_single_depth() {
                     Ok(WrappedSequence::SingleDepth(original_cursor.clone()))
                 } else {
-                    let full_cursor = self.gen_link(self.sig_kp.public, ref_link, original_cursor.seq_no);
+                    let full_cursor = self.gen_link(*self.sig_kp.public_key(), ref_link, original_cursor.seq_no);
                     let rel_cursor = Cursor::new_at(full_cursor.link.rel().clone(), 0, full_cursor.seq_no);
                     Ok(WrappedSequence::single_branch(rel_cursor))
                 }
@@ -857,7 +1078,7 @@ where
         cursor.link = wrapped_state.link.rel().clone();
         cursor.next_seq();
         self.key_store
-            .insert_cursor(Identifier::EdPubKey(self.sig_kp.public.into()), cursor)?;
+            .insert_cursor(Identifier::EdPubKey((*self.sig_kp.public_key()).into()), cursor)?;
         let link = wrapped_state.link.clone();
         wrapped_state.commit(&mut self.link_store, info)?;
         Ok(Some(link))
@@ -884,9 +1105,9 @@ where
         info: <LS as LinkStore<F, <Link as HasLink>::Rel>>::Info,
         store: bool,
     ) -> Result<GenericMessage<Link, sequence::ContentUnwrap<Link>>> {
-        let preparsed = msg.parse_header().await?;
+        let preparsed = msg.parse_header_with_limits(self.unwrap_limits).await?;
         let sender_id = preparsed.header.sender_id;
-        let prev_link = Link::try_from_bytes(&preparsed.header.previous_msg_link.0)?;
+        let prev_link = Link::try_from_bytes(preparsed.header.previous_msg_link.as_slice())?;
         let content = self
             .unwrap_sequence(preparsed)
             .await?
@@ -897,6 +1118,56 @@ where
         Ok(GenericMessage::new(msg.link.clone(), prev_link, content))
     }
 
+    /// Unwrap a message without changing any state and check that it does not conflict with the
+    /// message committed before at its link.
+    ///
+    /// Fails where handling the message would, with `Equivocation` for a conflicting message.
+    pub async fn check_conflict(&self, msg: &BinaryMessage<Link>) -> Result<()> {
+        let preparsed = msg.parse_header_with_limits(self.unwrap_limits).await?;
+        match preparsed.content_type() {
+            ANNOUNCE => self
+                .unwrap_announcement(preparsed)
+                .await?
+                .check_conflict(&self.link_store),
+            SUBSCRIBE | SUBSCRIBE_KE_PK => {
+                let unwrapped = self.unwrap_subscribe(preparsed, &*self.ke_kp).await?;
+                // Subscribing again replaces the previous subscription, see `handle_subscribe`
+                if self.key_store.contains(&unwrapped.pcf.content.subscriber_sig_pk.into()) {
+                    Ok(())
+                } else {
+                    unwrapped.check_conflict(&self.link_store)
+                }
+            }
+            UNSUBSCRIBE => self
+                .unwrap_unsubscribe(preparsed)
+                .await?
+                .check_conflict(&self.link_store),
+            KEYLOAD => {
+                let keys_lookup = KeysLookup::new(&self.key_store);
+                let own_keys = OwnKeys(&*self.sig_kp, &*self.ke_kp);
+                let unwrapped = self
+                    .unwrap_keyload(preparsed, keys_lookup, own_keys, self.author_sig_pk.as_ref())
+                    .await?;
+                // Keyloads are only committed by their recipients, see `handle_keyload`
+                if unwrapped.pcf.content.key.is_some() {
+                    unwrapped.check_conflict(&self.link_store)
+                } else {
+                    Ok(())
+                }
+            }
+            SIGNED_PACKET => self
+                .unwrap_signed_packet(preparsed)
+                .await?
+                .check_conflict(&self.link_store),
+            TAGGED_PACKET => self
+                .unwrap_tagged_packet(preparsed)
+                .await?
+                .check_conflict(&self.link_store),
+            SEQUENCE => self.unwrap_sequence(preparsed).await?.check_conflict(&self.link_store),
+            unknown_content => err!(UnknownMsgType(unknown_content)),
+        }
+    }
+
     pub fn is_multi_branching(&self) -> bool {
         (self.flags & FLAG_BRANCHING_MASK) != 0
     }
@@ -908,7 +1179,7 @@ where
     // TODO: own seq_no should be stored outside of pk_store to avoid lookup and Option
     pub fn get_seq_no(&self) -> Option<u32> {
         self.key_store
-            .get(&Identifier::EdPubKey(self.sig_kp.public.into()))
+            .get(&Identifier::EdPubKey((*self.sig_kp.public_key()).into()))
             .map(|cursor| cursor.seq_no)
     }
 
@@ -1018,7 +1289,7 @@ where
     pub fn store_state_for_all(&mut self, link: <Link as HasLink>::Rel, seq_no: u32) -> Result<()> {
         if &seq_no > self.get_seq_no().as_ref().unwrap_or(&0) {
             self.key_store.insert_cursor(
-                Identifier::EdPubKey(self.sig_kp.public.into()),
+                Identifier::EdPubKey((*self.sig_kp.public_key()).into()),
                 Cursor::new_at(link.clone(), 0, seq_no),
             )?;
             for (_pk, cursor) in self.key_store.iter_mut() {
@@ -1057,6 +1328,20 @@ where
     }
 }
 
+impl<F, Link, LG, LS, Keys> User<F, Link, LG, LS, Keys>
+where
+    F: PRP,
+    Link: HasLink,
+{
+    /// Own key pairs, if both are held in memory and can be exported.
+    fn exportable_keys(&self) -> Result<(&ed25519::Keypair, &x25519::StaticSecret)> {
+        match (self.sig_kp.keypair(), self.ke_kp.static_secret()) {
+            (Some(sig_kp), Some(ke_sk)) => Ok((sig_kp, ke_sk)),
+            _ => err(KeyNotExportable),
+        }
+    }
+}
+
 #[async_trait(?Send)]
 impl<F, Link, LG, LS, Keys> ContentSizeof<F> for User<F, Link, LG, LS, Keys>
 where
@@ -1070,9 +1355,12 @@ where
     Keys: KeyStore<Cursor<<Link as HasLink>::Rel>, F>,
 {
     async fn sizeof<'c>(&self, ctx: &'c mut sizeof::Context<F>) -> Result<&'c mut sizeof::Context<F>> {
-        ctx.mask(<&NBytes<U32>>::from(&self.sig_kp.secret.as_bytes()[..]))?
+        let (sig_kp, ke_sk) = self.exportable_keys()?;
+        let ke_sk_bytes = ke_sk.to_bytes();
+        ctx.mask(<&NBytes<U32>>::from(&sig_kp.secret.as_bytes()[..]))?
+            .mask(<&NBytes<U32>>::from(&ke_sk_bytes[..]))?
             .absorb(Uint8(self.flags))?
-            .absorb(<&Bytes>::from(&self.message_encoding))?
+            .absorb(&Bytes::from(self.message_encoding.clone()))?
             .absorb(Uint64(self.uniform_payload_length as u64))?;
 
         let oneof_appinst = Uint8(if self.appinst.is_some() { 1 } else { 0 });
@@ -1106,6 +1394,14 @@ where
             ctx.absorb(<&Fallback<<Link as HasLink>::Rel>>::from(&cursor.link))?
                 .absorb(Uint32(cursor.branch_no))?
                 .absorb(Uint32(cursor.seq_no))?;
+            if let Some(ke_pk) = self.key_store.get_ke_pk(id) {
+                let ke_rotation = self.key_store.get_ke_rotation(id);
+                let oneof_ke_rotation = Uint8(if ke_rotation.is_some() { 1 } else { 0 });
+                ctx.absorb(ke_pk)?.absorb(&oneof_ke_rotation)?;
+                if let Some(ke_rotation) = ke_rotation {
+                    ctx.absorb(Uint32(ke_rotation))?;
+                }
+            }
         }
         ctx.commit()?.squeeze(Mac(32))?;
         Ok(ctx)
@@ -1130,9 +1426,12 @@ where
         store: &Store,
         ctx: &'c mut wrap::Context<F, OS>,
     ) -> Result<&'c mut wrap::Context<F, OS>> {
-        ctx.mask(<&NBytes<U32>>::from(&self.sig_kp.secret.as_bytes()[..]))?
+        let (sig_kp, ke_sk) = self.exportable_keys()?;
+        let ke_sk_bytes = ke_sk.to_bytes();
+        ctx.mask(<&NBytes<U32>>::from(&sig_kp.secret.as_bytes()[..]))?
+            .mask(<&NBytes<U32>>::from(&ke_sk_bytes[..]))?
             .absorb(Uint8(self.flags))?
-            .absorb(<&Bytes>::from(&self.message_encoding))?
+            .absorb(&Bytes::from(self.message_encoding.clone()))?
             .absorb(Uint64(self.uniform_payload_length as u64))?;
 
         let oneof_appinst = Uint8(if self.appinst.is_some() { 1 } else { 0 });
@@ -1166,6 +1465,14 @@ where
             ctx.absorb(<&Fallback<<Link as HasLink>::Rel>>::from(&cursor.borrow().link))?
                 .absorb(Uint32(cursor.branch_no))?
                 .absorb(Uint32(cursor.seq_no))?;
+            if let Some(ke_pk) = self.key_store.get_ke_pk(id) {
+                let ke_rotation = self.key_store.get_ke_rotation(id);
+                let oneof_ke_rotation = Uint8(if ke_rotation.is_some() { 1 } else { 0 });
+                ctx.absorb(ke_pk)?.absorb(&oneof_ke_rotation)?;
+                if let Some(ke_rotation) = ke_rotation {
+                    ctx.absorb(Uint32(ke_rotation))?;
+                }
+            }
         }
         ctx.commit()?.squeeze(Mac(32))?;
         Ok(ctx)
@@ -1190,14 +1497,45 @@ where
         store: &Store,
         ctx: &'c mut unwrap::Context<F, IS>,
     ) -> Result<&'c mut unwrap::Context<F, IS>> {
+        self.unwrap_version(EXPORT_VERSION, store, ctx).await
+    }
+}
+
+impl<F, Link, LG, LS, Keys> User<F, Link, LG, LS, Keys>
+where
+    F: PRP,
+    Link: HasLink + AbsorbExternalFallback<F> + AbsorbFallback<F>,
+    <Link as HasLink>::Base: Eq + fmt::Debug + fmt::Display,
+    <Link as HasLink>::Rel: Eq + fmt::Debug + SkipFallback<F> + AbsorbFallback<F>,
+    LG: LinkGenerator<Link>,
+    LS: LinkStore<F, <Link as HasLink>::Rel> + Default,
+    <LS as LinkStore<F, <Link as HasLink>::Rel>>::Info: Default + AbsorbFallback<F>,
+    Keys: KeyStore<Cursor<<Link as HasLink>::Rel>, F> + Default,
+{
+    /// Unwrap the user exported in the given `version` of the export format.
+    async fn unwrap_version<'c, Store, IS>(
+        &mut self,
+        version: u8,
+        store: &Store,
+        ctx: &'c mut unwrap::Context<F, IS>,
+    ) -> Result<&'c mut unwrap::Context<F, IS>>
+    where
+        Store: LinkStore<F, <Link as HasLink>::Rel>,
+        IS: io::IStream,
+    {
         let mut sig_sk_bytes = NBytes::<U32>::default();
+        let mut ke_sk_bytes = NBytes::<U32>::default();
         let mut flags = Uint8(0);
         let mut message_encoding = Bytes::new();
         let mut uniform_payload_length = Uint64(0);
         ctx
             //.absorb(&self.sig_kp.public)
-            .mask(&mut sig_sk_bytes)?
-            .absorb(&mut flags)?
+            .mask(&mut sig_sk_bytes)?;
+        // Version 0 users have the key exchange keys derived from their Ed25519 keys
+        if version > 0 {
+            ctx.mask(&mut ke_sk_bytes)?;
+        }
+        ctx.absorb(&mut flags)?
             .absorb(&mut message_encoding)?
             .absorb(&mut uniform_payload_length)?;
 
@@ -1250,17 +1588,40 @@ where
             let (id, ctx) = Identifier::unwrap_new(store, ctx).await?;
             ctx.absorb(&mut link)?.absorb(&mut branch_no)?.absorb(&mut seq_no)?;
             key_store.insert_cursor(id, Cursor::new_at(link.0, branch_no.0, seq_no.0))?;
+            if let (Identifier::EdPubKey(_), true) = (id, version > 0) {
+                let mut ke_pk = x25519::PublicKey::from([0_u8; x25519::PUBLIC_KEY_LENGTH]);
+                ctx.absorb(&mut ke_pk)?;
+                key_store.insert_ke_pk(id, ke_pk)?;
+                // Version 1 users have no rotation numbers, any Subscribe is taken as newer
+                if version > 1 {
+                    let mut oneof_ke_rotation = Uint8(0);
+                    ctx.absorb(&mut oneof_ke_rotation)?
+                        .guard(oneof_ke_rotation.0 < 2, KeyRotationRecoveryFailure(oneof_ke_rotation.0))?;
+                    if oneof_ke_rotation.0 == 1 {
+                        let mut ke_rotation = Uint32(0);
+                        ctx.absorb(&mut ke_rotation)?;
+                        key_store.insert_ke_rotation(id, ke_rotation.0)?;
+                    }
+                }
+            }
         }
 
         ctx.commit()?.squeeze(Mac(32))?;
 
         let sig_sk = ed25519::SecretKey::from_bytes(sig_sk_bytes.as_ref()).unwrap();
         let sig_pk = ed25519::PublicKey::from(&sig_sk);
-        self.sig_kp = ed25519::Keypair {
+        let sig_kp = ed25519::Keypair {
             secret: sig_sk,
             public: sig_pk,
         };
-        self.ke_kp = x25519::keypair_from_ed25519(&self.sig_kp);
+        self.ke_kp = if version > 0 {
+            let mut ke_sk = [0_u8; 32];
+            ke_sk.copy_from_slice(ke_sk_bytes.as_ref());
+            Box::new(x25519::StaticSecret::from(ke_sk))
+        } else {
+            Box::new(x25519::keypair_from_ed25519(&sig_kp).0)
+        };
+        self.sig_kp = Box::new(sig_kp);
         self.link_store = link_store;
         self.key_store = key_store;
         self.author_sig_pk = author_sig_pk;
@@ -1269,7 +1630,7 @@ where
         }
         self.appinst = appinst;
         self.flags = flags.0;
-        self.message_encoding = message_encoding.0;
+        self.message_encoding = message_encoding.into_vec();
         self.uniform_payload_length = uniform_payload_length.0 as usize;
         Ok(ctx)
     }
@@ -1287,10 +1648,9 @@ where
     Keys: KeyStore<Cursor<<Link as HasLink>::Rel>, F>,
 {
     pub async fn export(&self, flag: u8, pwd: &str) -> Result<Vec<u8>> {
-        const VERSION: u8 = 0;
         let buf_size = {
             let mut ctx = sizeof::Context::<F>::new();
-            ctx.absorb(Uint8(VERSION))?.absorb(Uint8(flag))?;
+            ctx.absorb(Uint8(EXPORT_VERSION))?.absorb(Uint8(flag))?;
             self.sizeof(&mut ctx).await?;
             ctx.get_size()
         };
@@ -1301,7 +1661,7 @@ where
             let mut ctx = wrap::Context::new(&mut buf[..]);
             let prng = prng::from_seed::<F>("IOTA Streams Channels app", pwd);
             let key = NBytes::<U32>(prng.gen_arr("user export key"));
-            ctx.absorb(Uint8(VERSION))?
+            ctx.absorb(Uint8(EXPORT_VERSION))?
                 .absorb(Uint8(flag))?
                 .absorb(External(&key))?;
             let store = EmptyLinkStore::<F, <Link as HasLink>::Rel, ()>::default();
@@ -1324,23 +1684,25 @@ where
     <LS as LinkStore<F, <Link as HasLink>::Rel>>::Info: Default + AbsorbFallback<F>,
     Keys: KeyStore<Cursor<<Link as HasLink>::Rel>, F> + Default,
 {
+    /// Import a user exported in the current or in an earlier version of the export format.
     pub async fn import(bytes: &[u8], flag: u8, pwd: &str) -> Result<Self> {
-        const VERSION: u8 = 0;
-
         let mut ctx = unwrap::Context::new(bytes);
         let prng = prng::from_seed::<F>("IOTA Streams Channels app", pwd);
         let key = NBytes::<U32>(prng.gen_arr("user export key"));
         let mut version = Uint8(0);
         let mut flag2 = Uint8(0);
         ctx.absorb(&mut version)?
-            .guard(version.0 == VERSION, UserVersionRecoveryFailure(VERSION, version.0))?
+            .guard(
+                version.0 <= EXPORT_VERSION,
+                UserVersionRecoveryFailure(EXPORT_VERSION, version.0),
+            )?
             .absorb(&mut flag2)?
             .guard(flag2.0 == flag, UserFlagRecoveryFailure(flag, flag2.0))?
             .absorb(External(&key))?;
 
         let mut user = User::default();
         let store = EmptyLinkStore::<F, <Link as HasLink>::Rel, ()>::default();
-        user.unwrap(&store, &mut ctx).await?;
+        user.unwrap_version(version.0, &store, &mut ctx).await?;
         try_or!(ctx.stream.is_empty(), InputStreamNotFullyConsumed(ctx.stream.len()))?;
         Ok(user)
     }
@@ -1376,14 +1738,14 @@ where
     }
 }
 
-pub struct OwnKeys<'a>(&'a ed25519::Keypair, &'a (x25519::StaticSecret, x25519::PublicKey));
+pub struct OwnKeys<'a>(&'a dyn ed25519::Signer, &'a dyn x25519::KeyAgreement);
 
-impl<'a> Lookup<&Identifier, &'a x25519::StaticSecret> for OwnKeys<'a> {
-    fn lookup(&self, id: &Identifier) -> Option<&'a x25519::StaticSecret> {
-        let Self(ed25519::Keypair { public: sig_pk, .. }, (ke_sk, _)) = self;
+impl<'a> Lookup<&Identifier, &'a dyn x25519::KeyAgreement> for OwnKeys<'a> {
+    fn lookup(&self, id: &Identifier) -> Option<&'a dyn x25519::KeyAgreement> {
+        let Self(sig_kp, ke_sk) = *self;
         match id.get_pk() {
             Some(pk_id) => {
-                if sig_pk == pk_id {
+                if sig_kp.public_key() == pk_id {
                     Some(ke_sk)
                 } else {
                     None
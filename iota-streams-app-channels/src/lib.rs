@@ -44,6 +44,7 @@ pub trait Lookup<Key, Value> {
 pub use api::tangle::{
     Address,
     Author,
+    BoxedTransport,
     ChannelType,
     MessageContent,
     Subscriber,
@@ -51,5 +52,19 @@ pub use api::tangle::{
 };
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 pub use iota_streams_app::transport::tangle::client::Client as Tangle;
+#[cfg(feature = "middleware")]
+pub use iota_streams_app::transport::middleware::{
+    CachingTransport,
+    CircuitBreakerTransport,
+    FanOutTransport,
+    RecordingTransport,
+    ReplayTransport,
+    RetryTransport,
+    TimeoutTransport,
+};
+#[cfg(feature = "sqlite")]
+pub use iota_streams_app::transport::tangle::sqlite::SqliteTransport;
+#[cfg(feature = "http-client")]
+pub use iota_streams_app::transport::tangle::http::HttpClient;
 
 pub use iota_streams_ddml::types::Bytes;
@@ -1,12 +1,19 @@
 //! `Announce` message content. This is the initial message of the Channel application instance.
 //!
-//! It announces channel owner's public keys: Ed25519 signature key and corresponding X25519 key
-//! exchange key (derived from Ed25519 public key). The `Announce` message is similar to
-//! self-signed certificate in a conventional PKI.
+//! It announces channel owner's public keys: Ed25519 signature key and X25519 key exchange key.
+//! The X25519 key is either derived from the Ed25519 public key or, when the owner uses a separate
+//! key exchange key, carried explicitly and covered by the signature. The `Announce` message is
+//! similar to self-signed certificate in a conventional PKI.
+//!
+//! Owners using the derived key exchange key produce the same message as before separate keys
+//! were supported.
 //!
 //! ```ddml
 //! message Announce {
 //!     absorb u8 ed25519pk[32];
+//!     absorb u8 flags;
+//!     // Only if `flags & FLAG_SEPARATE_KE_PK`
+//!     absorb u8 x25519pk[32];
 //!     commit;
 //!     squeeze external u8 tag[32];
 //!     ed25519(tag) sig;
@@ -17,6 +24,10 @@
 //!
 //! * `ed25519pk` -- channel owner's Ed25519 public key.
 //!
+//! * `flags` -- channel type flags, and `FLAG_SEPARATE_KE_PK` if `x25519pk` follows.
+//!
+//! * `x25519pk` -- channel owner's X25519 public key, present only if it is not derived from `ed25519pk`.
+//!
 //! * `tag` -- hash-value to be signed.
 //!
 //! * `sig` -- signature of `tag` field produced with the Ed25519 private key corresponding to ed25519pk`.
@@ -39,16 +50,25 @@ use iota_streams_ddml::{
     types::*,
 };
 
+/// Announce flag set when the owner's X25519 public key is carried instead of derived from its Ed25519 public key.
+pub const FLAG_SEPARATE_KE_PK: u8 = 0x80;
+
 pub struct ContentWrap<'a, F> {
-    sig_kp: &'a ed25519::Keypair,
+    sig_kp: &'a dyn ed25519::Signer,
+    ke_pk: Option<x25519::PublicKey>,
     flags: Uint8,
     _phantom: core::marker::PhantomData<F>,
 }
 
 impl<'a, F> ContentWrap<'a, F> {
-    pub fn new(sig_kp: &'a ed25519::Keypair, flags: u8) -> Self {
+    pub fn new(sig_kp: &'a dyn ed25519::Signer, ke_pk: Option<x25519::PublicKey>, flags: u8) -> Self {
+        let flags = match ke_pk {
+            Some(_) => flags | FLAG_SEPARATE_KE_PK,
+            None => flags,
+        };
         Self {
             sig_kp,
+            ke_pk,
             flags: Uint8(flags),
             _phantom: core::marker::PhantomData,
         }
@@ -58,8 +78,11 @@ impl<'a, F> ContentWrap<'a, F> {
 #[async_trait(?Send)]
 impl<'a, F: PRP> message::ContentSizeof<F> for ContentWrap<'a, F> {
     async fn sizeof<'c>(&self, ctx: &'c mut sizeof::Context<F>) -> Result<&'c mut sizeof::Context<F>> {
-        ctx.absorb(&self.sig_kp.public)?;
+        ctx.absorb(self.sig_kp.public_key())?;
         ctx.absorb(&self.flags)?;
+        if let Some(ke_pk) = &self.ke_pk {
+            ctx.absorb(ke_pk)?;
+        }
         ctx.ed25519(self.sig_kp, HashSig)?;
         Ok(ctx)
     }
@@ -72,8 +95,11 @@ impl<'a, F: PRP, Store> message::ContentWrap<F, Store> for ContentWrap<'a, F> {
         _store: &Store,
         ctx: &'c mut wrap::Context<F, OS>,
     ) -> Result<&'c mut wrap::Context<F, OS>> {
-        ctx.absorb(&self.sig_kp.public)?;
+        ctx.absorb(self.sig_kp.public_key())?;
         ctx.absorb(&self.flags)?;
+        if let Some(ke_pk) = &self.ke_pk {
+            ctx.absorb(ke_pk)?;
+        }
         ctx.ed25519(self.sig_kp, HashSig)?;
         Ok(ctx)
     }
@@ -81,8 +107,6 @@ impl<'a, F: PRP, Store> message::ContentWrap<F, Store> for ContentWrap<'a, F> {
 
 pub struct ContentUnwrap<F> {
     pub(crate) sig_pk: ed25519::PublicKey,
-
-    #[allow(dead_code)]
     pub(crate) ke_pk: x25519::PublicKey,
     pub(crate) flags: Uint8,
     _phantom: core::marker::PhantomData<F>,
@@ -114,8 +138,14 @@ where
         ctx: &'c mut unwrap::Context<F, IS>,
     ) -> Result<&'c mut unwrap::Context<F, IS>> {
         ctx.absorb(&mut self.sig_pk)?;
-        self.ke_pk = x25519::public_from_ed25519(&self.sig_pk)?;
         ctx.absorb(&mut self.flags)?;
+        if self.flags.0 & FLAG_SEPARATE_KE_PK != 0 {
+            ctx.absorb(&mut self.ke_pk)?;
+            // Only the channel type is kept in the flags of the user
+            self.flags.0 &= !FLAG_SEPARATE_KE_PK;
+        } else {
+            self.ke_pk = x25519::public_from_ed25519(&self.sig_pk)?;
+        }
         ctx.ed25519(&self.sig_pk, HashSig)?;
         Ok(ctx)
     }
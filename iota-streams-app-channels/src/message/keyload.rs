@@ -47,8 +47,7 @@
 //!
 //! Notes:
 //! 1) Keys identities are not encrypted and may be linked to recipients identities.
-//! 2) Keyload is not authenticated (signed). It can later be implicitly authenticated
-//!     via `SignedPacket`.
+//! 2) Keyload is not authenticated (signed). It can later be implicitly authenticated via `SignedPacket`.
 
 use crate::Lookup;
 
@@ -68,6 +67,7 @@ use iota_streams_core::{
         Box,
         Vec,
     },
+    prng,
     psk,
     sponge::{
         prp::PRP,
@@ -99,8 +99,10 @@ where
     pub(crate) link: &'a <Link as HasLink>::Rel,
     pub nonce: NBytes<U16>,
     pub key: NBytes<U32>,
+    /// Seed of the X25519 ephemeral keys, drawn from the sender's RNG.
+    pub(crate) ephemeral_seed: prng::Key,
     pub(crate) keys: Vec<(&'a Identifier, Vec<u8>)>,
-    pub(crate) sig_kp: &'a ed25519::Keypair,
+    pub(crate) sig_kp: &'a dyn ed25519::Signer,
     pub(crate) _phantom: core::marker::PhantomData<(F, Link)>,
 }
 
@@ -161,6 +163,10 @@ where
         ctx: &'c mut wrap::Context<F, OS>,
     ) -> Result<&'c mut wrap::Context<F, OS>> {
         let mut id_hash = External(NBytes::<U64>::default());
+        let mut ephemeral_rng = prng::Rng::new(
+            prng::Prng::<F>::init_with_seed(self.ephemeral_seed),
+            self.nonce.as_slice().to_vec(),
+        );
         let repeated_keys = Size(self.keys.len());
         ctx.join(store, self.link)?.absorb(&self.nonce)?;
 
@@ -182,7 +188,10 @@ where
                             .commit()?
                             .mask(&self.key)?,
                         Identifier::EdPubKey(_pk) => match <[u8; 32]>::try_from(store_id.as_ref()) {
-                            Ok(slice) => ctx.x25519(&x25519::PublicKey::from(slice), &self.key)?,
+                            Ok(slice) => {
+                                let ephemeral_ke_sk = x25519::EphemeralSecret::new(&mut ephemeral_rng);
+                                ctx.x25519((ephemeral_ke_sk, &x25519::PublicKey::from(slice)), &self.key)?
+                            }
                             Err(e) => return Err(wrapped_err(BadIdentifier, WrappedError(e))),
                         },
                     };
@@ -246,7 +255,7 @@ where
     Link::Rel: Eq + Default + SkipFallback<F>,
     LStore: LinkStore<F, Link::Rel>,
     PskStore: for<'c> Lookup<&'c Identifier, psk::Psk>,
-    KeSkStore: for<'c> Lookup<&'c Identifier, &'b x25519::StaticSecret> + 'b,
+    KeSkStore: for<'c> Lookup<&'c Identifier, &'b dyn x25519::KeyAgreement> + 'b,
 {
     async fn unwrap<'c, IS: io::IStream>(
         &mut self,
@@ -264,6 +273,7 @@ where
         {
             let saved_fork = ctx.spongos.fork();
             ctx.absorb(&mut repeated_keys)?;
+            ctx.check_repeated(repeated_keys.0)?;
             // Loop through provided number of identifiers and subsequent keys
             for _ in 0..repeated_keys.0 {
                 let (id, ctx) = Identifier::unwrap_new(store, ctx).await?;
@@ -326,4 +336,5 @@ where
     }
 }
 
-// TODO: add test cases: 0,1,2 pks + 0,1,2 psks + key found/notfound + unwrap modify/fuzz to check sig does work
+// TODO: add test cases: 0,1,2 pks + 0,1,2 psks + key found/notfound; modified keyloads are covered by
+// `fuzz/fuzz_targets/handle_message.rs`
@@ -35,3 +35,5 @@ pub const TAGGED_PACKET: u8 = 4;
 pub const SUBSCRIBE: u8 = 5;
 /// Unsubscribe Message Type
 pub const UNSUBSCRIBE: u8 = 6;
+/// Subscribe Message Type carrying a separate X25519 key exchange key
+pub const SUBSCRIBE_KE_PK: u8 = 7;
@@ -32,84 +32,43 @@ use iota_streams_app::message::{
     self,
     HasLink,
 };
-use iota_streams_core::{
-    async_trait,
-    prelude::Box,
-    sponge::prp::PRP,
-    Result,
-};
 use iota_streams_core_edsig::signature::ed25519;
-use iota_streams_ddml::{
-    command::*,
-    io,
-    link_store::{
-        EmptyLinkStore,
-        LinkStore,
-    },
-    types::*,
-};
+use iota_streams_ddml::types::*;
 
-pub struct ContentWrap<'a, F, Link>
-where
-    Link: HasLink,
-    <Link as HasLink>::Rel: 'a,
-{
-    pub(crate) link: &'a <Link as HasLink>::Rel,
-    pub(crate) public_payload: &'a Bytes,
-    pub(crate) masked_payload: &'a Bytes,
-    pub(crate) sig_kp: &'a ed25519::Keypair,
-    pub(crate) _phantom: core::marker::PhantomData<(F, Link)>,
-}
+message::contents! {
+    #![ddml(
+        join(link),
+        absorb(sig_pk),
+        absorb(public_payload),
+        mask(masked_payload),
+        ed25519(key, HashSig)
+    )]
 
-#[async_trait(?Send)]
-impl<'a, F, Link> message::ContentSizeof<F> for ContentWrap<'a, F, Link>
-where
-    F: PRP,
-    Link: HasLink,
-    <Link as HasLink>::Rel: 'a + Eq + SkipFallback<F>,
-{
-    async fn sizeof<'c>(&self, ctx: &'c mut sizeof::Context<F>) -> Result<&'c mut sizeof::Context<F>> {
-        let store = EmptyLinkStore::<F, <Link as HasLink>::Rel, ()>::default();
-        ctx.join(&store, self.link)?
-            .absorb(&self.sig_kp.public)?
-            .absorb(self.public_payload)?
-            .mask(self.masked_payload)?
-            .ed25519(self.sig_kp, HashSig)?;
-        // TODO: Is both public and masked payloads are ok? Leave public only or masked only?
-        Ok(ctx)
+    #[ddml(let sig_pk = self.sig_kp.public_key(), let key = sig_kp)]
+    #[ddml(bound = "<Link as HasLink>::Rel: 'a + Eq + SkipFallback<F>")]
+    pub struct ContentWrap<'a, F, Link>
+    where
+        Link: HasLink,
+        <Link as HasLink>::Rel: 'a,
+    {
+        pub(crate) link: &'a <Link as HasLink>::Rel,
+        pub(crate) public_payload: &'a Bytes,
+        pub(crate) masked_payload: &'a Bytes,
+        pub(crate) sig_kp: &'a dyn ed25519::Signer,
+        pub(crate) _phantom: core::marker::PhantomData<(F, Link)>,
     }
-}
 
-#[async_trait(?Send)]
-impl<'a, F, Link, Store> message::ContentWrap<F, Store> for ContentWrap<'a, F, Link>
-where
-    F: PRP,
-    Link: HasLink,
-    <Link as HasLink>::Rel: 'a + Eq + SkipFallback<F>,
-    Store: LinkStore<F, <Link as HasLink>::Rel>,
-{
-    async fn wrap<'c, OS: io::OStream>(
-        &self,
-        store: &Store,
-        ctx: &'c mut wrap::Context<F, OS>,
-    ) -> Result<&'c mut wrap::Context<F, OS>> {
-        ctx.join(store, self.link)?
-            .absorb(&self.sig_kp.public)?
-            .absorb(self.public_payload)?
-            .mask(self.masked_payload)?
-            .ed25519(self.sig_kp, HashSig)?;
-        Ok(ctx)
+    #[ddml(let key = sig_pk)]
+    #[ddml(bound = "<Link as HasLink>::Rel: Eq + Default + SkipFallback<F>")]
+    pub struct ContentUnwrap<F, Link: HasLink> {
+        pub(crate) link: <Link as HasLink>::Rel,
+        pub(crate) public_payload: Bytes,
+        pub(crate) masked_payload: Bytes,
+        pub(crate) sig_pk: ed25519::PublicKey,
+        pub(crate) _phantom: core::marker::PhantomData<(F, Link)>,
     }
 }
 
-pub struct ContentUnwrap<F, Link: HasLink> {
-    pub(crate) link: <Link as HasLink>::Rel,
-    pub(crate) public_payload: Bytes,
-    pub(crate) masked_payload: Bytes,
-    pub(crate) sig_pk: ed25519::PublicKey,
-    pub(crate) _phantom: core::marker::PhantomData<(F, Link)>,
-}
-
 impl<F, Link> Default for ContentUnwrap<F, Link>
 where
     Link: HasLink,
@@ -125,25 +84,3 @@ where
         }
     }
 }
-
-#[async_trait(?Send)]
-impl<F, Link, Store> message::ContentUnwrap<F, Store> for ContentUnwrap<F, Link>
-where
-    F: PRP,
-    Link: HasLink,
-    <Link as HasLink>::Rel: Eq + Default + SkipFallback<F>,
-    Store: LinkStore<F, <Link as HasLink>::Rel>,
-{
-    async fn unwrap<'c, IS: io::IStream>(
-        &mut self,
-        store: &Store,
-        ctx: &'c mut unwrap::Context<F, IS>,
-    ) -> Result<&'c mut unwrap::Context<F, IS>> {
-        ctx.join(store, &mut self.link)?
-            .absorb(&mut self.sig_pk)?
-            .absorb(&mut self.public_payload)?
-            .mask(&mut self.masked_payload)?
-            .ed25519(&self.sig_pk, HashSig)?;
-        Ok(ctx)
-    }
-}
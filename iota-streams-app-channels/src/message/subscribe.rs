@@ -2,20 +2,30 @@
 //! a subscriber to this channel.
 //!
 //! It contains subscriber's Ed25519 public key that will be used
-//! in keyload to encrypt session keys. Subscriber's Ed25519 public key is encrypted with
+//! in keyload to encrypt session keys, and optionally a separate X25519 public key to be used
+//! for key exchange instead of the one derived from the Ed25519 public key. Both are encrypted with
 //! the `unsubscribe_key` which in turn is encapsulated for channel owner using
-//! owner's Ed25519 public key. The resulting spongos state will be used for unsubscription.
+//! owner's X25519 public key. The resulting spongos state will be used for unsubscription.
 //! Subscriber must trust channel owner's Ed25519 public key in order to maintain privacy.
 //!
 //! Channel Owner must maintain the resulting spongos state associated to the Subscriber's
 //! Ed25519 public key.
 //!
+//! Subscribers using the derived key exchange key send `SUBSCRIBE` messages, which do not carry
+//! `xpk`. Subscribers with a separate key exchange key send `SUBSCRIBE_KE_PK` messages instead, and
+//! so do subscribers replacing their key exchange key, even with the derived one. A subscriber numbers
+//! the replacements of its key with `rotation`, and the channel owner only accepts a key with a
+//! higher rotation than the one it holds, so that older Subscribe messages can't roll it back.
+//!
 //! ```ddml
 //! message Subscribe {
 //!     join link msgid;
 //!     x25519(key) byte unsubscribe_key[32];
 //!     commit;
 //!     mask byte pk[32];
+//!     // Only in `SUBSCRIBE_KE_PK` messages
+//!     mask byte xpk[32];
+//!     mask u32 rotation;
 //!     commit;
 //!     squeeze external byte hash[78];
 //!     mssig(hash) sig;
@@ -32,6 +42,10 @@
 //!
 //! * `pk` -- subscriber's Ed25519 public key.
 //!
+//! * `xpk` -- subscriber's X25519 public key, present only in `SUBSCRIBE_KE_PK` messages.
+//!
+//! * `rotation` -- number of times the subscriber replaced its X25519 key, present only in `SUBSCRIBE_KE_PK` messages.
+//!
 //! * `hash` -- hash value to be signed.
 //!
 //! * `sig` -- message signature generated with the senders private key.
@@ -45,7 +59,11 @@ use iota_streams_app::message::{
 };
 use iota_streams_core::{
     async_trait,
-    prelude::Box,
+    prelude::{
+        Box,
+        Vec,
+    },
+    prng,
     sponge::prp::PRP,
     wrapped_err,
     Errors::MessageCreationFailure,
@@ -69,7 +87,11 @@ use iota_streams_ddml::{
 pub struct ContentWrap<'a, F, Link: HasLink> {
     pub(crate) link: &'a <Link as HasLink>::Rel,
     pub unsubscribe_key: NBytes<U32>,
-    pub(crate) subscriber_sig_kp: &'a ed25519::Keypair,
+    /// Seed of the X25519 ephemeral key, drawn from the subscriber's RNG.
+    pub(crate) ephemeral_seed: prng::Key,
+    pub(crate) subscriber_sig_kp: &'a dyn ed25519::Signer,
+    pub(crate) subscriber_ke_pk: Option<x25519::PublicKey>,
+    pub(crate) ke_rotation: u32,
     pub(crate) author_ke_pk: &'a x25519::PublicKey,
     pub(crate) _phantom: core::marker::PhantomData<(Link, F)>,
 }
@@ -85,8 +107,11 @@ where
         let store = EmptyLinkStore::<F, <Link as HasLink>::Rel, ()>::default();
         ctx.join(&store, self.link)?
             .x25519(self.author_ke_pk, &self.unsubscribe_key)?
-            .mask(&self.subscriber_sig_kp.public)?
-            .ed25519(self.subscriber_sig_kp, HashSig)?;
+            .mask(self.subscriber_sig_kp.public_key())?;
+        if let Some(ke_pk) = &self.subscriber_ke_pk {
+            ctx.mask(ke_pk)?.mask(Uint32(self.ke_rotation))?;
+        }
+        ctx.ed25519(self.subscriber_sig_kp, HashSig)?;
         Ok(ctx)
     }
 }
@@ -104,10 +129,15 @@ where
         store: &Store,
         ctx: &'c mut wrap::Context<F, OS>,
     ) -> Result<&'c mut wrap::Context<F, OS>> {
+        let mut ephemeral_rng = prng::Rng::new(prng::Prng::<F>::init_with_seed(self.ephemeral_seed), Vec::new());
+        let ephemeral_ke_sk = x25519::EphemeralSecret::new(&mut ephemeral_rng);
         ctx.join(store, self.link)?
-            .x25519(self.author_ke_pk, &self.unsubscribe_key)?
-            .mask(&self.subscriber_sig_kp.public)?
-            .ed25519(self.subscriber_sig_kp, HashSig)?;
+            .x25519((ephemeral_ke_sk, self.author_ke_pk), &self.unsubscribe_key)?
+            .mask(self.subscriber_sig_kp.public_key())?;
+        if let Some(ke_pk) = &self.subscriber_ke_pk {
+            ctx.mask(ke_pk)?.mask(&Uint32(self.ke_rotation))?;
+        }
+        ctx.ed25519(self.subscriber_sig_kp, HashSig)?;
         Ok(ctx)
     }
 }
@@ -116,7 +146,12 @@ pub struct ContentUnwrap<'a, F, Link: HasLink> {
     pub link: <Link as HasLink>::Rel,
     pub unsubscribe_key: NBytes<U32>,
     pub subscriber_sig_pk: ed25519::PublicKey,
-    author_ke_sk: &'a x25519::StaticSecret,
+    pub subscriber_ke_pk: Option<x25519::PublicKey>,
+    /// Rotation number of `subscriber_ke_pk`, 0 in `SUBSCRIBE` messages.
+    pub ke_rotation: u32,
+    /// Whether the message carries a separate X25519 public key, ie. it is a `SUBSCRIBE_KE_PK` message.
+    pub(crate) has_ke_pk: bool,
+    author_ke_sk: &'a dyn x25519::KeyAgreement,
     _phantom: core::marker::PhantomData<(F, Link)>,
 }
 
@@ -126,12 +161,15 @@ where
     Link: HasLink,
     <Link as HasLink>::Rel: Eq + Default + SkipFallback<F>,
 {
-    pub fn new(author_ke_sk: &'a x25519::StaticSecret) -> Result<Self> {
+    pub fn new(author_ke_sk: &'a dyn x25519::KeyAgreement) -> Result<Self> {
         match ed25519::PublicKey::from_bytes(&[0_u8; ed25519::PUBLIC_KEY_LENGTH]) {
             Ok(pk) => Ok(Self {
                 link: <<Link as HasLink>::Rel as Default>::default(),
                 unsubscribe_key: NBytes::<U32>::default(),
                 subscriber_sig_pk: pk,
+                subscriber_ke_pk: None,
+                ke_rotation: 0,
+                has_ke_pk: false,
                 author_ke_sk,
                 _phantom: core::marker::PhantomData,
             }),
@@ -155,8 +193,17 @@ where
     ) -> Result<&'c mut unwrap::Context<F, IS>> {
         ctx.join(store, &mut self.link)?
             .x25519(self.author_ke_sk, &mut self.unsubscribe_key)?
-            .mask(&mut self.subscriber_sig_pk)?
-            .ed25519(&self.subscriber_sig_pk, HashSig)?;
+            .mask(&mut self.subscriber_sig_pk)?;
+        self.subscriber_ke_pk = if self.has_ke_pk {
+            let mut ke_pk = x25519::PublicKey::from([0_u8; x25519::PUBLIC_KEY_LENGTH]);
+            let mut ke_rotation = Uint32(0);
+            ctx.mask(&mut ke_pk)?.mask(&mut ke_rotation)?;
+            self.ke_rotation = ke_rotation.0;
+            Some(ke_pk)
+        } else {
+            None
+        };
+        ctx.ed25519(&self.subscriber_sig_pk, HashSig)?;
         Ok(ctx)
     }
 }
@@ -29,89 +29,42 @@ use iota_streams_app::message::{
     HasLink,
 };
 use iota_streams_core::{
-    async_trait,
-    prelude::{
-        typenum::Unsigned as _,
-        Box,
-    },
-    sponge::{
-        prp::PRP,
-        spongos,
-    },
-    Result,
-};
-use iota_streams_ddml::{
-    command::*,
-    io,
-    link_store::{
-        EmptyLinkStore,
-        LinkStore,
-    },
-    types::*,
+    prelude::typenum::Unsigned as _,
+    sponge::spongos,
 };
+use iota_streams_ddml::types::*;
 
-pub struct ContentWrap<'a, F, Link>
-where
-    Link: HasLink,
-    <Link as HasLink>::Rel: 'a,
-{
-    pub(crate) link: &'a <Link as HasLink>::Rel,
-    pub(crate) public_payload: &'a Bytes,
-    pub(crate) masked_payload: &'a Bytes,
-    pub(crate) _phantom: core::marker::PhantomData<(F, Link)>,
-}
+message::contents! {
+    #![ddml(
+        join(link),
+        absorb(public_payload),
+        mask(masked_payload),
+        commit,
+        squeeze(&Mac(spongos::MacSize::<F>::USIZE))
+    )]
 
-#[async_trait(?Send)]
-impl<'a, F, Link> message::ContentSizeof<F> for ContentWrap<'a, F, Link>
-where
-    F: PRP,
-    Link: HasLink,
-    <Link as HasLink>::Rel: 'a + Eq + SkipFallback<F>,
-{
-    async fn sizeof<'c>(&self, ctx: &'c mut sizeof::Context<F>) -> Result<&'c mut sizeof::Context<F>> {
-        let store = EmptyLinkStore::<F, <Link as HasLink>::Rel, ()>::default();
-        let mac = Mac(spongos::MacSize::<F>::USIZE);
-        ctx.join(&store, self.link)?
-            .absorb(self.public_payload)?
-            .mask(self.masked_payload)?
-            .commit()?
-            .squeeze(&mac)?;
-        // TODO: Is bot public and masked payloads are ok? Leave public only or masked only?
-        Ok(ctx)
+    #[ddml(bound = "<Link as HasLink>::Rel: 'a + Eq + SkipFallback<F>")]
+    pub struct ContentWrap<'a, F, Link>
+    where
+        Link: HasLink,
+        <Link as HasLink>::Rel: 'a,
+    {
+        pub(crate) link: &'a <Link as HasLink>::Rel,
+        pub(crate) public_payload: &'a Bytes,
+        pub(crate) masked_payload: &'a Bytes,
+        pub(crate) _phantom: core::marker::PhantomData<(F, Link)>,
     }
-}
 
-#[async_trait(?Send)]
-impl<'a, F, Link, Store> message::ContentWrap<F, Store> for ContentWrap<'a, F, Link>
-where
-    F: PRP,
-    Link: HasLink,
-    <Link as HasLink>::Rel: 'a + Eq + SkipFallback<F>,
-    Store: LinkStore<F, <Link as HasLink>::Rel>,
-{
-    async fn wrap<'c, OS: io::OStream>(
-        &self,
-        store: &Store,
-        ctx: &'c mut wrap::Context<F, OS>,
-    ) -> Result<&'c mut wrap::Context<F, OS>> {
-        let mac = Mac(spongos::MacSize::<F>::USIZE);
-        ctx.join(store, self.link)?
-            .absorb(self.public_payload)?
-            .mask(self.masked_payload)?
-            .commit()?
-            .squeeze(&mac)?;
-        Ok(ctx)
+    // TODO: factor out `public_payload` and `masked_payload` into `pub struct Content`
+    #[ddml(bound = "<Link as HasLink>::Rel: Eq + Default + SkipFallback<F>")]
+    pub struct ContentUnwrap<F, Link: HasLink> {
+        pub(crate) link: <Link as HasLink>::Rel,
+        pub(crate) public_payload: Bytes,
+        pub(crate) masked_payload: Bytes,
+        pub(crate) _phantom: core::marker::PhantomData<(F, Link)>,
     }
 }
 
-// TODO: factor out `public_payload` and `masked_payload` into `pub struct Content`
-pub struct ContentUnwrap<F, Link: HasLink> {
-    pub(crate) link: <Link as HasLink>::Rel,
-    pub(crate) public_payload: Bytes,
-    pub(crate) masked_payload: Bytes,
-    pub(crate) _phantom: core::marker::PhantomData<(F, Link)>,
-}
-
 impl<F, Link> Default for ContentUnwrap<F, Link>
 where
     Link: HasLink,
@@ -125,26 +78,3 @@ where
         }
     }
 }
-
-#[async_trait(?Send)]
-impl<F, Link, Store> message::ContentUnwrap<F, Store> for ContentUnwrap<F, Link>
-where
-    F: PRP,
-    Link: HasLink,
-    <Link as HasLink>::Rel: Eq + Default + SkipFallback<F>,
-    Store: LinkStore<F, <Link as HasLink>::Rel>,
-{
-    async fn unwrap<'c, IS: io::IStream>(
-        &mut self,
-        store: &Store,
-        ctx: &'c mut unwrap::Context<F, IS>,
-    ) -> Result<&'c mut unwrap::Context<F, IS>> {
-        let mac = Mac(spongos::MacSize::<F>::USIZE);
-        ctx.join(store, &mut self.link)?
-            .absorb(&mut self.public_payload)?
-            .mask(&mut self.masked_payload)?
-            .commit()?
-            .squeeze(&mac)?;
-        Ok(ctx)
-    }
-}
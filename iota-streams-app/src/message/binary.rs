@@ -2,38 +2,58 @@ use core::fmt;
 use iota_streams_core::Result;
 
 use super::*;
-use core::fmt::Debug;
+use core::{
+    fmt::Debug,
+    marker::PhantomData,
+};
 use iota_streams_core::{
     prelude::Vec,
     sponge::prp::PRP,
 };
 use iota_streams_ddml::{
-    command::unwrap,
+    command::{
+        unwrap,
+        Label,
+    },
+    io,
     link_store::EmptyLinkStore,
     types::*,
 };
 
 /// Binary Message body with information of how to parse it.
+///
+/// Clones share the buffer, and so do the `Bytes` fields unwrapped from the body.
 #[derive(Clone, Hash, Default, PartialEq, Eq)]
-pub struct BinaryBody(Vec<u8>);
+pub struct BinaryBody(SharedBuffer);
 
 impl BinaryBody {
     pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    pub fn shared(&self) -> &SharedBuffer {
         &self.0
     }
 
+    /// Mutable access to the body, copying it first if the buffer is shared.
+    pub fn to_mut(&mut self) -> &mut Vec<u8> {
+        self.0.to_mut()
+    }
+
+    /// Take the body out, copying it only if the buffer is still shared.
     pub fn into_bytes(self) -> Vec<u8> {
-        self.0
+        self.0.into_vec()
     }
 
     pub fn to_bytes(&self) -> Vec<u8> {
-        self.0.clone()
+        self.0.to_vec()
     }
 }
 impl fmt::Debug for BinaryBody {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // TODO: first 10 bytes of body is average HDF
-        write!(f, "{}", hex::encode(&self.as_bytes()[..10]))
+        let bytes = self.as_bytes();
+        write!(f, "{}", hex::encode(&bytes[..core::cmp::min(10, bytes.len())]))
     }
 }
 
@@ -45,6 +65,12 @@ impl fmt::Display for BinaryBody {
 
 impl From<Vec<u8>> for BinaryBody {
     fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes.into())
+    }
+}
+
+impl From<SharedBuffer> for BinaryBody {
+    fn from(bytes: SharedBuffer) -> Self {
         Self(bytes)
     }
 }
@@ -64,13 +90,49 @@ impl<AbsLink> BinaryMessage<AbsLink> {
         F: PRP,
         AbsLink: Clone + AbsorbExternalFallback<F> + HasLink + Debug,
     {
-        let mut ctx = unwrap::Context::new(self.body.as_bytes());
+        self.parse_header_with_limits(unwrap::Limits::default()).await
+    }
+
+    /// Parse header enforcing resource `limits` for the whole message, including its content.
+    pub async fn parse_header_with_limits<F>(&self, limits: unwrap::Limits) -> Result<PreparsedMessage<'_, F, AbsLink>>
+    where
+        F: PRP,
+        AbsLink: Clone + AbsorbExternalFallback<F> + HasLink + Debug,
+    {
+        let ctx = unwrap::Context::new(io::SharedIStream::new(self.body.shared().clone())).with_limits(limits);
+        self.parse_header_in(ctx).await
+    }
+
+    /// Parse header enforcing resource `limits` and recording a trace of the message, which
+    /// `PreparsedMessage::unwrap_traced` completes with the content.
+    pub async fn parse_header_traced<F>(&self, limits: unwrap::Limits) -> Result<PreparsedMessage<'_, F, AbsLink>>
+    where
+        F: PRP,
+        AbsLink: Clone + AbsorbExternalFallback<F> + HasLink + Debug,
+    {
+        let ctx = unwrap::Context::new_traced(io::SharedIStream::new(self.body.shared().clone())).with_limits(limits);
+        self.parse_header_in(ctx).await
+    }
+
+    async fn parse_header_in<F>(
+        &self,
+        mut ctx: unwrap::Context<F, io::SharedIStream>,
+    ) -> Result<PreparsedMessage<'_, F, AbsLink>>
+    where
+        F: PRP,
+        AbsLink: Clone + AbsorbExternalFallback<F> + HasLink + Debug,
+    {
         let mut header =
-            HDF::<AbsLink>::new(self.link().clone()).with_previous_msg_link(Bytes(self.prev_link().to_bytes()));
+            HDF::<AbsLink>::new(self.link().clone()).with_previous_msg_link(Bytes::from(self.prev_link().to_bytes()));
         let store = EmptyLinkStore::<F, AbsLink, ()>::default();
+        ctx.label("header")?;
         header.unwrap(&store, &mut ctx).await?;
 
-        Ok(PreparsedMessage { header, ctx })
+        Ok(PreparsedMessage {
+            header,
+            ctx,
+            _phantom: PhantomData,
+        })
     }
 }
 
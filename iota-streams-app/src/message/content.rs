@@ -13,6 +13,14 @@ use iota_streams_ddml::{
     io,
 };
 
+/// Derive the content traits from a DDML description, see [`iota_streams_ddml_derive`].
+pub use iota_streams_ddml_derive::{
+    contents,
+    ContentSizeof,
+    ContentUnwrap,
+    ContentWrap,
+};
+
 #[async_trait(?Send)]
 pub trait ContentSizeof<F> {
     async fn sizeof<'c>(&self, ctx: &'c mut sizeof::Context<F>) -> Result<&'c mut sizeof::Context<F>>;
@@ -137,7 +137,7 @@ pub trait LinkGenerator<Link: HasLink>: Default {
     ) -> Result<HDF<Link>> {
         HDF::new_with_fields(
             self.uniform_link_from(cursor),
-            Bytes(previous_msg_link.to_bytes()),
+            Bytes::from(previous_msg_link.to_bytes()),
             content_type,
             payload_length,
             seq_num,
@@ -157,7 +157,7 @@ pub trait LinkGenerator<Link: HasLink>: Default {
     ) -> Result<HDF<Link>> {
         HDF::new_with_fields(
             self.link_from(id, cursor),
-            Bytes(previous_msg_link.to_bytes()),
+            Bytes::from(previous_msg_link.to_bytes()),
             content_type,
             payload_length,
             seq_num,
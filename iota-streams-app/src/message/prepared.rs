@@ -10,7 +10,9 @@ use iota_streams_ddml::{
     command::{
         sizeof,
         wrap,
+        Label,
     },
+    trace::Trace,
     types::*,
 };
 
@@ -43,6 +45,27 @@ where
     Link::Rel: Eq + SkipFallback<F>,
 {
     pub async fn wrap<Store>(&self, store: &Store) -> Result<WrappedMessage<F, Link>>
+    where
+        HDF<Link>: ContentWrap<F, Store>,
+        Content: ContentWrap<F, Store>,
+    {
+        let (message, _) = self.do_wrap(store, false).await?;
+        Ok(message)
+    }
+
+    /// Wrap the message recording a trace of its DDML commands, to be compared with the trace of an
+    /// unwrap (see `PreparsedMessage::unwrap_traced`). Header and content are labelled `header` and `content`
+    /// unless the content labels its own fields.
+    pub async fn wrap_traced<Store>(&self, store: &Store) -> Result<(WrappedMessage<F, Link>, Trace)>
+    where
+        HDF<Link>: ContentWrap<F, Store>,
+        Content: ContentWrap<F, Store>,
+    {
+        let (message, trace) = self.do_wrap(store, true).await?;
+        Ok((message, trace.unwrap_or_default()))
+    }
+
+    async fn do_wrap<Store>(&self, store: &Store, traced: bool) -> Result<(WrappedMessage<F, Link>, Option<Trace>)>
     where
         HDF<Link>: ContentWrap<F, Store>,
         Content: ContentWrap<F, Store>,
@@ -56,15 +79,21 @@ where
 
         let mut buf = vec![0; buf_size];
 
-        let spongos = {
-            let mut ctx = wrap::Context::new(&mut buf[..]);
+        let (spongos, trace) = {
+            let mut ctx = if traced {
+                wrap::Context::new_traced(&mut buf[..])
+            } else {
+                wrap::Context::new(&mut buf[..])
+            };
+            ctx.label("header")?;
             self.header.wrap(store, &mut ctx).await?;
+            ctx.label("content")?;
             self.content.wrap(store, &mut ctx).await?;
             try_or!(ctx.stream.is_empty(), OutputStreamNotFullyConsumed(ctx.stream.len()))?;
-            ctx.spongos
+            (ctx.spongos, ctx.trace)
         };
 
-        Ok(WrappedMessage {
+        let message = WrappedMessage {
             wrapped: WrapState {
                 link: self.header.link.clone(),
                 spongos,
@@ -74,6 +103,7 @@ where
                 prev_link: Link::default(),
                 body: buf.into(),
             },
-        })
+        };
+        Ok((message, trace))
     }
 }
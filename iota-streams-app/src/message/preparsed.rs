@@ -1,15 +1,28 @@
 use iota_streams_core::Result;
 
-use core::fmt;
+use core::{
+    fmt,
+    marker::PhantomData,
+};
 
 use super::*;
 use iota_streams_core::sponge::prp::PRP;
-use iota_streams_ddml::command::unwrap;
+use iota_streams_ddml::{
+    command::{
+        unwrap,
+        Label,
+    },
+    io,
+    trace::Trace,
+};
 
 /// Message context preparsed for unwrapping.
+///
+/// The context reads the body of the binary message through its shared buffer.
 pub struct PreparsedMessage<'a, F, Link: Default> {
     pub header: HDF<Link>,
-    pub(crate) ctx: unwrap::Context<F, &'a [u8]>,
+    pub(crate) ctx: unwrap::Context<F, io::SharedIStream>,
+    pub(crate) _phantom: PhantomData<&'a ()>,
 }
 
 impl<'a, F, Link: Default + Clone> PreparsedMessage<'a, F, Link> {
@@ -31,7 +44,7 @@ impl<'a, F, Link: Default + Clone> PreparsedMessage<'a, F, Link> {
         F: PRP,
     {
         let mut pcf = pcf::PCF::default_with_content(content);
-        pcf.unwrap(store, &mut self.ctx).await?;
+        Self::unwrap_pcf(&mut self.ctx, store, &mut pcf).await?;
         // Discard what's left of `self.ctx.stream`
         Ok(UnwrappedMessage {
             link: self.header.link,
@@ -39,6 +52,49 @@ impl<'a, F, Link: Default + Clone> PreparsedMessage<'a, F, Link> {
             spongos: self.ctx.spongos,
         })
     }
+
+    /// Unwrap the content recording a trace of its DDML commands, returned even if unwrapping fails
+    /// so that it can be compared with the trace of the wrap (see `PreparedMessage::wrap_traced`)
+    /// to find where they diverge.
+    ///
+    /// The trace covers the header too if the message was parsed with `BinaryMessage::parse_header_traced`,
+    /// otherwise it starts at the content.
+    pub async fn unwrap_traced<Store, Content>(
+        mut self,
+        store: &Store,
+        content: Content,
+    ) -> (Result<UnwrappedMessage<F, Link, Content>>, Trace)
+    where
+        Content: ContentUnwrap<F, Store>,
+        F: PRP,
+    {
+        if self.ctx.trace.is_none() {
+            self.ctx.trace = Some(Trace::with_state(&self.ctx.spongos));
+        }
+        let mut pcf = pcf::PCF::default_with_content(content);
+        let unwrapped = Self::unwrap_pcf(&mut self.ctx, store, &mut pcf).await;
+        let trace = self.ctx.trace.take().unwrap_or_default();
+        let unwrapped = unwrapped.map(|()| UnwrappedMessage {
+            link: self.header.link,
+            pcf,
+            spongos: self.ctx.spongos,
+        });
+        (unwrapped, trace)
+    }
+
+    async fn unwrap_pcf<Store, Content>(
+        ctx: &mut unwrap::Context<F, io::SharedIStream>,
+        store: &Store,
+        pcf: &mut pcf::PCF<Content>,
+    ) -> Result<()>
+    where
+        Content: ContentUnwrap<F, Store>,
+        F: PRP,
+    {
+        ctx.label("content")?;
+        pcf.unwrap(store, ctx).await?;
+        Ok(())
+    }
 }
 
 impl<'a, F, Link> Clone for PreparsedMessage<'a, F, Link>
@@ -50,6 +106,7 @@ where
         Self {
             header: self.header.clone(),
             ctx: self.ctx.clone(),
+            _phantom: PhantomData,
         }
     }
 }
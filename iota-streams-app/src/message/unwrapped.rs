@@ -1,4 +1,8 @@
-use iota_streams_core::Result;
+use iota_streams_core::{
+    try_or,
+    Errors::Equivocation,
+    Result,
+};
 
 use super::*;
 use iota_streams_core::sponge::{
@@ -20,7 +24,41 @@ where
     Link: HasLink,
 {
     /// Save link for the current unwrapped message and associated info into the store.
+    ///
+    /// The first message committed at a link makes the history, committing another message at
+    /// the same link fails with `Equivocation`.
     pub fn commit<Store>(mut self, store: &mut Store, info: Store::Info) -> Result<Content>
+    where
+        Store: LinkStore<F, Link::Rel>,
+    {
+        self.spongos.commit();
+        if let Ok((known, _)) = store.lookup(self.link.rel()) {
+            try_or!(known.to_inner()? == self.spongos.to_inner()?, Equivocation)?;
+        }
+        store.update(self.link.rel(), self.spongos, info)?;
+        Ok(self.pcf.content)
+    }
+
+    /// Check that the message does not conflict with the message committed at the same link, if
+    /// any, without saving anything into the store.
+    ///
+    /// Fails with `Equivocation` where `commit` would.
+    pub fn check_conflict<Store>(mut self, store: &Store) -> Result<()>
+    where
+        Store: LinkStore<F, Link::Rel>,
+    {
+        if let Ok((known, _)) = store.lookup(self.link.rel()) {
+            self.spongos.commit();
+            try_or!(known.to_inner()? == self.spongos.to_inner()?, Equivocation)?;
+        }
+        Ok(())
+    }
+
+    /// Save link for the current unwrapped message and associated info into the store, replacing
+    /// the message committed before at the same link, if any.
+    ///
+    /// Only for messages superseding the ones sent before by the same publisher at their link.
+    pub fn commit_replacing<Store>(mut self, store: &mut Store, info: Store::Info) -> Result<Content>
     where
         Store: LinkStore<F, Link::Rel>,
     {
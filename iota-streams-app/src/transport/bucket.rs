@@ -38,6 +38,26 @@ where
     pub fn new() -> Self {
         Self { bucket: HashMap::new() }
     }
+
+    fn insert(&mut self, msg: &Msg)
+    where
+        Link: Clone,
+        Msg: LinkedMessage<Link> + Clone,
+    {
+        self.bucket.entry(msg.link().clone()).or_default().push(msg.clone());
+    }
+
+    fn lookup(&self, link: &Link) -> Result<Vec<Msg>>
+    where
+        Link: core::fmt::Display,
+        Msg: Clone,
+    {
+        if let Some(msgs) = self.bucket.get(link) {
+            Ok(msgs.clone())
+        } else {
+            err!(MessageLinkNotFoundInBucket(link.to_string()))
+        }
+    }
 }
 
 impl<Link, Msg> TransportOptions for BucketTransport<Link, Msg> {
@@ -57,32 +77,36 @@ where
     Msg: LinkedMessage<Link> + Clone + core::marker::Send + core::marker::Sync,
 {
     async fn send_message(&mut self, msg: &Msg) -> Result<()> {
-        if let Some(msgs) = self.bucket.get_mut(msg.link()) {
-            msgs.push(msg.clone());
-            Ok(())
-        } else {
-            self.bucket.insert(msg.link().clone(), vec![msg.clone()]);
-            Ok(())
-        }
+        self.insert(msg);
+        Ok(())
     }
 
     async fn recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>> {
-        if let Some(msgs) = self.bucket.get(link) {
-            Ok(msgs.clone())
-        } else {
-            err!(MessageLinkNotFoundInBucket(link.to_string()))
-        }
+        self.lookup(link)
     }
 
     async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
         let mut msgs = self.recv_messages(link).await?;
         if let Some(msg) = msgs.pop() {
-            try_or!(msgs.is_empty(), MessageNotUnique(link.to_string())).unwrap();
+            try_or!(msgs.is_empty(), MessageNotUnique(link.to_string()))?;
             Ok(msg)
         } else {
             err!(MessageLinkNotFoundInBucket(link.to_string()))?
         }
     }
+
+    async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        msgs.iter()
+            .map(|msg| {
+                self.insert(msg);
+                Ok(())
+            })
+            .collect()
+    }
+
+    async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        links.iter().map(|link| self.lookup(link)).collect()
+    }
 }
 
 #[async_trait(?Send)]
@@ -0,0 +1,177 @@
+use core::any::Any;
+
+use super::*;
+
+/// Object-safe counterpart of `Transport`, for picking a transport at runtime.
+///
+/// It is implemented by every cloneable transport, and `Box<dyn DynTransport<Link, Msg>>` is in
+/// turn a cloneable `Transport` which users can be generic over. Boxed transports have no
+/// options, transports are configured before being boxed. Their link details are type-erased and
+/// can be downcast to the `Details` of the boxed transport.
+///
+/// Methods are prefixed with `dyn_` so that they don't clash with the `Transport` methods of the
+/// transports implementing both traits.
+#[async_trait(?Send)]
+pub trait DynTransport<Link, Msg> {
+    async fn dyn_send_message(&mut self, msg: &Msg) -> Result<()>;
+
+    async fn dyn_recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>>;
+
+    async fn dyn_recv_message(&mut self, link: &Link) -> Result<Msg>;
+
+    async fn dyn_send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>>;
+
+    async fn dyn_recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>>;
+
+    /// Link details of the underlying transport, boxed as `Any`.
+    async fn dyn_get_link_details(&mut self, link: &Link) -> Result<Box<dyn Any>>;
+
+    /// Clone the transport into a new box.
+    fn dyn_clone(&self) -> Box<dyn DynTransport<Link, Msg>>;
+}
+
+#[async_trait(?Send)]
+impl<Link, Msg, Tsp> DynTransport<Link, Msg> for Tsp
+where
+    Tsp: Transport<Link, Msg> + Clone + 'static,
+    <Tsp as TransportDetails<Link>>::Details: 'static,
+{
+    async fn dyn_send_message(&mut self, msg: &Msg) -> Result<()> {
+        self.send_message(msg).await
+    }
+
+    async fn dyn_recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>> {
+        self.recv_messages(link).await
+    }
+
+    async fn dyn_recv_message(&mut self, link: &Link) -> Result<Msg> {
+        self.recv_message(link).await
+    }
+
+    async fn dyn_send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        self.send_messages(msgs).await
+    }
+
+    async fn dyn_recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        self.recv_messages_many(links).await
+    }
+
+    async fn dyn_get_link_details(&mut self, link: &Link) -> Result<Box<dyn Any>> {
+        Ok(Box::new(self.get_link_details(link).await?))
+    }
+
+    fn dyn_clone(&self) -> Box<dyn DynTransport<Link, Msg>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<Link: 'static, Msg: 'static> Clone for Box<dyn DynTransport<Link, Msg>> {
+    fn clone(&self) -> Self {
+        (**self).dyn_clone()
+    }
+}
+
+impl<Link, Msg> TransportOptions for Box<dyn DynTransport<Link, Msg>> {
+    type SendOptions = ();
+    fn get_send_options(&self) {}
+    fn set_send_options(&mut self, _opt: ()) {}
+
+    type RecvOptions = ();
+    fn get_recv_options(&self) {}
+    fn set_recv_options(&mut self, _opt: ()) {}
+}
+
+#[async_trait(?Send)]
+impl<Link, Msg> TransportDetails<Link> for Box<dyn DynTransport<Link, Msg>> {
+    type Details = Box<dyn Any>;
+    async fn get_link_details(&mut self, link: &Link) -> Result<Self::Details> {
+        (**self).dyn_get_link_details(link).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Msg> Transport<Link, Msg> for Box<dyn DynTransport<Link, Msg>> {
+    async fn send_message(&mut self, msg: &Msg) -> Result<()> {
+        (**self).dyn_send_message(msg).await
+    }
+
+    async fn recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>> {
+        (**self).dyn_recv_messages(link).await
+    }
+
+    async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
+        (**self).dyn_recv_message(link).await
+    }
+
+    async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        (**self).dyn_send_messages(msgs).await
+    }
+
+    async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        (**self).dyn_recv_messages_many(links).await
+    }
+}
+
+#[cfg(all(test, feature = "tangle"))]
+mod tests {
+    use super::*;
+    use crate::transport::{
+        tangle::{
+            test_message,
+            TangleAddress,
+            TangleMessage,
+        },
+        BucketTransport,
+        Faults,
+        FaultyTransport,
+    };
+    use iota_streams_core::ensure;
+
+    type Boxed = Box<dyn DynTransport<TangleAddress, TangleMessage>>;
+
+    #[tokio::test]
+    async fn boxed_transports_are_interchangeable() -> Result<()> {
+        let msgs: Vec<TangleMessage> = (0..3).map(|n| test_message(n, &[n])).collect();
+        let bucket = Rc::new(RefCell::new(BucketTransport::new()));
+        let faulty = FaultyTransport::new(bucket.clone(), Faults::default(), 0);
+        let mut transports: Vec<Boxed> = vec![Box::new(Rc::new(RefCell::new(faulty))), Box::new(bucket)];
+
+        transports[0].send_message(&msgs[0]).await?;
+        for result in transports[0].send_messages(&msgs[1..]).await {
+            result?;
+        }
+        // Clones of a box share the underlying transport
+        let mut clone = transports[1].clone();
+        ensure!(
+            clone.recv_message(&msgs[0].link).await? == msgs[0],
+            "message not shared"
+        );
+        let links: Vec<TangleAddress> = msgs.iter().map(|msg| msg.link).collect();
+        for (msg, result) in msgs.iter().zip(transports[1].recv_messages_many(&links).await) {
+            ensure!(result? == [msg.clone()], "messages not shared");
+        }
+
+        let details = transports[1].get_link_details(&msgs[0].link).await?;
+        ensure!(details.downcast_ref::<()>().is_some(), "details lost through the box");
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn details_of_boxed_transports_are_downcast() -> Result<()> {
+        use crate::transport::tangle::sqlite::{
+            Details,
+            SqliteTransport,
+        };
+
+        let msg = test_message(1, &[1]);
+        let mut archive: Boxed = Box::new(Rc::new(RefCell::new(SqliteTransport::open_in_memory()?)));
+        archive.send_message(&msg).await?;
+        let details = archive.get_link_details(&msg.link).await?;
+        ensure!(
+            details.downcast_ref::<Details>().map(|d| d.seq) == Some(1),
+            "archive details lost through the box"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,605 @@
+use super::*;
+use crate::message::{
+    BinaryBody,
+    GenericMessage,
+};
+use core::fmt::Display;
+
+use iota_streams_core::{
+    async_trait,
+    err,
+    prelude::{
+        string::ToString,
+        Box,
+        VecDeque,
+    },
+    Errors::{
+        InjectedTransportFailure,
+        TransportOutcomeMissing,
+    },
+};
+
+/// Fault injected into a single transport call.
+///
+/// All faults apply to `send_message` calls. `recv_messages` and `recv_message` calls only
+/// apply `Fail`, `Corrupt` and `Duplicate`, other faults let them through.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Fault {
+    /// Let the call through.
+    None,
+    /// Fail the call without reaching the inner transport.
+    Fail,
+    /// Lose the sent message.
+    Drop,
+    /// Send the message after the given number of further calls.
+    Delay(usize),
+    /// Send the message after the next sent message.
+    Reorder,
+    /// Send the message twice, or receive the messages twice.
+    Duplicate,
+    /// Flip a bit of the sent message, or of the received messages.
+    Corrupt,
+}
+
+/// Probabilities of the faults injected into transport calls without a scripted fault.
+///
+/// A send call fails, drops, delays, reorders, duplicates or corrupts its message with the
+/// respective probability, in this order of precedence. A receive call fails with `fail_recv`
+/// probability. By default no faults are injected.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Faults {
+    pub fail_send: f64,
+    pub fail_recv: f64,
+    pub drop: f64,
+    pub delay: f64,
+    /// Max number of calls a message is delayed by.
+    pub max_delay: usize,
+    pub reorder: f64,
+    pub duplicate: f64,
+    pub corrupt: f64,
+}
+
+impl Faults {
+    pub fn with_fail_send(mut self, p: f64) -> Self {
+        self.fail_send = p;
+        self
+    }
+
+    pub fn with_fail_recv(mut self, p: f64) -> Self {
+        self.fail_recv = p;
+        self
+    }
+
+    pub fn with_drop(mut self, p: f64) -> Self {
+        self.drop = p;
+        self
+    }
+
+    pub fn with_delay(mut self, p: f64, max_delay: usize) -> Self {
+        self.delay = p;
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn with_reorder(mut self, p: f64) -> Self {
+        self.reorder = p;
+        self
+    }
+
+    pub fn with_duplicate(mut self, p: f64) -> Self {
+        self.duplicate = p;
+        self
+    }
+
+    pub fn with_corrupt(mut self, p: f64) -> Self {
+        self.corrupt = p;
+        self
+    }
+}
+
+/// Messages which `FaultyTransport` can corrupt.
+pub trait Corrupt {
+    /// Flip a bit of the message chosen by `n`.
+    fn corrupt(&mut self, n: u64);
+}
+
+impl<Link> Corrupt for GenericMessage<Link, BinaryBody> {
+    fn corrupt(&mut self, n: u64) {
+        let bytes = self.body.to_mut();
+        if !bytes.is_empty() {
+            let i = (n / 8 % bytes.len() as u64) as usize;
+            bytes[i] ^= 1 << (n % 8);
+        }
+    }
+}
+
+/// Transport wrapper injecting network faults into the calls of the inner transport.
+///
+/// Faults are taken from the send and receive scripts first, one per call, and picked at random
+/// with the `Faults` probabilities once the scripts are exhausted. The random faults are
+/// reproducible for a given seed. Delayed and reordered messages are kept by the wrapper until
+/// their turn comes, or until `flush`.
+pub struct FaultyTransport<Tsp, Msg> {
+    inner: Tsp,
+    faults: Faults,
+    send_script: VecDeque<Fault>,
+    recv_script: VecDeque<Fault>,
+    // State of the splitmix64 generator picking random faults
+    rng: u64,
+    // Delayed messages along with the number of calls left before sending them
+    delayed: Vec<(usize, Msg)>,
+    reordered: Option<Msg>,
+    injected: usize,
+}
+
+impl<Tsp, Msg> FaultyTransport<Tsp, Msg> {
+    pub fn new(inner: Tsp, faults: Faults, seed: u64) -> Self {
+        Self {
+            inner,
+            faults,
+            send_script: VecDeque::new(),
+            recv_script: VecDeque::new(),
+            rng: seed,
+            delayed: Vec::new(),
+            reordered: None,
+            injected: 0,
+        }
+    }
+
+    /// Faults to inject into the next send calls, in order.
+    pub fn with_send_script(mut self, script: impl IntoIterator<Item = Fault>) -> Self {
+        self.send_script.extend(script);
+        self
+    }
+
+    /// Faults to inject into the next receive calls, in order.
+    pub fn with_recv_script(mut self, script: impl IntoIterator<Item = Fault>) -> Self {
+        self.recv_script.extend(script);
+        self
+    }
+
+    pub fn faults(&self) -> Faults {
+        self.faults
+    }
+
+    pub fn set_faults(&mut self, faults: Faults) {
+        self.faults = faults;
+    }
+
+    /// Number of faults injected so far.
+    pub fn injected(&self) -> usize {
+        self.injected
+    }
+
+    pub fn inner(&self) -> &Tsp {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Tsp {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> Tsp {
+        self.inner
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn roll(&mut self, p: f64) -> bool {
+        // 53 random bits give a uniform float in [0, 1)
+        p > 0.0 && ((self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64) < p
+    }
+
+    fn send_fault(&mut self) -> Fault {
+        let fault = match self.send_script.pop_front() {
+            Some(fault) => fault,
+            None => {
+                let f = self.faults;
+                if self.roll(f.fail_send) {
+                    Fault::Fail
+                } else if self.roll(f.drop) {
+                    Fault::Drop
+                } else if self.roll(f.delay) {
+                    Fault::Delay((self.next_u64() % (f.max_delay as u64 + 1)) as usize)
+                } else if self.roll(f.reorder) {
+                    Fault::Reorder
+                } else if self.roll(f.duplicate) {
+                    Fault::Duplicate
+                } else if self.roll(f.corrupt) {
+                    Fault::Corrupt
+                } else {
+                    Fault::None
+                }
+            }
+        };
+        if fault != Fault::None {
+            self.injected += 1;
+        }
+        fault
+    }
+
+    fn recv_fault(&mut self) -> Fault {
+        let fault = match self.recv_script.pop_front() {
+            Some(fault @ (Fault::Fail | Fault::Corrupt | Fault::Duplicate)) => fault,
+            Some(_) => Fault::None,
+            None if self.roll(self.faults.fail_recv) => Fault::Fail,
+            None => Fault::None,
+        };
+        if fault != Fault::None {
+            self.injected += 1;
+        }
+        fault
+    }
+}
+
+impl<Tsp, Msg> FaultyTransport<Tsp, Msg>
+where
+    Msg: Clone + Corrupt,
+{
+    fn corrupt(&mut self, msg: &mut Msg) {
+        let n = self.next_u64();
+        msg.corrupt(n);
+    }
+
+    /// Take the delayed messages whose turn has come, counting one more call for the others.
+    fn due(&mut self) -> Vec<Msg> {
+        let mut due = Vec::new();
+        self.delayed.retain(|(calls, msg)| {
+            if *calls == 0 {
+                due.push(msg.clone());
+            }
+            *calls > 0
+        });
+        for (calls, _) in self.delayed.iter_mut() {
+            *calls -= 1;
+        }
+        due
+    }
+
+    /// Send the delayed messages whose turn has come.
+    async fn tick<Link>(&mut self) -> Result<()>
+    where
+        Tsp: Transport<Link, Msg>,
+    {
+        for msg in self.due() {
+            self.inner.send_message(&msg).await?;
+        }
+        Ok(())
+    }
+
+    /// Send all the delayed and reordered messages.
+    pub async fn flush<Link>(&mut self) -> Result<()>
+    where
+        Tsp: Transport<Link, Msg>,
+    {
+        let mut msgs: Vec<Msg> = self.delayed.drain(..).map(|(_, msg)| msg).collect();
+        msgs.extend(self.reordered.take());
+        for msg in msgs {
+            self.inner.send_message(&msg).await?;
+        }
+        Ok(())
+    }
+
+    fn recv_faulty(&mut self, fault: Fault, mut msgs: Vec<Msg>) -> Vec<Msg> {
+        match fault {
+            Fault::Corrupt => {
+                for msg in msgs.iter_mut() {
+                    self.corrupt(msg);
+                }
+            }
+            Fault::Duplicate => msgs.extend(msgs.clone()),
+            _ => {}
+        }
+        msgs
+    }
+}
+
+impl<Tsp: TransportOptions, Msg> TransportOptions for FaultyTransport<Tsp, Msg> {
+    type SendOptions = <Tsp as TransportOptions>::SendOptions;
+    fn get_send_options(&self) -> Self::SendOptions {
+        self.inner.get_send_options()
+    }
+    fn set_send_options(&mut self, opt: Self::SendOptions) {
+        self.inner.set_send_options(opt)
+    }
+
+    type RecvOptions = <Tsp as TransportOptions>::RecvOptions;
+    fn get_recv_options(&self) -> Self::RecvOptions {
+        self.inner.get_recv_options()
+    }
+    fn set_recv_options(&mut self, opt: Self::RecvOptions) {
+        self.inner.set_recv_options(opt)
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Tsp: TransportDetails<Link>, Msg> TransportDetails<Link> for FaultyTransport<Tsp, Msg> {
+    type Details = <Tsp as TransportDetails<Link>>::Details;
+    async fn get_link_details(&mut self, link: &Link) -> Result<Self::Details> {
+        self.inner.get_link_details(link).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Msg, Tsp> Transport<Link, Msg> for FaultyTransport<Tsp, Msg>
+where
+    Link: Clone + Display,
+    Msg: Clone + Corrupt + crate::message::LinkedMessage<Link>,
+    Tsp: Transport<Link, Msg>,
+{
+    async fn send_message(&mut self, msg: &Msg) -> Result<()> {
+        self.tick().await?;
+        match self.send_fault() {
+            Fault::None => self.inner.send_message(msg).await?,
+            Fault::Fail => return err!(InjectedTransportFailure(msg.link().to_string())),
+            Fault::Drop => {}
+            Fault::Delay(calls) => self.delayed.push((calls, msg.clone())),
+            Fault::Reorder => {
+                // A message already held back is sent now, in order
+                if let Some(held) = self.reordered.replace(msg.clone()) {
+                    self.inner.send_message(&held).await?;
+                }
+                return Ok(());
+            }
+            Fault::Duplicate => {
+                self.inner.send_message(msg).await?;
+                self.inner.send_message(msg).await?;
+            }
+            Fault::Corrupt => {
+                let mut msg = msg.clone();
+                self.corrupt(&mut msg);
+                self.inner.send_message(&msg).await?;
+            }
+        }
+        if let Some(held) = self.reordered.take() {
+            self.inner.send_message(&held).await?;
+        }
+        Ok(())
+    }
+
+    async fn recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>> {
+        self.tick().await?;
+        let fault = self.recv_fault();
+        if fault == Fault::Fail {
+            return err!(InjectedTransportFailure(link.to_string()));
+        }
+        let msgs = self.inner.recv_messages(link).await?;
+        Ok(self.recv_faulty(fault, msgs))
+    }
+
+    async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
+        self.tick().await?;
+        match self.recv_fault() {
+            Fault::Fail => err!(InjectedTransportFailure(link.to_string())),
+            Fault::Corrupt => {
+                let mut msg = self.inner.recv_message(link).await?;
+                self.corrupt(&mut msg);
+                Ok(msg)
+            }
+            // A single message can't be duplicated
+            _ => self.inner.recv_message(link).await,
+        }
+    }
+
+    /// Inject a fault per message as `send_message` would, then send what's left in one batch.
+    ///
+    /// A failure of a held back message is reported for the message whose call released it.
+    async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        let mut results: Vec<Result<()>> = msgs.iter().map(|_| Ok(())).collect();
+        // Messages handed to the inner transport, along with the index of the message they're sent for
+        let mut batch: Vec<(usize, Msg)> = self.due().into_iter().map(|msg| (0, msg)).collect();
+        for (i, msg) in msgs.iter().enumerate() {
+            match self.send_fault() {
+                Fault::None => batch.push((i, msg.clone())),
+                Fault::Fail => {
+                    results[i] = err!(InjectedTransportFailure(msg.link().to_string()));
+                    continue;
+                }
+                Fault::Drop => {}
+                Fault::Delay(calls) => self.delayed.push((calls, msg.clone())),
+                Fault::Reorder => {
+                    if let Some(held) = self.reordered.replace(msg.clone()) {
+                        batch.push((i, held));
+                    }
+                    continue;
+                }
+                Fault::Duplicate => {
+                    batch.push((i, msg.clone()));
+                    batch.push((i, msg.clone()));
+                }
+                Fault::Corrupt => {
+                    let mut msg = msg.clone();
+                    self.corrupt(&mut msg);
+                    batch.push((i, msg));
+                }
+            }
+            if let Some(held) = self.reordered.take() {
+                batch.push((i, held));
+            }
+        }
+        let (origins, batch): (Vec<usize>, Vec<Msg>) = batch.into_iter().unzip();
+        for (i, result) in origins.into_iter().zip(self.inner.send_messages(&batch).await) {
+            if let (Some(Ok(())), Err(e)) = (results.get(i), result) {
+                results[i] = Err(e);
+            }
+        }
+        results
+    }
+
+    /// Inject a fault per link as `recv_messages` would, then look the links up in one batch.
+    async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        let due = self.due();
+        let delivered = self.inner.send_messages(&due).await.into_iter().find(Result::is_err);
+        let faults: Vec<Fault> = links.iter().map(|_| self.recv_fault()).collect();
+        let asked: Vec<Link> = links
+            .iter()
+            .zip(&faults)
+            .filter(|(_, fault)| **fault != Fault::Fail)
+            .map(|(link, _)| link.clone())
+            .collect();
+        let mut found = self.inner.recv_messages_many(&asked).await.into_iter();
+        let mut results = Vec::with_capacity(links.len());
+        for (link, fault) in links.iter().zip(faults) {
+            let result = match fault {
+                Fault::Fail => err!(InjectedTransportFailure(link.to_string())),
+                _ => match found.next() {
+                    Some(Ok(msgs)) => Ok(self.recv_faulty(fault, msgs)),
+                    Some(Err(e)) => Err(e),
+                    None => err!(TransportOutcomeMissing(link.to_string())),
+                },
+            };
+            results.push(result);
+        }
+        // A failure to send the delayed messages fails the first lookup, as it would a single one
+        if let (Some(Err(e)), Some(first)) = (delivered, results.first_mut()) {
+            *first = Err(e);
+        }
+        results
+    }
+}
+
+#[cfg(all(test, feature = "tangle"))]
+mod tests {
+    use super::*;
+    use crate::transport::{
+        tangle::{
+            test_message,
+            TangleAddress,
+            TangleMessage,
+        },
+        BucketTransport,
+    };
+    use iota_streams_core::ensure;
+
+    type Bucket = BucketTransport<TangleAddress, TangleMessage>;
+
+    /// Number of messages which reached the bucket at the link of `msg`
+    async fn found(faulty: &mut FaultyTransport<Bucket, TangleMessage>, msg: &TangleMessage) -> usize {
+        faulty
+            .inner_mut()
+            .recv_messages(&msg.link)
+            .await
+            .map_or(0, |msgs| msgs.len())
+    }
+
+    #[tokio::test]
+    async fn scripted_faults_alter_received_messages() -> Result<()> {
+        let msg = test_message(1, &[0, 0]);
+        let mut bucket = Bucket::new();
+        bucket.send_message(&msg).await?;
+        let mut faulty = FaultyTransport::new(bucket, Faults::default(), 0).with_recv_script([
+            Fault::Fail,
+            Fault::Corrupt,
+            Fault::Duplicate,
+            Fault::Drop,
+        ]);
+
+        ensure!(
+            faulty.recv_messages(&msg.link).await.is_err(),
+            "injected failure ignored"
+        );
+        let corrupted = faulty.recv_messages(&msg.link).await?;
+        ensure!(corrupted.len() == 1 && corrupted[0] != msg, "message not corrupted");
+        ensure!(
+            faulty.recv_messages(&msg.link).await? == [msg.clone(), msg.clone()],
+            "messages not duplicated"
+        );
+        // Faults of sent messages let receive calls through
+        ensure!(faulty.recv_message(&msg.link).await? == msg, "message altered");
+        ensure!(faulty.injected() == 3, "{} faults injected", faulty.injected());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn scripted_faults_alter_sent_messages() -> Result<()> {
+        let msgs: Vec<TangleMessage> = (0..7).map(|n| test_message(n, &[n])).collect();
+        let mut faulty = FaultyTransport::new(Bucket::new(), Faults::default(), 0).with_send_script([
+            Fault::Fail,
+            Fault::Drop,
+            Fault::Delay(1),
+            Fault::Reorder,
+            Fault::Duplicate,
+            Fault::Corrupt,
+            Fault::Delay(5),
+        ]);
+
+        ensure!(faulty.send_message(&msgs[0]).await.is_err(), "injected failure ignored");
+        for msg in &msgs[1..4] {
+            faulty.send_message(msg).await?;
+        }
+        ensure!(found(&mut faulty, &msgs[1]).await == 0, "dropped message sent");
+        ensure!(
+            found(&mut faulty, &msgs[2]).await == 0 && found(&mut faulty, &msgs[3]).await == 0,
+            "held back messages sent"
+        );
+
+        // The delayed message is sent after one more call, the reordered one after the next message
+        faulty.send_message(&msgs[4]).await?;
+        ensure!(found(&mut faulty, &msgs[2]).await == 1, "delayed message not sent");
+        ensure!(found(&mut faulty, &msgs[3]).await == 1, "reordered message not sent");
+        ensure!(found(&mut faulty, &msgs[4]).await == 2, "message not duplicated");
+
+        faulty.send_message(&msgs[5]).await?;
+        ensure!(
+            faulty.inner_mut().recv_message(&msgs[5].link).await? != msgs[5],
+            "message not corrupted"
+        );
+        faulty.send_message(&msgs[6]).await?;
+        ensure!(found(&mut faulty, &msgs[6]).await == 0, "delayed message sent early");
+        faulty.flush().await?;
+        ensure!(found(&mut faulty, &msgs[6]).await == 1, "delayed message not flushed");
+        ensure!(faulty.injected() == 7, "{} faults injected", faulty.injected());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn scripted_faults_alter_batches_per_message() -> Result<()> {
+        let msgs: Vec<TangleMessage> = (0..3).map(|n| test_message(n, &[n])).collect();
+        let mut faulty = FaultyTransport::new(Bucket::new(), Faults::default(), 0).with_send_script([
+            Fault::None,
+            Fault::Fail,
+            Fault::Duplicate,
+        ]);
+        let results = faulty.send_messages(&msgs).await;
+        ensure!(
+            results[0].is_ok() && results[1].is_err() && results[2].is_ok(),
+            "unexpected results {:?}",
+            results
+        );
+        ensure!(
+            found(&mut faulty, &msgs[0]).await == 1
+                && found(&mut faulty, &msgs[1]).await == 0
+                && found(&mut faulty, &msgs[2]).await == 2,
+            "batch not sent message by message"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn random_faults_are_reproducible() -> Result<()> {
+        let msgs: Vec<TangleMessage> = (0..32).map(|n| test_message(n, &[n])).collect();
+        let faults = Faults::default().with_drop(0.5).with_fail_recv(0.5);
+        let mut runs = Vec::new();
+        for _ in 0..2 {
+            let mut faulty = FaultyTransport::new(Bucket::new(), faults, 7);
+            let mut found = Vec::new();
+            for msg in &msgs {
+                faulty.send_message(msg).await?;
+                found.push(faulty.recv_messages(&msg.link).await.is_ok());
+            }
+            runs.push((found, faulty.injected()));
+        }
+        ensure!(runs[0] == runs[1], "faults differ for the same seed");
+        ensure!(
+            runs[0].0.contains(&true) && runs[0].0.contains(&false),
+            "faults not picked at random"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,664 @@
+use core::{
+    convert::TryInto,
+    fmt::Display,
+    hash::Hash,
+    time::Duration,
+};
+use std::{
+    fs,
+    path::PathBuf,
+    time::Instant,
+};
+
+use iota_streams_core::{
+    async_trait,
+    err,
+    prelude::{
+        hex,
+        string::ToString,
+        BTreeMap,
+        Box,
+        HashMap,
+        Vec,
+    },
+    try_or,
+    Errors::{
+        MalformedCacheEntry,
+        MessageLinkNotFoundInCache,
+        MessageNotUnique,
+        TransportOutcomeMissing,
+    },
+    Result,
+    TransportErrorKind,
+};
+
+use super::transport_error_kind;
+use crate::{
+    message::{
+        BinaryBody,
+        GenericMessage,
+        HasLink,
+    },
+    transport::{
+        Transport,
+        TransportDetails,
+        TransportOptions,
+    },
+};
+
+/// Cache settings of a `CachingTransport`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CacheOptions<Opt = ()> {
+    /// Max number of links kept in memory, nothing is cached in memory if 0.
+    pub capacity: usize,
+    /// Time after which the messages found at a link are fetched again, never if `None`.
+    ///
+    /// Anyone can publish at any link, so messages found at a link might be spam published before
+    /// the authentic message, which is only found once they are fetched again.
+    pub ttl: Option<Duration>,
+    /// Time during which a link without messages is not fetched again, nothing is cached if zero.
+    pub negative_ttl: Duration,
+    pub inner: Opt,
+}
+
+impl<Opt: Default> Default for CacheOptions<Opt> {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            ttl: Some(Duration::from_secs(60)),
+            negative_ttl: Duration::from_secs(10),
+            inner: Opt::default(),
+        }
+    }
+}
+
+impl<Opt> CacheOptions<Opt> {
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub fn with_negative_ttl(mut self, negative_ttl: Duration) -> Self {
+        self.negative_ttl = negative_ttl;
+        self
+    }
+
+    pub fn with_inner<Inner>(self, inner: Inner) -> CacheOptions<Inner> {
+        CacheOptions {
+            capacity: self.capacity,
+            ttl: self.ttl,
+            negative_ttl: self.negative_ttl,
+            inner,
+        }
+    }
+
+    /// Split the options of the layer from the options of the inner transport.
+    pub fn split(self) -> (CacheOptions, Opt) {
+        let inner = self.inner;
+        (
+            CacheOptions {
+                capacity: self.capacity,
+                ttl: self.ttl,
+                negative_ttl: self.negative_ttl,
+                inner: (),
+            },
+            inner,
+        )
+    }
+}
+
+/// Counters of the lookups served by a `CachingTransport`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct CacheStats {
+    /// Lookups served from memory with the messages found at the link.
+    pub hits: usize,
+    /// Lookups served from memory for a link without messages.
+    pub negative_hits: usize,
+    /// Lookups served from disk.
+    pub disk_hits: usize,
+    /// Lookups reaching the inner transport.
+    pub misses: usize,
+    /// Links dropped from memory to stay within capacity.
+    pub evictions: usize,
+}
+
+/// Messages which `CachingTransport` can keep on disk.
+pub trait CacheEncoding: Sized {
+    fn encode(&self, buf: &mut Vec<u8>);
+    /// Decode a message from the front of `bytes`, advancing it.
+    fn decode(bytes: &mut &[u8]) -> Result<Self>;
+}
+
+pub(super) fn encode_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+pub(super) fn decode_bytes<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8]> {
+    try_or!(bytes.len() >= 4, MalformedCacheEntry)?;
+    let (len, rest) = bytes.split_at(4);
+    let len = u32::from_be_bytes(len.try_into()?) as usize;
+    try_or!(rest.len() >= len, MalformedCacheEntry)?;
+    let (data, rest) = rest.split_at(len);
+    *bytes = rest;
+    Ok(data)
+}
+
+impl<Link: HasLink> CacheEncoding for GenericMessage<Link, BinaryBody> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        encode_bytes(buf, &self.link.to_bytes());
+        encode_bytes(buf, &self.prev_link.to_bytes());
+        encode_bytes(buf, self.body.as_bytes());
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self> {
+        let link = Link::try_from_bytes(decode_bytes(bytes)?)?;
+        let prev_link = Link::try_from_bytes(decode_bytes(bytes)?)?;
+        let body = BinaryBody::from(decode_bytes(bytes)?.to_vec());
+        Ok(Self::new(link, prev_link, body))
+    }
+}
+
+enum Entry<Msg> {
+    Found(Vec<Msg>),
+    // Whether the inner transport reported the missing messages with an error, or with no messages
+    Missing { error: bool },
+}
+
+struct Slot<Msg> {
+    tick: u64,
+    stored_at: Instant,
+    entry: Entry<Msg>,
+}
+
+/// Transport keeping the messages received from the inner transport by link.
+///
+/// Lookups are served from the memory cache first, then from the disk cache if enabled, and reach
+/// the inner transport last. Memory keeps the least recently used links up to the capacity, the
+/// disk keeps all the links with messages. Sending a message drops its link from both caches.
+///
+/// Links with messages are cached for `ttl`, and links without messages for `negative_ttl`, which
+/// delays new messages at a link by as much.
+pub struct CachingTransport<Tsp, Link, Msg> {
+    inner: Tsp,
+    opt: CacheOptions,
+    slots: HashMap<Link, Slot<Msg>>,
+    // Links by last use, oldest first
+    lru: BTreeMap<u64, Link>,
+    tick: u64,
+    dir: Option<PathBuf>,
+    stats: CacheStats,
+}
+
+impl<Tsp, Link, Msg> CachingTransport<Tsp, Link, Msg>
+where
+    Link: HasLink + Hash + Display,
+    Msg: CacheEncoding + Clone,
+{
+    /// Wrap the inner transport, with the default settings and no disk cache.
+    pub fn new(inner: Tsp) -> Self {
+        Self {
+            inner,
+            opt: CacheOptions::default(),
+            slots: HashMap::new(),
+            lru: BTreeMap::new(),
+            tick: 0,
+            dir: None,
+            stats: CacheStats::default(),
+        }
+    }
+
+    pub fn with_options(mut self, opt: CacheOptions) -> Self {
+        self.set_options(opt);
+        self
+    }
+
+    /// Keep the messages in files of the directory, one per link, which is created if needed.
+    pub fn with_disk_cache(mut self, dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        self.dir = Some(dir);
+        Ok(self)
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+
+    /// Drop the link from memory and disk, so that the next lookup reaches the inner transport.
+    pub fn invalidate(&mut self, link: &Link) {
+        self.invalidate_memory(link);
+        if let Some(path) = self.path(link) {
+            // The file might not exist
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// Drop all the links from memory, the disk cache is left as is.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.lru.clear();
+    }
+
+    pub fn inner(&self) -> &Tsp {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Tsp {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> Tsp {
+        self.inner
+    }
+
+    fn set_options(&mut self, opt: CacheOptions) {
+        self.opt = opt;
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.slots.len() > self.opt.capacity {
+            let oldest = match self.lru.keys().next() {
+                Some(&tick) => tick,
+                None => break,
+            };
+            if let Some(link) = self.lru.remove(&oldest) {
+                self.slots.remove(&link);
+            }
+            self.stats.evictions += 1;
+        }
+    }
+
+    fn is_fresh(&self, slot: &Slot<Msg>) -> bool {
+        let ttl = match slot.entry {
+            Entry::Found(_) => self.opt.ttl,
+            Entry::Missing { .. } => Some(self.opt.negative_ttl),
+        };
+        !matches!(ttl, Some(ttl) if slot.stored_at.elapsed() >= ttl)
+    }
+
+    /// Cached result of the lookup of the link in memory, if still fresh.
+    fn lookup(&mut self, link: &Link) -> Option<Result<Vec<Msg>>> {
+        let fresh = self.is_fresh(self.slots.get(link)?);
+        if !fresh {
+            self.invalidate_memory(link);
+            return None;
+        }
+        self.tick += 1;
+        let tick = self.tick;
+        let slot = self.slots.get_mut(link)?;
+        self.lru.remove(&slot.tick);
+        self.lru.insert(tick, link.clone());
+        slot.tick = tick;
+        match &slot.entry {
+            Entry::Found(msgs) => {
+                self.stats.hits += 1;
+                Some(Ok(msgs.clone()))
+            }
+            Entry::Missing { error } => {
+                self.stats.negative_hits += 1;
+                if *error {
+                    Some(err!(MessageLinkNotFoundInCache(link.to_string())))
+                } else {
+                    Some(Ok(Vec::new()))
+                }
+            }
+        }
+    }
+
+    /// Cached messages at the link, from memory or disk.
+    fn cached(&mut self, link: &Link) -> Option<Result<Vec<Msg>>> {
+        if let Some(result) = self.lookup(link) {
+            return Some(result);
+        }
+        let msgs = self.read_disk(link)?;
+        self.stats.disk_hits += 1;
+        self.store(link, Entry::Found(msgs.clone()));
+        Some(Ok(msgs))
+    }
+
+    /// Cache the result of the lookup of the link by the inner transport.
+    fn fetched(&mut self, link: &Link, result: Result<Vec<Msg>>) -> Result<Vec<Msg>> {
+        self.stats.misses += 1;
+        match result {
+            Ok(msgs) if msgs.is_empty() => {
+                self.store(link, Entry::Missing { error: false });
+                Ok(msgs)
+            }
+            Ok(msgs) => {
+                self.write_disk(link, &msgs);
+                self.store(link, Entry::Found(msgs.clone()));
+                Ok(msgs)
+            }
+            Err(e) => {
+                if transport_error_kind(&e) == TransportErrorKind::Missing {
+                    self.store(link, Entry::Missing { error: true });
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn invalidate_memory(&mut self, link: &Link) {
+        if let Some(slot) = self.slots.remove(link) {
+            self.lru.remove(&slot.tick);
+        }
+    }
+
+    fn store(&mut self, link: &Link, entry: Entry<Msg>) {
+        if self.opt.capacity == 0 {
+            return;
+        }
+        if let Entry::Missing { .. } = entry {
+            if self.opt.negative_ttl == Duration::ZERO {
+                return;
+            }
+        }
+        self.invalidate_memory(link);
+        self.tick += 1;
+        self.lru.insert(self.tick, link.clone());
+        self.slots.insert(
+            link.clone(),
+            Slot {
+                tick: self.tick,
+                stored_at: Instant::now(),
+                entry,
+            },
+        );
+        self.evict();
+    }
+
+    fn path(&self, link: &Link) -> Option<PathBuf> {
+        self.dir.as_ref().map(|dir| dir.join(hex::encode(link.to_bytes())))
+    }
+
+    fn read_disk(&mut self, link: &Link) -> Option<Vec<Msg>> {
+        let path = self.path(link)?;
+        let stored_at = fs::metadata(&path).and_then(|metadata| metadata.modified()).ok()?;
+        if let Some(ttl) = self.opt.ttl {
+            if stored_at.elapsed().map_or(true, |elapsed| elapsed >= ttl) {
+                let _ = fs::remove_file(path);
+                return None;
+            }
+        }
+        let bytes = fs::read(&path).ok()?;
+        let mut bytes = &bytes[..];
+        let mut msgs = Vec::new();
+        while !bytes.is_empty() {
+            match Msg::decode(&mut bytes) {
+                Ok(msg) => msgs.push(msg),
+                Err(_) => {
+                    // Left over by an interrupted write, fetch the messages again
+                    let _ = fs::remove_file(path);
+                    return None;
+                }
+            }
+        }
+        Some(msgs)
+    }
+
+    fn write_disk(&self, link: &Link, msgs: &[Msg]) {
+        if let Some(path) = self.path(link) {
+            let mut bytes = Vec::new();
+            for msg in msgs {
+                msg.encode(&mut bytes);
+            }
+            // The disk cache is best effort, the messages are fetched again if the write fails
+            let tmp = path.with_extension("tmp");
+            if fs::write(&tmp, bytes).is_ok() {
+                let _ = fs::rename(tmp, path);
+            }
+        }
+    }
+}
+
+impl<Tsp: TransportOptions, Link, Msg> TransportOptions for CachingTransport<Tsp, Link, Msg>
+where
+    Link: HasLink + Hash + Display,
+    Msg: CacheEncoding + Clone,
+{
+    type SendOptions = <Tsp as TransportOptions>::SendOptions;
+    fn get_send_options(&self) -> Self::SendOptions {
+        self.inner.get_send_options()
+    }
+    fn set_send_options(&mut self, opt: Self::SendOptions) {
+        self.inner.set_send_options(opt)
+    }
+
+    type RecvOptions = CacheOptions<<Tsp as TransportOptions>::RecvOptions>;
+    fn get_recv_options(&self) -> Self::RecvOptions {
+        self.opt.with_inner(self.inner.get_recv_options())
+    }
+    fn set_recv_options(&mut self, opt: Self::RecvOptions) {
+        let (opt, inner) = opt.split();
+        self.set_options(opt);
+        self.inner.set_recv_options(inner)
+    }
+}
+
+#[async_trait(?Send)]
+impl<Tsp: TransportDetails<Link>, Link, Msg> TransportDetails<Link> for CachingTransport<Tsp, Link, Msg> {
+    type Details = <Tsp as TransportDetails<Link>>::Details;
+    async fn get_link_details(&mut self, link: &Link) -> Result<Self::Details> {
+        self.inner.get_link_details(link).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<Tsp, Link, Msg> Transport<Link, Msg> for CachingTransport<Tsp, Link, Msg>
+where
+    Link: HasLink + Hash + Display,
+    Msg: CacheEncoding + Clone + crate::message::LinkedMessage<Link>,
+    Tsp: Transport<Link, Msg>,
+{
+    async fn send_message(&mut self, msg: &Msg) -> Result<()> {
+        let result = self.inner.send_message(msg).await;
+        // Even a failed send might have reached the inner transport
+        self.invalidate(msg.link());
+        result
+    }
+
+    async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        let results = self.inner.send_messages(msgs).await;
+        for msg in msgs {
+            self.invalidate(msg.link());
+        }
+        results
+    }
+
+    async fn recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>> {
+        match self.cached(link) {
+            Some(result) => result,
+            None => {
+                let result = self.inner.recv_messages(link).await;
+                self.fetched(link, result)
+            }
+        }
+    }
+
+    /// Serve the cached links, then look the others up in one batch of the inner transport.
+    async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        let mut results: Vec<Option<Result<Vec<Msg>>>> = links.iter().map(|link| self.cached(link)).collect();
+        let misses: Vec<Link> = links
+            .iter()
+            .zip(&results)
+            .filter(|(_, result)| result.is_none())
+            .map(|(link, _)| link.clone())
+            .collect();
+        if !misses.is_empty() {
+            let mut fetched = self.inner.recv_messages_many(&misses).await.into_iter();
+            for (link, result) in links.iter().zip(results.iter_mut()) {
+                if result.is_none() {
+                    let inner = fetched
+                        .next()
+                        .unwrap_or_else(|| err!(TransportOutcomeMissing(link.to_string())));
+                    *result = Some(self.fetched(link, inner));
+                }
+            }
+        }
+        results.into_iter().flatten().collect()
+    }
+
+    async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
+        let mut msgs = self.recv_messages(link).await?;
+        match msgs.pop() {
+            Some(msg) => {
+                try_or!(msgs.is_empty(), MessageNotUnique(link.to_string()))?;
+                Ok(msg)
+            }
+            None => err!(MessageLinkNotFoundInCache(link.to_string())),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tangle"))]
+mod tests {
+    use core::cell::RefCell;
+
+    use super::*;
+    use crate::transport::{
+        tangle::{
+            test_message,
+            TangleAddress,
+            TangleMessage,
+        },
+        BucketTransport,
+    };
+    use iota_streams_core::{
+        ensure,
+        prelude::Rc,
+    };
+
+    type Bucket = Rc<RefCell<BucketTransport<TangleAddress, TangleMessage>>>;
+    type Cache = CachingTransport<Bucket, TangleAddress, TangleMessage>;
+
+    #[tokio::test]
+    async fn repeated_lookups_are_served_from_memory() -> Result<()> {
+        let msg = test_message(1, &[1]);
+        let missing = test_message(2, &[2]);
+        let mut bucket = Bucket::default();
+        bucket.send_message(&msg).await?;
+        let mut cache = Cache::new(bucket.clone()).with_options(CacheOptions::default().with_capacity(1));
+
+        for _ in 0..2 {
+            ensure!(cache.recv_message(&msg.link).await? == msg, "message not received");
+            ensure!(
+                cache.recv_message(&missing.link).await.is_err(),
+                "missing message found"
+            );
+        }
+        let stats = cache.stats();
+        ensure!(
+            stats.misses == 4 && stats.hits == 0 && stats.negative_hits == 0 && stats.evictions == 3,
+            "lookups cached beyond capacity {:?}",
+            stats
+        );
+
+        cache.set_recv_options(CacheOptions::default());
+        cache.reset_stats();
+        for _ in 0..2 {
+            ensure!(cache.recv_message(&msg.link).await? == msg, "message not received");
+            ensure!(
+                cache.recv_message(&missing.link).await.is_err(),
+                "missing message found"
+            );
+        }
+        let stats = cache.stats();
+        ensure!(
+            stats.misses == 1 && stats.hits == 1 && stats.negative_hits == 2,
+            "lookups not cached {:?}",
+            stats
+        );
+
+        // A message sent through the cache drops its link
+        cache.send_message(&missing).await?;
+        ensure!(
+            cache.recv_message(&missing.link).await? == missing,
+            "sent message not found"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn lookups_are_fetched_again_once_expired() -> Result<()> {
+        let msg = test_message(1, &[1]);
+        let forged = TangleMessage::new(msg.link, msg.prev_link, vec![0].into());
+        let mut bucket = Bucket::default();
+        let mut cache = Cache::new(bucket.clone()).with_options(
+            CacheOptions::default()
+                .with_ttl(Duration::from_secs(60))
+                .with_negative_ttl(Duration::from_secs(60)),
+        );
+
+        // A new message at a link cached as missing shows up once the negative TTL expires
+        ensure!(cache.recv_messages(&msg.link).await.is_err(), "missing message found");
+        bucket.send_message(&forged).await?;
+        ensure!(
+            cache.recv_messages(&msg.link).await.is_err(),
+            "missing message not cached"
+        );
+        let mut opt = cache.get_recv_options();
+        opt.negative_ttl = Duration::ZERO;
+        cache.set_recv_options(opt);
+        ensure!(
+            cache.recv_messages(&msg.link).await? == [forged.clone()],
+            "new message not found"
+        );
+
+        // Spam found at a link is fetched again once the TTL expires, along with the authentic message
+        bucket.send_message(&msg).await?;
+        ensure!(
+            cache.recv_messages(&msg.link).await? == [forged.clone()],
+            "messages found not cached"
+        );
+        let mut opt = cache.get_recv_options();
+        opt.ttl = Some(Duration::ZERO);
+        cache.set_recv_options(opt);
+        ensure!(
+            cache.recv_messages(&msg.link).await? == [forged, msg],
+            "authentic message not found"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn messages_are_served_from_disk() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let msg = test_message(1, &[1]);
+        let mut bucket = Bucket::default();
+        bucket.send_message(&msg).await?;
+        let mut cache = Cache::new(bucket).with_disk_cache(dir.path())?;
+        ensure!(cache.recv_message(&msg.link).await? == msg, "message not received");
+
+        // Another cache on the same directory serves the message without reaching its transport
+        let mut disk = Cache::new(Bucket::default()).with_disk_cache(dir.path())?;
+        ensure!(
+            disk.recv_message(&msg.link).await? == msg,
+            "message not served from disk"
+        );
+        ensure!(
+            disk.stats().disk_hits == 1 && disk.stats().misses == 0,
+            "message not cached on disk {:?}",
+            disk.stats()
+        );
+
+        // A file left over by an interrupted write is dropped
+        let path = disk.path(&msg.link).unwrap();
+        let bytes = fs::read(&path)?;
+        fs::write(&path, &bytes[..bytes.len() - 1])?;
+        let mut disk = Cache::new(Bucket::default()).with_disk_cache(dir.path())?;
+        ensure!(disk.recv_message(&msg.link).await.is_err(), "truncated message served");
+        ensure!(!path.exists(), "truncated file kept");
+        Ok(())
+    }
+}
@@ -0,0 +1,345 @@
+use core::{
+    fmt::Display,
+    time::Duration,
+};
+use std::time::Instant;
+
+use iota_streams_core::{
+    async_trait,
+    prelude::{
+        string::ToString,
+        Box,
+        Vec,
+    },
+    try_or,
+    Error,
+    Errors::TransportCircuitOpen,
+    Result,
+};
+
+use super::is_transient;
+use crate::{
+    message::LinkedMessage,
+    transport::{
+        Transport,
+        TransportDetails,
+        TransportOptions,
+    },
+};
+
+/// Circuit breaker settings of the calls of a `CircuitBreakerTransport`.
+///
+/// The circuit opens after `failure_threshold` consecutive transient failures, no threshold if 0.
+/// Calls are rejected while the circuit is open. After `reset_timeout` a single call is let through,
+/// which closes the circuit on success and opens it again on failure.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CircuitBreakerOptions<Opt = ()> {
+    pub failure_threshold: usize,
+    pub reset_timeout: Duration,
+    pub inner: Opt,
+}
+
+impl<Opt: Default> Default for CircuitBreakerOptions<Opt> {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            reset_timeout: Duration::from_secs(30),
+            inner: Opt::default(),
+        }
+    }
+}
+
+impl<Opt> CircuitBreakerOptions<Opt> {
+    pub fn with_failure_threshold(mut self, failure_threshold: usize) -> Self {
+        self.failure_threshold = failure_threshold;
+        self
+    }
+
+    pub fn with_reset_timeout(mut self, reset_timeout: Duration) -> Self {
+        self.reset_timeout = reset_timeout;
+        self
+    }
+
+    pub fn with_inner<Inner>(self, inner: Inner) -> CircuitBreakerOptions<Inner> {
+        CircuitBreakerOptions {
+            failure_threshold: self.failure_threshold,
+            reset_timeout: self.reset_timeout,
+            inner,
+        }
+    }
+
+    /// Split the options of the layer from the options of the inner transport.
+    pub fn split(self) -> (CircuitBreakerOptions, Opt) {
+        let inner = self.inner;
+        (
+            CircuitBreakerOptions {
+                failure_threshold: self.failure_threshold,
+                reset_timeout: self.reset_timeout,
+                inner: (),
+            },
+            inner,
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Circuit {
+    opt: CircuitBreakerOptions,
+    failures: usize,
+    opened_at: Option<Instant>,
+}
+
+impl Circuit {
+    fn new(opt: CircuitBreakerOptions) -> Self {
+        Self {
+            opt,
+            failures: 0,
+            opened_at: None,
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        matches!(self.opened_at, Some(opened_at) if opened_at.elapsed() < self.opt.reset_timeout)
+    }
+
+    fn check(&self, link: &impl Display) -> Result<()> {
+        try_or!(!self.is_open(), TransportCircuitOpen(link.to_string()))
+    }
+
+    fn record<T>(&mut self, result: &Result<T>, is_transient: fn(&Error) -> bool) {
+        match result {
+            Err(e) if is_transient(e) => {
+                self.failures += 1;
+                if self.opt.failure_threshold != 0 && self.failures >= self.opt.failure_threshold {
+                    self.opened_at = Some(Instant::now());
+                }
+            }
+            // Permanent errors are answers of a working transport
+            _ => self.reset(),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.failures = 0;
+        self.opened_at = None;
+    }
+}
+
+/// Transport rejecting calls without reaching the inner transport while it keeps failing.
+///
+/// Send and receive calls trip separate circuits. Only errors deemed transient by the classifier
+/// count as failures, `is_transient` by default.
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerTransport<Tsp> {
+    inner: Tsp,
+    send: Circuit,
+    recv: Circuit,
+    is_transient: fn(&Error) -> bool,
+}
+
+impl<Tsp> CircuitBreakerTransport<Tsp> {
+    /// Wrap the inner transport, with the default settings for send and receive calls.
+    pub fn new(inner: Tsp) -> Self {
+        Self {
+            inner,
+            send: Circuit::new(CircuitBreakerOptions::default()),
+            recv: Circuit::new(CircuitBreakerOptions::default()),
+            is_transient,
+        }
+    }
+
+    pub fn with_send_options(mut self, opt: CircuitBreakerOptions) -> Self {
+        self.send.opt = opt;
+        self
+    }
+
+    pub fn with_recv_options(mut self, opt: CircuitBreakerOptions) -> Self {
+        self.recv.opt = opt;
+        self
+    }
+
+    /// Classify the errors counting as failures.
+    pub fn with_classifier(mut self, is_transient: fn(&Error) -> bool) -> Self {
+        self.is_transient = is_transient;
+        self
+    }
+
+    pub fn is_send_open(&self) -> bool {
+        self.send.is_open()
+    }
+
+    pub fn is_recv_open(&self) -> bool {
+        self.recv.is_open()
+    }
+
+    /// Close both circuits.
+    pub fn reset(&mut self) {
+        self.send.reset();
+        self.recv.reset();
+    }
+
+    pub fn inner(&self) -> &Tsp {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Tsp {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> Tsp {
+        self.inner
+    }
+}
+
+impl<Tsp: TransportOptions> TransportOptions for CircuitBreakerTransport<Tsp> {
+    type SendOptions = CircuitBreakerOptions<<Tsp as TransportOptions>::SendOptions>;
+    fn get_send_options(&self) -> Self::SendOptions {
+        self.send.opt.with_inner(self.inner.get_send_options())
+    }
+    fn set_send_options(&mut self, opt: Self::SendOptions) {
+        let (send_opt, inner) = opt.split();
+        self.send.opt = send_opt;
+        self.inner.set_send_options(inner)
+    }
+
+    type RecvOptions = CircuitBreakerOptions<<Tsp as TransportOptions>::RecvOptions>;
+    fn get_recv_options(&self) -> Self::RecvOptions {
+        self.recv.opt.with_inner(self.inner.get_recv_options())
+    }
+    fn set_recv_options(&mut self, opt: Self::RecvOptions) {
+        let (recv_opt, inner) = opt.split();
+        self.recv.opt = recv_opt;
+        self.inner.set_recv_options(inner)
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Tsp: TransportDetails<Link>> TransportDetails<Link> for CircuitBreakerTransport<Tsp> {
+    type Details = <Tsp as TransportDetails<Link>>::Details;
+    async fn get_link_details(&mut self, link: &Link) -> Result<Self::Details> {
+        self.inner.get_link_details(link).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Msg, Tsp> Transport<Link, Msg> for CircuitBreakerTransport<Tsp>
+where
+    Link: Display,
+    Msg: LinkedMessage<Link>,
+    Tsp: Transport<Link, Msg>,
+{
+    async fn send_message(&mut self, msg: &Msg) -> Result<()> {
+        self.send.check(msg.link())?;
+        let result = self.inner.send_message(msg).await;
+        self.send.record(&result, self.is_transient);
+        result
+    }
+
+    async fn recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>> {
+        self.recv.check(link)?;
+        let result = self.inner.recv_messages(link).await;
+        self.recv.record(&result, self.is_transient);
+        result
+    }
+
+    async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
+        self.recv.check(link)?;
+        let result = self.inner.recv_message(link).await;
+        self.recv.record(&result, self.is_transient);
+        result
+    }
+
+    /// Reject the whole batch while the circuit is open, each result counts as a call otherwise.
+    async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        if self.send.is_open() {
+            return msgs.iter().map(|msg| self.send.check(msg.link())).collect();
+        }
+        let results = self.inner.send_messages(msgs).await;
+        for result in &results {
+            self.send.record(result, self.is_transient);
+        }
+        results
+    }
+
+    /// Reject the whole batch while the circuit is open, each result counts as a call otherwise.
+    async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        if self.recv.is_open() {
+            return links
+                .iter()
+                .map(|link| self.recv.check(link).map(|_| Vec::new()))
+                .collect();
+        }
+        let results = self.inner.recv_messages_many(links).await;
+        for result in &results {
+            self.recv.record(result, self.is_transient);
+        }
+        results
+    }
+}
+
+#[cfg(all(test, feature = "tangle"))]
+mod tests {
+    use super::*;
+    use crate::transport::{
+        tangle::{
+            test_message,
+            TangleAddress,
+            TangleMessage,
+        },
+        BucketTransport,
+        Faults,
+        FaultyTransport,
+    };
+    use iota_streams_core::{
+        ensure,
+        Errors,
+    };
+
+    #[tokio::test]
+    async fn circuit_opens_on_consecutive_failures_and_closes_after_reset_timeout() -> Result<()> {
+        let msg = test_message(1, &[1]);
+        let mut bucket = BucketTransport::<TangleAddress, TangleMessage>::new();
+        bucket.send_message(&msg).await?;
+        let faulty = FaultyTransport::new(bucket, Faults::default(), 0);
+        let mut transport = CircuitBreakerTransport::new(faulty)
+            .with_recv_options(CircuitBreakerOptions::default().with_failure_threshold(2));
+
+        // Missing messages don't trip the circuit
+        let missing = test_message(2, &[]).link;
+        for _ in 0..3 {
+            ensure!(transport.recv_message(&missing).await.is_err(), "missing message found");
+        }
+        ensure!(!transport.is_recv_open(), "circuit opened by missing messages");
+
+        transport.inner_mut().set_faults(Faults::default().with_fail_recv(1.0));
+        for _ in 0..2 {
+            ensure!(
+                transport.recv_message(&msg.link).await.is_err(),
+                "injected failure ignored"
+            );
+        }
+        ensure!(transport.is_recv_open(), "circuit not opened");
+        let e = transport.recv_message(&msg.link).await.unwrap_err();
+        ensure!(
+            matches!(e.downcast_ref::<Errors>(), Some(Errors::TransportCircuitOpen(_))),
+            "call not rejected: {}",
+            e
+        );
+        let results = transport.recv_messages_many(&[msg.link, missing]).await;
+        ensure!(
+            results.iter().all(|r| r.is_err()),
+            "batch not rejected while the circuit is open"
+        );
+        ensure!(transport.inner().injected() == 2, "open circuit reached the transport");
+        ensure!(!transport.is_send_open(), "send circuit opened by receive calls");
+
+        // A call is let through once the reset timeout has passed, and closes the circuit on success
+        let mut opt = transport.get_recv_options();
+        opt.reset_timeout = Duration::ZERO;
+        transport.set_recv_options(opt);
+        transport.inner_mut().set_faults(Faults::default());
+        ensure!(transport.recv_message(&msg.link).await? == msg, "message not received");
+        ensure!(!transport.is_recv_open(), "circuit not closed");
+        Ok(())
+    }
+}
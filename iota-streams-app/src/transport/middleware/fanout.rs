@@ -0,0 +1,416 @@
+use core::future::Future;
+
+use futures::future::{
+    join,
+    select,
+    Either,
+};
+
+use iota_streams_core::{
+    async_trait,
+    prelude::{
+        Box,
+        Vec,
+    },
+    Result,
+};
+
+use crate::transport::{
+    Transport,
+    TransportDetails,
+    TransportOptions,
+};
+
+/// How a `FanOutTransport` publishes messages.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SendPolicy {
+    /// Succeed if the message reached both transports.
+    All,
+    /// Succeed if the message reached at least the primary or the secondary transport.
+    Any,
+}
+
+// `#[default]` on enum variants needs Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for SendPolicy {
+    fn default() -> Self {
+        Self::All
+    }
+}
+
+/// How a `FanOutTransport` looks messages up.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RecvPolicy {
+    /// Ask the primary transport, and the secondary transport only if the primary misses.
+    ///
+    /// Anyone can publish at a link, so spam found by the primary transport shadows the messages
+    /// only the secondary transport holds at the same link. Cheapest policy when both transports
+    /// are expected to hold the same messages, `Merge` otherwise.
+    Failover,
+    /// Ask both transports at once and take the first hit.
+    ///
+    /// Spam found by the transport answering first shadows the other one, as with `Failover`.
+    Race,
+    /// Ask both transports at once and take the messages found by either, since those found by one
+    /// transport might all be spam while the authentic message is found by the other.
+    Merge,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for RecvPolicy {
+    fn default() -> Self {
+        Self::Failover
+    }
+}
+
+/// Transport publishing every message to a primary and a secondary transport.
+///
+/// Messages are sent to both transports concurrently. A failed send reports the error of the
+/// primary transport if it failed, of the secondary otherwise. A lookup hits when it finds at least one message; when
+/// both transports miss, the outcome of the one answering last is returned.
+/// More transports can be combined by nesting, e.g. `FanOutTransport<Client, FanOutTransport<Client, Archive>>`.
+///
+/// Link details are always taken from the primary transport.
+#[derive(Clone, Debug)]
+pub struct FanOutTransport<Primary, Secondary> {
+    primary: Primary,
+    secondary: Secondary,
+    send_policy: SendPolicy,
+    recv_policy: RecvPolicy,
+}
+
+impl<Primary, Secondary> FanOutTransport<Primary, Secondary> {
+    /// Combine the transports, with the default `All` send and `Failover` receive policies.
+    pub fn new(primary: Primary, secondary: Secondary) -> Self {
+        Self {
+            primary,
+            secondary,
+            send_policy: SendPolicy::default(),
+            recv_policy: RecvPolicy::default(),
+        }
+    }
+
+    pub fn with_send_policy(mut self, send_policy: SendPolicy) -> Self {
+        self.send_policy = send_policy;
+        self
+    }
+
+    pub fn with_recv_policy(mut self, recv_policy: RecvPolicy) -> Self {
+        self.recv_policy = recv_policy;
+        self
+    }
+
+    pub fn send_policy(&self) -> SendPolicy {
+        self.send_policy
+    }
+
+    pub fn recv_policy(&self) -> RecvPolicy {
+        self.recv_policy
+    }
+
+    pub fn primary(&self) -> &Primary {
+        &self.primary
+    }
+
+    pub fn primary_mut(&mut self) -> &mut Primary {
+        &mut self.primary
+    }
+
+    pub fn secondary(&self) -> &Secondary {
+        &self.secondary
+    }
+
+    pub fn secondary_mut(&mut self) -> &mut Secondary {
+        &mut self.secondary
+    }
+
+    pub fn into_inner(self) -> (Primary, Secondary) {
+        (self.primary, self.secondary)
+    }
+}
+
+fn is_hit<Msg>(result: &Result<Vec<Msg>>) -> bool {
+    matches!(result, Ok(msgs) if !msgs.is_empty())
+}
+
+/// Combine the outcomes of a send call to both transports according to `policy`.
+fn fan_in(policy: SendPolicy, primary: Result<()>, secondary: Result<()>) -> Result<()> {
+    match policy {
+        SendPolicy::All => primary.and(secondary),
+        SendPolicy::Any => primary.or_else(|e| secondary.map_err(|_| e)),
+    }
+}
+
+/// Combine the messages found by both transports, skipping those found by both. Fails only if both
+/// transports fail.
+fn merge<Msg: PartialEq>(primary: Result<Vec<Msg>>, secondary: Result<Vec<Msg>>) -> Result<Vec<Msg>> {
+    match (primary, secondary) {
+        (Ok(mut msgs), Ok(more)) => {
+            for msg in more {
+                if !msgs.contains(&msg) {
+                    msgs.push(msg);
+                }
+            }
+            Ok(msgs)
+        }
+        (Ok(msgs), Err(_)) | (Err(_), Ok(msgs)) => Ok(msgs),
+        (Err(e), Err(_)) => Err(e),
+    }
+}
+
+/// Look a link up in both transports according to `policy`. A single message can't be merged, so
+/// `Merge` takes the first hit like `Race`.
+async fn lookup<T, P, S>(policy: RecvPolicy, primary: P, secondary: S, is_hit: fn(&Result<T>) -> bool) -> Result<T>
+where
+    P: Future<Output = Result<T>> + Unpin,
+    S: Future<Output = Result<T>> + Unpin,
+{
+    match policy {
+        RecvPolicy::Failover => {
+            let result = primary.await;
+            if is_hit(&result) {
+                result
+            } else {
+                secondary.await
+            }
+        }
+        RecvPolicy::Race | RecvPolicy::Merge => match select(primary, secondary).await {
+            Either::Left((result, other)) if !is_hit(&result) => other.await,
+            Either::Right((result, other)) if !is_hit(&result) => other.await,
+            Either::Left((result, _)) | Either::Right((result, _)) => result,
+        },
+    }
+}
+
+impl<Primary: TransportOptions, Secondary: TransportOptions> TransportOptions for FanOutTransport<Primary, Secondary> {
+    type SendOptions = (
+        <Primary as TransportOptions>::SendOptions,
+        <Secondary as TransportOptions>::SendOptions,
+    );
+    fn get_send_options(&self) -> Self::SendOptions {
+        (self.primary.get_send_options(), self.secondary.get_send_options())
+    }
+    fn set_send_options(&mut self, (primary, secondary): Self::SendOptions) {
+        self.primary.set_send_options(primary);
+        self.secondary.set_send_options(secondary);
+    }
+
+    type RecvOptions = (
+        <Primary as TransportOptions>::RecvOptions,
+        <Secondary as TransportOptions>::RecvOptions,
+    );
+    fn get_recv_options(&self) -> Self::RecvOptions {
+        (self.primary.get_recv_options(), self.secondary.get_recv_options())
+    }
+    fn set_recv_options(&mut self, (primary, secondary): Self::RecvOptions) {
+        self.primary.set_recv_options(primary);
+        self.secondary.set_recv_options(secondary);
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Primary: TransportDetails<Link>, Secondary> TransportDetails<Link> for FanOutTransport<Primary, Secondary> {
+    type Details = <Primary as TransportDetails<Link>>::Details;
+    async fn get_link_details(&mut self, link: &Link) -> Result<Self::Details> {
+        self.primary.get_link_details(link).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Msg, Primary, Secondary> Transport<Link, Msg> for FanOutTransport<Primary, Secondary>
+where
+    Link: Clone,
+    Msg: PartialEq,
+    Primary: Transport<Link, Msg>,
+    Secondary: Transport<Link, Msg>,
+{
+    async fn send_message(&mut self, msg: &Msg) -> Result<()> {
+        let (primary, secondary) = join(self.primary.send_message(msg), self.secondary.send_message(msg)).await;
+        fan_in(self.send_policy, primary, secondary)
+    }
+
+    async fn recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>> {
+        if self.recv_policy == RecvPolicy::Merge {
+            let (primary, secondary) = join(self.primary.recv_messages(link), self.secondary.recv_messages(link)).await;
+            return merge(primary, secondary);
+        }
+        lookup(
+            self.recv_policy,
+            self.primary.recv_messages(link),
+            self.secondary.recv_messages(link),
+            is_hit,
+        )
+        .await
+    }
+
+    async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
+        lookup(
+            self.recv_policy,
+            self.primary.recv_message(link),
+            self.secondary.recv_message(link),
+            Result::is_ok,
+        )
+        .await
+    }
+
+    async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        let (primary, secondary) = join(self.primary.send_messages(msgs), self.secondary.send_messages(msgs)).await;
+        primary
+            .into_iter()
+            .zip(secondary)
+            .map(|(primary, secondary)| fan_in(self.send_policy, primary, secondary))
+            .collect()
+    }
+
+    async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        match self.recv_policy {
+            RecvPolicy::Failover => {
+                let mut results = self.primary.recv_messages_many(links).await;
+                let missed: Vec<usize> = (0..results.len()).filter(|&i| !is_hit(&results[i])).collect();
+                if !missed.is_empty() {
+                    let missed_links: Vec<Link> = missed.iter().map(|&i| links[i].clone()).collect();
+                    let fallback = self.secondary.recv_messages_many(&missed_links).await;
+                    for (i, result) in missed.into_iter().zip(fallback) {
+                        results[i] = result;
+                    }
+                }
+                results
+            }
+            // Both batches are needed to pick a hit per link, so the lookups are joined
+            RecvPolicy::Race => {
+                let (primary, secondary) = join(
+                    self.primary.recv_messages_many(links),
+                    self.secondary.recv_messages_many(links),
+                )
+                .await;
+                primary
+                    .into_iter()
+                    .zip(secondary)
+                    .map(|(primary, secondary)| if is_hit(&primary) { primary } else { secondary })
+                    .collect()
+            }
+            RecvPolicy::Merge => {
+                let (primary, secondary) = join(
+                    self.primary.recv_messages_many(links),
+                    self.secondary.recv_messages_many(links),
+                )
+                .await;
+                primary
+                    .into_iter()
+                    .zip(secondary)
+                    .map(|(primary, secondary)| merge(primary, secondary))
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tangle"))]
+mod tests {
+    use core::cell::RefCell;
+
+    use super::*;
+    use crate::transport::{
+        tangle::{
+            test_message,
+            TangleAddress,
+            TangleMessage,
+        },
+        BucketTransport,
+        Fault,
+        Faults,
+        FaultyTransport,
+    };
+    use iota_streams_core::{
+        ensure,
+        prelude::Rc,
+    };
+
+    type Bucket = Rc<RefCell<BucketTransport<TangleAddress, TangleMessage>>>;
+
+    #[tokio::test]
+    async fn messages_are_sent_to_both_transports() -> Result<()> {
+        let msgs: Vec<TangleMessage> = (0..3).map(|n| test_message(n, &[n])).collect();
+        let (primary, secondary) = (Bucket::default(), Bucket::default());
+        let mut transport = FanOutTransport::new(primary.clone(), secondary.clone());
+        transport.send_message(&msgs[0]).await?;
+        for result in transport.send_messages(&msgs[1..]).await {
+            result?;
+        }
+        let links: Vec<TangleAddress> = msgs.iter().map(|msg| msg.link).collect();
+        for mut replica in [primary, secondary] {
+            let results = replica.recv_messages_many(&links).await;
+            ensure!(results.iter().all(|r| r.is_ok()), "message not replicated");
+        }
+
+        // A message reaching a single transport is sent only for the `Any` policy
+        for (policy, sent) in [(SendPolicy::All, false), (SendPolicy::Any, true)] {
+            let failing = FaultyTransport::new(Bucket::default(), Faults::default(), 0).with_send_script([Fault::Fail]);
+            let mut transport = FanOutTransport::new(failing, Bucket::default()).with_send_policy(policy);
+            ensure!(
+                transport.send_message(&msgs[0]).await.is_ok() == sent,
+                "unexpected outcome with {:?} policy",
+                policy
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn secondary_backs_a_missing_primary() -> Result<()> {
+        let msg = test_message(1, &[1]);
+        let mut secondary = Bucket::default();
+        secondary.send_message(&msg).await?;
+        for policy in [RecvPolicy::Failover, RecvPolicy::Race, RecvPolicy::Merge] {
+            let mut transport = FanOutTransport::new(Bucket::default(), secondary.clone()).with_recv_policy(policy);
+            ensure!(
+                transport.recv_messages(&msg.link).await? == [msg.clone()],
+                "messages not found with {:?} policy",
+                policy
+            );
+            ensure!(
+                transport.recv_message(&msg.link).await? == msg,
+                "message not found with {:?} policy",
+                policy
+            );
+            let results = transport.recv_messages_many(&[msg.link]).await;
+            ensure!(
+                matches!(&results[..], [Ok(msgs)] if msgs[..] == [msg.clone()]),
+                "batch not found with {:?} policy",
+                policy
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn spam_found_by_one_transport_does_not_hide_the_other() -> Result<()> {
+        let msg = test_message(1, &[1]);
+        let forged = TangleMessage::new(msg.link, msg.prev_link, vec![0].into());
+        let (mut primary, mut secondary) = (Bucket::default(), Bucket::default());
+        primary.send_message(&forged).await?;
+        for msg in [&forged, &msg] {
+            secondary.send_message(msg).await?;
+        }
+
+        // A failover only asks the secondary if the primary misses, the spam shadows the message
+        let mut transport = FanOutTransport::new(primary.clone(), secondary.clone());
+        ensure!(
+            transport.recv_messages(&msg.link).await? == [forged.clone()],
+            "secondary asked"
+        );
+
+        // Messages found by both transports are merged once each
+        let mut transport = FanOutTransport::new(primary, secondary).with_recv_policy(RecvPolicy::Merge);
+        ensure!(
+            transport.recv_messages(&msg.link).await? == [forged.clone(), msg.clone()],
+            "messages not merged"
+        );
+        let results = transport.recv_messages_many(&[msg.link]).await;
+        ensure!(
+            matches!(&results[..], [Ok(msgs)] if msgs[..] == [forged.clone(), msg.clone()]),
+            "batch not merged"
+        );
+        Ok(())
+    }
+}
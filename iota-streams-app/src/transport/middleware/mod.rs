@@ -0,0 +1,175 @@
+//! Transport layers guarding and caching the calls of an inner transport, and combining transports.
+//!
+//! Each layer implements `Transport` around any transport and can be stacked with the others,
+//! e.g. `CachingTransport<CircuitBreakerTransport<RetryTransport<TimeoutTransport<Client>>>, _, _>`
+//! serves known links from the cache, gives up on a call after a timeout, retries it with backoff,
+//! and stops reaching a node that keeps failing.
+//!
+//! The settings of a layer are part of its `SendOptions` and `RecvOptions`, along with the options
+//! of the inner transport in the `inner` field.
+//!
+//! `FanOutTransport` publishes messages to two transports, e.g. a node and an archive, and reads
+//! them from either. `RecordingTransport` logs the calls of a transport and their outcomes, which
+//! `ReplayTransport` serves again to reproduce the behavior of a user.
+
+use iota_streams_core::{
+    Error,
+    Errors,
+    TransportErrorKind,
+};
+
+mod cache;
+mod circuit_breaker;
+mod fanout;
+mod record;
+mod retry;
+mod timeout;
+
+pub use cache::{
+    CacheEncoding,
+    CacheOptions,
+    CacheStats,
+    CachingTransport,
+};
+pub use circuit_breaker::{
+    CircuitBreakerOptions,
+    CircuitBreakerTransport,
+};
+pub use fanout::{
+    FanOutTransport,
+    RecvPolicy,
+    SendPolicy,
+};
+pub use record::{
+    RecordingTransport,
+    ReplayTransport,
+};
+pub use retry::{
+    RetryOptions,
+    RetryTransport,
+};
+pub use timeout::{
+    TimeoutOptions,
+    TimeoutTransport,
+};
+
+/// Kind of a transport error, see `Errors::transport_error_kind`. Errors other than `Errors` are
+/// transient.
+pub fn transport_error_kind(e: &Error) -> TransportErrorKind {
+    e.downcast_ref::<Errors>()
+        .map_or(TransportErrorKind::Transient, Errors::transport_error_kind)
+}
+
+/// Whether a transport error is worth retrying.
+///
+/// Missing or non-unique messages are answers of the transport rather than failures to reach it,
+/// so they are not transient. `Messages` polls links which are mostly empty and relies on missing
+/// messages being reported without delay.
+pub fn is_transient(e: &Error) -> bool {
+    transport_error_kind(e) == TransportErrorKind::Transient
+}
+
+#[cfg(all(test, feature = "tangle"))]
+mod tests {
+    use core::time::Duration;
+
+    use super::*;
+    use crate::transport::{
+        tangle::{
+            test_message,
+            TangleAddress,
+            TangleMessage,
+        },
+        BucketTransport,
+        Transport,
+        TransportDetails,
+        TransportOptions,
+    };
+    use iota_streams_core::{
+        async_trait,
+        ensure,
+        prelude::{
+            Box,
+            Vec,
+        },
+        Result,
+    };
+
+    // Bucket counting the calls reaching it
+    #[derive(Default)]
+    struct CountingTransport {
+        bucket: BucketTransport<TangleAddress, TangleMessage>,
+        calls: usize,
+        batches: usize,
+    }
+
+    impl TransportOptions for CountingTransport {
+        type SendOptions = ();
+        fn get_send_options(&self) {}
+        fn set_send_options(&mut self, _opt: ()) {}
+
+        type RecvOptions = ();
+        fn get_recv_options(&self) {}
+        fn set_recv_options(&mut self, _opt: ()) {}
+    }
+
+    #[async_trait(?Send)]
+    impl TransportDetails<TangleAddress> for CountingTransport {
+        type Details = ();
+        async fn get_link_details(&mut self, _link: &TangleAddress) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl Transport<TangleAddress, TangleMessage> for CountingTransport {
+        async fn send_message(&mut self, msg: &TangleMessage) -> Result<()> {
+            self.calls += 1;
+            self.bucket.send_message(msg).await
+        }
+
+        async fn recv_messages(&mut self, link: &TangleAddress) -> Result<Vec<TangleMessage>> {
+            self.calls += 1;
+            self.bucket.recv_messages(link).await
+        }
+
+        async fn recv_message(&mut self, link: &TangleAddress) -> Result<TangleMessage> {
+            self.calls += 1;
+            self.bucket.recv_message(link).await
+        }
+
+        async fn send_messages(&mut self, msgs: &[TangleMessage]) -> Vec<Result<()>> {
+            self.batches += 1;
+            self.bucket.send_messages(msgs).await
+        }
+
+        async fn recv_messages_many(&mut self, links: &[TangleAddress]) -> Vec<Result<Vec<TangleMessage>>> {
+            self.batches += 1;
+            self.bucket.recv_messages_many(links).await
+        }
+    }
+
+    #[tokio::test]
+    async fn layers_forward_batches() -> Result<()> {
+        let msgs: Vec<TangleMessage> = (0..3).map(|n| test_message(n, &[n])).collect();
+        let links: Vec<TangleAddress> = msgs.iter().map(|msg| msg.link).collect();
+        let stack = TimeoutTransport::new(CountingTransport::default(), Duration::from_secs(10));
+        let stack = RetryTransport::new(CircuitBreakerTransport::new(stack));
+        let mut stack = CachingTransport::new(stack);
+
+        for result in stack.send_messages(&msgs).await {
+            result?;
+        }
+        for result in stack.recv_messages_many(&links).await {
+            ensure!(result?.len() == 1, "message not received");
+        }
+        let counting = stack.inner().inner().inner().inner();
+        ensure!(
+            counting.batches == 2 && counting.calls == 0,
+            "{} batches and {} calls reached the transport",
+            counting.batches,
+            counting.calls
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,555 @@
+use core::{
+    convert::TryInto,
+    fmt::Display,
+    hash::Hash,
+};
+use std::{
+    fs::{
+        self,
+        File,
+    },
+    io::Write,
+    path::Path,
+};
+
+use iota_streams_core::{
+    async_trait,
+    err,
+    prelude::{
+        string::ToString,
+        Box,
+        HashMap,
+        String,
+        Vec,
+        VecDeque,
+    },
+    try_or,
+    Error,
+    Errors::{
+        self,
+        MalformedTransportLog,
+        MessageNotUnique,
+        RecordedTransportFailure,
+        TransportLogExhausted,
+    },
+    Result,
+    TransportErrorKind,
+};
+
+use super::{
+    cache::{
+        decode_bytes,
+        encode_bytes,
+        CacheEncoding,
+    },
+    transport_error_kind,
+};
+use crate::{
+    message::{
+        HasLink,
+        LinkedMessage,
+    },
+    transport::{
+        Transport,
+        TransportDetails,
+        TransportOptions,
+    },
+};
+
+// Log file header, followed by one length-prefixed record per call:
+// - the call, one byte,
+// - the link, length-prefixed,
+// - the sent message for send calls,
+// - the outcome, one byte: 0 for success, followed by the received messages prefixed with their number for receive
+//   calls, or 1 for failure, followed by the kind of the error, one byte, and its message, length-prefixed.
+const MAGIC: &[u8] = b"STREAMS-TRANSPORT-LOG-3\n";
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Call {
+    Send,
+    RecvMessage,
+    RecvMessages,
+}
+
+impl Call {
+    fn from_byte(byte: u8) -> Result<Self> {
+        match byte {
+            0 => Ok(Self::Send),
+            1 => Ok(Self::RecvMessage),
+            2 => Ok(Self::RecvMessages),
+            _ => err!(MalformedTransportLog),
+        }
+    }
+
+    fn to_byte(self) -> u8 {
+        match self {
+            Self::Send => 0,
+            Self::RecvMessage => 1,
+            Self::RecvMessages => 2,
+        }
+    }
+}
+
+fn encode_outcome<Msg: CacheEncoding>(buf: &mut Vec<u8>, outcome: core::result::Result<&[Msg], &Error>) {
+    match outcome {
+        Ok(msgs) => {
+            buf.push(0);
+            buf.extend_from_slice(&(msgs.len() as u32).to_be_bytes());
+            for msg in msgs {
+                msg.encode(buf);
+            }
+        }
+        Err(e) => {
+            buf.push(1);
+            encode_error(buf, e);
+        }
+    }
+}
+
+/// Encode the kind of a transport error along with its message, so that the replayed error is
+/// handled as the recorded one.
+fn encode_error(buf: &mut Vec<u8>, e: &Error) {
+    let kind = transport_error_kind(e);
+    let message = match e.downcast_ref::<Errors>() {
+        Some(RecordedTransportFailure(_, message)) => message.clone(),
+        _ => format!("{:#}", e),
+    };
+    buf.push(match kind {
+        TransportErrorKind::Missing => 0,
+        TransportErrorKind::Answered => 1,
+        TransportErrorKind::Transient => 2,
+    });
+    encode_bytes(buf, message.as_bytes());
+}
+
+fn decode_error(bytes: &mut &[u8]) -> Result<Errors> {
+    let kind = match take_byte(bytes)? {
+        0 => TransportErrorKind::Missing,
+        1 => TransportErrorKind::Answered,
+        2 => TransportErrorKind::Transient,
+        _ => return err!(MalformedTransportLog),
+    };
+    let message = String::from_utf8_lossy(decode_bytes(bytes)?).into_owned();
+    Ok(RecordedTransportFailure(kind, message))
+}
+
+/// Transport logging every call to the inner transport along with its outcome to a file.
+///
+/// `send_message`, `recv_message` and `recv_messages` calls are logged, batch calls are forwarded as
+/// such and logged one message or link at a time. A call fails if its record can't be written. Link
+/// details aren't logged.
+pub struct RecordingTransport<Tsp> {
+    inner: Tsp,
+    log: File,
+    records: usize,
+}
+
+impl<Tsp> RecordingTransport<Tsp> {
+    /// Wrap the inner transport, logging its calls to the file at `path`, which is overwritten.
+    pub fn new(inner: Tsp, path: impl AsRef<Path>) -> Result<Self> {
+        let mut log = File::create(path)?;
+        log.write_all(MAGIC)?;
+        Ok(Self { inner, log, records: 0 })
+    }
+
+    /// Number of calls logged so far.
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    pub fn inner(&self) -> &Tsp {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Tsp {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> Tsp {
+        self.inner
+    }
+
+    fn record<Link, Msg>(
+        &mut self,
+        call: Call,
+        link: &Link,
+        sent: Option<&Msg>,
+        outcome: core::result::Result<&[Msg], &Error>,
+    ) -> Result<()>
+    where
+        Link: HasLink,
+        Msg: CacheEncoding,
+    {
+        let mut buf = vec![call.to_byte()];
+        encode_bytes(&mut buf, &link.to_bytes());
+        if let Some(msg) = sent {
+            msg.encode(&mut buf);
+        }
+        encode_outcome(&mut buf, outcome);
+        let mut record = Vec::with_capacity(4 + buf.len());
+        encode_bytes(&mut record, &buf);
+        // A single write per record, so that a crash leaves at most the last record incomplete
+        self.log.write_all(&record)?;
+        self.records += 1;
+        Ok(())
+    }
+}
+
+impl<Tsp: TransportOptions> TransportOptions for RecordingTransport<Tsp> {
+    type SendOptions = <Tsp as TransportOptions>::SendOptions;
+    fn get_send_options(&self) -> Self::SendOptions {
+        self.inner.get_send_options()
+    }
+    fn set_send_options(&mut self, opt: Self::SendOptions) {
+        self.inner.set_send_options(opt)
+    }
+
+    type RecvOptions = <Tsp as TransportOptions>::RecvOptions;
+    fn get_recv_options(&self) -> Self::RecvOptions {
+        self.inner.get_recv_options()
+    }
+    fn set_recv_options(&mut self, opt: Self::RecvOptions) {
+        self.inner.set_recv_options(opt)
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Tsp: TransportDetails<Link>> TransportDetails<Link> for RecordingTransport<Tsp> {
+    type Details = <Tsp as TransportDetails<Link>>::Details;
+    async fn get_link_details(&mut self, link: &Link) -> Result<Self::Details> {
+        self.inner.get_link_details(link).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Msg, Tsp> Transport<Link, Msg> for RecordingTransport<Tsp>
+where
+    Link: HasLink,
+    Msg: LinkedMessage<Link> + CacheEncoding,
+    Tsp: Transport<Link, Msg>,
+{
+    async fn send_message(&mut self, msg: &Msg) -> Result<()> {
+        let result = self.inner.send_message(msg).await;
+        let outcome = result.as_ref().map(|_| &[][..]);
+        self.record(Call::Send, msg.link(), Some(msg), outcome)?;
+        result
+    }
+
+    async fn recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>> {
+        let result = self.inner.recv_messages(link).await;
+        self.record::<_, Msg>(Call::RecvMessages, link, None, result.as_ref().map(Vec::as_slice))?;
+        result
+    }
+
+    async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
+        let result = self.inner.recv_message(link).await;
+        let outcome = result.as_ref().map(core::slice::from_ref);
+        self.record::<_, Msg>(Call::RecvMessage, link, None, outcome)?;
+        result
+    }
+
+    /// Forward the batch, logging each message as a call of its own so that it replays either way.
+    async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        let results = self.inner.send_messages(msgs).await;
+        msgs.iter()
+            .zip(results)
+            .map(|(msg, result)| {
+                let outcome = result.as_ref().map(|_| &[][..]);
+                self.record(Call::Send, msg.link(), Some(msg), outcome)?;
+                result
+            })
+            .collect()
+    }
+
+    /// Forward the batch, logging each link as a call of its own so that it replays either way.
+    async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        let results = self.inner.recv_messages_many(links).await;
+        links
+            .iter()
+            .zip(results)
+            .map(|(link, result)| {
+                self.record::<_, Msg>(Call::RecvMessages, link, None, result.as_ref().map(Vec::as_slice))?;
+                result
+            })
+            .collect()
+    }
+}
+
+enum Outcome<Msg> {
+    Succeeded(Vec<Msg>),
+    Failed(Errors),
+}
+
+/// Transport serving the outcomes of the calls logged by a `RecordingTransport`.
+///
+/// Each call is answered with the outcome of the next logged call of the same kind with the same
+/// link, so the answers for a link change over time as they did when recording. Recorded errors are
+/// replayed as `RecordedTransportFailure` with the kind and the message of the original error. Calls
+/// beyond the logged ones fail with `TransportLogExhausted`.
+pub struct ReplayTransport<Link, Msg> {
+    calls: HashMap<(Call, Link), VecDeque<Outcome<Msg>>>,
+    remaining: usize,
+}
+
+impl<Link, Msg> ReplayTransport<Link, Msg>
+where
+    Link: HasLink + Eq + Hash,
+    Msg: CacheEncoding,
+{
+    /// Load the log written by a `RecordingTransport` at `path`.
+    ///
+    /// An incomplete last record, left by an interrupted recording, is ignored.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    /// Load a log written by a `RecordingTransport`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut bytes = match bytes.strip_prefix(MAGIC) {
+            Some(bytes) => bytes,
+            None => return err!(MalformedTransportLog),
+        };
+        let mut replay = Self {
+            calls: HashMap::new(),
+            remaining: 0,
+        };
+        while !bytes.is_empty() {
+            let mut record = match decode_bytes(&mut bytes) {
+                Ok(record) => record,
+                // Only the last record can be cut short, by an interrupted recording
+                Err(_) => break,
+            };
+            let (call, link, outcome) = Self::decode_record(&mut record).or_else(|_| err!(MalformedTransportLog))?;
+            try_or!(record.is_empty(), MalformedTransportLog)?;
+            replay.calls.entry((call, link)).or_default().push_back(outcome);
+            replay.remaining += 1;
+        }
+        Ok(replay)
+    }
+
+    fn decode_record(bytes: &mut &[u8]) -> Result<(Call, Link, Outcome<Msg>)> {
+        let call = Call::from_byte(take_byte(bytes)?)?;
+        let link = Link::try_from_bytes(decode_bytes(bytes)?)?;
+        if call == Call::Send {
+            Msg::decode(bytes)?;
+        }
+        let outcome = match take_byte(bytes)? {
+            0 => {
+                let count = u32::from_be_bytes(take(bytes, 4)?.try_into()?);
+                let msgs = (0..count).map(|_| Msg::decode(bytes)).collect::<Result<_>>()?;
+                Outcome::Succeeded(msgs)
+            }
+            1 => Outcome::Failed(decode_error(bytes)?),
+            _ => return err!(MalformedTransportLog),
+        };
+        Ok((call, link, outcome))
+    }
+
+    /// Number of logged calls not replayed yet.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    fn replay(&mut self, call: Call, link: &Link) -> Result<Vec<Msg>>
+    where
+        Link: Clone + Display,
+    {
+        match self.calls.get_mut(&(call, link.clone())).and_then(VecDeque::pop_front) {
+            Some(Outcome::Succeeded(msgs)) => {
+                self.remaining -= 1;
+                Ok(msgs)
+            }
+            Some(Outcome::Failed(e)) => {
+                self.remaining -= 1;
+                err!(e)
+            }
+            None => err!(TransportLogExhausted(link.to_string())),
+        }
+    }
+}
+
+fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    try_or!(bytes.len() >= n, MalformedTransportLog)?;
+    let (head, rest) = bytes.split_at(n);
+    *bytes = rest;
+    Ok(head)
+}
+
+fn take_byte(bytes: &mut &[u8]) -> Result<u8> {
+    Ok(take(bytes, 1)?[0])
+}
+
+impl<Link, Msg> TransportOptions for ReplayTransport<Link, Msg> {
+    type SendOptions = ();
+    fn get_send_options(&self) {}
+    fn set_send_options(&mut self, _opt: ()) {}
+
+    type RecvOptions = ();
+    fn get_recv_options(&self) {}
+    fn set_recv_options(&mut self, _opt: ()) {}
+}
+
+#[async_trait(?Send)]
+impl<Link, Msg> TransportDetails<Link> for ReplayTransport<Link, Msg> {
+    type Details = ();
+    async fn get_link_details(&mut self, _link: &Link) -> Result<Self::Details> {
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Msg> Transport<Link, Msg> for ReplayTransport<Link, Msg>
+where
+    Link: HasLink + Eq + Hash + Clone + Display,
+    Msg: LinkedMessage<Link> + CacheEncoding,
+{
+    async fn send_message(&mut self, msg: &Msg) -> Result<()> {
+        self.replay(Call::Send, msg.link()).map(|_| ())
+    }
+
+    async fn recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>> {
+        self.replay(Call::RecvMessages, link)
+    }
+
+    async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
+        let mut msgs = self.replay(Call::RecvMessage, link)?;
+        match msgs.pop() {
+            Some(msg) if msgs.is_empty() => Ok(msg),
+            _ => err!(MessageNotUnique(link.to_string())),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "tangle"))]
+mod tests {
+    use core::cell::RefCell;
+
+    use super::*;
+    use crate::transport::{
+        tangle::{
+            test_message,
+            TangleAddress,
+            TangleMessage,
+        },
+        BucketTransport,
+        Fault,
+        Faults,
+        FaultyTransport,
+    };
+    use iota_streams_core::{
+        ensure,
+        prelude::Rc,
+    };
+
+    type Bucket = Rc<RefCell<BucketTransport<TangleAddress, TangleMessage>>>;
+    type Replay = ReplayTransport<TangleAddress, TangleMessage>;
+
+    #[tokio::test]
+    async fn replay_answers_calls_as_recorded() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("transport.log");
+        let msgs: Vec<TangleMessage> = (0..3).map(|n| test_message(n, &[n])).collect();
+        let mut recording = RecordingTransport::new(Bucket::default(), &path)?;
+
+        // The answers for a link change over time
+        ensure!(
+            recording.recv_messages(&msgs[0].link).await.is_err(),
+            "missing message found"
+        );
+        recording.send_message(&msgs[0]).await?;
+        let found = recording.recv_messages(&msgs[0].link).await?;
+        // Batches are logged one message or link at a time
+        for result in recording.send_messages(&msgs[1..]).await {
+            result?;
+        }
+        let links: Vec<TangleAddress> = msgs.iter().map(|msg| msg.link).collect();
+        let fetched: Vec<Vec<TangleMessage>> = recording
+            .recv_messages_many(&links)
+            .await
+            .into_iter()
+            .collect::<Result<_>>()?;
+        ensure!(recording.records() == 8, "{} calls logged", recording.records());
+
+        let mut replay = Replay::open(&path)?;
+        ensure!(
+            replay.recv_messages(&msgs[0].link).await.is_err(),
+            "missing message replayed"
+        );
+        replay.send_message(&msgs[0]).await?;
+        ensure!(
+            replay.recv_messages(&msgs[0].link).await? == found,
+            "found messages not replayed"
+        );
+        for result in replay.send_messages(&msgs[1..]).await {
+            result?;
+        }
+        for (msgs, result) in fetched.iter().zip(replay.recv_messages_many(&links).await) {
+            ensure!(result? == *msgs, "fetched messages not replayed");
+        }
+        ensure!(replay.remaining() == 0, "{} calls not replayed", replay.remaining());
+        let e = replay.recv_messages(&msgs[0].link).await.unwrap_err();
+        ensure!(
+            matches!(e.downcast_ref::<Errors>(), Some(TransportLogExhausted(_))),
+            "unexpected error: {}",
+            e
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn errors_are_replayed_with_their_kind() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("transport.log");
+        let link = test_message(1, &[1]).link;
+        let faulty = FaultyTransport::new(Bucket::default(), Faults::default(), 0).with_recv_script([Fault::Fail]);
+        let mut recording = RecordingTransport::new(faulty, &path)?;
+        for _ in 0..2 {
+            ensure!(recording.recv_message(&link).await.is_err(), "missing message found");
+        }
+
+        let mut replay = Replay::open(&path)?;
+        let e = replay.recv_message(&link).await.unwrap_err();
+        ensure!(
+            transport_error_kind(&e) == TransportErrorKind::Transient,
+            "injected failure replayed as {}",
+            e
+        );
+        let e = replay.recv_message(&link).await.unwrap_err();
+        ensure!(
+            matches!(
+                e.downcast_ref::<Errors>(),
+                Some(RecordedTransportFailure(TransportErrorKind::Missing, message)) if message.contains("Bucket")
+            ),
+            "missing message replayed as {}",
+            e
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn only_the_last_record_can_be_cut_short() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("transport.log");
+        let msg = test_message(1, &[1]);
+        let mut recording = RecordingTransport::new(Bucket::default(), &path)?;
+        recording.send_message(&msg).await?;
+        recording.recv_message(&msg.link).await?;
+        let log = fs::read(&path)?;
+
+        let replay = Replay::from_bytes(&log[..log.len() - 1])?;
+        ensure!(replay.remaining() == 1, "complete record not replayed");
+
+        // The call of the first record follows the header and the length of the record
+        let mut corrupted = log.clone();
+        corrupted[MAGIC.len() + 4] = 0xff;
+        ensure!(
+            matches!(
+                Replay::from_bytes(&corrupted).map_err(|e| e.downcast::<Errors>()),
+                Err(Ok(MalformedTransportLog))
+            ),
+            "corrupted record replayed"
+        );
+        ensure!(Replay::from_bytes(&log[1..]).is_err(), "log without header replayed");
+        Ok(())
+    }
+}
@@ -0,0 +1,324 @@
+use core::{
+    mem,
+    time::Duration,
+};
+
+use futures_timer::Delay;
+
+use iota_streams_core::{
+    async_trait,
+    prelude::{
+        Box,
+        Vec,
+    },
+    Error,
+    Result,
+};
+
+use super::is_transient;
+use crate::transport::{
+    Transport,
+    TransportDetails,
+    TransportOptions,
+};
+
+/// Retry policy of the calls of a `RetryTransport`.
+///
+/// A call failing with a transient error is retried up to `max_retries` times. The first retry
+/// waits for `initial_backoff`, each further retry waits `multiplier` times longer, up to `max_backoff`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RetryOptions<Opt = ()> {
+    pub max_retries: usize,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: u32,
+    pub inner: Opt,
+}
+
+impl<Opt: Default> Default for RetryOptions<Opt> {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2,
+            inner: Opt::default(),
+        }
+    }
+}
+
+impl<Opt> RetryOptions<Opt> {
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_backoff(mut self, initial_backoff: Duration, max_backoff: Duration, multiplier: u32) -> Self {
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn with_inner<Inner>(self, inner: Inner) -> RetryOptions<Inner> {
+        RetryOptions {
+            max_retries: self.max_retries,
+            initial_backoff: self.initial_backoff,
+            max_backoff: self.max_backoff,
+            multiplier: self.multiplier,
+            inner,
+        }
+    }
+
+    /// Split the options of the layer from the options of the inner transport.
+    pub fn split(self) -> (RetryOptions, Opt) {
+        let inner = self.inner;
+        (
+            RetryOptions {
+                max_retries: self.max_retries,
+                initial_backoff: self.initial_backoff,
+                max_backoff: self.max_backoff,
+                multiplier: self.multiplier,
+                inner: (),
+            },
+            inner,
+        )
+    }
+
+    fn next_backoff(&self, backoff: Duration) -> Duration {
+        backoff
+            .checked_mul(self.multiplier)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
+
+/// Transport retrying the failed calls of the inner transport with exponential backoff.
+///
+/// Only errors deemed transient by the classifier are retried, `is_transient` by default.
+#[derive(Clone, Debug)]
+pub struct RetryTransport<Tsp> {
+    inner: Tsp,
+    send_opt: RetryOptions,
+    recv_opt: RetryOptions,
+    is_transient: fn(&Error) -> bool,
+}
+
+impl<Tsp> RetryTransport<Tsp> {
+    /// Wrap the inner transport, with the default policy for send and receive calls.
+    pub fn new(inner: Tsp) -> Self {
+        Self {
+            inner,
+            send_opt: RetryOptions::default(),
+            recv_opt: RetryOptions::default(),
+            is_transient,
+        }
+    }
+
+    pub fn with_send_options(mut self, opt: RetryOptions) -> Self {
+        self.send_opt = opt;
+        self
+    }
+
+    pub fn with_recv_options(mut self, opt: RetryOptions) -> Self {
+        self.recv_opt = opt;
+        self
+    }
+
+    /// Classify the errors worth retrying.
+    pub fn with_classifier(mut self, is_transient: fn(&Error) -> bool) -> Self {
+        self.is_transient = is_transient;
+        self
+    }
+
+    pub fn inner(&self) -> &Tsp {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Tsp {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> Tsp {
+        self.inner
+    }
+}
+
+/// Evaluate `$call` until it succeeds, fails with a permanent error or runs out of retries.
+///
+/// With `$first`, the outcome of a first call made otherwise, e.g. as part of a batch.
+macro_rules! retry {
+    ($self:ident, $opt:ident, $call:expr) => {
+        retry!($self, $opt, $call, $call.await)
+    };
+    ($self:ident, $opt:ident, $call:expr, $first:expr) => {{
+        let opt = $self.$opt;
+        let mut backoff = opt.initial_backoff;
+        let mut retries = 0;
+        let mut result = $first;
+        loop {
+            match result {
+                Err(e) if retries < opt.max_retries && ($self.is_transient)(&e) => {
+                    Delay::new(backoff).await;
+                    backoff = opt.next_backoff(backoff);
+                    retries += 1;
+                    result = $call.await;
+                }
+                result => break result,
+            }
+        }
+    }};
+}
+
+impl<Tsp: TransportOptions> TransportOptions for RetryTransport<Tsp> {
+    type SendOptions = RetryOptions<<Tsp as TransportOptions>::SendOptions>;
+    fn get_send_options(&self) -> Self::SendOptions {
+        self.send_opt.with_inner(self.inner.get_send_options())
+    }
+    fn set_send_options(&mut self, opt: Self::SendOptions) {
+        let (send_opt, inner) = opt.split();
+        self.send_opt = send_opt;
+        self.inner.set_send_options(inner)
+    }
+
+    type RecvOptions = RetryOptions<<Tsp as TransportOptions>::RecvOptions>;
+    fn get_recv_options(&self) -> Self::RecvOptions {
+        self.recv_opt.with_inner(self.inner.get_recv_options())
+    }
+    fn set_recv_options(&mut self, opt: Self::RecvOptions) {
+        let (recv_opt, inner) = opt.split();
+        self.recv_opt = recv_opt;
+        self.inner.set_recv_options(inner)
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Tsp: TransportDetails<Link>> TransportDetails<Link> for RetryTransport<Tsp> {
+    type Details = <Tsp as TransportDetails<Link>>::Details;
+    async fn get_link_details(&mut self, link: &Link) -> Result<Self::Details> {
+        self.inner.get_link_details(link).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Msg, Tsp: Transport<Link, Msg>> Transport<Link, Msg> for RetryTransport<Tsp> {
+    async fn send_message(&mut self, msg: &Msg) -> Result<()> {
+        retry!(self, send_opt, self.inner.send_message(msg))
+    }
+
+    async fn recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>> {
+        retry!(self, recv_opt, self.inner.recv_messages(link))
+    }
+
+    async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
+        retry!(self, recv_opt, self.inner.recv_message(link))
+    }
+
+    /// Send the messages in one batch, then retry the failed ones one by one.
+    async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        let mut results = self.inner.send_messages(msgs).await;
+        for (msg, result) in msgs.iter().zip(results.iter_mut()) {
+            let first = mem::replace(result, Ok(()));
+            *result = retry!(self, send_opt, self.inner.send_message(msg), first);
+        }
+        results
+    }
+
+    /// Look the links up in one batch, then retry the failed ones one by one.
+    async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        let mut results = self.inner.recv_messages_many(links).await;
+        for (link, result) in links.iter().zip(results.iter_mut()) {
+            let first = mem::replace(result, Ok(Vec::new()));
+            *result = retry!(self, recv_opt, self.inner.recv_messages(link), first);
+        }
+        results
+    }
+}
+
+#[cfg(all(test, feature = "tangle"))]
+mod tests {
+    use super::*;
+    use crate::transport::{
+        tangle::{
+            test_message,
+            TangleAddress,
+            TangleMessage,
+        },
+        BucketTransport,
+        Fault,
+        Faults,
+        FaultyTransport,
+    };
+    use iota_streams_core::{
+        ensure,
+        Errors,
+    };
+
+    type Faulty = FaultyTransport<BucketTransport<TangleAddress, TangleMessage>, TangleMessage>;
+
+    fn retrying(faulty: Faulty) -> RetryTransport<Faulty> {
+        let opt = RetryOptions::default()
+            .with_max_retries(2)
+            .with_backoff(Duration::ZERO, Duration::ZERO, 2);
+        RetryTransport::new(faulty)
+            .with_send_options(opt)
+            .with_recv_options(opt)
+    }
+
+    #[tokio::test]
+    async fn transient_failures_are_retried() -> Result<()> {
+        let msg = test_message(1, &[1]);
+        let mut bucket = BucketTransport::new();
+        bucket.send_message(&msg).await?;
+        let faulty = FaultyTransport::new(bucket, Faults::default(), 0).with_recv_script([
+            Fault::Fail,
+            Fault::Fail,
+            Fault::None,
+            Fault::Fail,
+            Fault::Fail,
+            Fault::Fail,
+        ]);
+        let mut transport = retrying(faulty);
+
+        ensure!(transport.recv_message(&msg.link).await? == msg, "message not received");
+        ensure!(transport.inner().injected() == 2, "failures not retried");
+        let e = transport.recv_message(&msg.link).await.unwrap_err();
+        ensure!(
+            matches!(e.downcast_ref::<Errors>(), Some(Errors::InjectedTransportFailure(_))),
+            "unexpected error: {}",
+            e
+        );
+        ensure!(transport.inner().injected() == 5, "call retried beyond the max retries");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn missing_messages_are_not_retried() -> Result<()> {
+        // A retry would meet the injected failure
+        let faulty = FaultyTransport::new(BucketTransport::new(), Faults::default(), 0)
+            .with_recv_script([Fault::None, Fault::Fail]);
+        let mut transport = retrying(faulty);
+        let link = test_message(1, &[]).link;
+        ensure!(transport.recv_message(&link).await.is_err(), "missing message found");
+        ensure!(transport.inner().injected() == 0, "missing message retried");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn failed_messages_of_a_batch_are_retried() -> Result<()> {
+        let msgs = [test_message(1, &[1]), test_message(2, &[2])];
+        let faulty = FaultyTransport::new(BucketTransport::new(), Faults::default(), 0)
+            .with_send_script([Fault::None, Fault::Fail]);
+        let mut transport = retrying(faulty);
+        for result in transport.send_messages(&msgs).await {
+            result?;
+        }
+        for msg in &msgs {
+            ensure!(
+                transport.inner_mut().inner_mut().recv_message(&msg.link).await? == *msg,
+                "message not sent"
+            );
+        }
+        ensure!(transport.inner().injected() == 1, "no failure injected");
+        Ok(())
+    }
+}
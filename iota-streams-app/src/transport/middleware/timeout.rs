@@ -0,0 +1,285 @@
+use core::{
+    fmt::Display,
+    future::Future,
+    time::Duration,
+};
+
+use futures::future::{
+    self,
+    Either,
+};
+use futures_timer::Delay;
+
+use iota_streams_core::{
+    async_trait,
+    err,
+    prelude::{
+        string::{
+            String,
+            ToString,
+        },
+        Box,
+        Vec,
+    },
+    Errors::TransportTimeout,
+    Result,
+};
+
+use crate::{
+    message::LinkedMessage,
+    transport::{
+        Transport,
+        TransportDetails,
+        TransportOptions,
+    },
+};
+
+/// Timeout of the calls of a `TimeoutTransport`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TimeoutOptions<Opt = ()> {
+    /// Time after which a call fails, no timeout if `None`.
+    pub timeout: Option<Duration>,
+    pub inner: Opt,
+}
+
+impl<Opt> TimeoutOptions<Opt> {
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_inner<Inner>(self, inner: Inner) -> TimeoutOptions<Inner> {
+        TimeoutOptions {
+            timeout: self.timeout,
+            inner,
+        }
+    }
+
+    /// Split the options of the layer from the options of the inner transport.
+    pub fn split(self) -> (TimeoutOptions, Opt) {
+        (
+            TimeoutOptions {
+                timeout: self.timeout,
+                inner: (),
+            },
+            self.inner,
+        )
+    }
+}
+
+/// Transport failing the calls of the inner transport which don't complete in time.
+///
+/// The timed out call is dropped, which cancels it for any transport driven by its future.
+#[derive(Clone, Debug)]
+pub struct TimeoutTransport<Tsp> {
+    inner: Tsp,
+    send_opt: TimeoutOptions,
+    recv_opt: TimeoutOptions,
+}
+
+impl<Tsp> TimeoutTransport<Tsp> {
+    /// Wrap the inner transport, with the same timeout for send and receive calls.
+    pub fn new(inner: Tsp, timeout: Duration) -> Self {
+        let opt = TimeoutOptions::default().with_timeout(timeout);
+        Self {
+            inner,
+            send_opt: opt,
+            recv_opt: opt,
+        }
+    }
+
+    pub fn with_send_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.send_opt.timeout = timeout;
+        self
+    }
+
+    pub fn with_recv_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.recv_opt.timeout = timeout;
+        self
+    }
+
+    pub fn inner(&self) -> &Tsp {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut Tsp {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> Tsp {
+        self.inner
+    }
+}
+
+async fn timed<T>(timeout: Option<Duration>, link: &impl Display, call: impl Future<Output = Result<T>>) -> Result<T> {
+    match timeout {
+        None => call.await,
+        Some(timeout) => match future::select(Box::pin(call), Delay::new(timeout)).await {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => err!(TransportTimeout(link.to_string(), timeout.as_millis())),
+        },
+    }
+}
+
+/// Time a batch call as a whole, every item of the batch fails if it doesn't complete in time.
+async fn timed_batch<T>(
+    timeout: Option<Duration>,
+    links: Vec<String>,
+    call: impl Future<Output = Vec<Result<T>>>,
+) -> Vec<Result<T>> {
+    match timeout {
+        None => call.await,
+        Some(timeout) => match future::select(Box::pin(call), Delay::new(timeout)).await {
+            Either::Left((results, _)) => results,
+            Either::Right(_) => links
+                .into_iter()
+                .map(|link| err!(TransportTimeout(link, timeout.as_millis())))
+                .collect(),
+        },
+    }
+}
+
+impl<Tsp: TransportOptions> TransportOptions for TimeoutTransport<Tsp> {
+    type SendOptions = TimeoutOptions<<Tsp as TransportOptions>::SendOptions>;
+    fn get_send_options(&self) -> Self::SendOptions {
+        self.send_opt.with_inner(self.inner.get_send_options())
+    }
+    fn set_send_options(&mut self, opt: Self::SendOptions) {
+        let (send_opt, inner) = opt.split();
+        self.send_opt = send_opt;
+        self.inner.set_send_options(inner)
+    }
+
+    type RecvOptions = TimeoutOptions<<Tsp as TransportOptions>::RecvOptions>;
+    fn get_recv_options(&self) -> Self::RecvOptions {
+        self.recv_opt.with_inner(self.inner.get_recv_options())
+    }
+    fn set_recv_options(&mut self, opt: Self::RecvOptions) {
+        let (recv_opt, inner) = opt.split();
+        self.recv_opt = recv_opt;
+        self.inner.set_recv_options(inner)
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Tsp: TransportDetails<Link>> TransportDetails<Link> for TimeoutTransport<Tsp> {
+    type Details = <Tsp as TransportDetails<Link>>::Details;
+    async fn get_link_details(&mut self, link: &Link) -> Result<Self::Details> {
+        self.inner.get_link_details(link).await
+    }
+}
+
+#[async_trait(?Send)]
+impl<Link, Msg, Tsp> Transport<Link, Msg> for TimeoutTransport<Tsp>
+where
+    Link: Display,
+    Msg: LinkedMessage<Link>,
+    Tsp: Transport<Link, Msg>,
+{
+    async fn send_message(&mut self, msg: &Msg) -> Result<()> {
+        timed(self.send_opt.timeout, msg.link(), self.inner.send_message(msg)).await
+    }
+
+    async fn recv_messages(&mut self, link: &Link) -> Result<Vec<Msg>> {
+        timed(self.recv_opt.timeout, link, self.inner.recv_messages(link)).await
+    }
+
+    async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
+        timed(self.recv_opt.timeout, link, self.inner.recv_message(link)).await
+    }
+
+    async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        let links = msgs.iter().map(|msg| msg.link().to_string()).collect();
+        timed_batch(self.send_opt.timeout, links, self.inner.send_messages(msgs)).await
+    }
+
+    async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        let names = links.iter().map(ToString::to_string).collect();
+        timed_batch(self.recv_opt.timeout, names, self.inner.recv_messages_many(links)).await
+    }
+}
+
+#[cfg(all(test, feature = "tangle"))]
+mod tests {
+    use super::*;
+    use crate::transport::{
+        tangle::{
+            test_message,
+            TangleAddress,
+            TangleMessage,
+        },
+        BucketTransport,
+    };
+    use iota_streams_core::{
+        ensure,
+        Errors,
+    };
+
+    // Transport never completing its calls, like a stuck node
+    struct StuckTransport;
+
+    impl TransportOptions for StuckTransport {
+        type SendOptions = ();
+        fn get_send_options(&self) {}
+        fn set_send_options(&mut self, _opt: ()) {}
+
+        type RecvOptions = ();
+        fn get_recv_options(&self) {}
+        fn set_recv_options(&mut self, _opt: ()) {}
+    }
+
+    #[async_trait(?Send)]
+    impl TransportDetails<TangleAddress> for StuckTransport {
+        type Details = ();
+        async fn get_link_details(&mut self, _link: &TangleAddress) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl Transport<TangleAddress, TangleMessage> for StuckTransport {
+        async fn send_message(&mut self, _msg: &TangleMessage) -> Result<()> {
+            future::pending().await
+        }
+
+        async fn recv_messages(&mut self, _link: &TangleAddress) -> Result<Vec<TangleMessage>> {
+            future::pending().await
+        }
+
+        async fn recv_message(&mut self, _link: &TangleAddress) -> Result<TangleMessage> {
+            future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn stuck_calls_time_out() -> Result<()> {
+        let msg = test_message(1, &[1]);
+        let mut transport = TimeoutTransport::new(StuckTransport, Duration::from_millis(10));
+        let e = transport.recv_message(&msg.link).await.unwrap_err();
+        ensure!(
+            matches!(e.downcast_ref::<Errors>(), Some(Errors::TransportTimeout(_, 10))),
+            "call not timed out: {}",
+            e
+        );
+        ensure!(transport.send_message(&msg).await.is_err(), "send call not timed out");
+
+        // Every item of a batch fails with the batch
+        let results = transport
+            .recv_messages_many(&[msg.link, test_message(2, &[]).link])
+            .await;
+        ensure!(
+            results.len() == 2 && results.iter().all(|r| r.is_err()),
+            "batch not timed out"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn calls_completing_in_time_go_through() -> Result<()> {
+        let msg = test_message(1, &[1]);
+        let mut transport = TimeoutTransport::new(BucketTransport::new(), Duration::from_secs(10));
+        transport.send_message(&msg).await?;
+        ensure!(transport.recv_message(&msg.link).await? == msg, "message not received");
+        Ok(())
+    }
+}
@@ -39,6 +39,29 @@ pub trait Transport<Link, Msg>: TransportOptions + TransportDetails<Link> {
 
     /// Receive a message with default options.
     async fn recv_message(&mut self, link: &Link) -> Result<Msg>;
+
+    /// Send messages with default options, one result per message in order.
+    ///
+    /// A failure doesn't stop the other messages from being sent, and transports may send them
+    /// concurrently. Transports able to send several messages in one go should override it.
+    async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        let mut results = Vec::with_capacity(msgs.len());
+        for msg in msgs {
+            results.push(self.send_message(msg).await);
+        }
+        results
+    }
+
+    /// Receive the messages of several links with default options, one result per link in order.
+    ///
+    /// Transports able to look up several links in one go should override it.
+    async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        let mut results = Vec::with_capacity(links.len());
+        for link in links {
+            results.push(self.recv_messages(link).await);
+        }
+        results
+    }
 }
 
 impl<Tsp: TransportOptions> TransportOptions for Rc<RefCell<Tsp>> {
@@ -83,6 +106,16 @@ impl<Link, Msg, Tsp: Transport<Link, Msg>> Transport<Link, Msg> for Rc<RefCell<T
     async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
         self.borrow_mut().recv_message(link).await
     }
+
+    // Send messages.
+    async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+        self.borrow_mut().send_messages(msgs).await
+    }
+
+    // Receive the messages of several links with default options.
+    async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+        self.borrow_mut().recv_messages_many(links).await
+    }
 }
 
 #[cfg(any(feature = "sync-spin", feature = "sync-parking-lot"))]
@@ -145,12 +178,36 @@ mod sync {
         async fn recv_message(&mut self, link: &Link) -> Result<Msg> {
             self.lock().recv_message(link).await
         }
+
+        // Send messages.
+        async fn send_messages(&mut self, msgs: &[Msg]) -> Vec<Result<()>> {
+            self.lock().send_messages(msgs).await
+        }
+
+        // Receive the messages of several links with default options.
+        async fn recv_messages_many(&mut self, links: &[Link]) -> Vec<Result<Vec<Msg>>> {
+            self.lock().recv_messages_many(links).await
+        }
     }
 }
 
 mod bucket;
 pub use bucket::BucketTransport;
+
+mod dynamic;
+pub use dynamic::DynTransport;
+
+mod faulty;
+pub use faulty::{
+    Corrupt,
+    Fault,
+    Faults,
+    FaultyTransport,
+};
 use iota_streams_core::try_or;
 
+#[cfg(feature = "middleware")]
+pub mod middleware;
+
 #[cfg(feature = "tangle")]
 pub mod tangle;
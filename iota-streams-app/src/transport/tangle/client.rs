@@ -27,6 +27,7 @@ use iota_streams_core::{
     prelude::Vec,
     try_or,
     wrapped_err,
+    Errors,
     Errors::*,
     Result,
     WrappedError,
@@ -80,12 +81,8 @@ fn handle_client_result<T>(result: iota_client::Result<T>) -> Result<T> {
 /// Checked bundles are returned by `client.get_message().index`.
 pub fn msg_from_tangle_message(message: &Message, link: &TangleAddress) -> Result<TangleMessage> {
     if let Some(Payload::Indexation(i)) = message.payload().as_ref() {
-        let mut bytes = Vec::<u8>::new();
-        for b in i.data() {
-            bytes.push(*b);
-        }
-
-        let binary = BinaryMessage::new(*link, TangleAddress::default(), bytes.into());
+        // Indexation payloads only lend their data, copy it once into the shared body buffer.
+        let binary = BinaryMessage::new(*link, TangleAddress::default(), i.data().to_vec().into());
         Ok(binary)
     } else {
         err!(BadMessagePayload)
@@ -114,7 +111,7 @@ async fn get_messages(client: &iota_client::Client, link: &TangleAddress) -> Res
 pub async fn async_send_message_with_options(client: &iota_client::Client, msg: &TangleMessage) -> Result<()> {
     let hash = msg.link.to_msg_index();
 
-    // TODO: Get rid of copy caused by to_owned
+    // The client takes ownership of the data, so the body is copied once here.
     client
         .message()
         .with_index(hash)
@@ -131,7 +128,9 @@ pub async fn async_recv_messages(client: &iota_client::Client, link: &TangleAddr
             .iter()
             .filter_map(|b| msg_from_tangle_message(b, link).ok()) // Ignore errors
             .collect()),
-        Err(_) => Ok(Vec::new()), // Just ignore the error?
+        // An unknown index just has no messages, other failures are left to the caller to retry
+        Err(e) if matches!(e.downcast_ref::<Errors>(), Some(IndexNotFound)) => Ok(Vec::new()),
+        Err(e) => Err(e),
     }
 }
 
@@ -255,6 +254,20 @@ impl Transport<TangleAddress, TangleMessage> for Client {
             err!(MessageLinkNotFoundInTangle(link.to_string()))
         }
     }
+
+    /// Send Streams messages over the Tangle concurrently.
+    async fn send_messages(&mut self, msgs: &[TangleMessage]) -> Vec<Result<()>> {
+        join_all(
+            msgs.iter()
+                .map(|msg| async_send_message_with_options(&self.client, msg)),
+        )
+        .await
+    }
+
+    /// Receive the messages of several links concurrently.
+    async fn recv_messages_many(&mut self, links: &[TangleAddress]) -> Vec<Result<Vec<TangleMessage>>> {
+        join_all(links.iter().map(|link| async_recv_messages(&self.client, link))).await
+    }
 }
 
 #[async_trait(?Send)]
@@ -0,0 +1,157 @@
+use futures::future::join_all;
+use hyper::{
+    body::{
+        self,
+        Bytes,
+    },
+    client::HttpConnector,
+    Body,
+    Method,
+    Request,
+    StatusCode,
+    Uri,
+};
+
+use iota_streams_core::{
+    async_trait,
+    err,
+    prelude::{
+        string::ToString,
+        Box,
+        String,
+        Vec,
+    },
+    try_or,
+    wrapped_err,
+    Errors::{
+        HttpOperationFailure,
+        HttpStatusFailure,
+        MessageLinkNotFoundOnServer,
+        MessageNotUnique,
+    },
+    Result,
+    WrappedError,
+};
+
+use super::{
+    decode_messages,
+    MESSAGES_PATH,
+};
+use crate::{
+    message::BinaryMessage,
+    transport::{
+        tangle::{
+            TangleAddress,
+            TangleMessage,
+        },
+        Transport,
+        TransportDetails,
+        TransportOptions,
+    },
+};
+
+fn handle_http_result<T, E: core::fmt::Debug>(result: core::result::Result<T, E>) -> Result<T> {
+    result.map_err(|err| wrapped_err!(HttpOperationFailure, WrappedError(err)))
+}
+
+/// Tangle transport client of a server of the HTTP message protocol.
+#[derive(Clone, Debug)]
+pub struct HttpClient {
+    url: String,
+    client: hyper::Client<HttpConnector>,
+}
+
+impl HttpClient {
+    /// Create a client of the server at `url`, e.g. `http://localhost:8080`.
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            client: hyper::Client::new(),
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    async fn request(&self, method: Method, link: &TangleAddress, body: Body) -> Result<(StatusCode, Bytes)> {
+        let uri: Uri =
+            handle_http_result(format!("{}{}{}", self.url, MESSAGES_PATH, hex::encode(link.to_msg_index())).parse())?;
+        let request = handle_http_result(Request::builder().method(method).uri(uri).body(body))?;
+        let response = handle_http_result(self.client.request(request).await)?;
+        let status = response.status();
+        let bytes = handle_http_result(body::to_bytes(response.into_body()).await)?;
+        Ok((status, bytes))
+    }
+
+    async fn send(&self, msg: &TangleMessage) -> Result<()> {
+        let (status, _) = self
+            .request(Method::PUT, &msg.link, Body::from(msg.body.to_bytes()))
+            .await?;
+        try_or!(
+            status.is_success(),
+            HttpStatusFailure(msg.link.to_string(), status.as_u16())
+        )
+    }
+
+    async fn recv(&self, link: &TangleAddress) -> Result<Vec<TangleMessage>> {
+        let (status, bytes) = self.request(Method::GET, link, Body::empty()).await?;
+        match status {
+            StatusCode::OK => Ok(decode_messages(&bytes)?
+                .into_iter()
+                .map(|body| BinaryMessage::new(*link, TangleAddress::default(), body.into()))
+                .collect()),
+            StatusCode::NOT_FOUND => err!(MessageLinkNotFoundOnServer(link.to_string())),
+            status => err!(HttpStatusFailure(link.to_string(), status.as_u16())),
+        }
+    }
+}
+
+impl TransportOptions for HttpClient {
+    type SendOptions = ();
+    fn get_send_options(&self) {}
+    fn set_send_options(&mut self, _opt: ()) {}
+
+    type RecvOptions = ();
+    fn get_recv_options(&self) {}
+    fn set_recv_options(&mut self, _opt: ()) {}
+}
+
+#[async_trait(?Send)]
+impl Transport<TangleAddress, TangleMessage> for HttpClient {
+    async fn send_message(&mut self, msg: &TangleMessage) -> Result<()> {
+        self.send(msg).await
+    }
+
+    async fn recv_messages(&mut self, link: &TangleAddress) -> Result<Vec<TangleMessage>> {
+        self.recv(link).await
+    }
+
+    async fn recv_message(&mut self, link: &TangleAddress) -> Result<TangleMessage> {
+        let mut msgs = self.recv(link).await?;
+        if let Some(msg) = msgs.pop() {
+            try_or!(msgs.is_empty(), MessageNotUnique(link.to_string()))?;
+            Ok(msg)
+        } else {
+            err!(MessageLinkNotFoundOnServer(link.to_string()))
+        }
+    }
+
+    /// Send the messages concurrently.
+    async fn send_messages(&mut self, msgs: &[TangleMessage]) -> Vec<Result<()>> {
+        join_all(msgs.iter().map(|msg| self.send(msg))).await
+    }
+
+    /// Receive the messages of the links concurrently.
+    async fn recv_messages_many(&mut self, links: &[TangleAddress]) -> Vec<Result<Vec<TangleMessage>>> {
+        join_all(links.iter().map(|link| self.recv(link))).await
+    }
+}
+
+#[async_trait(?Send)]
+impl TransportDetails<TangleAddress> for HttpClient {
+    type Details = ();
+    async fn get_link_details(&mut self, _link: &TangleAddress) -> Result<Self::Details> {
+        Ok(())
+    }
+}
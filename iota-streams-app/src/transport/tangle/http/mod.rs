@@ -0,0 +1,147 @@
+//! Plain HTTP protocol storing Streams messages, for deployments without an IOTA node.
+//!
+//! A server stores message bodies under the index of their link, the lowercase hex encoding of
+//! `TangleAddress::to_msg_index`, which is also the index of the messages on the Tangle:
+//!
+//! - `PUT /messages/{index}` stores the request body as a message under the index. The server answers `204 No Content`,
+//!   `400 Bad Request` if the body is empty, or `413 Payload Too Large` if it is longer than `MAX_MESSAGE_SIZE`. A body
+//!   already stored under the index is not stored again and also gets `204 No Content`. Messages are never removed, the
+//!   server answers `507 Insufficient Storage` once the index holds `MAX_MESSAGES_PER_INDEX` messages or the server
+//!   `MAX_MESSAGES` messages.
+//! - `GET /messages/{index}` answers `200 OK` with the messages stored under the index in insertion order, each one
+//!   prefixed with its length as a 4-byte big-endian integer, or `404 Not Found` if there are none.
+//!
+//! Malformed indexes and other paths get `404 Not Found`, other methods `405 Method Not Allowed`.
+//! As on the Tangle, the previous link of a message isn't stored.
+//!
+//! Anyone can publish under any index, and the server doesn't verify messages. Spam can fill an
+//! index before the authentic message is published, which then gets `507 Insufficient Storage`,
+//! and channel users only read the first `MAX_CANDIDATES_PER_LINK` (16) messages found at a link, so
+//! an authentic message stored after as much spam is not read either. The protocol is meant for
+//! deployments where only trusted publishers can reach the server.
+
+use iota_streams_core::{
+    err,
+    prelude::Vec,
+    Errors::MalformedHttpResponse,
+    Result,
+};
+
+#[cfg(feature = "http-client")]
+mod client;
+#[cfg(feature = "http-client")]
+pub use client::HttpClient;
+
+/// Reference server of the protocol, storing messages in memory
+#[cfg(feature = "http-server")]
+pub mod server;
+
+/// Max length of a message body accepted by a server, in bytes.
+pub const MAX_MESSAGE_SIZE: usize = 1 << 20;
+
+/// Max number of messages stored by a server under a single index.
+pub const MAX_MESSAGES_PER_INDEX: usize = 64;
+
+/// Max number of messages stored by a server over all indexes.
+pub const MAX_MESSAGES: usize = 1 << 12;
+
+const MESSAGES_PATH: &str = "/messages/";
+
+#[cfg_attr(not(feature = "http-server"), allow(dead_code))]
+fn encode_messages(msgs: &[Vec<u8>]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(msgs.iter().map(|msg| 4 + msg.len()).sum());
+    for msg in msgs {
+        bytes.extend_from_slice(&(msg.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(msg);
+    }
+    bytes
+}
+
+#[cfg_attr(not(feature = "http-client"), allow(dead_code))]
+fn decode_messages(mut bytes: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let mut msgs = Vec::new();
+    while !bytes.is_empty() {
+        if bytes.len() < 4 {
+            return err!(MalformedHttpResponse);
+        }
+        let (len, rest) = bytes.split_at(4);
+        let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize;
+        if rest.len() < len {
+            return err!(MalformedHttpResponse);
+        }
+        let (msg, rest) = rest.split_at(len);
+        msgs.push(msg.to_vec());
+        bytes = rest;
+    }
+    Ok(msgs)
+}
+
+#[cfg(all(test, feature = "http-client", feature = "http-server"))]
+mod tests {
+    use super::{
+        server::{
+            bind,
+            MessageStore,
+        },
+        *,
+    };
+    use crate::transport::{
+        tangle::{
+            test_message,
+            TangleMessage,
+        },
+        Transport,
+    };
+    use iota_streams_core::{
+        ensure,
+        Errors,
+    };
+
+    #[tokio::test]
+    async fn client_stores_messages_on_local_server() -> Result<()> {
+        let store = MessageStore::new();
+        let (addr, server) = bind(&([127, 0, 0, 1], 0).into(), store.clone())?;
+        let server = tokio::spawn(server);
+        let mut client = HttpClient::new(&format!("http://{}/", addr));
+
+        let msgs: Vec<TangleMessage> = (0..3).map(|n| test_message(n, &[n])).collect();
+        client.send_message(&msgs[0]).await?;
+        for result in client.send_messages(&msgs[1..]).await {
+            result?;
+        }
+        ensure!(
+            client.recv_message(&msgs[0].link).await? == msgs[0],
+            "message not received"
+        );
+        let links: Vec<_> = msgs.iter().map(|msg| msg.link).collect();
+        for (msg, result) in msgs.iter().zip(client.recv_messages_many(&links).await) {
+            ensure!(result? == [msg.clone()], "messages not received");
+        }
+
+        // Messages are stored under the Tangle index of their link
+        let index = hex::encode(msgs[0].link.to_msg_index());
+        ensure!(
+            store.get(&index) == [msgs[0].body.to_bytes()],
+            "message not stored under its index"
+        );
+        let missing = client.recv_messages(&test_message(3, &[]).link).await;
+        ensure!(
+            matches!(
+                missing.map_err(|e| e.downcast::<Errors>()),
+                Err(Ok(Errors::MessageLinkNotFoundOnServer(_)))
+            ),
+            "missing message not reported"
+        );
+
+        // Empty messages are refused
+        let empty = TangleMessage::new(msgs[0].link, msgs[0].prev_link, Vec::new().into());
+        let e = client.send_message(&empty).await.unwrap_err();
+        ensure!(
+            matches!(e.downcast_ref::<Errors>(), Some(Errors::HttpStatusFailure(_, 400))),
+            "unexpected error: {}",
+            e
+        );
+        server.abort();
+        Ok(())
+    }
+}
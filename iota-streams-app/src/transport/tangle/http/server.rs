@@ -0,0 +1,240 @@
+use core::{
+    convert::Infallible,
+    future::Future,
+};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        Arc,
+        Mutex,
+        MutexGuard,
+        PoisonError,
+    },
+};
+
+use hyper::{
+    body::HttpBody,
+    header::{
+        HeaderValue,
+        CONTENT_TYPE,
+    },
+    service::{
+        make_service_fn,
+        service_fn,
+    },
+    Body,
+    Method,
+    Request,
+    Response,
+    Server,
+    StatusCode,
+};
+
+use iota_streams_core::{
+    err,
+    prelude::{
+        string::ToString,
+        String,
+        Vec,
+    },
+    wrapped_err,
+    Errors::{
+        HttpOperationFailure,
+        MessageStoreFull,
+    },
+    Result,
+    WrappedError,
+};
+
+use super::{
+    encode_messages,
+    MAX_MESSAGES,
+    MAX_MESSAGES_PER_INDEX,
+    MAX_MESSAGE_SIZE,
+    MESSAGES_PATH,
+};
+
+#[derive(Debug, Default)]
+struct Messages {
+    by_index: HashMap<String, Vec<Vec<u8>>>,
+    count: usize,
+}
+
+/// Messages stored by a server, by index, shared by its connections.
+///
+/// At most `MAX_MESSAGES_PER_INDEX` messages are stored under an index, and `MAX_MESSAGES` in total.
+#[derive(Clone, Debug, Default)]
+pub struct MessageStore {
+    messages: Arc<Mutex<Messages>>,
+}
+
+impl MessageStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store a message body under `index`, after the messages already stored there.
+    ///
+    /// A body already stored under `index` is not stored again, and succeeds even if the index is
+    /// full. Fails with `MessageStoreFull`, storing nothing, once the index or the store holds as
+    /// many messages as allowed.
+    pub fn insert(&self, index: &str, msg: Vec<u8>) -> Result<()> {
+        let mut messages = self.messages();
+        if matches!(messages.by_index.get(index), Some(msgs) if msgs.contains(&msg)) {
+            return Ok(());
+        }
+        if messages.count >= MAX_MESSAGES {
+            return err!(MessageStoreFull(index.to_string()));
+        }
+        let msgs = messages.by_index.entry(index.to_string()).or_default();
+        if msgs.len() >= MAX_MESSAGES_PER_INDEX {
+            return err!(MessageStoreFull(index.to_string()));
+        }
+        msgs.push(msg);
+        messages.count += 1;
+        Ok(())
+    }
+
+    /// Message bodies stored under `index`, in insertion order.
+    pub fn get(&self, index: &str) -> Vec<Vec<u8>> {
+        self.messages().by_index.get(index).cloned().unwrap_or_default()
+    }
+
+    fn messages(&self) -> MutexGuard<'_, Messages> {
+        // A panicking connection leaves the store consistent, messages are counted once stored
+        self.messages.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn is_index(index: &str) -> bool {
+    index.len() == 64 && index.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+fn status(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = status;
+    response
+}
+
+async fn read_message(mut body: Body) -> core::result::Result<Vec<u8>, StatusCode> {
+    let mut msg = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|_| StatusCode::BAD_REQUEST)?;
+        if msg.len() + chunk.len() > MAX_MESSAGE_SIZE {
+            return Err(StatusCode::PAYLOAD_TOO_LARGE);
+        }
+        msg.extend_from_slice(&chunk);
+    }
+    if msg.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(msg)
+}
+
+async fn handle(store: MessageStore, request: Request<Body>) -> core::result::Result<Response<Body>, Infallible> {
+    let index = match request.uri().path().strip_prefix(MESSAGES_PATH) {
+        Some(index) if is_index(index) => index.to_string(),
+        _ => return Ok(status(StatusCode::NOT_FOUND)),
+    };
+    let response = match *request.method() {
+        Method::GET => {
+            let msgs = store.get(&index);
+            if msgs.is_empty() {
+                status(StatusCode::NOT_FOUND)
+            } else {
+                let mut response = Response::new(Body::from(encode_messages(&msgs)));
+                response
+                    .headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_static("application/octet-stream"));
+                response
+            }
+        }
+        Method::PUT => match read_message(request.into_body()).await {
+            Ok(msg) => match store.insert(&index, msg) {
+                Ok(()) => status(StatusCode::NO_CONTENT),
+                Err(_) => status(StatusCode::INSUFFICIENT_STORAGE),
+            },
+            Err(code) => status(code),
+        },
+        _ => status(StatusCode::METHOD_NOT_ALLOWED),
+    };
+    Ok(response)
+}
+
+/// Bind a server storing messages in `store` to `addr`, within a Tokio runtime.
+///
+/// Returns the bound address, with the port picked by the system if `addr` has port 0, and the
+/// future serving requests until the server fails.
+pub fn bind(addr: &SocketAddr, store: MessageStore) -> Result<(SocketAddr, impl Future<Output = Result<()>>)> {
+    let make_service = make_service_fn(move |_| {
+        let store = store.clone();
+        async move { Ok::<_, Infallible>(service_fn(move |request| handle(store.clone(), request))) }
+    });
+    let server = Server::try_bind(addr)
+        .map_err(|err| wrapped_err!(HttpOperationFailure, WrappedError(err)))?
+        .serve(make_service);
+    let local_addr = server.local_addr();
+    Ok((local_addr, async move {
+        server
+            .await
+            .map_err(|err| wrapped_err!(HttpOperationFailure, WrappedError(err)))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iota_streams_core::ensure;
+
+    #[tokio::test]
+    async fn messages_are_read_up_to_the_max_size() -> Result<()> {
+        let msg = read_message(Body::from(vec![1; MAX_MESSAGE_SIZE])).await;
+        ensure!(msg.map(|msg| msg.len()) == Ok(MAX_MESSAGE_SIZE), "message not read");
+        let oversized = read_message(Body::from(vec![1; MAX_MESSAGE_SIZE + 1])).await;
+        ensure!(
+            oversized == Err(StatusCode::PAYLOAD_TOO_LARGE),
+            "oversized message read"
+        );
+        ensure!(
+            read_message(Body::empty()).await == Err(StatusCode::BAD_REQUEST),
+            "empty message read"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_messages_are_stored_once() -> Result<()> {
+        let store = MessageStore::new();
+        let index = format!("{:064x}", 0);
+        store.insert(&index, vec![1])?;
+        store.insert(&index, vec![2])?;
+        store.insert(&index, vec![1])?;
+        ensure!(store.get(&index) == [vec![1], vec![2]], "duplicate message stored");
+        ensure!(store.messages().count == 2, "duplicate message counted");
+        Ok(())
+    }
+
+    #[test]
+    fn messages_are_stored_up_to_the_max_counts() -> Result<()> {
+        let store = MessageStore::new();
+        let index = |n: usize| format!("{:064x}", n);
+        for n in 0..MAX_MESSAGES_PER_INDEX {
+            store.insert(&index(0), vec![n as u8])?;
+        }
+        let full: Vec<Vec<u8>> = (0..MAX_MESSAGES_PER_INDEX).map(|n| vec![n as u8]).collect();
+        ensure!(store.insert(&index(0), vec![0xff]).is_err(), "index overfilled");
+        ensure!(
+            store.insert(&index(0), vec![0]).is_ok(),
+            "duplicate rejected by a full index"
+        );
+        ensure!(store.get(&index(0)) == full, "messages of a full index changed");
+
+        for n in MAX_MESSAGES_PER_INDEX..MAX_MESSAGES {
+            store.insert(&index(n), vec![1])?;
+        }
+        ensure!(store.insert(&index(1), vec![2]).is_err(), "store overfilled");
+        ensure!(store.get(&index(1)).is_empty(), "message stored in a full store");
+        Ok(())
+    }
+}
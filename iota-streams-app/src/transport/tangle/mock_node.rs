@@ -0,0 +1,458 @@
+//! In-process stand-in for a Chrysalis node, to run the Tangle `Client` without network access.
+//!
+//! The node serves the part of the node REST API used by `tangle::client::Client`:
+//!
+//! - `GET /health` and `GET /api/v1/info`, the node being always healthy and offering remote PoW,
+//! - `GET /api/v1/tips`, the last message received,
+//! - `POST /api/v1/messages`, a JSON or binary (`application/octet-stream`) message with an indexation payload or no
+//!   payload,
+//! - `GET /api/v1/messages?index={index}`, the ids of the messages with the hex encoded index, in arrival order,
+//! - `GET /api/v1/messages/{id}` and `GET /api/v1/messages/{id}/metadata`,
+//! - `GET /api/v1/milestones/{index}`.
+//!
+//! Every message is referenced by a milestone of its own as soon as it is received. Message ids
+//! are sequence numbers rather than hashes of the messages, and neither parents nor nonces are
+//! checked, so that remote PoW is never done.
+
+use core::convert::Infallible;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        mpsc,
+        Arc,
+        Mutex,
+        MutexGuard,
+        PoisonError,
+    },
+    thread::{
+        self,
+        JoinHandle,
+    },
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use bee_common::packable::Packable;
+use bee_message::Message;
+use bee_rest_api::types::dtos::MessageDto;
+use futures::channel::oneshot;
+use hyper::{
+    body,
+    header::{
+        HeaderValue,
+        CONTENT_TYPE,
+    },
+    service::{
+        make_service_fn,
+        service_fn,
+    },
+    Body,
+    Method,
+    Request,
+    Response,
+    Server,
+    StatusCode,
+};
+use serde_json::{
+    json,
+    Value,
+};
+
+use iota_streams_core::{
+    prelude::{
+        string::ToString,
+        String,
+        Vec,
+    },
+    wrapped_err,
+    Errors::HttpOperationFailure,
+    Result,
+    WrappedError,
+};
+
+const API_PATH: &str = "/api/v1/";
+const NETWORK: &str = "streams-mock-node";
+const OCTET_STREAM: &str = "application/octet-stream";
+
+struct Stored {
+    id: String,
+    message: Value,
+    milestone_index: u32,
+}
+
+struct Milestone {
+    id: String,
+    timestamp: u64,
+}
+
+#[derive(Default)]
+struct Ledger {
+    messages: Vec<Stored>,
+    ids: HashMap<String, usize>,
+    indexes: HashMap<String, Vec<usize>>,
+    milestones: Vec<Milestone>,
+}
+
+fn id(kind: u8, seq: usize) -> String {
+    let mut id = [0; 32];
+    id[0] = kind;
+    id[24..].copy_from_slice(&(seq as u64).to_be_bytes());
+    hex::encode(id)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+fn is_hex(s: &str) -> bool {
+    hex::decode(s).is_ok()
+}
+
+/// Hex encoded index of the message, `None` if it has no payload.
+fn index_of(message: &Value) -> core::result::Result<Option<String>, &'static str> {
+    let parents = message["parentMessageIds"].as_array().ok_or("missing parents")?;
+    if parents.is_empty()
+        || !parents
+            .iter()
+            .all(|parent| matches!(parent.as_str(), Some(parent) if is_hex(parent)))
+    {
+        return Err("invalid parents");
+    }
+    let payload = &message["payload"];
+    if payload.is_null() {
+        return Ok(None);
+    }
+    if payload["type"] != 2 {
+        return Err("only indexation payloads are supported");
+    }
+    match (payload["index"].as_str(), payload["data"].as_str()) {
+        (Some(index), Some(data)) if !index.is_empty() && is_hex(index) && is_hex(data) => {
+            Ok(Some(index.to_ascii_lowercase()))
+        }
+        _ => Err("invalid indexation payload"),
+    }
+}
+
+impl Ledger {
+    fn tip(&self) -> String {
+        self.messages
+            .last()
+            .map_or_else(|| id(0, 0), |stored| stored.id.clone())
+    }
+
+    fn insert(&mut self, message: Value) -> core::result::Result<String, &'static str> {
+        let index = index_of(&message)?;
+        let seq = self.messages.len();
+        let message_id = id(0, seq + 1);
+        self.milestones.push(Milestone {
+            id: id(1, seq + 1),
+            timestamp: now(),
+        });
+        if let Some(index) = index {
+            self.indexes.entry(index).or_default().push(seq);
+        }
+        self.ids.insert(message_id.clone(), seq);
+        self.messages.push(Stored {
+            id: message_id.clone(),
+            message,
+            milestone_index: self.milestones.len() as u32,
+        });
+        Ok(message_id)
+    }
+
+    fn get(&self, id: &str) -> Option<&Stored> {
+        self.ids.get(&id.to_ascii_lowercase()).map(|&seq| &self.messages[seq])
+    }
+
+    fn info(&self) -> Value {
+        let milestone_index = self.milestones.len();
+        json!({
+            "name": "streams-mock-node",
+            "version": env!("CARGO_PKG_VERSION"),
+            "isHealthy": true,
+            "networkId": NETWORK,
+            "bech32HRP": "atoi",
+            "minPoWScore": 0.0,
+            "messagesPerSecond": 0.0,
+            "referencedMessagesPerSecond": 0.0,
+            "referencedRate": 0.0,
+            "latestMilestoneTimestamp": self.milestones.last().map_or(0, |milestone| milestone.timestamp),
+            "latestMilestoneIndex": milestone_index,
+            "confirmedMilestoneIndex": milestone_index,
+            "pruningIndex": 0,
+            "features": ["PoW"],
+        })
+    }
+
+    fn find(&self, index: &str) -> Value {
+        let ids = self
+            .indexes
+            .get(&index.to_ascii_lowercase())
+            .map(|seqs| {
+                seqs.iter()
+                    .map(|&seq| self.messages[seq].id.clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        json!({
+            "index": index,
+            "maxResults": 1000,
+            "count": ids.len(),
+            "messageIds": ids,
+        })
+    }
+
+    fn metadata(stored: &Stored) -> Value {
+        json!({
+            "messageId": stored.id,
+            "parentMessageIds": stored.message["parentMessageIds"],
+            "isSolid": true,
+            "referencedByMilestoneIndex": stored.milestone_index,
+            "ledgerInclusionState": "noTransaction",
+        })
+    }
+
+    fn milestone(&self, index: &str) -> Option<Value> {
+        let index = index.parse::<usize>().ok().filter(|&index| index > 0)?;
+        self.milestones.get(index - 1).map(|milestone| {
+            json!({
+                "index": index,
+                "messageId": milestone.id,
+                "timestamp": milestone.timestamp,
+            })
+        })
+    }
+}
+
+/// JSON form of a message posted either as JSON or packed.
+fn parse(bytes: &[u8], binary: bool) -> Option<Value> {
+    if binary {
+        let message = Message::unpack(&mut &bytes[..]).ok()?;
+        serde_json::to_value(MessageDto::from(&message)).ok()
+    } else {
+        serde_json::from_slice(bytes).ok()
+    }
+}
+
+fn respond(status: StatusCode, body: Value) -> Response<Body> {
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+fn data(body: Value) -> Response<Body> {
+    respond(StatusCode::OK, json!({ "data": body }))
+}
+
+fn error(status: StatusCode, message: &str) -> Response<Body> {
+    respond(
+        status,
+        json!({ "error": { "code": status.as_str(), "message": message } }),
+    )
+}
+
+fn lock(ledger: &Mutex<Ledger>) -> MutexGuard<'_, Ledger> {
+    // Every update leaves the ledger consistent before it can panic
+    ledger.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+async fn handle(
+    ledger: Arc<Mutex<Ledger>>,
+    request: Request<Body>,
+) -> core::result::Result<Response<Body>, Infallible> {
+    let path = request.uri().path().to_string();
+    let query = request.uri().query().unwrap_or_default().to_string();
+    let route = match path.strip_prefix(API_PATH) {
+        Some(route) => route.trim_end_matches('/').split('/').collect::<Vec<_>>(),
+        None if path == "/health" => return Ok(Response::new(Body::empty())),
+        None => return Ok(error(StatusCode::NOT_FOUND, "unknown path")),
+    };
+
+    let response = match (request.method(), route.as_slice()) {
+        (&Method::GET, ["info"]) => data(lock(&ledger).info()),
+        (&Method::GET, ["tips"]) => data(json!({ "tipMessageIds": [lock(&ledger).tip()] })),
+        (&Method::GET, ["messages"]) => match query.split('&').find_map(|param| param.strip_prefix("index=")) {
+            Some(index) if is_hex(index) => data(lock(&ledger).find(index)),
+            _ => error(StatusCode::BAD_REQUEST, "invalid index"),
+        },
+        (&Method::POST, ["messages"]) => {
+            let binary = request.headers().get(CONTENT_TYPE) == Some(&HeaderValue::from_static(OCTET_STREAM));
+            let message = body::to_bytes(request.into_body())
+                .await
+                .ok()
+                .and_then(|bytes| parse(&bytes, binary));
+            match message.map(|message| lock(&ledger).insert(message)) {
+                Some(Ok(id)) => respond(StatusCode::CREATED, json!({ "data": { "messageId": id } })),
+                Some(Err(reason)) => error(StatusCode::BAD_REQUEST, reason),
+                None => error(StatusCode::BAD_REQUEST, "invalid message"),
+            }
+        }
+        (&Method::GET, ["messages", id]) => match lock(&ledger).get(id) {
+            Some(stored) => data(stored.message.clone()),
+            None => error(StatusCode::NOT_FOUND, "message not found"),
+        },
+        (&Method::GET, ["messages", id, "metadata"]) => match lock(&ledger).get(id) {
+            Some(stored) => data(Ledger::metadata(stored)),
+            None => error(StatusCode::NOT_FOUND, "message not found"),
+        },
+        (&Method::GET, ["milestones", index]) => match lock(&ledger).milestone(index) {
+            Some(milestone) => data(milestone),
+            None => error(StatusCode::NOT_FOUND, "milestone not found"),
+        },
+        (_, ["info"]) | (_, ["tips"]) | (_, ["messages", ..]) | (_, ["milestones", _]) => {
+            error(StatusCode::METHOD_NOT_ALLOWED, "method not allowed")
+        }
+        _ => error(StatusCode::NOT_FOUND, "unknown path"),
+    };
+    Ok(response)
+}
+
+/// Mock node listening on a free port of localhost.
+///
+/// The node runs on a thread of its own, so that it keeps serving requests while the caller
+/// blocks on the client, and stops when dropped. Messages are kept in memory.
+pub struct MockNode {
+    url: String,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockNode {
+    /// Start a node with an empty ledger.
+    pub fn spawn() -> Result<Self> {
+        let (shutdown, stop) = oneshot::channel::<()>();
+        let (started, bound) = mpsc::channel();
+        let thread = thread::spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+                Ok(runtime) => runtime,
+                Err(err) => {
+                    return started
+                        .send(Err(wrapped_err!(HttpOperationFailure, WrappedError(err))))
+                        .unwrap_or(())
+                }
+            };
+            runtime.block_on(async move {
+                let ledger = Arc::new(Mutex::new(Ledger::default()));
+                let make_service = make_service_fn(move |_| {
+                    let ledger = ledger.clone();
+                    async move { Ok::<_, Infallible>(service_fn(move |request| handle(ledger.clone(), request))) }
+                });
+                match Server::try_bind(&SocketAddr::from(([127, 0, 0, 1], 0))) {
+                    Ok(builder) => {
+                        let server = builder.serve(make_service);
+                        if started.send(Ok(server.local_addr())).is_ok() {
+                            // Failures of a node nobody is waiting for have nobody to be reported to
+                            let _ = server
+                                .with_graceful_shutdown(async {
+                                    let _ = stop.await;
+                                })
+                                .await;
+                        }
+                    }
+                    Err(err) => started
+                        .send(Err(wrapped_err!(HttpOperationFailure, WrappedError(err))))
+                        .unwrap_or(()),
+                }
+            })
+        });
+        match bound.recv() {
+            Ok(Ok(addr)) => Ok(Self {
+                url: format!("http://{}", addr),
+                shutdown: Some(shutdown),
+                thread: Some(thread),
+            }),
+            Ok(Err(err)) => Err(err),
+            Err(err) => Err(wrapped_err!(HttpOperationFailure, WrappedError(err))),
+        }
+    }
+
+    /// URL of the node, e.g. `http://127.0.0.1:43210`.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(all(test, feature = "client"))]
+mod tests {
+    use super::*;
+    use crate::transport::{
+        tangle::{
+            client::Client,
+            test_message,
+            TangleAddress,
+        },
+        Transport,
+        TransportDetails,
+    };
+    use iota_streams_core::ensure;
+
+    // The client blocks on its own futures when cloned, which needs a worker of its own
+    #[tokio::test(flavor = "multi_thread")]
+    async fn client_stores_messages_on_the_node() -> Result<()> {
+        let node = MockNode::spawn()?;
+        let mut client = Client::new_from_url(node.url());
+        let msgs = [test_message(1, &[1]), test_message(2, &[2]), test_message(3, &[3])];
+
+        client.send_message(&msgs[0]).await?;
+        for result in client.clone().send_messages(&msgs[1..]).await {
+            result?;
+        }
+        ensure!(
+            client.recv_message(&msgs[0].link).await? == msgs[0],
+            "message not fetched"
+        );
+        let links: Vec<TangleAddress> = msgs.iter().map(|msg| msg.link).collect();
+        for (msg, result) in msgs.iter().zip(client.recv_messages_many(&links).await) {
+            ensure!(result? == [msg.clone()], "messages not fetched");
+        }
+
+        // The node references every message by a milestone of its own, in arrival order
+        let details = client.get_link_details(&msgs[0].link).await?;
+        ensure!(
+            details.metadata.referenced_by_milestone_index == Some(1),
+            "message not referenced by its milestone"
+        );
+        ensure!(
+            details.milestone.map(|milestone| milestone.index) == Some(1),
+            "milestone of the message not fetched"
+        );
+        let missing = client.recv_messages(&TangleAddress::default()).await?;
+        ensure!(missing.is_empty(), "messages found at an unused link");
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn unreachable_node_is_not_taken_for_missing_messages() -> Result<()> {
+        let node = MockNode::spawn()?;
+        let mut client = Client::new_from_url(node.url());
+        let msg = test_message(1, &[1]);
+        client.send_message(&msg).await?;
+
+        // A stopped node refuses connections, which says nothing about the index
+        drop(node);
+        ensure!(
+            client.recv_messages(&msg.link).await.is_err(),
+            "failure to reach the node taken for missing messages"
+        );
+        Ok(())
+    }
+}
@@ -547,3 +547,22 @@ impl<F: PRP> AbsorbFallback<F> for MsgId {
 /// crate for node interfacing
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 pub mod client;
+
+/// Tangle-specific Transport on an embedded SQLite database, for offline channels and archives
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+/// Tangle-specific Transport over a plain HTTP protocol, with its reference server
+#[cfg(any(feature = "http-client", feature = "http-server"))]
+pub mod http;
+
+/// In-process stand-in for a Chrysalis node, for testing the Tangle-specific Transport Client
+#[cfg(feature = "mock-node")]
+pub mod mock_node;
+
+/// Message at a link made out of `n`, for transport tests
+#[cfg(test)]
+pub(crate) fn test_message(n: u8, body: &[u8]) -> TangleMessage {
+    let link = TangleAddress::new([n; APPINST_SIZE][..].into(), [n; MSGID_SIZE][..].into());
+    TangleMessage::new(link, TangleAddress::default(), body.to_vec().into())
+}
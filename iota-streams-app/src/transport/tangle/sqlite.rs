@@ -0,0 +1,331 @@
+use core::fmt;
+use std::{
+    path::Path,
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use rusqlite::{
+    params,
+    Connection,
+    OptionalExtension,
+};
+
+use iota_streams_core::{
+    async_trait,
+    crypto::hashes::{
+        blake2b,
+        Digest,
+    },
+    err,
+    prelude::{
+        string::ToString,
+        Box,
+        Vec,
+    },
+    try_or,
+    wrapped_err,
+    Errors::{
+        DatabaseOperationFailure,
+        MessageLinkNotFoundInDatabase,
+        MessageNotUnique,
+    },
+    Result,
+    WrappedError,
+};
+
+use crate::{
+    message::{
+        BinaryMessage,
+        HasLink,
+    },
+    transport::{
+        tangle::{
+            TangleAddress,
+            TangleMessage,
+        },
+        Transport,
+        TransportDetails,
+        TransportOptions,
+    },
+};
+
+// Messages are looked up by link. The unique constraint on the hash of the body keeps a message sent
+// twice only once, without indexing the bodies. Different messages sent to the same link are all
+// kept, in insertion order.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS messages (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    link BLOB NOT NULL,
+    prev_link BLOB NOT NULL,
+    body BLOB NOT NULL,
+    body_hash BLOB NOT NULL,
+    inserted_at INTEGER NOT NULL,
+    UNIQUE (link, body_hash)
+);
+CREATE INDEX IF NOT EXISTS messages_link ON messages (link);
+";
+
+/// Metadata of a message stored in a `SqliteTransport`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Details {
+    /// Insertion order of the message in the database, starting from 1.
+    pub seq: i64,
+    /// Arrival time of the message, in milliseconds since the Unix epoch.
+    pub inserted_at: u64,
+}
+
+impl fmt::Display for Details {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<seq={}, inserted_at={}>", self.seq, self.inserted_at)
+    }
+}
+
+fn handle_db_result<T>(result: rusqlite::Result<T>) -> Result<T> {
+    result.map_err(|err| wrapped_err!(DatabaseOperationFailure, WrappedError(err)))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+fn msg_from_row(link: TangleAddress, prev_link: &[u8], body: Vec<u8>) -> Result<TangleMessage> {
+    Ok(BinaryMessage::new(
+        link,
+        TangleAddress::try_from_bytes(prev_link)?,
+        body.into(),
+    ))
+}
+
+/// Tangle transport storing messages in an embedded SQLite database.
+///
+/// Users opening the same database file share a channel without a node. Fanned out along with a
+/// node client, it keeps a durable archive of every message sent or seen.
+pub struct SqliteTransport {
+    conn: Connection,
+}
+
+impl SqliteTransport {
+    /// Open the database at `path`, creating it if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_connection(handle_db_result(Connection::open(path))?)
+    }
+
+    /// Open a database living in memory only.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::from_connection(handle_db_result(Connection::open_in_memory())?)
+    }
+
+    fn from_connection(conn: Connection) -> Result<Self> {
+        handle_db_result(conn.execute_batch(SCHEMA))?;
+        Ok(Self { conn })
+    }
+
+    /// Messages inserted after the message with insertion order `seq`, in insertion order.
+    pub fn messages_after(&self, seq: i64) -> Result<Vec<(Details, TangleMessage)>> {
+        let mut stmt = handle_db_result(self.conn.prepare_cached(
+            "SELECT seq, inserted_at, link, prev_link, body FROM messages WHERE seq > ?1 ORDER BY seq",
+        ))?;
+        let rows = handle_db_result(stmt.query_map(params![seq], |row| {
+            Ok((
+                Details {
+                    seq: row.get(0)?,
+                    inserted_at: row.get::<_, i64>(1)? as u64,
+                },
+                row.get::<_, Vec<u8>>(2)?,
+                row.get::<_, Vec<u8>>(3)?,
+                row.get::<_, Vec<u8>>(4)?,
+            ))
+        }))?;
+        let mut msgs = Vec::new();
+        for row in rows {
+            let (details, link, prev_link, body) = handle_db_result(row)?;
+            msgs.push((
+                details,
+                msg_from_row(TangleAddress::try_from_bytes(&link)?, &prev_link, body)?,
+            ));
+        }
+        Ok(msgs)
+    }
+
+    fn insert(conn: &Connection, msg: &TangleMessage, inserted_at: u64) -> rusqlite::Result<()> {
+        let mut stmt = conn.prepare_cached(
+            "INSERT OR IGNORE INTO messages (link, prev_link, body, body_hash, inserted_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        let body_hash = blake2b::Blake2b256::digest(msg.body.as_bytes());
+        stmt.execute(params![
+            msg.link.to_bytes(),
+            msg.prev_link.to_bytes(),
+            msg.body.as_bytes(),
+            &body_hash[..],
+            inserted_at as i64
+        ])?;
+        Ok(())
+    }
+
+    fn insert_all(&mut self, msgs: &[TangleMessage]) -> rusqlite::Result<()> {
+        let tx = self.conn.transaction()?;
+        let inserted_at = now();
+        for msg in msgs {
+            Self::insert(&tx, msg, inserted_at)?;
+        }
+        tx.commit()
+    }
+
+    fn lookup(&self, link: &TangleAddress) -> Result<Vec<TangleMessage>> {
+        let mut stmt = handle_db_result(
+            self.conn
+                .prepare_cached("SELECT prev_link, body FROM messages WHERE link = ?1 ORDER BY seq"),
+        )?;
+        let rows = handle_db_result(stmt.query_map(params![link.to_bytes()], |row| {
+            Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, Vec<u8>>(1)?))
+        }))?;
+        let mut msgs = Vec::new();
+        for row in rows {
+            let (prev_link, body) = handle_db_result(row)?;
+            msgs.push(msg_from_row(*link, &prev_link, body)?);
+        }
+        try_or!(!msgs.is_empty(), MessageLinkNotFoundInDatabase(link.to_string()))?;
+        Ok(msgs)
+    }
+}
+
+impl TransportOptions for SqliteTransport {
+    type SendOptions = ();
+    fn get_send_options(&self) {}
+    fn set_send_options(&mut self, _opt: ()) {}
+
+    type RecvOptions = ();
+    fn get_recv_options(&self) {}
+    fn set_recv_options(&mut self, _opt: ()) {}
+}
+
+#[async_trait(?Send)]
+impl Transport<TangleAddress, TangleMessage> for SqliteTransport {
+    async fn send_message(&mut self, msg: &TangleMessage) -> Result<()> {
+        handle_db_result(Self::insert(&self.conn, msg, now()))
+    }
+
+    async fn recv_messages(&mut self, link: &TangleAddress) -> Result<Vec<TangleMessage>> {
+        self.lookup(link)
+    }
+
+    async fn recv_message(&mut self, link: &TangleAddress) -> Result<TangleMessage> {
+        let mut msgs = self.lookup(link)?;
+        if let Some(msg) = msgs.pop() {
+            try_or!(msgs.is_empty(), MessageNotUnique(link.to_string()))?;
+            Ok(msg)
+        } else {
+            err!(MessageLinkNotFoundInDatabase(link.to_string()))
+        }
+    }
+
+    /// Insert the messages in a single transaction, either all of them or none.
+    async fn send_messages(&mut self, msgs: &[TangleMessage]) -> Vec<Result<()>> {
+        match self.insert_all(msgs) {
+            Ok(()) => msgs.iter().map(|_| Ok(())).collect(),
+            Err(e) => msgs
+                .iter()
+                .map(|_| Err(wrapped_err!(DatabaseOperationFailure, WrappedError(&e))))
+                .collect(),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl TransportDetails<TangleAddress> for SqliteTransport {
+    type Details = Details;
+    /// Details of the first message inserted at `link`.
+    async fn get_link_details(&mut self, link: &TangleAddress) -> Result<Self::Details> {
+        let mut stmt = handle_db_result(
+            self.conn
+                .prepare_cached("SELECT seq, inserted_at FROM messages WHERE link = ?1 ORDER BY seq LIMIT 1"),
+        )?;
+        let details = handle_db_result(
+            stmt.query_row(params![link.to_bytes()], |row| {
+                Ok(Details {
+                    seq: row.get(0)?,
+                    inserted_at: row.get::<_, i64>(1)? as u64,
+                })
+            })
+            .optional(),
+        )?;
+        match details {
+            Some(details) => Ok(details),
+            None => err!(MessageLinkNotFoundInDatabase(link.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::tangle::test_message;
+    use iota_streams_core::{
+        ensure,
+        Errors,
+    };
+
+    #[tokio::test]
+    async fn connections_to_the_same_file_share_messages() -> Result<()> {
+        // Removed along with the database on every path out of the test
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("streams.db");
+        let (msg, next) = (test_message(1, &[1]), test_message(2, &[2]));
+        let forged = TangleMessage::new(next.link, next.prev_link, vec![0].into());
+        let mut writer = SqliteTransport::open(&path)?;
+        writer.send_message(&msg).await?;
+
+        // A message sent twice is kept once, a different message sent to the same link is kept as well
+        for result in writer
+            .send_messages(&[next.clone(), forged.clone(), next.clone()])
+            .await
+        {
+            result?;
+        }
+
+        let mut reader = SqliteTransport::open(&path)?;
+        ensure!(
+            reader.recv_messages(&next.link).await? == [next.clone(), forged.clone()],
+            "messages not shared"
+        );
+        let e = reader.recv_message(&next.link).await.unwrap_err();
+        ensure!(
+            matches!(e.downcast_ref::<Errors>(), Some(Errors::MessageNotUnique(_))),
+            "unexpected error: {}",
+            e
+        );
+        let e = reader.recv_messages(&test_message(3, &[]).link).await.unwrap_err();
+        ensure!(
+            matches!(
+                e.downcast_ref::<Errors>(),
+                Some(Errors::MessageLinkNotFoundInDatabase(_))
+            ),
+            "unexpected error: {}",
+            e
+        );
+
+        let first = reader.get_link_details(&msg.link).await?;
+        let second = reader.get_link_details(&next.link).await?;
+        ensure!(
+            first.seq < second.seq && first.inserted_at <= second.inserted_at,
+            "unexpected details {} and {}",
+            first,
+            second
+        );
+        let archived: Vec<TangleMessage> = reader.messages_after(0)?.into_iter().map(|(_, msg)| msg).collect();
+        ensure!(
+            archived == [msg, next, forged],
+            "messages not archived in order, once each"
+        );
+        ensure!(
+            reader.messages_after(second.seq)?.len() == 1,
+            "messages not listed after an insertion order"
+        );
+        Ok(())
+    }
+}
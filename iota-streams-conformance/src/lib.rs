@@ -0,0 +1,27 @@
+//! Golden test vectors for the IOTA Streams wire format.
+//!
+//! A fixed scenario is run with seeded users and RNGs, every message sent is recorded along
+//! with its parsed header and the results of unwrapping it by its recipients. Fixtures of the
+//! scenario are stored in the `vectors` directory as JSON, one file per channel type.
+//!
+//! The tests of this crate check that the current implementation produces exactly the stored
+//! binaries and that it unwraps the stored binaries to the stored results. Fixtures are meant
+//! to be shared with other implementations of the protocol, they only change along with the
+//! wire format. To regenerate them run the tests with `STREAMS_UPDATE_VECTORS` set:
+//!
+//! ```text
+//! STREAMS_UPDATE_VECTORS=1 cargo test -p iota-streams-conformance
+//! ```
+
+/// Fixture format.
+mod vectors;
+pub use vectors::*;
+
+/// Transport serving fixture messages.
+pub mod transport;
+
+/// Conformance scenario.
+pub mod scenario;
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,289 @@
+use core::{
+    cell::RefCell,
+    str::FromStr,
+};
+use std::rc::Rc;
+
+use iota_streams_app::{
+    identifier::Identifier,
+    message::BinaryBody,
+};
+use iota_streams_app_channels::api::{
+    tangle::{
+        test::test_rng,
+        Address,
+        Author,
+        Bytes,
+        ChannelType,
+        Cursor,
+        Message,
+        Subscriber,
+    },
+    DefaultF,
+};
+use iota_streams_core::{
+    anyhow,
+    prelude::hex,
+    psk,
+    Result,
+};
+
+use crate::{
+    transport::FixtureTransport,
+    Header,
+    Inputs,
+    Vector,
+    Vectors,
+    FORMAT_VERSION,
+};
+
+type Transport = Rc<RefCell<FixtureTransport>>;
+
+/// Name of `channel_type` as used in fixtures.
+pub fn channel_type_name(channel_type: &ChannelType) -> &'static str {
+    match channel_type {
+        ChannelType::SingleBranch => "single_branch",
+        ChannelType::MultiBranch => "multi_branch",
+        ChannelType::SingleDepth => "single_depth",
+    }
+}
+
+/// Run the conformance scenario and collect the sent messages along with unwrap results.
+///
+/// The author announces a channel, subscriber A subscribes, the author sends a keyload for
+/// subscriber A's key and for a PSK shared with subscriber B, followed by a signed packet.
+/// Subscriber A answers with a tagged packet and unsubscribes. Subscriber C only reads the
+/// announcement and is denied access by the keyload. Multi-branch channels additionally
+/// produce sequence messages.
+///
+/// If `fixtures` are given, recipients unwrap the fixture binaries instead of the ones sent.
+pub async fn run(channel_type: ChannelType, fixtures: Option<&Vectors>) -> Result<Vectors> {
+    let inputs = Inputs::default();
+    let transport = Rc::new(RefCell::new(FixtureTransport::new(match fixtures {
+        Some(fixtures) => fixtures
+            .vectors
+            .iter()
+            .map(fixture_message)
+            .collect::<Result<Vec<_>>>()?,
+        None => Vec::new(),
+    })));
+    let mut vectors = Vectors {
+        format_version: FORMAT_VERSION,
+        channel_type: channel_type_name(&channel_type).to_string(),
+        inputs: inputs.clone(),
+        vectors: Vec::new(),
+    };
+
+    let mut author = Author::new(&inputs.author_seed, channel_type, transport.clone());
+    author.set_rng(test_rng(&inputs.author_rng));
+    let mut subscriber_a = Subscriber::new(&inputs.subscriber_a_seed, transport.clone());
+    subscriber_a.set_rng(test_rng(&inputs.subscriber_a_rng));
+    let mut subscriber_b = Subscriber::new(&inputs.subscriber_b_seed, transport.clone());
+    subscriber_b.set_rng(test_rng(&inputs.subscriber_b_rng));
+    let mut subscriber_c = Subscriber::new(&inputs.subscriber_c_seed, transport.clone());
+    subscriber_c.set_rng(test_rng(&inputs.subscriber_c_rng));
+
+    let psk = psk::psk_from_seed::<DefaultF>(inputs.psk_seed.as_bytes());
+    let pskid = psk::pskid_from_psk::<DefaultF>(&psk);
+    let public_payload = Bytes::from(inputs.public_payload.as_bytes().to_vec());
+    let masked_payload = Bytes::from(inputs.masked_payload.as_bytes().to_vec());
+
+    let announce = author.send_announce().await?;
+    sent(&mut vectors, &transport, "announce", &announce).await?;
+    subscriber_a.receive_announcement(&announce).await?;
+    subscriber_b.receive_announcement(&announce).await?;
+    subscriber_c.receive_announcement(&announce).await?;
+    let author_pk = subscriber_a
+        .author_public_key()
+        .ok_or_else(|| anyhow!("author public key is unknown"))?;
+    let channel_address = subscriber_a
+        .channel_address()
+        .ok_or_else(|| anyhow!("channel address is unknown"))?;
+    content(
+        &mut vectors,
+        "announce",
+        "subscriber_a.author_public_key",
+        hex::encode(author_pk),
+    );
+    content(
+        &mut vectors,
+        "announce",
+        "subscriber_a.channel_address",
+        channel_address,
+    );
+
+    let subscribe = subscriber_a.send_subscribe(&announce).await?;
+    sent(&mut vectors, &transport, "subscribe", &subscribe).await?;
+    author.receive_subscribe(&subscribe).await?;
+    content(
+        &mut vectors,
+        "subscribe",
+        "author.state",
+        format_state(author.fetch_state()?),
+    );
+
+    author.store_psk(pskid, psk)?;
+    subscriber_b.store_psk(pskid, psk)?;
+    let recipients = [
+        Identifier::PskId(pskid),
+        Identifier::EdPubKey((*subscriber_a.get_public_key()).into()),
+    ];
+    let (keyload, keyload_seq) = author.send_keyload(&announce, recipients.iter()).await?;
+    sent(&mut vectors, &transport, "keyload", &keyload).await?;
+    if let Some(seq) = keyload_seq {
+        sent(&mut vectors, &transport, "keyload_sequence", &seq).await?;
+        let referenced = subscriber_a.receive_sequence(&seq).await?;
+        content(
+            &mut vectors,
+            "keyload_sequence",
+            "subscriber_a.referenced_link",
+            referenced,
+        );
+    }
+    for (name, subscriber) in [
+        ("subscriber_a", &mut subscriber_a),
+        ("subscriber_b", &mut subscriber_b),
+        ("subscriber_c", &mut subscriber_c),
+    ] {
+        let access = subscriber.receive_keyload(&keyload).await?;
+        content(&mut vectors, "keyload", &format!("{}.access", name), access);
+    }
+
+    let (signed_packet, signed_packet_seq) = author
+        .send_signed_packet(&keyload, &public_payload, &masked_payload)
+        .await?;
+    sent(&mut vectors, &transport, "signed_packet", &signed_packet).await?;
+    if let Some(seq) = signed_packet_seq {
+        sent(&mut vectors, &transport, "signed_packet_sequence", &seq).await?;
+        let referenced = subscriber_a.receive_sequence(&seq).await?;
+        content(
+            &mut vectors,
+            "signed_packet_sequence",
+            "subscriber_a.referenced_link",
+            referenced,
+        );
+    }
+    for (name, subscriber) in [("subscriber_a", &mut subscriber_a), ("subscriber_b", &mut subscriber_b)] {
+        let (pk, public, masked) = subscriber.receive_signed_packet(&signed_packet).await?;
+        content(
+            &mut vectors,
+            "signed_packet",
+            &format!("{}.public_key", name),
+            hex::encode(pk),
+        );
+        content(
+            &mut vectors,
+            "signed_packet",
+            &format!("{}.public_payload", name),
+            hex::encode(public.as_slice()),
+        );
+        content(
+            &mut vectors,
+            "signed_packet",
+            &format!("{}.masked_payload", name),
+            hex::encode(masked.as_slice()),
+        );
+    }
+
+    let (tagged_packet, tagged_packet_seq) = subscriber_a
+        .send_tagged_packet(&signed_packet, &public_payload, &masked_payload)
+        .await?;
+    sent(&mut vectors, &transport, "tagged_packet", &tagged_packet).await?;
+    if let Some(seq) = tagged_packet_seq {
+        sent(&mut vectors, &transport, "tagged_packet_sequence", &seq).await?;
+        let referenced = author.receive_sequence(&seq).await?;
+        content(
+            &mut vectors,
+            "tagged_packet_sequence",
+            "author.referenced_link",
+            referenced,
+        );
+    }
+    let (public, masked) = author.receive_tagged_packet(&tagged_packet).await?;
+    content(
+        &mut vectors,
+        "tagged_packet",
+        "author.public_payload",
+        hex::encode(public.as_slice()),
+    );
+    content(
+        &mut vectors,
+        "tagged_packet",
+        "author.masked_payload",
+        hex::encode(masked.as_slice()),
+    );
+    let (public, masked) = subscriber_b.receive_tagged_packet(&tagged_packet).await?;
+    content(
+        &mut vectors,
+        "tagged_packet",
+        "subscriber_b.public_payload",
+        hex::encode(public.as_slice()),
+    );
+    content(
+        &mut vectors,
+        "tagged_packet",
+        "subscriber_b.masked_payload",
+        hex::encode(masked.as_slice()),
+    );
+
+    let unsubscribe = subscriber_a.send_unsubscribe(&keyload).await?;
+    sent(&mut vectors, &transport, "unsubscribe", &unsubscribe).await?;
+    author.receive_unsubscribe(&unsubscribe).await?;
+    content(
+        &mut vectors,
+        "unsubscribe",
+        "author.state",
+        format_state(author.fetch_state()?),
+    );
+
+    Ok(vectors)
+}
+
+/// Record message sent at `link` as step `name`.
+async fn sent(vectors: &mut Vectors, transport: &Transport, name: &str, link: &Address) -> Result<()> {
+    let msg = transport.borrow().sent(link)?.clone();
+    let preparsed = msg.parse_header::<DefaultF>().await?;
+    let hdf = preparsed.header;
+    vectors.vectors.push(Vector {
+        name: name.to_string(),
+        link: msg.link.to_string(),
+        prev_link: msg.prev_link.to_string(),
+        binary: hex::encode(msg.body.as_bytes()),
+        header: Header {
+            encoding: hdf.encoding.0,
+            version: hdf.version.0,
+            content_type: hdf.content_type,
+            payload_length: hdf.payload_length,
+            frame_type: hdf.frame_type.0,
+            payload_frame_count: hdf.payload_frame_count,
+            previous_msg_link: hex::encode(hdf.previous_msg_link.as_slice()),
+            seq_num: hdf.seq_num.0,
+            sender_id: hex::encode(hdf.sender_id.as_bytes()),
+        },
+        content: Default::default(),
+    });
+    Ok(())
+}
+
+/// Record unwrap result `key` of step `name`.
+fn content(vectors: &mut Vectors, name: &str, key: &str, value: impl ToString) {
+    if let Some(v) = vectors.vectors.iter_mut().rev().find(|v| v.name == name) {
+        v.content.insert(key.to_string(), value.to_string());
+    }
+}
+
+fn format_state(mut state: Vec<(String, Cursor<Address>)>) -> String {
+    state.sort_by(|a, b| a.0.cmp(&b.0));
+    state
+        .iter()
+        .map(|(id, cursor)| format!("{}={}@{}.{}", id, cursor.link, cursor.branch_no, cursor.seq_no))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn fixture_message(v: &Vector) -> Result<Message> {
+    let link = Address::from_str(&v.link).map_err(|e| anyhow!("bad link of {}: {:?}", v.name, e))?;
+    let prev_link = Address::from_str(&v.prev_link).map_err(|e| anyhow!("bad prev_link of {}: {:?}", v.name, e))?;
+    let binary = hex::decode(&v.binary).map_err(|e| anyhow!("bad binary of {}: {}", v.name, e))?;
+    Ok(Message::new(link, prev_link, BinaryBody::from(binary)))
+}
@@ -0,0 +1,63 @@
+use std::{
+    env,
+    fs,
+    path::PathBuf,
+};
+
+use iota_streams_app_channels::api::tangle::ChannelType;
+use iota_streams_core::{
+    anyhow,
+    Result,
+};
+
+use crate::{
+    scenario::{
+        self,
+        channel_type_name,
+    },
+    Vectors,
+};
+
+fn fixtures_path(channel_type: &ChannelType) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("vectors")
+        .join(format!("{}.json", channel_type_name(channel_type)))
+}
+
+async fn conformance(channel_type: ChannelType) -> Result<()> {
+    let path = fixtures_path(&channel_type);
+    if env::var_os("STREAMS_UPDATE_VECTORS").is_some() {
+        let vectors = scenario::run(channel_type, None).await?;
+        fs::write(&path, vectors.to_json()? + "\n")?;
+        return Ok(());
+    }
+
+    let json = fs::read_to_string(&path).map_err(|e| anyhow!("failed to read {}: {}", path.display(), e))?;
+    let expected = Vectors::from_json(&json)?;
+    // Messages wrapped by the current implementation must match the fixtures...
+    scenario::run(channel_type.clone(), None).await?.check(&expected)?;
+    // ...and the fixtures must unwrap to the same results.
+    scenario::run(channel_type, Some(&expected)).await?.check(&expected)
+}
+
+#[tokio::test]
+async fn single_branch_conformance() -> Result<()> {
+    conformance(ChannelType::SingleBranch).await
+}
+
+#[tokio::test]
+async fn multi_branch_conformance() -> Result<()> {
+    conformance(ChannelType::MultiBranch).await
+}
+
+#[tokio::test]
+async fn fixtures_are_checked() -> Result<()> {
+    let mut expected = scenario::run(ChannelType::SingleBranch, None).await?;
+    expected.vectors[1].binary.replace_range(..2, "ff");
+    let tampered = scenario::run(ChannelType::SingleBranch, Some(&expected)).await;
+    iota_streams_core::ensure!(
+        tampered.is_err() || tampered?.check(&expected).is_err(),
+        "tampered fixture passed conformance check"
+    );
+    Ok(())
+}
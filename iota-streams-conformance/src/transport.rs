@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use iota_streams_app::transport::{
+    Transport,
+    TransportDetails,
+    TransportOptions,
+};
+use iota_streams_app_channels::api::tangle::{
+    Address,
+    Message,
+};
+use iota_streams_core::{
+    anyhow,
+    async_trait,
+    ensure,
+    prelude::Box,
+    Result,
+};
+
+/// Transport keeping messages as sent and serving fixture messages in place of them.
+///
+/// Recipients unwrap the fixture binaries if present, so that unwrapping is checked against
+/// the fixtures independently of wrapping.
+#[derive(Default)]
+pub struct FixtureTransport {
+    sent: HashMap<Address, Message>,
+    fixtures: HashMap<Address, Message>,
+}
+
+impl FixtureTransport {
+    pub fn new(fixtures: impl IntoIterator<Item = Message>) -> Self {
+        Self {
+            sent: HashMap::new(),
+            fixtures: fixtures.into_iter().map(|msg| (msg.link, msg)).collect(),
+        }
+    }
+
+    /// Message as sent by the wrapping user.
+    pub fn sent(&self, link: &Address) -> Result<&Message> {
+        self.sent
+            .get(link)
+            .ok_or_else(|| anyhow!("message {} was not sent", link))
+    }
+}
+
+impl TransportOptions for FixtureTransport {
+    type SendOptions = ();
+    fn get_send_options(&self) {}
+    fn set_send_options(&mut self, _opt: ()) {}
+
+    type RecvOptions = ();
+    fn get_recv_options(&self) {}
+    fn set_recv_options(&mut self, _opt: ()) {}
+}
+
+#[async_trait(?Send)]
+impl Transport<Address, Message> for FixtureTransport {
+    async fn send_message(&mut self, msg: &Message) -> Result<()> {
+        ensure!(!self.sent.contains_key(&msg.link), "message {} sent twice", msg.link);
+        self.sent.insert(msg.link, msg.clone());
+        Ok(())
+    }
+
+    async fn recv_messages(&mut self, link: &Address) -> Result<Vec<Message>> {
+        Ok(vec![self.recv_message(link).await?])
+    }
+
+    async fn recv_message(&mut self, link: &Address) -> Result<Message> {
+        // Serve fixtures only once sent, senders check that the link of a new message is unused.
+        match self.sent.get(link) {
+            Some(msg) => Ok(self.fixtures.get(link).unwrap_or(msg).clone()),
+            None => Err(anyhow!("message {} not found", link)),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl TransportDetails<Address> for FixtureTransport {
+    type Details = ();
+    async fn get_link_details(&mut self, _link: &Address) -> Result<Self::Details> {
+        Ok(())
+    }
+}
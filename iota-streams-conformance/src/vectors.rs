@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use iota_streams_core::{
+    anyhow,
+    ensure,
+    Result,
+};
+
+/// Version of the fixture file format.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Test vectors of a single scenario run.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Vectors {
+    /// Version of the fixture file format, see `FORMAT_VERSION`.
+    pub format_version: u8,
+    /// Channel type used by the author: `single_branch` or `multi_branch`.
+    pub channel_type: String,
+    /// Fixed inputs of the scenario.
+    pub inputs: Inputs,
+    /// Messages in the order they were sent.
+    pub vectors: Vec<Vector>,
+}
+
+/// Fixed seeds and payloads used by the scenario.
+///
+/// User keys are derived from the seeds, session keys, nonces and ephemeral keys are drawn
+/// from `prng::Rng` instances initialized with `prng::dbg_init_str` of the RNG seeds.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Inputs {
+    pub author_seed: String,
+    pub author_rng: String,
+    pub subscriber_a_seed: String,
+    pub subscriber_a_rng: String,
+    pub subscriber_b_seed: String,
+    pub subscriber_b_rng: String,
+    pub subscriber_c_seed: String,
+    pub subscriber_c_rng: String,
+    /// Seed of the pre-shared key known to the author and subscriber B, see `psk::psk_from_seed`.
+    pub psk_seed: String,
+    pub public_payload: String,
+    pub masked_payload: String,
+}
+
+impl Default for Inputs {
+    fn default() -> Self {
+        Self {
+            author_seed: "AUTHOR9SEED".to_string(),
+            author_rng: "AUTHOR9RNG".to_string(),
+            subscriber_a_seed: "SUBSCRIBERA9SEED".to_string(),
+            subscriber_a_rng: "SUBSCRIBERA9RNG".to_string(),
+            subscriber_b_seed: "SUBSCRIBERB9SEED".to_string(),
+            subscriber_b_rng: "SUBSCRIBERB9RNG".to_string(),
+            subscriber_c_seed: "SUBSCRIBERC9SEED".to_string(),
+            subscriber_c_rng: "SUBSCRIBERC9RNG".to_string(),
+            psk_seed: "PSK9SEED".to_string(),
+            public_payload: "PUBLICPAYLOAD".to_string(),
+            masked_payload: "MASKEDPAYLOAD".to_string(),
+        }
+    }
+}
+
+/// A single message.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Vector {
+    /// Name of the scenario step which produced the message.
+    pub name: String,
+    /// Message address as `appinst:msgid`.
+    pub link: String,
+    /// Previous message address as `appinst:msgid`.
+    pub prev_link: String,
+    /// Hex encoded message body as sent by the wrapping user.
+    pub binary: String,
+    /// Header fields as parsed from `binary`.
+    pub header: Header,
+    /// Results of unwrapping the message by its recipients, keyed by name.
+    pub content: BTreeMap<String, String>,
+}
+
+/// Fields of `HDF`.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Header {
+    pub encoding: u8,
+    pub version: u8,
+    pub content_type: u8,
+    pub payload_length: usize,
+    pub frame_type: u8,
+    pub payload_frame_count: u32,
+    /// Hex encoded previous message link.
+    pub previous_msg_link: String,
+    pub seq_num: u64,
+    /// Hex encoded sender identifier.
+    pub sender_id: String,
+}
+
+impl Vectors {
+    /// Parse fixtures from JSON.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| anyhow!("bad conformance fixtures: {}", e))
+    }
+
+    /// Export fixtures as JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| anyhow!("failed to serialize conformance vectors: {}", e))
+    }
+
+    /// Check `self` against `expected` fixtures, the error describes the first difference.
+    pub fn check(&self, expected: &Vectors) -> Result<()> {
+        ensure!(
+            self.format_version == expected.format_version,
+            "fixture format version {} is not supported, expected {}",
+            expected.format_version,
+            self.format_version
+        );
+        ensure!(
+            self.channel_type == expected.channel_type,
+            "channel type differs: expected {}, found {}",
+            expected.channel_type,
+            self.channel_type
+        );
+        ensure!(self.inputs == expected.inputs, "scenario inputs differ");
+
+        for (actual, expected) in self.vectors.iter().zip(expected.vectors.iter()) {
+            ensure!(
+                actual.name == expected.name,
+                "expected message {}, found {}",
+                expected.name,
+                actual.name
+            );
+            check_field(&expected.name, "link", &expected.link, &actual.link)?;
+            check_field(&expected.name, "prev_link", &expected.prev_link, &actual.prev_link)?;
+            check_field(&expected.name, "header", &expected.header, &actual.header)?;
+            check_field(&expected.name, "binary", &expected.binary, &actual.binary)?;
+            check_field(&expected.name, "content", &expected.content, &actual.content)?;
+        }
+        ensure!(
+            self.vectors.len() == expected.vectors.len(),
+            "expected {} messages, found {}",
+            expected.vectors.len(),
+            self.vectors.len()
+        );
+        Ok(())
+    }
+}
+
+fn check_field<T: PartialEq + core::fmt::Debug>(name: &str, field: &str, expected: &T, actual: &T) -> Result<()> {
+    ensure!(
+        expected == actual,
+        "{} of message {} differs:\n  expected: {:?}\n  found:    {:?}",
+        field,
+        name,
+        expected,
+        actual
+    );
+    Ok(())
+}
@@ -43,6 +43,38 @@ pub fn public_from_ed25519(pk: &ed25519::PublicKey) -> Result<PublicKey> {
     }
 }
 
+/// X25519 key agreement key.
+///
+/// Implemented by [`StaticSecret`] for keys held in memory. Keys held elsewhere (a separate process,
+/// a PKCS#11 token, an OS keystore...) can implement it to take part in key exchanges without
+/// exposing the private key.
+pub trait KeyAgreement {
+    /// Public key corresponding to the private key.
+    fn public_key(&self) -> PublicKey;
+
+    /// Compute the Diffie-Hellman shared secret with `their_public`.
+    fn diffie_hellman(&self, their_public: &PublicKey) -> Result<[u8; 32]>;
+
+    /// The secret, if the private key is held in memory.
+    fn static_secret(&self) -> Option<&StaticSecret> {
+        None
+    }
+}
+
+impl KeyAgreement for StaticSecret {
+    fn public_key(&self) -> PublicKey {
+        PublicKey::from(self)
+    }
+
+    fn diffie_hellman(&self, their_public: &PublicKey) -> Result<[u8; 32]> {
+        Ok(StaticSecret::diffie_hellman(self, their_public).to_bytes())
+    }
+
+    fn static_secret(&self) -> Option<&StaticSecret> {
+        Some(self)
+    }
+}
+
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
 pub struct PublicKeyWrap(pub PublicKey);
 
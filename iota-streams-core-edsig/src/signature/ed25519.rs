@@ -13,6 +13,15 @@ use core::hash::{
     Hasher,
 };
 
+use iota_streams_core::{
+    wrapped_err,
+    Errors::SignatureFailure,
+    Result,
+    WrappedError,
+};
+
+pub use super::prehashed::Prehashed;
+
 pub type IPk<'a> = &'a PublicKey;
 
 #[derive(Copy, Clone, Default, Eq, Debug)]
@@ -53,3 +62,36 @@ impl<'a> From<&'a mut PublicKey> for &'a mut PublicKeyWrap {
         unsafe { &mut *(ptr as *mut PublicKeyWrap) }
     }
 }
+
+/// Ed25519 signing key.
+///
+/// Implemented by [`Keypair`] for keys held in memory. Keys held elsewhere (a separate process,
+/// a PKCS#11 token, an OS keystore...) can implement it to sign messages without exposing the
+/// private key.
+pub trait Signer {
+    /// Public key corresponding to the signing key.
+    fn public_key(&self) -> &PublicKey;
+
+    /// Sign the `prehashed` message with Ed25519ph and the given `context`.
+    fn sign_prehashed(&self, prehashed: Prehashed, context: &[u8]) -> Result<Signature>;
+
+    /// The key pair, if the private key is held in memory.
+    fn keypair(&self) -> Option<&Keypair> {
+        None
+    }
+}
+
+impl Signer for Keypair {
+    fn public_key(&self) -> &PublicKey {
+        &self.public
+    }
+
+    fn sign_prehashed(&self, prehashed: Prehashed, context: &[u8]) -> Result<Signature> {
+        Keypair::sign_prehashed(self, prehashed, Some(context))
+            .map_err(|e| wrapped_err!(SignatureFailure, WrappedError(e)))
+    }
+
+    fn keypair(&self) -> Option<&Keypair> {
+        Some(self)
+    }
+}
@@ -0,0 +1,60 @@
+use core::convert::AsRef;
+
+use iota_streams_core::prelude::digest::Digest;
+
+use iota_streams_core::prelude::{
+    generic_array::GenericArray,
+    typenum::U64,
+};
+
+/// Hash value computed outside of Ed25519ph, passed as a `Digest` that only returns it.
+///
+/// The hash value is not computed from the input, it is set by `update` with the 64-byte value,
+/// shorter input being zero-padded and longer input truncated.
+#[derive(Clone, Default)]
+pub struct Prehashed(pub GenericArray<u8, U64>);
+
+impl Prehashed {
+    fn set(&mut self, data: &[u8]) {
+        let len = data.len().min(self.0.len());
+        self.0 = GenericArray::default();
+        self.0[..len].copy_from_slice(&data[..len]);
+    }
+}
+
+impl Digest for Prehashed {
+    type OutputSize = U64;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.set(data.as_ref());
+    }
+
+    fn chain(mut self, data: impl AsRef<[u8]>) -> Self {
+        self.set(data.as_ref());
+        self
+    }
+
+    fn finalize(self) -> GenericArray<u8, Self::OutputSize> {
+        self.0
+    }
+
+    fn finalize_reset(&mut self) -> GenericArray<u8, Self::OutputSize> {
+        core::mem::take(&mut self.0)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn output_size() -> usize {
+        64
+    }
+
+    fn digest(data: &[u8]) -> GenericArray<u8, Self::OutputSize> {
+        Self::new().chain(data).finalize()
+    }
+}
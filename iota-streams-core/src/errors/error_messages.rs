@@ -5,6 +5,18 @@ use displaydoc::Display;
 
 pub struct WrappedError<T: Debug>(pub T);
 
+/// How a caller of a transport reacts to an error, see [`Errors::transport_error_kind`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TransportErrorKind {
+    /// There are no messages at the link.
+    Missing,
+    /// The transport answered, calling it again gives the same outcome, e.g. several messages are
+    /// found at the link or the circuit is open.
+    Answered,
+    /// The transport failed to answer, calling it again might succeed.
+    Transient,
+}
+
 #[derive(Display, Debug)]
 pub enum Errors {
     //////////
@@ -52,6 +64,12 @@ pub enum Errors {
     NoStdRngMissing,
     /// Oneof value is unexpected
     BadOneof,
+    /// Bytes are not valid UTF-8
+    InvalidUtf8,
+    /// Map keys are not in strictly ascending order
+    UnorderedMapKeys,
+    /// Unwrap limit exceeded for {0} (limit: {1}, found: {2})
+    UnwrapLimitExceeded(String, usize, usize),
 
     //////////
     // DDML IO
@@ -64,12 +82,20 @@ pub enum Errors {
     OutputStreamNotFullyConsumed(usize),
     /// Input stream has not been exhausted. Remaining: {0}
     InputStreamNotFullyConsumed(usize),
+    /// Failed to write to output stream
+    StreamWriteFailure,
+    /// Failed to read from input stream
+    StreamReadFailure,
 
     //////////
     // Generic Transport
     //////////
     /// More than one message found: with link {0}
     MessageNotUnique(String),
+    /// None of the {0} messages found with link {1} was accepted
+    NoMessageAccepted(usize, String),
+    /// Message conflicts with another message already handled at the same link
+    Equivocation,
     /// Message at link {0} not found in state store
     MessageLinkNotFoundInStore(String),
     /// Message at link {0} not found in Tangle
@@ -78,6 +104,38 @@ pub enum Errors {
     MessageLinkNotFoundInBucket(String),
     /// Transport object is already borrowed
     TransportNotAvailable,
+    /// Transport failure injected with link {0}
+    InjectedTransportFailure(String),
+    /// Transport call with link {0} timed out after {1} ms
+    TransportTimeout(String, u128),
+    /// Transport circuit is open, call with link {0} rejected
+    TransportCircuitOpen(String),
+    /// Message at link {0} not found in cache
+    MessageLinkNotFoundInCache(String),
+    /// Malformed cache entry
+    MalformedCacheEntry,
+    /// Message at link {0} not found in database
+    MessageLinkNotFoundInDatabase(String),
+    /// Database failed to perform operation.
+    DatabaseOperationFailure,
+    /// Message at link {0} not found on HTTP server
+    MessageLinkNotFoundOnServer(String),
+    /// HTTP server store is full, message with index {0} rejected
+    MessageStoreFull(String),
+    /// HTTP transport failed to perform operation.
+    HttpOperationFailure,
+    /// HTTP request with link {0} failed with status {1}
+    HttpStatusFailure(String, u16),
+    /// Malformed HTTP transport response
+    MalformedHttpResponse,
+    /// Malformed transport log
+    MalformedTransportLog,
+    /// Transport log has no more recorded calls with link {0}
+    TransportLogExhausted(String),
+    /// Recorded transport failure: {1}
+    RecordedTransportFailure(TransportErrorKind, String),
+    /// Transport reported no outcome for the call with link {0}
+    TransportOutcomeMissing(String),
 
     //////////
     // Iota Transport
@@ -164,6 +222,12 @@ pub enum Errors {
     ChannelNotSingleDepth,
     /// Message '{0}' does not have a previous message
     NoPreviousMessage(String),
+    /// Signature key is held outside of memory, the key exchange key can not be derived from it
+    SigKeyNotInMemory,
+    /// Key exchange key rotation {0} is not newer than the known rotation {1}
+    StaleKeyExchangeKey(u32, u32),
+    /// Author key exchange key can not be replaced once the channel is announced
+    AuthorExchangeKeyAnnounced,
 
     //////////
     // User Recovery
@@ -172,10 +236,14 @@ pub enum Errors {
     AppInstRecoveryFailure(u8),
     /// Author signature pubkey recovery failed (expected: 0 | 1, found: {0})
     AuthorSigPkRecoveryFailure(u8),
+    /// Key exchange key rotation recovery failed (expected: 0 | 1, found: {0})
+    KeyRotationRecoveryFailure(u8),
     /// User Version does not match (expected: {0}, found: {1}
     UserVersionRecoveryFailure(u8, u8),
     /// Recovered flag does not match expected: flag (expected: {0}, found: {1})
     UserFlagRecoveryFailure(u8, u8),
+    /// User keys are held outside of memory and cannot be exported
+    KeyNotExportable,
 
     //////////
     // Examples
@@ -211,3 +279,26 @@ pub enum Errors {
     /// Subscriber {0} failed to unwrap message, may not have access to branch
     MessageUnwrapFailure(String),
 }
+
+impl Errors {
+    /// Kind of the error when returned by a transport.
+    ///
+    /// Errors which are not specific to transports are deemed transient, as a transport failing in
+    /// an unforeseen way might succeed when called again.
+    pub fn transport_error_kind(&self) -> TransportErrorKind {
+        match self {
+            Self::MessageLinkNotFoundInTangle(_)
+            | Self::MessageLinkNotFoundInBucket(_)
+            | Self::MessageLinkNotFoundInCache(_)
+            | Self::MessageLinkNotFoundInDatabase(_)
+            | Self::MessageLinkNotFoundOnServer(_)
+            | Self::IndexNotFound => TransportErrorKind::Missing,
+            Self::MessageNotUnique(_)
+            | Self::TransportCircuitOpen(_)
+            | Self::TransportLogExhausted(_)
+            | Self::TransportOutcomeMissing(_) => TransportErrorKind::Answered,
+            Self::RecordedTransportFailure(kind, _) => *kind,
+            _ => TransportErrorKind::Transient,
+        }
+    }
+}
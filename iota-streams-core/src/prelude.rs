@@ -4,7 +4,13 @@ pub use alloc::{
         self,
         Box,
     },
-    collections::vec_deque::VecDeque,
+    collections::{
+        btree_map::{
+            self,
+            BTreeMap,
+        },
+        vec_deque::VecDeque,
+    },
     format,
     rc::{
         self,
@@ -31,7 +37,13 @@ pub use std::{
         self,
         Box,
     },
-    collections::vec_deque::VecDeque,
+    collections::{
+        btree_map::{
+            self,
+            BTreeMap,
+        },
+        vec_deque::VecDeque,
+    },
     format,
     rc::{
         self,
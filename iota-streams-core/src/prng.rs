@@ -21,11 +21,21 @@ use crate::{
     },
 };
 
+pub use rand::{
+    CryptoRng,
+    RngCore,
+};
+
+/// Cryptographically secure random number generator, usable as a trait object.
+pub trait CryptoRngCore: RngCore + CryptoRng {}
+
+impl<R: RngCore + CryptoRng> CryptoRngCore for R {}
+
 /// Generate cryptographically secure bytes.
 /// Suitable for generating session and ephemeral keys.
 pub fn random_bytes<R, N: ArrayLength<u8>>(rng: &mut R) -> GenericArray<u8, N>
 where
-    R: rand::RngCore + rand::CryptoRng,
+    R: RngCore + CryptoRng + ?Sized,
 {
     let mut rnd = GenericArray::default();
     rng.fill_bytes(rnd.as_mut_slice());
@@ -35,15 +45,21 @@ where
 pub type Nonce = GenericArray<u8, U16>;
 
 /// Generate a random nonce.
-pub fn random_nonce() -> Nonce {
-    random_bytes(&mut rng())
+pub fn random_nonce<R>(rng: &mut R) -> Nonce
+where
+    R: RngCore + CryptoRng + ?Sized,
+{
+    random_bytes(rng)
 }
 
 pub type Key = GenericArray<u8, U32>;
 
 /// Generate a random key.
-pub fn random_key() -> Key {
-    random_bytes(&mut rng())
+pub fn random_key<R>(rng: &mut R) -> Key
+where
+    R: RngCore + CryptoRng + ?Sized,
+{
+    random_bytes(rng)
 }
 
 #[cfg(all(feature = "std", not(target_os = "espidf")))]
@@ -62,11 +78,6 @@ pub fn rng() -> rand::rngs::StdRng {
     <rand::rngs::StdRng as rand::SeedableRng>::from_entropy()
 }
 
-#[cfg(not(feature = "std"))]
-pub fn rng() -> rand::rngs::StdRng {
-    unimplemented!("No default global RNG present.");
-}
-
 /// Prng fixed key size.
 pub type KeySize<F> = spongos::KeySize<F>;
 pub type KeyType<F> = spongos::KeyType<F>;
@@ -169,10 +180,9 @@ impl<G: PRP> Rng<G> {
         Self { prng, nonce }
     }
 
-    #[allow(clippy::assign_op_pattern)]
     fn inc(&mut self) -> bool {
         for i in self.nonce.iter_mut() {
-            *i = *i + 1;
+            *i = i.wrapping_add(1);
             if *i != 0 {
                 return true;
             }
@@ -182,7 +192,7 @@ impl<G: PRP> Rng<G> {
     }
 }
 
-impl<G: PRP> rand::RngCore for Rng<G> {
+impl<G: PRP> RngCore for Rng<G> {
     fn next_u32(&mut self) -> u32 {
         let mut v = [0_u8; 4];
         self.prng.gen(&self.nonce[..], &mut v);
@@ -205,4 +215,4 @@ impl<G: PRP> rand::RngCore for Rng<G> {
     }
 }
 
-impl<G: PRP> rand::CryptoRng for Rng<G> {}
+impl<G: PRP> CryptoRng for Rng<G> {}
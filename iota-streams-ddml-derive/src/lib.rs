@@ -0,0 +1,549 @@
+//! Derive macros implementing `ContentSizeof`, `ContentWrap` and `ContentUnwrap` from a single DDML description.
+//!
+//! The description is given as a list of commands in one or more `#[ddml(...)]` attributes and is processed in
+//! order by each of the derived implementations. Each command is labelled in traces of wrap and unwrap contexts with
+//! the name of its field:
+//!
+//! ```ignore
+//! #[derive(ContentSizeof, ContentWrap, ContentUnwrap, Default)]
+//! #[ddml(
+//!     join(link),
+//!     absorb(public_payload),
+//!     mask(masked_payload),
+//!     repeated(tags, absorb),
+//!     commit,
+//!     squeeze(&Mac(32)),
+//! )]
+//! #[ddml(bound = "Rel: Eq + Default + SkipFallback<F>")]
+//! pub struct Content<F, Rel> {
+//!     link: Rel,
+//!     public_payload: Bytes,
+//!     masked_payload: Bytes,
+//!     tags: Vec<NBytes<U16>>,
+//!     _phantom: PhantomData<F>,
+//! }
+//! ```
+//!
+//! # Commands
+//!
+//! * `absorb(arg)`, `mask(arg)`, `skip(arg)`, `squeeze(arg)` -- process a field.
+//! * `join(link)` -- join the spongos state of the linked message. Sizeof uses an empty link store, wrap and unwrap the
+//!   store they are given, which must implement `LinkStore<F, _>` for the type of the `link` field.
+//! * `commit` -- commit the spongos state.
+//! * `ed25519(key, hash)` -- sign (wrap) or verify (unwrap) the message with `key`.
+//! * `x25519(secret, key)` -- absorb the key exchanged with `secret`, then process `key`.
+//! * `repeated(items, op)` -- process the length of the `Vec` field `items` and then each of its elements with `op`,
+//!   one of `absorb`, `mask` or `skip`.
+//!
+//! # Arguments
+//!
+//! The name of a struct field is passed by reference to sizeof and wrap (or as-is if the field is already a
+//! reference) and by mutable reference to unwrap. Keys of `ed25519` and `x25519` are always passed by shared
+//! reference. `external(field)` wraps the field into `External`; external fields are known to both parties and
+//! are likewise passed by shared reference. Any other expression is passed verbatim and is
+//! evaluated with `self` in scope, so the same description can not only refer to fields but also to eg.
+//! `self.sig_kp.public_key()` or `&Mac(32)`.
+//!
+//! `#[ddml(let name = arg)]` names a field or expression, `name` is then replaced with `arg` wherever it is passed
+//! to a command.
+//!
+//! # Wrap and unwrap contents
+//!
+//! Messages usually borrow what they send and own what they receive, so that the wrap and unwrap sides are
+//! different structs. `contents!` defines both of them from a single description, given as an inner attribute. The
+//! first struct implements `ContentSizeof` and `ContentWrap`, the second one `ContentUnwrap`; bindings let the same
+//! description refer to differently named fields:
+//!
+//! ```ignore
+//! contents! {
+//!     #![ddml(join(link), absorb(sig_pk), mask(payload), ed25519(key, HashSig))]
+//!
+//!     #[ddml(let sig_pk = self.sig_kp.public_key(), let key = sig_kp)]
+//!     #[ddml(bound = "Rel: 'a + Eq + SkipFallback<F>")]
+//!     pub struct ContentWrap<'a, F, Rel> {
+//!         link: &'a Rel,
+//!         payload: &'a Bytes,
+//!         sig_kp: &'a dyn ed25519::Signer,
+//!         _phantom: PhantomData<F>,
+//!     }
+//!
+//!     #[derive(Default)]
+//!     #[ddml(let key = sig_pk)]
+//!     #[ddml(bound = "Rel: Eq + Default + SkipFallback<F>")]
+//!     pub struct ContentUnwrap<F, Rel> {
+//!         link: Rel,
+//!         payload: Bytes,
+//!         sig_pk: ed25519::PublicKey,
+//!         _phantom: PhantomData<F>,
+//!     }
+//! }
+//! ```
+//!
+//! # Generics
+//!
+//! The PRP type parameter of the implementations is the struct type parameter named `F`, or a new one if the struct
+//! has none. Additional bounds required by the commands are given with `#[ddml(bound = "...")]`, the where clause of
+//! the struct is kept.
+//!
+//! The generated code refers to the `iota_streams_core`, `iota_streams_ddml` and `iota_streams_app` crates, which
+//! must be dependencies of the crate using the derives.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{
+        Parse,
+        ParseStream,
+    },
+    parse_macro_input,
+    parse_quote,
+    Attribute,
+    Data,
+    DeriveInput,
+    Expr,
+    Fields,
+    GenericArgument,
+    GenericParam,
+    Ident,
+    PathArguments,
+    Result,
+    Type,
+};
+
+mod program;
+use program::{
+    is_ddml,
+    Command,
+    Program,
+};
+
+/// Derive `iota_streams_app::message::ContentSizeof` from the `#[ddml(...)]` description.
+#[proc_macro_derive(ContentSizeof, attributes(ddml))]
+pub fn derive_content_sizeof(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as DeriveInput), Role::Sizeof)
+}
+
+/// Derive `iota_streams_app::message::ContentWrap` from the `#[ddml(...)]` description.
+///
+/// `ContentWrap` requires `ContentSizeof`, usually derived from the same description.
+#[proc_macro_derive(ContentWrap, attributes(ddml))]
+pub fn derive_content_wrap(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as DeriveInput), Role::Wrap)
+}
+
+/// Derive `iota_streams_app::message::ContentUnwrap` from the `#[ddml(...)]` description.
+#[proc_macro_derive(ContentUnwrap, attributes(ddml))]
+pub fn derive_content_unwrap(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as DeriveInput), Role::Unwrap)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Sizeof,
+    Wrap,
+    Unwrap,
+}
+
+/// Define the wrap and unwrap contents of a message, deriving both sides from one `#![ddml(...)]` description.
+///
+/// The first struct gets `ContentSizeof` and `ContentWrap`, the second one `ContentUnwrap`. The `#[ddml(...)]`
+/// attributes of the structs give their bounds and bindings only.
+#[proc_macro]
+pub fn contents(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as Contents)
+        .expand()
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput, role: Role) -> TokenStream {
+    Program::from_attrs(&input.attrs)
+        .and_then(|program| Content::new(&input, program))
+        .and_then(|content| content.expand(role))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Input of `contents!`: the shared description followed by the wrap and unwrap structs.
+struct Contents {
+    description: Vec<Attribute>,
+    wrap: DeriveInput,
+    unwrap: DeriveInput,
+}
+
+impl Parse for Contents {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            description: input.call(Attribute::parse_inner)?,
+            wrap: input.parse()?,
+            unwrap: input.parse()?,
+        })
+    }
+}
+
+impl Contents {
+    fn expand(mut self) -> Result<TokenStream2> {
+        let mut description = Program::default();
+        for attr in &self.description {
+            if !is_ddml(attr) {
+                return Err(syn::Error::new_spanned(attr, "expected `#![ddml(...)]`"));
+            }
+            let program = Program::from_attr(attr)?;
+            if !program.bounds.is_empty() || !program.bindings.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "bounds and bindings are given in the `#[ddml(...)]` attributes of each struct",
+                ));
+            }
+            description.extend(program);
+        }
+
+        let wrap = Self::content(&mut self.wrap, &description)?;
+        let unwrap = Self::content(&mut self.unwrap, &description)?;
+        let (wrap_input, unwrap_input) = (&wrap.input, &unwrap.input);
+        let sizeof_impl = wrap.expand(Role::Sizeof)?;
+        let wrap_impl = wrap.expand(Role::Wrap)?;
+        let unwrap_impl = unwrap.expand(Role::Unwrap)?;
+        Ok(quote! {
+            #wrap_input
+            #unwrap_input
+            #sizeof_impl
+            #wrap_impl
+            #unwrap_impl
+        })
+    }
+
+    /// Content of one of the structs, whose `#[ddml(...)]` attributes are taken out as they are not emitted.
+    fn content<'a>(input: &'a mut DeriveInput, description: &Program) -> Result<Content<'a>> {
+        let mut program = description.clone();
+        for attr in input.attrs.iter().filter(|attr| is_ddml(attr)) {
+            let own = Program::from_attr(attr)?;
+            if !own.commands.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "the commands are given once for both structs in `#![ddml(...)]`",
+                ));
+            }
+            program.extend(own);
+        }
+        input.attrs.retain(|attr| !is_ddml(attr));
+        Content::new(input, program)
+    }
+}
+
+struct Content<'a> {
+    input: &'a DeriveInput,
+    fields: Vec<(&'a Ident, &'a Type)>,
+    program: Program,
+}
+
+impl<'a> Content<'a> {
+    fn new(input: &'a DeriveInput, program: Program) -> Result<Self> {
+        let fields = match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(fields) => fields
+                    .named
+                    .iter()
+                    .map(|field| (field.ident.as_ref().unwrap(), &field.ty))
+                    .collect(),
+                Fields::Unit => Vec::new(),
+                Fields::Unnamed(_) => {
+                    return Err(syn::Error::new_spanned(
+                        &input.ident,
+                        "DDML content must be a struct with named fields",
+                    ))
+                }
+            },
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "DDML content must be a struct with named fields",
+                ))
+            }
+        };
+        Ok(Self { input, fields, program })
+    }
+
+    fn field(&self, expr: &Expr) -> Option<(&'a Ident, &'a Type)> {
+        match expr {
+            Expr::Path(path) if path.qself.is_none() => path
+                .path
+                .get_ident()
+                .and_then(|ident| self.fields.iter().find(|(name, _)| *name == ident).copied()),
+            _ => None,
+        }
+    }
+
+    /// Field or expression bound to `expr` with `let`, or `expr` itself.
+    fn resolve<'e>(&'e self, expr: &'e Expr) -> &'e Expr {
+        match expr {
+            Expr::Path(path) if path.qself.is_none() => path
+                .path
+                .get_ident()
+                .and_then(|ident| self.program.bindings.iter().find(|(name, _)| name == ident))
+                .map_or(expr, |(_, bound)| bound),
+            _ => expr,
+        }
+    }
+
+    /// Field (or expression) passed to a command. Outputs are borrowed mutably when unwrapping.
+    fn arg(&self, expr: &Expr, role: Role, output: bool) -> TokenStream2 {
+        let expr = self.resolve(expr);
+        if let Some((ident, ty)) = self.field(expr) {
+            return borrow(ident, ty, role, output);
+        }
+        if let Expr::Call(call) = expr {
+            if let (Expr::Path(func), 1) = (&*call.func, call.args.len()) {
+                if func.path.is_ident("external") {
+                    if let Some((ident, ty)) = self.field(&call.args[0]) {
+                        let field = borrow(ident, ty, role, false);
+                        return quote!(::iota_streams_ddml::types::External(#field));
+                    }
+                }
+            }
+        }
+        quote!(#expr)
+    }
+
+    /// Type of the link joined by the description, used as the link type of the store.
+    fn link_type(&self) -> Option<&'a Type> {
+        self.program.commands.iter().find_map(|command| match command {
+            Command::Join(link) => self.field(self.resolve(link)).map(|(_, ty)| match ty {
+                Type::Reference(reference) => &*reference.elem,
+                ty => ty,
+            }),
+            _ => None,
+        })
+    }
+
+    /// Label of the command in traces: the name its argument has in the description, the command name otherwise.
+    fn label(command: &Command) -> String {
+        fn name(expr: &Expr) -> Option<String> {
+            match expr {
+                Expr::Path(path) if path.qself.is_none() => path.path.get_ident().map(Ident::to_string),
+                Expr::Call(call) if call.args.len() == 1 => match &*call.func {
+                    Expr::Path(func) if func.path.is_ident("external") => name(&call.args[0]),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        match command {
+            Command::Op(op, arg) => name(arg).unwrap_or_else(|| op.method().to_string()),
+            Command::Join(link) => name(link).unwrap_or_else(|| "join".to_string()),
+            Command::Commit => "commit".to_string(),
+            Command::Ed25519(..) => "ed25519".to_string(),
+            Command::X25519(_, key) => name(key).unwrap_or_else(|| "x25519".to_string()),
+            Command::Repeated(items, _) => items.to_string(),
+        }
+    }
+
+    fn command(&self, command: &Command, role: Role) -> Result<TokenStream2> {
+        let label = Self::label(command);
+        let command = self.command_body(command, role)?;
+        Ok(quote! {
+            ctx.label(#label)?;
+            #command
+        })
+    }
+
+    fn command_body(&self, command: &Command, role: Role) -> Result<TokenStream2> {
+        Ok(match command {
+            Command::Op(op, arg) => {
+                let method = op.method();
+                let arg = self.arg(arg, role, true);
+                quote!(ctx.#method(#arg)?;)
+            }
+            Command::Join(link) => {
+                let store = if role == Role::Sizeof {
+                    let link_type = self
+                        .link_type()
+                        .ok_or_else(|| syn::Error::new_spanned(link, "`join` expects the name of the link field"))?;
+                    quote!(&::iota_streams_ddml::link_store::EmptyLinkStore::<F, #link_type, ()>::default())
+                } else {
+                    quote!(store)
+                };
+                let link = self.arg(link, role, true);
+                quote!(ctx.join(#store, #link)?;)
+            }
+            Command::Commit => quote!(ctx.commit()?;),
+            Command::Ed25519(key, hash) => {
+                let key = self.arg(key, role, false);
+                let hash = self.arg(hash, role, false);
+                quote!(ctx.ed25519(#key, #hash)?;)
+            }
+            Command::X25519(secret, key) => {
+                let secret = self.arg(secret, role, false);
+                let key = self.arg(key, role, true);
+                quote!(ctx.x25519(#secret, #key)?;)
+            }
+            Command::Repeated(items, op) => {
+                let method = op.method();
+                let ty = self
+                    .fields
+                    .iter()
+                    .find(|(name, _)| *name == items)
+                    .and_then(|(_, ty)| vec_element(ty))
+                    .ok_or_else(|| syn::Error::new_spanned(items, "`repeated` expects the name of a `Vec` field"))?;
+                if role == Role::Unwrap {
+                    // The count is read from the message, `repeated` bounds it and the nesting depth
+                    // with the limits of the context
+                    quote! {
+                        let mut repeated = ::iota_streams_ddml::types::Size(0);
+                        ctx.#method(&mut repeated)?;
+                        self.#items.clear();
+                        let items = &mut self.#items;
+                        ctx.repeated(repeated, |ctx| {
+                            let mut item = <#ty as ::core::default::Default>::default();
+                            ctx.#method(&mut item)?;
+                            items.push(item);
+                            Ok(ctx)
+                        })?;
+                    }
+                } else {
+                    quote! {
+                        ctx.#method(&::iota_streams_ddml::types::Size(self.#items.len()))?;
+                        for item in self.#items.iter() {
+                            ctx.#method(item)?;
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    fn expand(&self, role: Role) -> Result<TokenStream2> {
+        let commands = self
+            .program
+            .commands
+            .iter()
+            .map(|command| self.command(command, role))
+            .collect::<Result<Vec<_>>>()?;
+
+        let name = &self.input.ident;
+        let (_, ty_generics, _) = self.input.generics.split_for_impl();
+        let mut generics = self.input.generics.clone();
+        let has_f = generics
+            .params
+            .iter()
+            .any(|param| matches!(param, GenericParam::Type(ty) if ty.ident == "F"));
+        if !has_f {
+            generics.params.push(parse_quote!(F));
+        }
+        if role != Role::Sizeof {
+            generics.params.push(parse_quote!(Store));
+        }
+        let where_clause = generics.make_where_clause();
+        where_clause
+            .predicates
+            .push(parse_quote!(F: ::iota_streams_core::sponge::prp::PRP));
+        if role != Role::Sizeof {
+            if let Some(link_type) = self.link_type() {
+                where_clause
+                    .predicates
+                    .push(parse_quote!(Store: ::iota_streams_ddml::link_store::LinkStore<F, #link_type>));
+            }
+        }
+        where_clause.predicates.extend(self.program.bounds.iter().cloned());
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        let commands_in_scope = quote! {
+            #[allow(unused_imports)]
+            use ::iota_streams_ddml::command::{
+                Absorb as _,
+                Commit as _,
+                Ed25519 as _,
+                Join as _,
+                Label as _,
+                Mask as _,
+                Repeated as _,
+                Skip as _,
+                Squeeze as _,
+                X25519 as _,
+            };
+        };
+        let body = match role {
+            Role::Sizeof => quote! {
+                impl #impl_generics ::iota_streams_app::message::ContentSizeof<F> for #name #ty_generics #where_clause {
+                    async fn sizeof<'c>(
+                        &self,
+                        ctx: &'c mut ::iota_streams_ddml::command::sizeof::Context<F>,
+                    ) -> ::iota_streams_core::Result<&'c mut ::iota_streams_ddml::command::sizeof::Context<F>> {
+                        #commands_in_scope
+                        #(#commands)*
+                        Ok(ctx)
+                    }
+                }
+            },
+            Role::Wrap => quote! {
+                impl #impl_generics ::iota_streams_app::message::ContentWrap<F, Store> for #name #ty_generics #where_clause {
+                    async fn wrap<'c, OS: ::iota_streams_ddml::io::OStream>(
+                        &self,
+                        store: &Store,
+                        ctx: &'c mut ::iota_streams_ddml::command::wrap::Context<F, OS>,
+                    ) -> ::iota_streams_core::Result<&'c mut ::iota_streams_ddml::command::wrap::Context<F, OS>> {
+                        #commands_in_scope
+                        let _ = store;
+                        #(#commands)*
+                        Ok(ctx)
+                    }
+                }
+            },
+            Role::Unwrap => quote! {
+                impl #impl_generics ::iota_streams_app::message::ContentUnwrap<F, Store> for #name #ty_generics #where_clause {
+                    async fn unwrap<'c, IS: ::iota_streams_ddml::io::IStream>(
+                        &mut self,
+                        store: &Store,
+                        ctx: &'c mut ::iota_streams_ddml::command::unwrap::Context<F, IS>,
+                    ) -> ::iota_streams_core::Result<&'c mut ::iota_streams_ddml::command::unwrap::Context<F, IS>> {
+                        #commands_in_scope
+                        let _ = store;
+                        #(#commands)*
+                        Ok(ctx)
+                    }
+                }
+            },
+        };
+
+        // `async_trait` boxes the futures, `Box` is not in the prelude of `no_std` crates.
+        Ok(quote! {
+            const _: () = {
+                use ::iota_streams_core::prelude::Box;
+                #[::iota_streams_core::async_trait(?Send)]
+                #body
+            };
+        })
+    }
+}
+
+fn is_reference(ty: &Type) -> bool {
+    matches!(ty, Type::Reference(_))
+}
+
+fn borrow(ident: &Ident, ty: &Type, role: Role, output: bool) -> TokenStream2 {
+    if role == Role::Unwrap && output {
+        quote!(&mut self.#ident)
+    } else if is_reference(ty) {
+        quote!(self.#ident)
+    } else {
+        quote!(&self.#ident)
+    }
+}
+
+fn vec_element(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last().filter(|segment| segment.ident == "Vec")?;
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
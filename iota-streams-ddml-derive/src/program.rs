@@ -0,0 +1,165 @@
+//! Parsing of the DDML description given in `#[ddml(...)]` attributes.
+
+use proc_macro2::Span;
+use syn::{
+    parenthesized,
+    parse::{
+        Parse,
+        ParseStream,
+    },
+    punctuated::Punctuated,
+    Attribute,
+    Expr,
+    Ident,
+    LitStr,
+    Result,
+    Token,
+    WherePredicate,
+};
+
+/// Commands taking a single field or expression.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Absorb,
+    Mask,
+    Skip,
+    Squeeze,
+}
+
+impl Op {
+    fn from_ident(ident: &Ident) -> Option<Self> {
+        match ident.to_string().as_str() {
+            "absorb" => Some(Op::Absorb),
+            "mask" => Some(Op::Mask),
+            "skip" => Some(Op::Skip),
+            "squeeze" => Some(Op::Squeeze),
+            _ => None,
+        }
+    }
+
+    pub fn method(self) -> Ident {
+        let name = match self {
+            Op::Absorb => "absorb",
+            Op::Mask => "mask",
+            Op::Skip => "skip",
+            Op::Squeeze => "squeeze",
+        };
+        Ident::new(name, Span::call_site())
+    }
+}
+
+#[derive(Clone)]
+pub enum Command {
+    Op(Op, Expr),
+    Join(Expr),
+    Commit,
+    Ed25519(Expr, Expr),
+    X25519(Expr, Expr),
+    Repeated(Ident, Op),
+}
+
+enum Item {
+    Command(Box<Command>),
+    Bound(Punctuated<WherePredicate, Token![,]>),
+    Binding(Box<(Ident, Expr)>),
+}
+
+/// DDML description of a message content.
+#[derive(Clone, Default)]
+pub struct Program {
+    pub commands: Vec<Command>,
+    pub bounds: Vec<WherePredicate>,
+    /// Names given to fields or expressions with `let name = expr`, usable as command arguments.
+    pub bindings: Vec<(Ident, Expr)>,
+}
+
+impl Program {
+    /// Collect the commands, bounds and bindings of all the `#[ddml(...)]` attributes, in order.
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut program = Program::default();
+        for attr in attrs.iter().filter(|attr| is_ddml(attr)) {
+            program.extend(Self::from_attr(attr)?);
+        }
+        Ok(program)
+    }
+
+    pub fn from_attr(attr: &Attribute) -> Result<Self> {
+        let mut program = Program::default();
+        let items = attr.parse_args_with(Punctuated::<Item, Token![,]>::parse_terminated)?;
+        for item in items {
+            match item {
+                Item::Command(command) => program.commands.push(*command),
+                Item::Bound(bounds) => program.bounds.extend(bounds),
+                Item::Binding(binding) => program.bindings.push(*binding),
+            }
+        }
+        Ok(program)
+    }
+
+    pub fn extend(&mut self, other: Program) {
+        self.commands.extend(other.commands);
+        self.bounds.extend(other.bounds);
+        self.bindings.extend(other.bindings);
+    }
+}
+
+pub fn is_ddml(attr: &Attribute) -> bool {
+    attr.path.is_ident("ddml")
+}
+
+impl Parse for Item {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![let]) {
+            input.parse::<Token![let]>()?;
+            let name = input.parse()?;
+            input.parse::<Token![=]>()?;
+            return Ok(Item::Binding(Box::new((name, input.parse()?))));
+        }
+        let ident: Ident = input.parse()?;
+        if ident == "bound" {
+            input.parse::<Token![=]>()?;
+            let bounds: LitStr = input.parse()?;
+            return Ok(Item::Bound(
+                bounds.parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?,
+            ));
+        }
+        if ident == "commit" {
+            return Ok(Item::Command(Box::new(Command::Commit)));
+        }
+
+        let content;
+        parenthesized!(content in input);
+        let command = if let Some(op) = Op::from_ident(&ident) {
+            Command::Op(op, content.parse()?)
+        } else if ident == "join" {
+            Command::Join(content.parse()?)
+        } else if ident == "ed25519" || ident == "x25519" {
+            let key = content.parse()?;
+            content.parse::<Token![,]>()?;
+            let other = content.parse()?;
+            if ident == "ed25519" {
+                Command::Ed25519(key, other)
+            } else {
+                Command::X25519(key, other)
+            }
+        } else if ident == "repeated" {
+            let field = content.parse()?;
+            content.parse::<Token![,]>()?;
+            let op_ident: Ident = content.parse()?;
+            let op = Op::from_ident(&op_ident)
+                .filter(|op| *op != Op::Squeeze)
+                .ok_or_else(|| syn::Error::new(op_ident.span(), "expected one of `absorb`, `mask` or `skip`"))?;
+            Command::Repeated(field, op)
+        } else {
+            return Err(syn::Error::new(
+                ident.span(),
+                "unknown DDML command, expected one of `absorb`, `mask`, `skip`, `squeeze`, `join`, `commit`, \
+                 `ed25519`, `x25519` or `repeated`",
+            ));
+        };
+        if !content.is_empty() {
+            return Err(content.error("unexpected tokens after command arguments"));
+        }
+        Ok(Item::Command(Box::new(command)))
+    }
+}
@@ -0,0 +1,238 @@
+use core::marker::PhantomData;
+
+use iota_streams_app::message::{
+    ContentSizeof,
+    ContentUnwrap,
+    ContentWrap,
+};
+use iota_streams_core::{
+    prelude::{
+        generic_array::GenericArray,
+        typenum::U16,
+    },
+    Result,
+};
+use iota_streams_core_edsig::signature::ed25519;
+use iota_streams_core_keccak::sponge::prp::keccak::KeccakF1600;
+use iota_streams_ddml::{
+    command::{
+        sizeof,
+        unwrap,
+        wrap,
+    },
+    types::*,
+};
+use iota_streams_ddml_derive::contents;
+
+type F = KeccakF1600;
+
+#[derive(Default, ContentSizeof, ContentWrap, ContentUnwrap)]
+#[ddml(
+    absorb(public_payload),
+    mask(masked_payload),
+    repeated(tags, mask),
+    absorb(external(context)),
+    commit,
+    squeeze(&Mac(32))
+)]
+struct CustomContent<F> {
+    public_payload: Bytes,
+    masked_payload: Bytes,
+    tags: Vec<NBytes<U16>>,
+    context: NBytes<U16>,
+    _phantom: PhantomData<F>,
+}
+
+contents! {
+    #![ddml(absorb(sig_pk), mask(payload), repeated(tags, absorb), ed25519(key, HashSig))]
+
+    #[ddml(let sig_pk = self.sig_kp.public_key(), let key = sig_kp)]
+    struct SignedWrap<'a, F> {
+        payload: &'a Bytes,
+        tags: Vec<NBytes<U16>>,
+        sig_kp: &'a dyn ed25519::Signer,
+        _phantom: PhantomData<F>,
+    }
+
+    #[derive(Default)]
+    #[ddml(let key = sig_pk)]
+    struct SignedUnwrap<F> {
+        payload: Bytes,
+        tags: Vec<NBytes<U16>>,
+        sig_pk: ed25519::PublicKey,
+        _phantom: PhantomData<F>,
+    }
+}
+
+fn tag(byte: u8) -> NBytes<U16> {
+    NBytes::<U16>(GenericArray::clone_from_slice(&[byte; 16]))
+}
+
+fn keypair() -> ed25519::Keypair {
+    let secret = ed25519::SecretKey::from_bytes(&[7; ed25519::SECRET_KEY_LENGTH]).unwrap();
+    let public = ed25519::PublicKey::from(&secret);
+    ed25519::Keypair { secret, public }
+}
+
+async fn wrap_content(content: &impl ContentWrap<F, ()>) -> Result<Vec<u8>> {
+    let mut ctx = sizeof::Context::<F>::new();
+    content.sizeof(&mut ctx).await?;
+    let mut buf = vec![0_u8; ctx.get_size()];
+    let mut ctx = wrap::Context::<F, &mut [u8]>::new(&mut buf[..]);
+    content.wrap(&(), &mut ctx).await?;
+    assert!(ctx.stream.is_empty());
+    Ok(buf)
+}
+
+async fn unwrap_content(content: &mut impl ContentUnwrap<F, ()>, buf: &[u8]) -> Result<()> {
+    let mut ctx = unwrap::Context::<F, &[u8]>::new(buf);
+    content.unwrap(&(), &mut ctx).await?;
+    assert!(ctx.stream.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn derived_content_round_trip() -> Result<()> {
+    let content = CustomContent::<F> {
+        public_payload: Bytes::from(b"public".to_vec()),
+        masked_payload: Bytes::from(b"masked".to_vec()),
+        tags: vec![tag(1), tag(2)],
+        context: tag(3),
+        _phantom: PhantomData,
+    };
+    let buf = wrap_content(&content).await?;
+
+    let mut unwrapped = CustomContent::<F> {
+        context: tag(3),
+        ..CustomContent::default()
+    };
+    unwrap_content(&mut unwrapped, &buf).await?;
+    assert_eq!(content.public_payload, unwrapped.public_payload);
+    assert_eq!(content.masked_payload, unwrapped.masked_payload);
+    assert_eq!(content.tags, unwrapped.tags);
+
+    let mut other_context = CustomContent::<F> {
+        context: tag(4),
+        ..CustomContent::default()
+    };
+    assert!(unwrap_content(&mut other_context, &buf).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn contents_wrap_and_unwrap_one_description() -> Result<()> {
+    let kp = keypair();
+    let payload = Bytes::from(b"payload".to_vec());
+    let content = SignedWrap::<F> {
+        payload: &payload,
+        tags: vec![tag(1), tag(2), tag(3)],
+        sig_kp: &kp,
+        _phantom: PhantomData,
+    };
+    let buf = wrap_content(&content).await?;
+    // Public key, size and content of the payload, number of tags, tags and signature; sizes take 2 bytes
+    assert_eq!(
+        buf.len(),
+        ed25519::PUBLIC_KEY_LENGTH + 2 + b"payload".len() + 2 + 3 * 16 + ed25519::SIGNATURE_LENGTH
+    );
+
+    let mut unwrapped = SignedUnwrap::<F>::default();
+    unwrap_content(&mut unwrapped, &buf).await?;
+    assert_eq!(unwrapped.sig_pk, kp.public);
+    assert_eq!(unwrapped.payload, payload);
+    assert_eq!(unwrapped.tags, content.tags);
+    Ok(())
+}
+
+#[tokio::test]
+async fn contents_unwrap_verifies_the_signature() -> Result<()> {
+    let kp = keypair();
+    let payload = Bytes::from(b"payload".to_vec());
+    let content = SignedWrap::<F> {
+        payload: &payload,
+        tags: vec![tag(1)],
+        sig_kp: &kp,
+        _phantom: PhantomData,
+    };
+    let buf = wrap_content(&content).await?;
+
+    // Any altered byte, be it the key, the masked payload, a tag or the signature, fails verification
+    for &i in &[
+        0,
+        ed25519::PUBLIC_KEY_LENGTH + 1,
+        buf.len() - ed25519::SIGNATURE_LENGTH - 1,
+        buf.len() - 1,
+    ] {
+        let mut tampered = buf.clone();
+        tampered[i] ^= 1;
+        let mut unwrapped = SignedUnwrap::<F>::default();
+        assert!(
+            unwrap_content(&mut unwrapped, &tampered).await.is_err(),
+            "byte {} altered",
+            i
+        );
+    }
+
+    let mut unwrapped = SignedUnwrap::<F>::default();
+    assert!(unwrap_content(&mut unwrapped, &buf[..buf.len() - 1]).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn derived_unwrap_enforces_repeated_limit() -> Result<()> {
+    let kp = keypair();
+    let payload = Bytes::from(b"payload".to_vec());
+    let content = SignedWrap::<F> {
+        payload: &payload,
+        tags: vec![tag(1), tag(2), tag(3)],
+        sig_kp: &kp,
+        _phantom: PhantomData,
+    };
+    let buf = wrap_content(&content).await?;
+
+    let mut unwrapped = SignedUnwrap::<F>::default();
+    let limits = unwrap::Limits::default().with_max_repeated(2);
+    let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]).with_limits(limits);
+    assert!(unwrapped.unwrap(&(), &mut ctx).await.is_err());
+    assert!(unwrapped.tags.is_empty());
+
+    let mut unwrapped = SignedUnwrap::<F>::default();
+    let limits = unwrap::Limits::default().with_max_repeated(3);
+    let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]).with_limits(limits);
+    unwrapped.unwrap(&(), &mut ctx).await?;
+    assert_eq!(unwrapped.tags, content.tags);
+    Ok(())
+}
+
+#[tokio::test]
+async fn commands_are_labelled_with_description_names() -> Result<()> {
+    let kp = keypair();
+    let payload = Bytes::from(b"payload".to_vec());
+    let content = SignedWrap::<F> {
+        payload: &payload,
+        tags: vec![tag(1)],
+        sig_kp: &kp,
+        _phantom: PhantomData,
+    };
+    let buf = wrap_content(&content).await?;
+    let mut wrap_buf = vec![0_u8; buf.len()];
+    let mut wrap_ctx = wrap::Context::<F, &mut [u8]>::new_traced(&mut wrap_buf[..]);
+    content.wrap(&(), &mut wrap_ctx).await?;
+    let mut unwrapped = SignedUnwrap::<F>::default();
+    let mut unwrap_ctx = unwrap::Context::<F, &[u8]>::new_traced(&buf[..]);
+    unwrapped.unwrap(&(), &mut unwrap_ctx).await?;
+
+    // Bound names of the description label both sides alike
+    let (wrap_trace, unwrap_trace) = (wrap_ctx.trace.unwrap(), unwrap_ctx.trace.unwrap());
+    let fields = wrap_trace
+        .entries()
+        .iter()
+        .map(|entry| entry.field.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(fields.first(), Some(&"sig_pk"));
+    for field in &["payload", "tags", "ed25519"] {
+        assert!(fields.contains(field), "field {} not labelled", field);
+    }
+    assert_eq!(wrap_trace.diverges_at(&unwrap_trace), None);
+    Ok(())
+}
@@ -0,0 +1,105 @@
+//! Types that can be nested into composite DDML types.
+
+use iota_streams_core::Result;
+
+use super::{
+    Absorb,
+    Mask,
+    Skip,
+};
+use crate::types::{
+    ArrayLength,
+    Bytes,
+    Int16,
+    Int32,
+    Int64,
+    Int8,
+    NBytes,
+    Size,
+    Str,
+    Uint16,
+    Uint32,
+    Uint64,
+    Uint8,
+    Varint,
+    Varuint,
+};
+
+/// Value of an `Option` or key or value of a `Map`, processed with context `C`.
+///
+/// It is implemented for references to the basic DDML types (`&T` when wrapping, `&mut T` when unwrapping) and to
+/// the enums declared with [`oneof!`](crate::oneof). Composite types can not be nested into each other: the set of
+/// element types is kept closed so that resolving the commands of composite types terminates.
+pub trait Element<C> {
+    fn absorb(self, ctx: &mut C) -> Result<&mut C>;
+    fn mask(self, ctx: &mut C) -> Result<&mut C>;
+    fn skip(self, ctx: &mut C) -> Result<&mut C>;
+}
+
+macro_rules! impl_element {
+    ($($t:ty),+) => {
+        $(
+            impl<'a, C> Element<C> for &'a $t
+            where
+                C: Absorb<&'a $t> + Mask<&'a $t> + Skip<&'a $t>,
+            {
+                fn absorb(self, ctx: &mut C) -> Result<&mut C> {
+                    ctx.absorb(self)
+                }
+                fn mask(self, ctx: &mut C) -> Result<&mut C> {
+                    ctx.mask(self)
+                }
+                fn skip(self, ctx: &mut C) -> Result<&mut C> {
+                    ctx.skip(self)
+                }
+            }
+
+            impl<'a, C> Element<C> for &'a mut $t
+            where
+                C: Absorb<&'a mut $t> + Mask<&'a mut $t> + Skip<&'a mut $t>,
+            {
+                fn absorb(self, ctx: &mut C) -> Result<&mut C> {
+                    ctx.absorb(self)
+                }
+                fn mask(self, ctx: &mut C) -> Result<&mut C> {
+                    ctx.mask(self)
+                }
+                fn skip(self, ctx: &mut C) -> Result<&mut C> {
+                    ctx.skip(self)
+                }
+            }
+        )+
+    };
+}
+
+impl_element!(Uint8, Uint16, Uint32, Uint64, Int8, Int16, Int32, Int64, Size, Varuint, Varint, Bytes, Str);
+
+impl<'a, C, N: ArrayLength<u8>> Element<C> for &'a NBytes<N>
+where
+    C: Absorb<&'a NBytes<N>> + Mask<&'a NBytes<N>> + Skip<&'a NBytes<N>>,
+{
+    fn absorb(self, ctx: &mut C) -> Result<&mut C> {
+        ctx.absorb(self)
+    }
+    fn mask(self, ctx: &mut C) -> Result<&mut C> {
+        ctx.mask(self)
+    }
+    fn skip(self, ctx: &mut C) -> Result<&mut C> {
+        ctx.skip(self)
+    }
+}
+
+impl<'a, C, N: ArrayLength<u8>> Element<C> for &'a mut NBytes<N>
+where
+    C: Absorb<&'a mut NBytes<N>> + Mask<&'a mut NBytes<N>> + Skip<&'a mut NBytes<N>>,
+{
+    fn absorb(self, ctx: &mut C) -> Result<&mut C> {
+        ctx.absorb(self)
+    }
+    fn mask(self, ctx: &mut C) -> Result<&mut C> {
+        ctx.mask(self)
+    }
+    fn skip(self, ctx: &mut C) -> Result<&mut C> {
+        ctx.skip(self)
+    }
+}
@@ -102,6 +102,9 @@ pub trait Guard {
 
 /// Dump context info into stdout.
 /// Use it like this: `ctx.dump(format_args!("checkpoint"))`
+///
+/// Wrap and unwrap contexts created with `new_traced` don't print anything, instead the
+/// arguments label subsequent fields in the trace (see `trace` module).
 pub trait Dump {
     #[allow(unused_variables)]
     fn dump<'a>(&mut self, args: core::fmt::Arguments<'a>) -> Result<&mut Self> {
@@ -111,6 +114,17 @@ pub trait Dump {
     }
 }
 
+/// Label the following commands in the trace of the context, if it has one.
+///
+/// Unlike `dump`, labelling is silent for contexts without a trace, so contents can label
+/// their fields unconditionally.
+pub trait Label {
+    fn label(&mut self, field: &str) -> Result<&mut Self>;
+}
+
+mod element;
+pub use element::Element;
+
 /// Implementation of command traits for message size calculation.
 pub mod sizeof;
 
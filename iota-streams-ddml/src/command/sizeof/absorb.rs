@@ -11,6 +11,7 @@ use crate::{
         Fallback,
         NBytes,
         Size,
+        Str,
         Uint16,
         Uint32,
         Uint64,
@@ -160,3 +161,11 @@ impl<'a, F, T: 'a + AbsorbFallback<F>> Absorb<&'a Fallback<T>> for Context<F> {
         Ok(self)
     }
 }
+
+/// `Str` is encoded as `Bytes`.
+impl<'a, F> Absorb<&'a Str> for Context<F> {
+    fn absorb(&mut self, s: &'a Str) -> Result<&mut Self> {
+        self.size += sizeof_sizet(s.0.len()) + s.0.len();
+        Ok(self)
+    }
+}
@@ -14,22 +14,22 @@ use crate::{
 use iota_streams_core_edsig::signature::ed25519;
 
 /// Signature size depends on Merkle tree height.
-impl<F> Ed25519<&ed25519::Keypair, &External<NBytes<U64>>> for Context<F> {
-    fn ed25519(&mut self, _sk: &ed25519::Keypair, _hash: &External<NBytes<U64>>) -> Result<&mut Self> {
+impl<'a, F, S: ed25519::Signer + ?Sized> Ed25519<&'a S, &'a External<NBytes<U64>>> for Context<F> {
+    fn ed25519(&mut self, _sk: &S, _hash: &External<NBytes<U64>>) -> Result<&mut Self> {
         self.size += ed25519::SIGNATURE_LENGTH;
         Ok(self)
     }
 }
 
-impl<F> Ed25519<&ed25519::Keypair, &External<Mac>> for Context<F> {
-    fn ed25519(&mut self, _sk: &ed25519::Keypair, _hash: &External<Mac>) -> Result<&mut Self> {
+impl<'a, F, S: ed25519::Signer + ?Sized> Ed25519<&'a S, &'a External<Mac>> for Context<F> {
+    fn ed25519(&mut self, _sk: &S, _hash: &External<Mac>) -> Result<&mut Self> {
         self.size += ed25519::SIGNATURE_LENGTH;
         Ok(self)
     }
 }
 
-impl<F> Ed25519<&ed25519::Keypair, HashSig> for Context<F> {
-    fn ed25519(&mut self, _sk: &ed25519::Keypair, _hash: HashSig) -> Result<&mut Self> {
+impl<'a, F, S: ed25519::Signer + ?Sized> Ed25519<&'a S, HashSig> for Context<F> {
+    fn ed25519(&mut self, _sk: &S, _hash: HashSig) -> Result<&mut Self> {
         // Squeeze external and commit cost nothing in the stream.
         self.size += ed25519::SIGNATURE_LENGTH;
         Ok(self)
@@ -0,0 +1,11 @@
+use iota_streams_core::Result;
+
+use super::Context;
+use crate::command::Label;
+
+/// Sizes do not depend on labels.
+impl<F> Label for Context<F> {
+    fn label(&mut self, _field: &str) -> Result<&mut Self> {
+        Ok(self)
+    }
+}
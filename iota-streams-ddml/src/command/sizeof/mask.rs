@@ -9,6 +9,7 @@ use crate::{
         Bytes,
         NBytes,
         Size,
+        Str,
         Uint16,
         Uint32,
         Uint64,
@@ -126,3 +127,11 @@ impl<F> Mask<&ed25519::PublicKey> for Context<F> {
         Ok(self)
     }
 }
+
+/// Mask `Str`, encoded as `Bytes`.
+impl<F> Mask<&Str> for Context<F> {
+    fn mask(&mut self, s: &Str) -> Result<&mut Self> {
+        self.size += sizeof_sizet(s.0.len()) + s.0.len();
+        Ok(self)
+    }
+}
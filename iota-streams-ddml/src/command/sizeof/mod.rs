@@ -25,9 +25,11 @@ impl<F> Context<F> {
 mod absorb;
 mod absorb_external;
 mod commit;
+mod composite;
 mod dump;
 mod fork;
 mod join;
+mod label;
 mod mask;
 mod repeated;
 mod skip;
@@ -40,6 +42,7 @@ mod x25519;
 pub use absorb::*;
 pub use absorb_external::*;
 pub use commit::*;
+pub use composite::*;
 pub use dump::*;
 pub use fork::*;
 pub use join::*;
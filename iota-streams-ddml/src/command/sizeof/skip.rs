@@ -11,6 +11,7 @@ use crate::{
         NBytes,
         Size,
         SkipFallback,
+        Str,
         Uint16,
         Uint32,
         Uint64,
@@ -133,3 +134,11 @@ impl<'a, F, T: 'a + SkipFallback<F>> Skip<&'a Fallback<T>> for Context<F> {
         Ok(self)
     }
 }
+
+/// `Str` is encoded as `Bytes`.
+impl<'a, F> Skip<&'a Str> for Context<F> {
+    fn skip(&mut self, s: &'a Str) -> Result<&mut Self> {
+        self.size += sizeof_sizet(s.0.len()) + s.0.len();
+        Ok(self)
+    }
+}
@@ -18,6 +18,14 @@ impl<'a, F> X25519<&'a x25519::StaticSecret, &'a x25519::PublicKey> for Context<
     }
 }
 
+impl<'a, F> X25519<&'a dyn x25519::KeyAgreement, &'a x25519::PublicKey> for Context<F> {
+    fn x25519(&mut self, _sk: &dyn x25519::KeyAgreement, _pk: &x25519::PublicKey) -> Result<&mut Self> {
+        // Only shared secret is absorbed externally.
+        self.size += 0;
+        Ok(self)
+    }
+}
+
 impl<'a, F> X25519<&'a x25519::EphemeralSecret, &'a x25519::PublicKey> for Context<F> {
     fn x25519(&mut self, _sk: &x25519::EphemeralSecret, _pk: &x25519::PublicKey) -> Result<&mut Self> {
         // Shared secret is absorbed externally.
@@ -32,3 +40,14 @@ impl<'a, F, N: ArrayLength<u8>> X25519<&'a x25519::PublicKey, &'a NBytes<N>> for
         Ok(self)
     }
 }
+
+impl<'a, F, N: ArrayLength<u8>> X25519<(x25519::EphemeralSecret, &'a x25519::PublicKey), &'a NBytes<N>> for Context<F> {
+    fn x25519(
+        &mut self,
+        _ephemeral_and_pk: (x25519::EphemeralSecret, &x25519::PublicKey),
+        _key: &NBytes<N>,
+    ) -> Result<&mut Self> {
+        self.size += 32 + N::USIZE;
+        Ok(self)
+    }
+}
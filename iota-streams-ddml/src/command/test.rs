@@ -1,5 +1,7 @@
 use iota_streams_core::{
+    ensure,
     prelude::{
+        hex,
         string::ToString,
         typenum::{
             U32,
@@ -21,6 +23,8 @@ use iota_streams_core_keccak::sponge::prp::keccak::KeccakF1600;
 
 use crate::{
     command::*,
+    io,
+    trace,
     types::*,
 };
 
@@ -126,13 +130,13 @@ fn absorb_mask_squeeze_bytes_mac<F: PRP>() -> Result<()> {
     let nonce = "TESTPRNGNONCE".as_bytes().to_vec();
 
     for n in NS.iter() {
-        let ta = Bytes(prng.gen_n(&nonce, *n));
+        let ta = Bytes::from(prng.gen_n(&nonce, *n));
         // nonce.slice_mut().inc();
         let nta = NBytes::<U64>(prng.gen_arr(&nonce));
         // nonce.slice_mut().inc();
         let enta = NBytes::<U64>(prng.gen_arr(&nonce));
         // nonce.slice_mut().inc();
-        let tm = Bytes(prng.gen_n(&nonce, *n));
+        let tm = Bytes::from(prng.gen_n(&nonce, *n));
         // nonce.slice_mut().inc();
         let ntm = NBytes::<U64>(prng.gen_arr(&nonce));
         // nonce.slice_mut().inc();
@@ -219,13 +223,526 @@ fn bytes() {
     assert!(dbg!(absorb_mask_squeeze_bytes_mac::<KeccakF1600>()).is_ok());
 }
 
+#[cfg(feature = "std")]
+fn wrap_payload<F: PRP, OS: io::OStream>(
+    ctx: &mut wrap::Context<F, OS>,
+    public: &Bytes,
+    masked: &Bytes,
+    tag: &mut External<NBytes<U32>>,
+) -> Result<()> {
+    ctx.commit()?
+        .absorb(Uint8(1))?
+        .absorb(public)?
+        .mask(masked)?
+        .skip(public)?
+        .commit()?
+        .squeeze(tag)?;
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+fn unwrap_payload<F: PRP, IS: io::IStream>(ctx: &mut unwrap::Context<F, IS>) -> Result<(Bytes, Bytes, NBytes<U32>)> {
+    let mut version = Uint8(0);
+    let mut public = Bytes::default();
+    let mut masked = Bytes::default();
+    let mut skipped = Bytes::default();
+    let mut tag = External(NBytes::<U32>::default());
+    ctx.commit()?
+        .absorb(&mut version)?
+        .absorb(&mut public)?
+        .mask(&mut masked)?
+        .skip(&mut skipped)?
+        .commit()?
+        .squeeze(&mut tag)?;
+    try_or!(version == Uint8(1), ValueMismatch(1, version.0 as usize))?;
+    try_or!(public == skipped, InvalidBytes(public.to_string(), skipped.to_string()))?;
+    Ok((public, masked, tag.0))
+}
+
+#[cfg(feature = "std")]
+fn streams<F: PRP>() -> Result<()> {
+    let prng = prng::dbg_init_str::<F>("TESTPRNGKEY");
+    let nonce = "TESTPRNGNONCE".as_bytes().to_vec();
+    let public = Bytes::from(prng.gen_n(&nonce, 3 * io::CHUNK_SIZE + 5));
+    let masked = Bytes::from(prng.gen_n(&nonce, 5 * io::CHUNK_SIZE - 1));
+
+    let mut tag = External(NBytes::<U32>::default());
+    let buf_size = {
+        let mut ctx = sizeof::Context::<F>::new();
+        ctx.commit()?
+            .absorb(Uint8(1))?
+            .absorb(&public)?
+            .mask(&masked)?
+            .skip(&public)?
+            .commit()?
+            .squeeze(&tag)?;
+        ctx.get_size()
+    };
+    let mut buf = vec![0_u8; buf_size];
+    wrap_payload(
+        &mut wrap::Context::<F, &mut [u8]>::new(&mut buf[..]),
+        &public,
+        &masked,
+        &mut tag,
+    )?;
+
+    // Wrap into a writer and into segments of odd sizes, no size has to be known in advance.
+    let mut written = Vec::new();
+    {
+        let mut ctx = wrap::Context::<F, _>::new(io::WriteStream::new(&mut written));
+        let mut tag2 = External(NBytes::<U32>::default());
+        wrap_payload(&mut ctx, &public, &masked, &mut tag2)?;
+        ctx.stream.flush()?;
+        try_or!(tag == tag2, InvalidTagSqueeze(tag.0.to_string(), tag2.0.to_string()))?;
+    }
+    try_or!(written == buf, InvalidBytes(hex::encode(&written), hex::encode(&buf)))?;
+
+    let mut segmented = vec![0_u8; buf_size];
+    {
+        let mut ctx = wrap::Context::<F, _>::new(io::SegmentedOStream::new(segmented.chunks_mut(333)));
+        let mut tag2 = External(NBytes::<U32>::default());
+        wrap_payload(&mut ctx, &public, &masked, &mut tag2)?;
+        try_or!(ctx.stream.is_empty(), OutputStreamNotFullyConsumed(0))?;
+    }
+    try_or!(
+        segmented == buf,
+        InvalidBytes(hex::encode(&segmented), hex::encode(&buf))
+    )?;
+
+    let mut short = vec![0_u8; buf_size - 1];
+    let mut ctx = wrap::Context::<F, _>::new(io::SegmentedOStream::new(short.chunks_mut(333)));
+    try_or!(
+        wrap_payload(&mut ctx, &public, &masked, &mut External(NBytes::default())).is_err(),
+        OutputStreamNotFullyConsumed(0)
+    )?;
+
+    // Unwrap from a reader and from segments.
+    let mut ctx = unwrap::Context::<F, _>::new(io::ReadStream::new(&buf[..]));
+    let (public2, masked2, tag2) = unwrap_payload(&mut ctx)?;
+    try_or!(
+        ctx.stream.get_ref().is_empty(),
+        InputStreamNotFullyConsumed(ctx.stream.get_ref().len())
+    )?;
+    try_or!(public == public2, InvalidBytes(public.to_string(), public2.to_string()))?;
+    try_or!(masked == masked2, InvalidBytes(masked.to_string(), masked2.to_string()))?;
+    try_or!(tag.0 == tag2, InvalidTagSqueeze(tag.0.to_string(), tag2.to_string()))?;
+
+    let mut ctx = unwrap::Context::<F, _>::new(io::SegmentedIStream::new(buf.chunks(333)));
+    let (public2, masked2, tag2) = unwrap_payload(&mut ctx)?;
+    try_or!(ctx.stream.is_empty(), InputStreamNotFullyConsumed(0))?;
+    try_or!(public == public2, InvalidBytes(public.to_string(), public2.to_string()))?;
+    try_or!(masked == masked2, InvalidBytes(masked.to_string(), masked2.to_string()))?;
+    try_or!(tag.0 == tag2, InvalidTagSqueeze(tag.0.to_string(), tag2.to_string()))?;
+
+    let mut ctx = unwrap::Context::<F, _>::new(io::ReadStream::new(&buf[..buf_size - 1]));
+    try_or!(unwrap_payload(&mut ctx).is_err(), InputStreamNotFullyConsumed(0))?;
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_streams() {
+    assert!(dbg!(streams::<KeccakF1600>()).is_ok());
+}
+
+fn wrap_traced<F: PRP>(buf: &mut [u8], public: &Bytes, masked: &Bytes) -> Result<trace::Trace> {
+    let mut ctx = wrap::Context::<F, &mut [u8]>::new_traced(buf);
+    ctx.dump(format_args!("version"))?
+        .absorb(Uint8(1))?
+        .dump(format_args!("public"))?
+        .absorb(public)?
+        .dump(format_args!("masked"))?
+        .mask(masked)?
+        .commit()?
+        .dump(format_args!("mac"))?
+        .squeeze(Mac(32))?;
+    Ok(ctx.trace.unwrap())
+}
+
+fn unwrap_traced<F: PRP>(buf: &[u8]) -> (Result<()>, trace::Trace) {
+    let mut version = Uint8(0);
+    let mut public = Bytes::default();
+    let mut masked = Bytes::default();
+    let mut ctx = unwrap::Context::<F, &[u8]>::new_traced(buf);
+    let r = ctx
+        .dump(format_args!("version"))
+        .and_then(|ctx| ctx.absorb(&mut version))
+        .and_then(|ctx| ctx.dump(format_args!("public")))
+        .and_then(|ctx| ctx.absorb(&mut public))
+        .and_then(|ctx| ctx.dump(format_args!("masked")))
+        .and_then(|ctx| ctx.mask(&mut masked))
+        .and_then(|ctx| ctx.commit())
+        .and_then(|ctx| ctx.dump(format_args!("mac")))
+        .and_then(|ctx| ctx.squeeze(Mac(32)))
+        .map(|_| ());
+    (r, ctx.trace.unwrap())
+}
+
+fn traces<F: PRP>() -> Result<()> {
+    let public = Bytes::from(vec![1_u8; 5]);
+    let masked = Bytes::from(vec![2_u8; 7]);
+    let mut buf = vec![0_u8; 1 + 2 + 5 + 2 + 7 + 32];
+
+    let wrapped = wrap_traced::<F>(&mut buf[..], &public, &masked)?;
+    let (r, unwrapped) = unwrap_traced::<F>(&buf[..]);
+    r?;
+
+    let fields: Vec<(&str, trace::Command, usize, usize)> = wrapped
+        .entries()
+        .iter()
+        .map(|e| (e.field.as_str(), e.command, e.offset, e.length))
+        .collect();
+    try_or!(
+        fields
+            == vec![
+                ("version", trace::Command::Absorb, 0, 1),
+                ("public", trace::Command::Absorb, 1, 7),
+                ("masked", trace::Command::Mask, 8, 9),
+                ("masked", trace::Command::Commit, 17, 0),
+                ("mac", trace::Command::Squeeze, 17, 32),
+            ],
+        ValueMismatch(5, fields.len())
+    )?;
+    try_or!(wrapped.diverges_at(&unwrapped).is_none(), ValueMismatch(0, 1))?;
+    try_or!(wrapped.to_json() == unwrapped.to_json(), ValueMismatch(0, 1))?;
+    try_or!(
+        wrapped
+            .to_json()
+            .starts_with("[{\"field\":\"version\",\"command\":\"absorb\",\"offset\":0,\"length\":1,"),
+        ValueMismatch(0, 1)
+    )?;
+
+    // Corrupt the last byte of the masked field, traces diverge at the mask command.
+    buf[16] ^= 1;
+    let (r, corrupted) = unwrap_traced::<F>(&buf[..]);
+    try_or!(r.is_err(), ValueMismatch(0, 1))?;
+    try_or!(wrapped.diverges_at(&corrupted) == Some(2), ValueMismatch(2, 0))?;
+    Ok(())
+}
+
+#[test]
+fn test_traces() {
+    assert!(dbg!(traces::<KeccakF1600>()).is_ok());
+}
+
+crate::oneof! {
+    #[derive(Clone, PartialEq, Debug)]
+    enum Reading {
+        0 => Text(Str),
+        1 => Value(Int32),
+        2 => Flags(Option<Uint8>),
+    }
+}
+
+impl Default for Reading {
+    fn default() -> Self {
+        Reading::Value(Int32(0))
+    }
+}
+
+fn composite_types<F: PRP>() -> Result<()> {
+    let i8s = Int8(-1);
+    let i16s = Int16(-300);
+    let i32s = Int32(i32::MIN);
+    let i64s = Int64(i64::MAX);
+    let text = Str::from("grüße");
+    let some = Some(Varuint(300));
+    let none: Option<Str> = None;
+    let mut readings = Map::new();
+    readings.0.insert(Str::from("b"), Reading::Value(Int32(-7)));
+    readings.0.insert(Str::from("a"), Reading::Text(Str::from("x")));
+    readings.0.insert(Str::from("c"), Reading::Flags(Some(Uint8(3))));
+    let mut varints = Map::new();
+    for i in [0, -1, 1, 127, -128, i64::MIN, i64::MAX].iter() {
+        varints.0.insert(Varint(*i), Varuint(*i as u64));
+    }
+    let reading = Reading::Flags(None);
+    let mut tag_wrap = External(NBytes::<U32>::default());
+    let mut tag_unwrap = External(NBytes::<U32>::default());
+
+    let buf_size = {
+        let mut ctx = sizeof::Context::<F>::new();
+        ctx.absorb(&i8s)?
+            .mask(&i16s)?
+            .skip(&i32s)?
+            .absorb(&i64s)?
+            .mask(&text)?
+            .absorb(&some)?
+            .skip(&none)?
+            .mask(&readings)?
+            .absorb(&varints)?
+            .skip(&reading)?
+            .commit()?
+            .squeeze(&tag_wrap)?;
+        ctx.get_size()
+    };
+    let mut buf = vec![0_u8; buf_size];
+
+    {
+        let mut ctx = wrap::Context::<F, &mut [u8]>::new(&mut buf[..]);
+        ctx.absorb(&i8s)?
+            .mask(&i16s)?
+            .skip(&i32s)?
+            .absorb(&i64s)?
+            .mask(&text)?
+            .absorb(&some)?
+            .skip(&none)?
+            .mask(&readings)?
+            .absorb(&varints)?
+            .skip(&reading)?
+            .commit()?
+            .squeeze(&mut tag_wrap)?;
+        try_or!(ctx.stream.is_empty(), OutputStreamNotFullyConsumed(ctx.stream.len()))?;
+    }
+
+    let mut i8s2 = Int8::default();
+    let mut i16s2 = Int16::default();
+    let mut i32s2 = Int32::default();
+    let mut i64s2 = Int64::default();
+    let mut text2 = Str::default();
+    let mut some2 = None;
+    let mut none2 = Some(Str::from("overwritten"));
+    let mut readings2 = Map::new();
+    let mut varints2 = Map::new();
+    let mut reading2 = Reading::default();
+    {
+        let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]);
+        ctx.absorb(&mut i8s2)?
+            .mask(&mut i16s2)?
+            .skip(&mut i32s2)?
+            .absorb(&mut i64s2)?
+            .mask(&mut text2)?
+            .absorb(&mut some2)?
+            .skip(&mut none2)?
+            .mask(&mut readings2)?
+            .absorb(&mut varints2)?
+            .skip(&mut reading2)?
+            .commit()?
+            .squeeze(&mut tag_unwrap)?;
+        try_or!(ctx.stream.is_empty(), InputStreamNotFullyConsumed(ctx.stream.len()))?;
+    }
+
+    try_or!(
+        (i8s, i16s, i32s, i64s) == (i8s2, i16s2, i32s2, i64s2),
+        ValueMismatch(i64s.0 as usize, i64s2.0 as usize)
+    )?;
+    try_or!(text == text2, InvalidBytes(text.to_string(), text2.to_string()))?;
+    try_or!(some == some2 && none == none2, BadOneof)?;
+    try_or!(
+        readings == readings2,
+        ValueMismatch(readings.0.len(), readings2.0.len())
+    )?;
+    try_or!(varints == varints2, ValueMismatch(varints.0.len(), varints2.0.len()))?;
+    try_or!(reading == reading2, BadOneof)?;
+    try_or!(
+        tag_wrap == tag_unwrap,
+        InvalidTagSqueeze(tag_wrap.0.to_string(), tag_unwrap.0.to_string())
+    )?;
+    Ok(())
+}
+
+fn composite_types_rejected<F: PRP>() -> Result<()> {
+    // Each encoding is valid for the type it is wrapped as but is rejected when unwrapped as `Str`, `Option`,
+    // `Reading` and `Map` respectively.
+    let invalid_utf8 = Bytes::from(vec![0xc3, 0x28]);
+    let bad_tag = Uint8(2);
+    let unordered = [Size(2), Size(2), Size(0), Size(1), Size(0)];
+
+    let mut buf = vec![0_u8; 64];
+    {
+        let mut ctx = wrap::Context::<F, &mut [u8]>::new(&mut buf[..]);
+        ctx.absorb(&invalid_utf8)?;
+    }
+    let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]);
+    try_or!(ctx.absorb(&mut Str::default()).is_err(), InvalidUtf8)?;
+
+    {
+        let mut ctx = wrap::Context::<F, &mut [u8]>::new(&mut buf[..]);
+        ctx.mask(&bad_tag)?;
+    }
+    let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]);
+    try_or!(ctx.mask(&mut Option::<Uint8>::None).is_err(), BadOneof)?;
+    {
+        let mut ctx = wrap::Context::<F, &mut [u8]>::new(&mut buf[..]);
+        ctx.mask(&Uint8(3))?;
+    }
+    let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]);
+    try_or!(ctx.mask(&mut Reading::default()).is_err(), BadOneof)?;
+
+    {
+        let mut ctx = wrap::Context::<F, &mut [u8]>::new(&mut buf[..]);
+        for size in unordered.iter() {
+            ctx.skip(size)?;
+        }
+    }
+    let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]);
+    try_or!(ctx.skip(&mut Map::<Size, Size>::new()).is_err(), UnorderedMapKeys)?;
+    Ok(())
+}
+
+fn unwrap_limits<F: PRP>() -> Result<()> {
+    let payload = Bytes::from(vec![7_u8; 100]);
+    let mut buf = vec![0_u8; 2 + 100 + 2 + 2 * (2 + 3)];
+    {
+        let mut ctx = wrap::Context::<F, &mut [u8]>::new(&mut buf[..]);
+        ctx.absorb(&payload)?.absorb(Size(2))?.repeated(0..2, |ctx, _| {
+            ctx.absorb(Size(3))?.repeated(0..3, |ctx, i| ctx.mask(&Uint8(i)))
+        })?;
+        try_or!(ctx.stream.is_empty(), OutputStreamNotFullyConsumed(ctx.stream.len()))?;
+    }
+
+    let unwrap = |limits: unwrap::Limits| -> Result<()> {
+        let mut bytes = Bytes::default();
+        let mut outer = Size(0);
+        let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]).with_limits(limits);
+        ctx.absorb(&mut bytes)?.absorb(&mut outer)?.repeated(outer, |ctx| {
+            let mut inner = Size(0);
+            ctx.absorb(&mut inner)?.repeated(inner, |ctx| ctx.mask(&mut Uint8(0)))
+        })?;
+        Ok(())
+    };
+
+    let exact = unwrap::Limits::default()
+        .with_max_bytes_len(100)
+        .with_max_repeated(3)
+        .with_max_allocation(100)
+        .with_max_nesting(2);
+    unwrap(unwrap::Limits::default())?;
+    unwrap(exact)?;
+    try_or!(unwrap(exact.with_max_bytes_len(99)).is_err(), ValueMismatch(100, 99))?;
+    try_or!(unwrap(exact.with_max_allocation(99)).is_err(), ValueMismatch(100, 99))?;
+    try_or!(unwrap(exact.with_max_repeated(2)).is_err(), ValueMismatch(3, 2))?;
+    try_or!(unwrap(exact.with_max_nesting(1)).is_err(), ValueMismatch(2, 1))?;
+
+    // Default limits are finite.
+    let unlimited = unwrap::Limits::unlimited();
+    try_or!(
+        unwrap::Limits::default() != unlimited,
+        ValueMismatch(unwrap::Limits::DEFAULT_MAX_BYTES_LEN, usize::MAX)
+    )?;
+    let many = Size(unwrap::Limits::DEFAULT_MAX_REPEATED + 1);
+    let mut buf = vec![0_u8; sizeof::Context::<F>::new().absorb(many)?.absorb(Uint8(0))?.get_size()];
+    wrap::Context::<F, &mut [u8]>::new(&mut buf[..])
+        .absorb(many)?
+        .absorb(Uint8(0))?;
+    let mut n = Size(0);
+    let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]);
+    try_or!(
+        ctx.absorb(&mut n)?.repeated(n, |ctx| Ok(ctx)).is_err(),
+        ValueMismatch(unwrap::Limits::DEFAULT_MAX_REPEATED, n.0)
+    )?;
+
+    // Sizes beyond the rest of the input are rejected before allocating, even without limits.
+    for &size in &[101, usize::MAX] {
+        let mut buf = vec![0_u8; sizeof::Context::<F>::new().absorb(Size(size))?.get_size() + 100];
+        wrap::Context::<F, &mut [u8]>::new(&mut buf[..]).absorb(Size(size))?;
+        let mut bytes = Bytes::default();
+        let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]).with_limits(unlimited);
+        try_or!(ctx.absorb(&mut bytes).is_err(), ValueMismatch(size, 100))?;
+        let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]).with_limits(unlimited);
+        try_or!(ctx.mask(&mut bytes).is_err(), ValueMismatch(size, 100))?;
+        let mut ctx = unwrap::Context::<F, &[u8]>::new(&buf[..]).with_limits(unlimited);
+        try_or!(ctx.skip(&mut bytes).is_err(), ValueMismatch(size, 100))?;
+    }
+    Ok(())
+}
+
+#[test]
+fn limits() {
+    assert!(dbg!(unwrap_limits::<KeccakF1600>()).is_ok());
+}
+
+#[cfg(feature = "std")]
+fn unwrap_limits_of_unknown_length<F: PRP>() -> Result<()> {
+    // Readers can't tell how much input is left, limits alone bound what is allocated.
+    for &(size, limits) in &[
+        (unwrap::Limits::DEFAULT_MAX_BYTES_LEN + 1, unwrap::Limits::default()),
+        (usize::MAX, unwrap::Limits::unlimited()),
+    ] {
+        let mut buf = vec![0_u8; sizeof::Context::<F>::new().absorb(Size(size))?.get_size()];
+        wrap::Context::<F, &mut [u8]>::new(&mut buf[..]).absorb(Size(size))?;
+        let mut bytes = Bytes::default();
+        let mut ctx = unwrap::Context::<F, _>::new(io::ReadStream::new(&buf[..])).with_limits(limits);
+        try_or!(ctx.absorb(&mut bytes).is_err(), ValueMismatch(size, 0))?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn limits_of_unknown_length() {
+    assert!(dbg!(unwrap_limits_of_unknown_length::<KeccakF1600>()).is_ok());
+}
+
+fn unwrap_shared<F: PRP>() -> Result<()> {
+    let absorbed = Bytes::from(vec![1_u8; 10]);
+    let skipped = Bytes::from(vec![2_u8; 20]);
+    let buf_size = sizeof::Context::<F>::new()
+        .absorb(&absorbed)?
+        .skip(&skipped)?
+        .get_size();
+    let mut buf = vec![0_u8; buf_size];
+    wrap::Context::<F, &mut [u8]>::new(&mut buf[..])
+        .absorb(&absorbed)?
+        .skip(&skipped)?;
+
+    let shared = SharedBuffer::from(buf.clone());
+    let mut absorbed2 = Bytes::default();
+    let mut skipped2 = Bytes::default();
+    let mut ctx = unwrap::Context::<F, io::SharedIStream>::new(io::SharedIStream::new(shared.clone()));
+    ctx.absorb(&mut absorbed2)?.skip(&mut skipped2)?;
+    try_or!(ctx.stream.is_empty(), InputStreamNotFullyConsumed(ctx.stream.len()))?;
+    try_or!(
+        absorbed == absorbed2,
+        InvalidBytes(absorbed.to_string(), absorbed2.to_string())
+    )?;
+    try_or!(
+        skipped == skipped2,
+        InvalidBytes(skipped.to_string(), skipped2.to_string())
+    )?;
+    ensure!(absorbed2.0.ptr_eq(&shared), "absorbed bytes were copied");
+    ensure!(skipped2.0.ptr_eq(&shared), "skipped bytes were copied");
+
+    // Spongos state must not depend on the input stream.
+    let mut ctx_shared = unwrap::Context::<F, io::SharedIStream>::new(io::SharedIStream::new(shared));
+    let mut ctx_slice = unwrap::Context::<F, &[u8]>::new(&buf[..]);
+    let mut tag_shared = External(NBytes::<U32>::default());
+    let mut tag_slice = External(NBytes::<U32>::default());
+    ctx_shared
+        .absorb(&mut Bytes::default())?
+        .skip(&mut Bytes::default())?
+        .commit()?
+        .squeeze(&mut tag_shared)?;
+    ctx_slice
+        .absorb(&mut Bytes::default())?
+        .skip(&mut Bytes::default())?
+        .commit()?
+        .squeeze(&mut tag_slice)?;
+    try_or!(
+        tag_shared == tag_slice,
+        InvalidTagSqueeze(tag_shared.0.to_string(), tag_slice.0.to_string())
+    )?;
+    Ok(())
+}
+
+#[test]
+fn shared() {
+    assert!(dbg!(unwrap_shared::<KeccakF1600>()).is_ok());
+}
+
+#[test]
+fn composite() {
+    assert!(dbg!(composite_types::<KeccakF1600>()).is_ok());
+    assert!(dbg!(composite_types_rejected::<KeccakF1600>()).is_ok());
+}
+
 fn absorb_ed25519<F: PRP>() -> Result<()> {
     let secret = ed25519::SecretKey::from_bytes(&[7; ed25519::SECRET_KEY_LENGTH]).unwrap();
     let public = ed25519::PublicKey::from(&secret);
     let kp = ed25519::Keypair { secret, public };
 
-    let ta = Bytes([3_u8; 17].to_vec());
-    let mut uta = Bytes(Vec::new());
+    let ta = Bytes::from([3_u8; 17].to_vec());
+    let mut uta = Bytes::new();
     let mut hash = External(NBytes::<U64>::default());
     let mut uhash = External(NBytes::<U64>::default());
 
@@ -281,8 +798,8 @@ fn x25519_static<F: PRP>() -> Result<()> {
     let public_b = x25519::PublicKey::from(&secret_b);
     let mut public_b2 = x25519::PublicKey::from([0_u8; 32]);
 
-    let ta = Bytes([3_u8; 17].to_vec());
-    let mut uta = Bytes(Vec::new());
+    let ta = Bytes::from([3_u8; 17].to_vec());
+    let mut uta = Bytes::new();
 
     let buf_size = {
         let mut ctx = sizeof::Context::<F>::new();
@@ -325,8 +842,8 @@ fn x25519_ephemeral<F: PRP>() -> Result<()> {
     let public_b = x25519::PublicKey::from(&secret_b);
     let mut public_b2 = x25519::PublicKey::from([0_u8; 32]);
 
-    let ta = Bytes([3_u8; 17].to_vec());
-    let mut uta = Bytes(Vec::new());
+    let ta = Bytes::from([3_u8; 17].to_vec());
+    let mut uta = Bytes::new();
 
     let buf_size = {
         let mut ctx = sizeof::Context::<F>::new();
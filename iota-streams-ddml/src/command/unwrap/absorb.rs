@@ -7,6 +7,7 @@ use super::{
 use crate::{
     command::Absorb,
     io,
+    trace,
     types::{
         AbsorbFallback,
         ArrayLength,
@@ -14,6 +15,7 @@ use crate::{
         Fallback,
         NBytes,
         Size,
+        Str,
         Uint16,
         Uint32,
         Uint64,
@@ -22,9 +24,15 @@ use crate::{
 };
 use iota_streams_core::{
     err,
+    prelude::String,
     sponge::prp::PRP,
-    Errors::PublicKeyGenerationFailure,
+    wrapped_err,
+    Errors::{
+        InvalidUtf8,
+        PublicKeyGenerationFailure,
+    },
     Result,
+    WrappedError,
 };
 use iota_streams_core_edsig::{
     key_exchange::x25519,
@@ -47,15 +55,20 @@ impl<F, IS> AsMut<Context<F, IS>> for AbsorbContext<F, IS> {
 
 impl<F: PRP, IS: io::IStream> Unwrap for AbsorbContext<F, IS> {
     fn unwrap_u8(&mut self, u: &mut u8) -> Result<&mut Self> {
+        let before = self.ctx.trace_begin();
         let slice = self.ctx.stream.try_advance(1)?;
         *u = slice[0];
         self.ctx.spongos.absorb(slice);
+        self.ctx.trace_end(trace::Command::Absorb, 1, before);
         Ok(self)
     }
     fn unwrapn(&mut self, bytes: &mut [u8]) -> Result<&mut Self> {
-        let slice = self.ctx.stream.try_advance(bytes.len())?;
-        bytes.copy_from_slice(slice);
-        self.ctx.spongos.absorb(bytes);
+        let before = self.ctx.trace_begin();
+        for chunk in bytes.chunks_mut(io::CHUNK_SIZE) {
+            chunk.copy_from_slice(self.ctx.stream.try_advance(chunk.len())?);
+        }
+        self.ctx.spongos.absorb(&bytes[..]);
+        self.ctx.trace_end(trace::Command::Absorb, bytes.len(), before);
         Ok(self)
     }
 }
@@ -137,8 +150,16 @@ impl<'a, F: PRP, IS: io::IStream> Absorb<&'a mut Bytes> for Context<F, IS> {
     fn absorb(&mut self, bytes: &'a mut Bytes) -> Result<&mut Self> {
         let mut size = Size(0);
         self.absorb(&mut size)?;
-        (bytes.0).resize(size.0, 0);
-        Ok(unwrap_absorb_bytes(self.as_mut(), &mut (bytes.0)[..])?.as_mut())
+        self.check_bytes(size.0)?;
+        if let Some(shared) = self.stream.try_advance_shared(size.0)? {
+            let before = self.trace_begin();
+            self.spongos.absorb(&shared[..]);
+            self.trace_end(trace::Command::Absorb, size.0, before);
+            bytes.0 = shared;
+            return Ok(self);
+        }
+        bytes.to_mut().resize(size.0, 0);
+        Ok(unwrap_absorb_bytes(self.as_mut(), &mut bytes.to_mut()[..])?.as_mut())
     }
 }
 
@@ -171,3 +192,13 @@ impl<'a, F, T: 'a + AbsorbFallback<F>, IS: io::IStream> Absorb<&'a mut Fallback<
         Ok(self)
     }
 }
+
+/// `Str` is unwrapped as `Bytes` and must be valid UTF-8.
+impl<'a, F: PRP, IS: io::IStream> Absorb<&'a mut Str> for Context<F, IS> {
+    fn absorb(&mut self, s: &'a mut Str) -> Result<&mut Self> {
+        let mut bytes = Bytes::from(mem::take(&mut s.0).into_bytes());
+        self.absorb(&mut bytes)?;
+        s.0 = String::from_utf8(bytes.into_vec()).map_err(|e| wrapped_err!(InvalidUtf8, WrappedError(e)))?;
+        Ok(self)
+    }
+}
@@ -7,6 +7,7 @@ use crate::{
         Absorb,
     },
     io,
+    trace,
     types::{
         AbsorbExternalFallback,
         ArrayLength,
@@ -45,11 +46,15 @@ impl<F, IS> AsMut<Context<F, IS>> for AbsorbExternalContext<F, IS> {
 
 impl<F: PRP, IS: io::IStream> Wrap for AbsorbExternalContext<F, IS> {
     fn wrap_u8(&mut self, u: u8) -> Result<&mut Self> {
+        let before = self.ctx.trace_begin();
         self.ctx.spongos.absorb(&[u]);
+        self.ctx.trace_end(trace::Command::AbsorbExternal, 1, before);
         Ok(self)
     }
     fn wrapn(&mut self, bytes: &[u8]) -> Result<&mut Self> {
+        let before = self.ctx.trace_begin();
         self.ctx.spongos.absorb(bytes);
+        self.ctx.trace_end(trace::Command::AbsorbExternal, bytes.len(), before);
         Ok(self)
     }
 }
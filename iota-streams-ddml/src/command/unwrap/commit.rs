@@ -1,13 +1,18 @@
 use iota_streams_core::Result;
 
 use super::Context;
-use crate::command::Commit;
+use crate::{
+    command::Commit,
+    trace,
+};
 use iota_streams_core::sponge::prp::PRP;
 
 /// Commit Spongos.
 impl<F: PRP, IS> Commit for Context<F, IS> {
     fn commit(&mut self) -> Result<&mut Self> {
+        let before = self.trace_begin();
         self.spongos.commit();
+        self.trace_end(trace::Command::Commit, 0, before);
         Ok(self)
     }
 }
@@ -0,0 +1,153 @@
+//! Types encoded in terms of the basic DDML types: signed and variable-size integers, `Option` and `Map`.
+
+use iota_streams_core::{
+    err,
+    sponge::prp::PRP,
+    try_or,
+    Errors::{
+        BadOneof,
+        UnorderedMapKeys,
+        ValueOutOfRange,
+    },
+    Result,
+};
+
+use super::Context;
+use crate::{
+    command::{
+        Absorb,
+        Element,
+        Mask,
+        Skip,
+    },
+    io,
+    types::{
+        Int16,
+        Int32,
+        Int64,
+        Int8,
+        Map,
+        Size,
+        Uint16,
+        Uint32,
+        Uint64,
+        Uint8,
+        Varint,
+        Varuint,
+    },
+};
+
+macro_rules! impl_composite {
+    ($trait:ident, $method:ident) => {
+        impl<'a, F: PRP, IS: io::IStream> $trait<&'a mut Int8> for Context<F, IS> {
+            fn $method(&mut self, i: &'a mut Int8) -> Result<&mut Self> {
+                let mut u = Uint8(0);
+                self.$method(&mut u)?;
+                i.0 = u.0 as i8;
+                Ok(self)
+            }
+        }
+
+        impl<'a, F: PRP, IS: io::IStream> $trait<&'a mut Int16> for Context<F, IS> {
+            fn $method(&mut self, i: &'a mut Int16) -> Result<&mut Self> {
+                let mut u = Uint16(0);
+                self.$method(&mut u)?;
+                i.0 = u.0 as i16;
+                Ok(self)
+            }
+        }
+
+        impl<'a, F: PRP, IS: io::IStream> $trait<&'a mut Int32> for Context<F, IS> {
+            fn $method(&mut self, i: &'a mut Int32) -> Result<&mut Self> {
+                let mut u = Uint32(0);
+                self.$method(&mut u)?;
+                i.0 = u.0 as i32;
+                Ok(self)
+            }
+        }
+
+        impl<'a, F: PRP, IS: io::IStream> $trait<&'a mut Int64> for Context<F, IS> {
+            fn $method(&mut self, i: &'a mut Int64) -> Result<&mut Self> {
+                let mut u = Uint64(0);
+                self.$method(&mut u)?;
+                i.0 = u.0 as i64;
+                Ok(self)
+            }
+        }
+
+        /// `Varuint` can not have more than 8 significant bytes.
+        impl<'a, F: PRP, IS: io::IStream> $trait<&'a mut Varuint> for Context<F, IS> {
+            fn $method(&mut self, u: &'a mut Varuint) -> Result<&mut Self> {
+                let mut d = Uint8(0);
+                self.$method(&mut d)?;
+                try_or!(d.0 <= 8, ValueOutOfRange(8, d.0 as usize))?;
+                let mut n = 0_u64;
+                for _ in 0..d.0 {
+                    let mut t = Uint8(0);
+                    self.$method(&mut t)?;
+                    n = (n << 8) | t.0 as u64;
+                }
+                u.0 = n;
+                Ok(self)
+            }
+        }
+
+        impl<'a, F: PRP, IS: io::IStream> $trait<&'a mut Varint> for Context<F, IS> {
+            fn $method(&mut self, i: &'a mut Varint) -> Result<&mut Self> {
+                let mut u = Varuint(0);
+                self.$method(&mut u)?;
+                *i = u.into();
+                Ok(self)
+            }
+        }
+
+        /// Tags other than 0 (`None`) and 1 (`Some`) are rejected.
+        impl<'a, F: PRP, IS: io::IStream, T: Default> $trait<&'a mut Option<T>> for Context<F, IS>
+        where
+            for<'b> &'b mut T: Element<Self>,
+        {
+            fn $method(&mut self, option: &'a mut Option<T>) -> Result<&mut Self> {
+                let mut tag = Uint8(0);
+                self.$method(&mut tag)?;
+                match tag.0 {
+                    0 => *option = None,
+                    1 => {
+                        let mut value = T::default();
+                        Element::$method(&mut value, self)?;
+                        *option = Some(value);
+                    }
+                    _ => return err!(BadOneof),
+                }
+                Ok(self)
+            }
+        }
+
+        /// Keys must be in strictly ascending order, which also rules out duplicate keys.
+        impl<'a, F: PRP, IS: io::IStream, K: Ord + Default, V: Default> $trait<&'a mut Map<K, V>> for Context<F, IS>
+        where
+            for<'b> &'b mut K: Element<Self>,
+            for<'b> &'b mut V: Element<Self>,
+        {
+            fn $method(&mut self, map: &'a mut Map<K, V>) -> Result<&mut Self> {
+                let mut size = Size(0);
+                self.$method(&mut size)?;
+                self.check_repeated(size.0)?;
+                map.0.clear();
+                for _ in 0..size.0 {
+                    let mut key = K::default();
+                    let mut value = V::default();
+                    Element::$method(&mut value, Element::$method(&mut key, &mut *self)?)?;
+                    if let Some((last, _)) = map.0.iter().next_back() {
+                        try_or!(*last < key, UnorderedMapKeys)?;
+                    }
+                    map.0.insert(key, value);
+                }
+                Ok(self)
+            }
+        }
+    };
+}
+
+impl_composite!(Absorb, absorb);
+impl_composite!(Mask, mask);
+impl_composite!(Skip, skip);
@@ -13,6 +13,10 @@ use iota_streams_core::{
 impl<F: PRP, IS: io::IStream> Dump for Context<F, IS> {
     #[allow(unused_variables)]
     fn dump<'a>(&mut self, args: core::fmt::Arguments<'a>) -> Result<&mut Self> {
+        if let Some(t) = self.trace.as_mut() {
+            t.label(args);
+            return Ok(self);
+        }
         println!(
             "dump: {}: istream=[{}] spongos=[{:?}]",
             args,
@@ -1,3 +1,5 @@
+use core::convert::TryFrom;
+
 use iota_streams_core::Result;
 
 use super::Context;
@@ -8,6 +10,7 @@ use crate::{
         Squeeze,
     },
     io,
+    trace,
     types::{
         External,
         HashSig,
@@ -33,8 +36,11 @@ impl<'a, F: PRP, IS: io::IStream> Ed25519<&'a ed25519::PublicKey, &'a External<N
         let mut bytes = [0_u8; ed25519::SIGNATURE_LENGTH];
         let slice = self.stream.try_advance(ed25519::SIGNATURE_LENGTH)?;
         bytes.copy_from_slice(slice);
-        #[allow(deprecated)]
-        let signature = ed25519::Signature::new(bytes);
+        self.trace_skip(trace::Command::Ed25519, ed25519::SIGNATURE_LENGTH);
+        let signature = match ed25519::Signature::try_from(&bytes[..]) {
+            Ok(signature) => signature,
+            Err(e) => return Err(wrapped_err!(SignatureMismatch, WrappedError(e))),
+        };
         match pk.verify_prehashed(prehashed, Some(context), &signature) {
             Ok(()) => Ok(self),
             Err(e) => Err(wrapped_err!(SignatureMismatch, WrappedError(e))),
@@ -15,6 +15,9 @@ where
         let saved_fork = self.spongos.fork();
         cont(self)?;
         self.spongos = saved_fork;
+        if let Some(t) = self.trace.as_mut() {
+            t.sync(&self.spongos);
+        }
         Ok(self)
     }
 }
@@ -5,6 +5,7 @@ use crate::{
     command::Join,
     io,
     link_store::LinkStore,
+    trace,
     types::SkipFallback,
 };
 use iota_streams_core::sponge::prp::PRP;
@@ -19,7 +20,9 @@ impl<'a, F: PRP, L: SkipFallback<F>, S: LinkStore<F, L>, IS: io::IStream> Join<&
         link.unwrap_skip(self)?;
         // TODO: Return and use info.
         let (mut s, _i) = store.lookup(link)?;
+        let before = self.trace_begin();
         self.spongos.join(&mut s);
+        self.trace_end(trace::Command::Join, 0, before);
         Ok(self)
     }
 }
@@ -0,0 +1,13 @@
+use iota_streams_core::Result;
+
+use super::Context;
+use crate::command::Label;
+
+impl<F, IS> Label for Context<F, IS> {
+    fn label(&mut self, field: &str) -> Result<&mut Self> {
+        if let Some(t) = self.trace.as_mut() {
+            t.label(field);
+        }
+        Ok(self)
+    }
+}
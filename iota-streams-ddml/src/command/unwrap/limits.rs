@@ -0,0 +1,69 @@
+/// Resource limits enforced while unwrapping messages.
+///
+/// Sizes of `Bytes` fields and counts of `repeated` values are read from the message and
+/// can't be trusted when the message comes from an unknown party. Exceeding any limit
+/// results in `UnwrapLimitExceeded` error before anything is allocated. The default limits
+/// are well beyond what a Tangle message can hold; `unlimited` disables them.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Limits {
+    /// Max length of a single `Bytes` (or `Str`) field.
+    pub max_bytes_len: usize,
+    /// Max number of values in a `repeated` modifier (or entries in a `Map`).
+    pub max_repeated: usize,
+    /// Max total length of all `Bytes` fields unwrapped with the context.
+    pub max_allocation: usize,
+    /// Max nesting depth of `repeated` modifiers.
+    pub max_nesting: usize,
+}
+
+impl Limits {
+    /// Default max length of a single `Bytes` field, 1 MiB.
+    pub const DEFAULT_MAX_BYTES_LEN: usize = 1 << 20;
+    /// Default max number of `repeated` values.
+    pub const DEFAULT_MAX_REPEATED: usize = 1 << 16;
+    /// Default max total length of `Bytes` fields, 16 MiB.
+    pub const DEFAULT_MAX_ALLOCATION: usize = 1 << 24;
+    /// Default max nesting depth of `repeated` modifiers.
+    pub const DEFAULT_MAX_NESTING: usize = 16;
+
+    /// No limits.
+    pub fn unlimited() -> Self {
+        Self {
+            max_bytes_len: usize::MAX,
+            max_repeated: usize::MAX,
+            max_allocation: usize::MAX,
+            max_nesting: usize::MAX,
+        }
+    }
+
+    pub fn with_max_bytes_len(mut self, max_bytes_len: usize) -> Self {
+        self.max_bytes_len = max_bytes_len;
+        self
+    }
+
+    pub fn with_max_repeated(mut self, max_repeated: usize) -> Self {
+        self.max_repeated = max_repeated;
+        self
+    }
+
+    pub fn with_max_allocation(mut self, max_allocation: usize) -> Self {
+        self.max_allocation = max_allocation;
+        self
+    }
+
+    pub fn with_max_nesting(mut self, max_nesting: usize) -> Self {
+        self.max_nesting = max_nesting;
+        self
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_bytes_len: Self::DEFAULT_MAX_BYTES_LEN,
+            max_repeated: Self::DEFAULT_MAX_REPEATED,
+            max_allocation: Self::DEFAULT_MAX_ALLOCATION,
+            max_nesting: Self::DEFAULT_MAX_NESTING,
+        }
+    }
+}
@@ -8,11 +8,13 @@ use super::{
 use crate::{
     command::Mask,
     io,
+    trace,
     types::{
         ArrayLength,
         Bytes,
         NBytes,
         Size,
+        Str,
         Uint16,
         Uint32,
         Uint64,
@@ -20,9 +22,13 @@ use crate::{
     },
 };
 use iota_streams_core::{
+    prelude::String,
     sponge::prp::PRP,
     wrapped_err,
-    Errors::PublicKeyGenerationFailure,
+    Errors::{
+        InvalidUtf8,
+        PublicKeyGenerationFailure,
+    },
     WrappedError,
 };
 use iota_streams_core_edsig::{
@@ -46,15 +52,21 @@ impl<F, IS> AsMut<Context<F, IS>> for MaskContext<F, IS> {
 
 impl<F: PRP, IS: io::IStream> Unwrap for MaskContext<F, IS> {
     fn unwrap_u8(&mut self, u: &mut u8) -> Result<&mut Self> {
+        let before = self.ctx.trace_begin();
         let y = self.ctx.stream.try_advance(1)?;
         let mut x = [0_u8; 1];
         self.ctx.spongos.decrypt(y, &mut x)?;
         *u = x[0];
+        self.ctx.trace_end(trace::Command::Mask, 1, before);
         Ok(self)
     }
     fn unwrapn(&mut self, bytes: &mut [u8]) -> Result<&mut Self> {
-        let y = self.ctx.stream.try_advance(bytes.len())?;
-        self.ctx.spongos.decrypt(y, bytes)?;
+        let before = self.ctx.trace_begin();
+        for chunk in bytes.chunks_mut(io::CHUNK_SIZE) {
+            let y = self.ctx.stream.try_advance(chunk.len())?;
+            self.ctx.spongos.decrypt(y, chunk)?;
+        }
+        self.ctx.trace_end(trace::Command::Mask, bytes.len(), before);
         Ok(self)
     }
 }
@@ -136,8 +148,9 @@ impl<'a, F: PRP, IS: io::IStream> Mask<&'a mut Bytes> for Context<F, IS> {
     fn mask(&mut self, bytes: &'a mut Bytes) -> Result<&mut Self> {
         let mut size = Size(0);
         self.mask(&mut size)?;
-        (bytes.0).resize(size.0, 0);
-        Ok(unwrap_mask_bytes(self.as_mut(), &mut (bytes.0)[..])?.as_mut())
+        self.check_bytes(size.0)?;
+        bytes.to_mut().resize(size.0, 0);
+        Ok(unwrap_mask_bytes(self.as_mut(), &mut bytes.to_mut()[..])?.as_mut())
     }
 }
 
@@ -163,3 +176,13 @@ impl<'a, F: PRP, IS: io::IStream> Mask<&'a mut ed25519::PublicKey> for Context<F
         }
     }
 }
+
+/// `Str` is unwrapped as `Bytes` and must be valid UTF-8.
+impl<'a, F: PRP, IS: io::IStream> Mask<&'a mut Str> for Context<F, IS> {
+    fn mask(&mut self, s: &'a mut Str) -> Result<&mut Self> {
+        let mut bytes = Bytes::from(mem::take(&mut s.0).into_bytes());
+        self.mask(&mut bytes)?;
+        s.0 = String::from_utf8(bytes.into_vec()).map_err(|e| wrapped_err!(InvalidUtf8, WrappedError(e)))?;
+        Ok(self)
+    }
+}
@@ -1,11 +1,24 @@
 //! Implementation of command traits for unwrapping.
 
-use iota_streams_core::Result;
+use iota_streams_core::{
+    err,
+    prelude::ToString,
+    Errors::{
+        StreamAllocationExceededIn,
+        UnwrapLimitExceeded,
+    },
+    Result,
+};
 
 use core::fmt;
 
 use crate::{
     io,
+    trace::{
+        self,
+        Fingerprint,
+        Trace,
+    },
     types::Size,
 };
 use iota_streams_core::sponge::{
@@ -16,6 +29,14 @@ use iota_streams_core::sponge::{
 pub struct Context<F, IS> {
     pub spongos: Spongos<F>,
     pub stream: IS,
+    /// Trace of processed commands, `None` unless created with `new_traced`.
+    pub trace: Option<Trace>,
+    /// Resource limits for values read from the stream.
+    pub limits: Limits,
+    /// Total length of `Bytes` fields unwrapped so far.
+    allocated: usize,
+    /// Current nesting depth of `repeated` modifiers.
+    depth: usize,
 }
 
 impl<F: PRP, IS> Context<F, IS> {
@@ -23,6 +44,86 @@ impl<F: PRP, IS> Context<F, IS> {
         Self {
             spongos: Spongos::<F>::init(),
             stream,
+            trace: None,
+            limits: Limits::default(),
+            allocated: 0,
+            depth: 0,
+        }
+    }
+
+    /// Create a context which records processed commands into a `Trace`.
+    pub fn new_traced(stream: IS) -> Self {
+        let spongos = Spongos::<F>::init();
+        let trace = Some(Trace::with_state(&spongos));
+        Self {
+            spongos,
+            stream,
+            trace,
+            limits: Limits::default(),
+            allocated: 0,
+            depth: 0,
+        }
+    }
+
+    /// Enforce resource `limits` for values read from the stream.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub(crate) fn trace_begin(&self) -> Option<Fingerprint> {
+        self.trace.as_ref().map(|_| trace::fingerprint(&self.spongos))
+    }
+
+    pub(crate) fn trace_end(&mut self, command: trace::Command, length: usize, before: Option<Fingerprint>) {
+        if let (Some(t), Some(before)) = (self.trace.as_mut(), before) {
+            t.record(command, length, before, trace::fingerprint(&self.spongos));
+        }
+    }
+}
+
+impl<F, IS: io::IStream> Context<F, IS> {
+    /// Check limits and the remaining input before allocating `Bytes` field of length `n`.
+    pub(crate) fn check_bytes(&mut self, n: usize) -> Result<()> {
+        // A field longer than the rest of the message is rejected before allocating it.
+        if let Some(remaining) = self.stream.remaining() {
+            if remaining < n {
+                return err!(StreamAllocationExceededIn(n, remaining));
+            }
+        }
+        // Sizes beyond `isize::MAX` can't be allocated at all, reject them even when unlimited.
+        let max_bytes_len = core::cmp::min(self.limits.max_bytes_len, isize::MAX as usize);
+        if max_bytes_len < n {
+            return err!(UnwrapLimitExceeded("bytes length".to_string(), max_bytes_len, n));
+        }
+        let allocated = self.allocated.saturating_add(n);
+        if self.limits.max_allocation < allocated {
+            return err!(UnwrapLimitExceeded(
+                "total allocation".to_string(),
+                self.limits.max_allocation,
+                allocated
+            ));
+        }
+        self.allocated = allocated;
+        Ok(())
+    }
+}
+
+impl<F, IS> Context<F, IS> {
+    /// Check limits before unwrapping `n` repeated values.
+    ///
+    /// Content types reading a count and looping over it without the `repeated` command
+    /// should check the count first.
+    pub fn check_repeated(&self, n: usize) -> Result<()> {
+        if self.limits.max_repeated < n {
+            return err!(UnwrapLimitExceeded("repeated count".to_string(), self.limits.max_repeated, n));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn trace_skip(&mut self, command: trace::Command, length: usize) {
+        if let Some(t) = self.trace.as_mut() {
+            t.record_skip(command, length);
         }
     }
 }
@@ -30,6 +131,7 @@ impl<F: PRP, IS> Context<F, IS> {
 impl<F, IS: io::IStream> Context<F, IS> {
     pub fn drop(&mut self, n: Size) -> Result<&mut Self> {
         self.stream.try_advance(n.0)?;
+        self.trace_skip(trace::Command::Drop, n.0);
         Ok(self)
     }
 }
@@ -49,6 +151,10 @@ where
         Self {
             spongos: self.spongos.clone(),
             stream: self.stream.clone(),
+            trace: self.trace.clone(),
+            limits: self.limits,
+            allocated: self.allocated,
+            depth: self.depth,
         }
     }
 }
@@ -58,10 +164,13 @@ mod unwrap;
 mod absorb;
 mod absorb_external;
 mod commit;
+mod composite;
 mod dump;
 mod fork;
 mod guard;
 mod join;
+mod label;
+mod limits;
 mod mask;
 mod repeated;
 mod skip;
@@ -74,10 +183,12 @@ mod x25519;
 pub use absorb::*;
 pub use absorb_external::*;
 pub use commit::*;
+pub use composite::*;
 pub use dump::*;
 pub use fork::*;
 pub use guard::*;
 pub use join::*;
+pub use limits::Limits;
 pub use mask::*;
 pub use repeated::*;
 pub use skip::*;
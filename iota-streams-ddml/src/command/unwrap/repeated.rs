@@ -1,4 +1,9 @@
-use iota_streams_core::Result;
+use iota_streams_core::{
+    err,
+    prelude::ToString,
+    Errors::UnwrapLimitExceeded,
+    Result,
+};
 
 use super::Context;
 use crate::{
@@ -13,9 +18,18 @@ where
     C: for<'a> FnMut(&'a mut Self) -> Result<&'a mut Self>,
 {
     fn repeated(&mut self, n: Size, mut value_handle: C) -> Result<&mut Self> {
-        for _ in 0..(n.0) {
-            value_handle(self)?;
+        self.check_repeated(n.0)?;
+        if self.limits.max_nesting <= self.depth {
+            return err!(UnwrapLimitExceeded(
+                "nesting".to_string(),
+                self.limits.max_nesting,
+                self.depth + 1
+            ));
         }
+        self.depth += 1;
+        let r = (0..n.0).try_for_each(|_| value_handle(self).map(|_| ()));
+        self.depth -= 1;
+        r?;
         Ok(self)
     }
 }
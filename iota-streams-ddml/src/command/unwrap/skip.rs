@@ -1,5 +1,11 @@
 use core::mem;
-use iota_streams_core::Result;
+use iota_streams_core::{
+    prelude::String,
+    wrapped_err,
+    Errors::InvalidUtf8,
+    Result,
+    WrappedError,
+};
 
 use super::{
     unwrap::*,
@@ -8,6 +14,7 @@ use super::{
 use crate::{
     command::Skip,
     io,
+    trace,
     types::{
         ArrayLength,
         Bytes,
@@ -15,6 +22,7 @@ use crate::{
         NBytes,
         Size,
         SkipFallback,
+        Str,
         Uint16,
         Uint32,
         Uint64,
@@ -40,11 +48,14 @@ impl<F, IS: io::IStream> Unwrap for SkipContext<F, IS> {
     fn unwrap_u8(&mut self, u: &mut u8) -> Result<&mut Self> {
         let slice = self.ctx.stream.try_advance(1)?;
         *u = slice[0];
+        self.ctx.trace_skip(trace::Command::Skip, 1);
         Ok(self)
     }
     fn unwrapn(&mut self, bytes: &mut [u8]) -> Result<&mut Self> {
-        let slice = self.ctx.stream.try_advance(bytes.len())?;
-        bytes.copy_from_slice(slice);
+        for chunk in bytes.chunks_mut(io::CHUNK_SIZE) {
+            chunk.copy_from_slice(self.ctx.stream.try_advance(chunk.len())?);
+        }
+        self.ctx.trace_skip(trace::Command::Skip, bytes.len());
         Ok(self)
     }
 }
@@ -126,8 +137,14 @@ impl<'a, F, IS: io::IStream> Skip<&'a mut Bytes> for Context<F, IS> {
     fn skip(&mut self, bytes: &'a mut Bytes) -> Result<&mut Self> {
         let mut size = Size(0);
         self.skip(&mut size)?;
-        (bytes.0).resize(size.0, 0);
-        Ok(unwrap_skip_bytes(self.as_mut(), &mut (bytes.0)[..])?.as_mut())
+        self.check_bytes(size.0)?;
+        if let Some(shared) = self.stream.try_advance_shared(size.0)? {
+            self.trace_skip(trace::Command::Skip, size.0);
+            bytes.0 = shared;
+            return Ok(self);
+        }
+        bytes.to_mut().resize(size.0, 0);
+        Ok(unwrap_skip_bytes(self.as_mut(), &mut bytes.to_mut()[..])?.as_mut())
     }
 }
 
@@ -137,3 +154,13 @@ impl<'a, F, T: 'a + SkipFallback<F>, IS: io::IStream> Skip<&'a mut Fallback<T>>
         Ok(self)
     }
 }
+
+/// `Str` is unwrapped as `Bytes` and must be valid UTF-8.
+impl<'a, F, IS: io::IStream> Skip<&'a mut Str> for Context<F, IS> {
+    fn skip(&mut self, s: &'a mut Str) -> Result<&mut Self> {
+        let mut bytes = Bytes::from(mem::take(&mut s.0).into_bytes());
+        self.skip(&mut bytes)?;
+        s.0 = String::from_utf8(bytes.into_vec()).map_err(|e| wrapped_err!(InvalidUtf8, WrappedError(e)))?;
+        Ok(self)
+    }
+}
@@ -4,6 +4,7 @@ use super::Context;
 use crate::{
     command::Squeeze,
     io,
+    trace,
     types::Mac,
 };
 use iota_streams_core::{
@@ -15,7 +16,10 @@ use iota_streams_core::{
 /// External values are not encoded. Squeeze and compare tag trits.
 impl<'a, F: PRP, IS: io::IStream> Squeeze<&'a Mac> for Context<F, IS> {
     fn squeeze(&mut self, val: &'a Mac) -> Result<&mut Self> {
-        try_or!(self.spongos.squeeze_eq(self.stream.try_advance(val.0)?), BadMac)?;
+        let before = self.trace_begin();
+        let eq = self.spongos.squeeze_eq(self.stream.try_advance(val.0)?);
+        self.trace_end(trace::Command::Squeeze, val.0, before);
+        try_or!(eq, BadMac)?;
         Ok(self)
     }
 }
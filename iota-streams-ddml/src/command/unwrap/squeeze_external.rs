@@ -3,6 +3,7 @@ use iota_streams_core::Result;
 use super::Context;
 use crate::{
     command::Squeeze,
+    trace,
     types::{
         ArrayLength,
         External,
@@ -14,7 +15,9 @@ use iota_streams_core::sponge::prp::PRP;
 /// This is just an external tag or hash value to-be-signed.
 impl<'a, F: PRP, N: ArrayLength<u8>, IS> Squeeze<&'a mut External<NBytes<N>>> for Context<F, IS> {
     fn squeeze(&mut self, val: &'a mut External<NBytes<N>>) -> Result<&mut Self> {
+        let before = self.trace_begin();
         self.spongos.squeeze((val.0).as_mut_slice());
+        self.trace_end(trace::Command::SqueezeExternal, (val.0).as_slice().len(), before);
         Ok(self)
     }
 }
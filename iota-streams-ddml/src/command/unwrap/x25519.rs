@@ -9,6 +9,7 @@ use crate::{
         X25519,
     },
     io,
+    trace,
     types::{
         ArrayLength,
         NBytes,
@@ -17,10 +18,14 @@ use crate::{
 use iota_streams_core::sponge::prp::PRP;
 use iota_streams_core_edsig::key_exchange::x25519;
 
-impl<'a, F: PRP, IS: io::IStream> X25519<&'a x25519::StaticSecret, &'a x25519::PublicKey> for Context<F, IS> {
-    fn x25519(&mut self, sk: &x25519::StaticSecret, pk: &x25519::PublicKey) -> Result<&mut Self> {
-        let shared = sk.diffie_hellman(pk);
-        self.spongos.absorb(shared.as_bytes());
+impl<'a, F: PRP, IS: io::IStream, K: x25519::KeyAgreement + ?Sized> X25519<&'a K, &'a x25519::PublicKey>
+    for Context<F, IS>
+{
+    fn x25519(&mut self, sk: &K, pk: &x25519::PublicKey) -> Result<&mut Self> {
+        let shared = sk.diffie_hellman(pk)?;
+        let before = self.trace_begin();
+        self.spongos.absorb(&shared[..]);
+        self.trace_end(trace::Command::X25519, shared.len(), before);
         Ok(self)
     }
 }
@@ -28,15 +33,17 @@ impl<'a, F: PRP, IS: io::IStream> X25519<&'a x25519::StaticSecret, &'a x25519::P
 impl<'a, F: PRP, IS: io::IStream> X25519<x25519::EphemeralSecret, &'a x25519::PublicKey> for Context<F, IS> {
     fn x25519(&mut self, sk: x25519::EphemeralSecret, pk: &x25519::PublicKey) -> Result<&mut Self> {
         let shared = sk.diffie_hellman(pk);
+        let before = self.trace_begin();
         self.spongos.absorb(shared.as_bytes());
+        self.trace_end(trace::Command::X25519, shared.as_bytes().len(), before);
         Ok(self)
     }
 }
 
-impl<'a, F: PRP, N: ArrayLength<u8>, IS: io::IStream> X25519<&'a x25519::StaticSecret, &'a mut NBytes<N>>
+impl<'a, F: PRP, N: ArrayLength<u8>, IS: io::IStream, K: x25519::KeyAgreement + ?Sized> X25519<&'a K, &'a mut NBytes<N>>
     for Context<F, IS>
 {
-    fn x25519(&mut self, sk: &x25519::StaticSecret, key: &mut NBytes<N>) -> Result<&mut Self> {
+    fn x25519(&mut self, sk: &K, key: &mut NBytes<N>) -> Result<&mut Self> {
         let mut ephemeral_ke_pk = x25519::PublicKey::from([0_u8; 32]);
         (*self)
             .absorb(&mut ephemeral_ke_pk)?
@@ -8,6 +8,7 @@ use super::{
 use crate::{
     command::Absorb,
     io,
+    trace,
     types::{
         AbsorbFallback,
         ArrayLength,
@@ -15,6 +16,7 @@ use crate::{
         Fallback,
         NBytes,
         Size,
+        Str,
         Uint16,
         Uint32,
         Uint64,
@@ -43,14 +45,20 @@ impl<F, OS> AsMut<Context<F, OS>> for AbsorbContext<F, OS> {
 
 impl<F: PRP, OS: io::OStream> Wrap for AbsorbContext<F, OS> {
     fn wrap_u8(&mut self, u: u8) -> Result<&mut Self> {
+        let before = self.ctx.trace_begin();
         let slice = self.ctx.stream.try_advance(1)?;
         slice[0] = u;
         self.ctx.spongos.absorb(slice);
+        self.ctx.trace_end(trace::Command::Absorb, 1, before);
         Ok(self)
     }
     fn wrapn(&mut self, bytes: &[u8]) -> Result<&mut Self> {
+        let before = self.ctx.trace_begin();
         self.ctx.spongos.absorb(bytes);
-        self.ctx.stream.try_advance(bytes.len())?.copy_from_slice(bytes);
+        for chunk in bytes.chunks(io::CHUNK_SIZE) {
+            self.ctx.stream.try_advance(chunk.len())?.copy_from_slice(chunk);
+        }
+        self.ctx.trace_end(trace::Command::Absorb, bytes.len(), before);
         Ok(self)
     }
 }
@@ -183,3 +191,10 @@ impl<'a, F, T: 'a + AbsorbFallback<F>, OS: io::OStream> Absorb<&'a Fallback<T>>
         Ok(self)
     }
 }
+
+impl<'a, F: PRP, OS: io::OStream> Absorb<&'a Str> for Context<F, OS> {
+    fn absorb(&mut self, s: &'a Str) -> Result<&mut Self> {
+        self.absorb(Size(s.0.len()))?;
+        Ok(wrap_absorb_bytes(self.as_mut(), s.0.as_bytes())?.as_mut())
+    }
+}
@@ -8,6 +8,7 @@ use super::{
 use crate::{
     command::Absorb,
     io,
+    trace,
     types::{
         AbsorbExternalFallback,
         ArrayLength,
@@ -43,11 +44,15 @@ impl<F, OS> AsMut<Context<F, OS>> for AbsorbExternalContext<F, OS> {
 
 impl<F: PRP, OS: io::OStream> Wrap for AbsorbExternalContext<F, OS> {
     fn wrap_u8(&mut self, u: u8) -> Result<&mut Self> {
+        let before = self.ctx.trace_begin();
         self.ctx.spongos.absorb(&[u]);
+        self.ctx.trace_end(trace::Command::AbsorbExternal, 1, before);
         Ok(self)
     }
     fn wrapn(&mut self, bytes: &[u8]) -> Result<&mut Self> {
+        let before = self.ctx.trace_begin();
         self.ctx.spongos.absorb(bytes);
+        self.ctx.trace_end(trace::Command::AbsorbExternal, bytes.len(), before);
         Ok(self)
     }
 }
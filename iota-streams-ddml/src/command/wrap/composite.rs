@@ -0,0 +1,110 @@
+//! Types encoded in terms of the basic DDML types: signed and variable-size integers, `Option` and `Map`.
+
+use iota_streams_core::Result;
+
+use super::Context;
+use crate::{
+    command::{
+        Absorb,
+        Element,
+        Mask,
+        Skip,
+    },
+    io,
+    types::{
+        varuint_bytes,
+        Int16,
+        Int32,
+        Int64,
+        Int8,
+        Map,
+        Size,
+        Uint16,
+        Uint32,
+        Uint64,
+        Uint8,
+        Varint,
+        Varuint,
+    },
+};
+use iota_streams_core::sponge::prp::PRP;
+
+macro_rules! impl_composite {
+    ($trait:ident, $method:ident) => {
+        /// Signed integers are encoded in two's complement.
+        impl<'a, F: PRP, OS: io::OStream> $trait<&'a Int8> for Context<F, OS> {
+            fn $method(&mut self, i: &'a Int8) -> Result<&mut Self> {
+                self.$method(&Uint8(i.0 as u8))
+            }
+        }
+
+        impl<'a, F: PRP, OS: io::OStream> $trait<&'a Int16> for Context<F, OS> {
+            fn $method(&mut self, i: &'a Int16) -> Result<&mut Self> {
+                self.$method(&Uint16(i.0 as u16))
+            }
+        }
+
+        impl<'a, F: PRP, OS: io::OStream> $trait<&'a Int32> for Context<F, OS> {
+            fn $method(&mut self, i: &'a Int32) -> Result<&mut Self> {
+                self.$method(&Uint32(i.0 as u32))
+            }
+        }
+
+        impl<'a, F: PRP, OS: io::OStream> $trait<&'a Int64> for Context<F, OS> {
+            fn $method(&mut self, i: &'a Int64) -> Result<&mut Self> {
+                self.$method(&Uint64(i.0 as u64))
+            }
+        }
+
+        /// `Varuint` is encoded as the number of significant bytes followed by these bytes.
+        impl<'a, F: PRP, OS: io::OStream> $trait<&'a Varuint> for Context<F, OS> {
+            fn $method(&mut self, u: &'a Varuint) -> Result<&mut Self> {
+                let d = varuint_bytes(u.0);
+                self.$method(&Uint8(d as u8))?;
+                for s in (0..d).rev() {
+                    self.$method(&Uint8((u.0 >> (s << 3)) as u8))?;
+                }
+                Ok(self)
+            }
+        }
+
+        /// `Varint` is zigzag-encoded as `Varuint`.
+        impl<'a, F: PRP, OS: io::OStream> $trait<&'a Varint> for Context<F, OS> {
+            fn $method(&mut self, i: &'a Varint) -> Result<&mut Self> {
+                self.$method(&Varuint::from(*i))
+            }
+        }
+
+        /// `Option` is encoded as a `Uint8` tag, 0 for `None` and 1 for `Some`, followed by the value if any.
+        impl<'a, F: PRP, OS: io::OStream, T> $trait<&'a Option<T>> for Context<F, OS>
+        where
+            &'a T: Element<Self>,
+        {
+            fn $method(&mut self, option: &'a Option<T>) -> Result<&mut Self> {
+                match option {
+                    None => self.$method(&Uint8(0)),
+                    Some(value) => Element::$method(value, self.$method(&Uint8(1))?),
+                }
+            }
+        }
+
+        /// `Map` is encoded as the number of entries followed by the entries in ascending key order.
+        impl<'a, F: PRP, OS: io::OStream, K, V> $trait<&'a Map<K, V>> for Context<F, OS>
+        where
+            &'a K: Element<Self>,
+            &'a V: Element<Self>,
+        {
+            fn $method(&mut self, map: &'a Map<K, V>) -> Result<&mut Self> {
+                self.$method(&Size(map.0.len()))?;
+                for (key, value) in map.0.iter() {
+                    Element::$method(value, Element::$method(key, &mut *self)?)?;
+                }
+                Ok(self)
+            }
+        }
+    };
+}
+
+impl_composite!(Absorb, absorb);
+impl_composite!(Mask, mask);
+impl_composite!(Skip, skip);
@@ -5,6 +5,7 @@ use crate::{
     command::Join,
     io,
     link_store::LinkStore,
+    trace,
     types::SkipFallback,
 };
 use iota_streams_core::sponge::prp::PRP;
@@ -14,7 +15,9 @@ impl<'a, F: PRP, L: SkipFallback<F>, S: LinkStore<F, L>, OS: io::OStream> Join<&
         // TODO: Return and use info.
         let (mut s, _i) = store.lookup(link)?;
         link.wrap_skip(self)?;
+        let before = self.trace_begin();
         self.spongos.join(&mut s);
+        self.trace_end(trace::Command::Join, 0, before);
         Ok(self)
     }
 }
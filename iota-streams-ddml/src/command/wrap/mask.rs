@@ -8,11 +8,13 @@ use super::{
 use crate::{
     command::Mask,
     io,
+    trace,
     types::{
         ArrayLength,
         Bytes,
         NBytes,
         Size,
+        Str,
         Uint16,
         Uint32,
         Uint64,
@@ -41,14 +43,20 @@ impl<F, OS> AsMut<Context<F, OS>> for MaskContext<F, OS> {
 
 impl<F: PRP, OS: io::OStream> Wrap for MaskContext<F, OS> {
     fn wrap_u8(&mut self, u: u8) -> Result<&mut Self> {
+        let before = self.ctx.trace_begin();
         let slice = self.ctx.stream.try_advance(1)?;
         slice[0] = u;
         self.ctx.spongos.encrypt_mut(slice);
+        self.ctx.trace_end(trace::Command::Mask, 1, before);
         Ok(self)
     }
     fn wrapn(&mut self, bytes: &[u8]) -> Result<&mut Self> {
-        let mut slice = self.ctx.stream.try_advance(bytes.len())?;
-        self.ctx.spongos.encrypt(bytes, &mut slice)?;
+        let before = self.ctx.trace_begin();
+        for chunk in bytes.chunks(io::CHUNK_SIZE) {
+            let slice = self.ctx.stream.try_advance(chunk.len())?;
+            self.ctx.spongos.encrypt(chunk, slice)?;
+        }
+        self.ctx.trace_end(trace::Command::Mask, bytes.len(), before);
         Ok(self)
     }
 }
@@ -145,3 +153,10 @@ impl<'a, F: PRP, OS: io::OStream> Mask<&'a ed25519::PublicKey> for Context<F, OS
         Ok(wrap_mask_bytes(self.as_mut(), &pk.to_bytes()[..])?.as_mut())
     }
 }
+
+impl<'a, F: PRP, OS: io::OStream> Mask<&'a Str> for Context<F, OS> {
+    fn mask(&mut self, s: &'a Str) -> Result<&mut Self> {
+        self.mask(&Size(s.0.len()))?;
+        Ok(wrap_mask_bytes(self.as_mut(), s.0.as_bytes())?.as_mut())
+    }
+}
@@ -5,9 +5,17 @@ use iota_streams_core::sponge::{
     spongos::*,
 };
 
+use crate::trace::{
+    self,
+    Fingerprint,
+    Trace,
+};
+
 pub struct Context<F, OS> {
     pub spongos: Spongos<F>,
     pub stream: OS,
+    /// Trace of processed commands, `None` unless created with `new_traced`.
+    pub trace: Option<Trace>,
 }
 
 impl<F: PRP, OS> Context<F, OS> {
@@ -15,6 +23,32 @@ impl<F: PRP, OS> Context<F, OS> {
         Self {
             spongos: Spongos::<F>::init(),
             stream,
+            trace: None,
+        }
+    }
+
+    /// Create a context which records processed commands into a `Trace`.
+    pub fn new_traced(stream: OS) -> Self {
+        let spongos = Spongos::<F>::init();
+        let trace = Some(Trace::with_state(&spongos));
+        Self { spongos, stream, trace }
+    }
+
+    pub(crate) fn trace_begin(&self) -> Option<Fingerprint> {
+        self.trace.as_ref().map(|_| trace::fingerprint(&self.spongos))
+    }
+
+    pub(crate) fn trace_end(&mut self, command: trace::Command, length: usize, before: Option<Fingerprint>) {
+        if let (Some(t), Some(before)) = (self.trace.as_mut(), before) {
+            t.record(command, length, before, trace::fingerprint(&self.spongos));
+        }
+    }
+}
+
+impl<F, OS> Context<F, OS> {
+    pub(crate) fn trace_skip(&mut self, command: trace::Command, length: usize) {
+        if let Some(t) = self.trace.as_mut() {
+            t.record_skip(command, length);
         }
     }
 }
@@ -25,10 +59,12 @@ pub(crate) use wrap::*;
 mod absorb;
 mod absorb_external;
 mod commit;
+mod composite;
 mod dump;
 mod fork;
 mod guard;
 mod join;
+mod label;
 mod mask;
 mod repeated;
 mod skip;
@@ -41,6 +77,7 @@ mod x25519;
 pub use absorb::*;
 pub use absorb_external::*;
 pub use commit::*;
+pub use composite::*;
 pub use dump::*;
 pub use fork::*;
 pub use guard::*;
@@ -8,6 +8,7 @@ use super::{
 use crate::{
     command::Skip,
     io,
+    trace,
     types::{
         ArrayLength,
         Bytes,
@@ -15,6 +16,7 @@ use crate::{
         NBytes,
         Size,
         SkipFallback,
+        Str,
         Uint16,
         Uint32,
         Uint64,
@@ -40,10 +42,14 @@ impl<F, OS: io::OStream> Wrap for SkipContext<F, OS> {
     fn wrap_u8(&mut self, u: u8) -> Result<&mut Self> {
         let slice = self.ctx.stream.try_advance(1)?;
         slice[0] = u;
+        self.ctx.trace_skip(trace::Command::Skip, 1);
         Ok(self)
     }
     fn wrapn(&mut self, bytes: &[u8]) -> Result<&mut Self> {
-        self.ctx.stream.try_advance(bytes.len())?.copy_from_slice(bytes);
+        for chunk in bytes.chunks(io::CHUNK_SIZE) {
+            self.ctx.stream.try_advance(chunk.len())?.copy_from_slice(chunk);
+        }
+        self.ctx.trace_skip(trace::Command::Skip, bytes.len());
         Ok(self)
     }
 }
@@ -164,3 +170,10 @@ impl<'a, F, T: 'a + SkipFallback<F>, OS: io::OStream> Skip<&'a Fallback<T>> for
         Ok(self)
     }
 }
+
+impl<'a, F, OS: io::OStream> Skip<&'a Str> for Context<F, OS> {
+    fn skip(&mut self, s: &'a Str) -> Result<&mut Self> {
+        wrap_skip_size(self.as_mut(), Size(s.0.len()))?;
+        Ok(wrap_skip_trits(self.as_mut(), s.0.as_bytes())?.as_mut())
+    }
+}
@@ -4,6 +4,7 @@ use super::Context;
 use crate::{
     command::Squeeze,
     io,
+    trace,
     types::Mac,
 };
 use iota_streams_core::sponge::prp::PRP;
@@ -11,7 +12,9 @@ use iota_streams_core::sponge::prp::PRP;
 /// External values are not encoded.
 impl<'a, F: PRP, OS: io::OStream> Squeeze<&'a Mac> for Context<F, OS> {
     fn squeeze(&mut self, mac: &'a Mac) -> Result<&mut Self> {
+        let before = self.trace_begin();
         self.spongos.squeeze(&mut self.stream.try_advance(mac.0)?);
+        self.trace_end(trace::Command::Squeeze, mac.0, before);
         Ok(self)
     }
 }
@@ -3,6 +3,7 @@ use iota_streams_core::Result;
 use super::Context;
 use crate::{
     command::Squeeze,
+    trace,
     types::{
         ArrayLength,
         External,
@@ -14,7 +15,13 @@ use iota_streams_core::sponge::prp::PRP;
 /// This is just an external tag or hash value to-be-signed.
 impl<'a, F: PRP, N: ArrayLength<u8>, OS> Squeeze<&'a mut External<NBytes<N>>> for Context<F, OS> {
     fn squeeze(&mut self, external_nbytes: &'a mut External<NBytes<N>>) -> Result<&mut Self> {
+        let before = self.trace_begin();
         self.spongos.squeeze((external_nbytes.0).as_mut_slice());
+        self.trace_end(
+            trace::Command::SqueezeExternal,
+            (external_nbytes.0).as_slice().len(),
+            before,
+        );
         Ok(self)
     }
 }
@@ -22,7 +29,13 @@ impl<'a, F: PRP, N: ArrayLength<u8>, OS> Squeeze<&'a mut External<NBytes<N>>> fo
 /// This is just an external tag or hash value to-be-signed.
 impl<'a, F: PRP, N: ArrayLength<u8>, OS> Squeeze<External<&'a mut NBytes<N>>> for Context<F, OS> {
     fn squeeze(&mut self, external_nbytes: External<&'a mut NBytes<N>>) -> Result<&mut Self> {
+        let before = self.trace_begin();
         self.spongos.squeeze((external_nbytes.0).as_mut_slice());
+        self.trace_end(
+            trace::Command::SqueezeExternal,
+            (external_nbytes.0).as_slice().len(),
+            before,
+        );
         Ok(self)
     }
 }
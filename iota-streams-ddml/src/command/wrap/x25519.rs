@@ -9,15 +9,15 @@ use iota_streams_core::prng::rng;
 use iota_streams_core::Result;
 
 use super::Context;
-#[cfg(feature = "std")]
-use crate::command::{
-    Absorb,
-    Commit,
-    Mask,
-};
 use crate::{
-    command::X25519,
+    command::{
+        Absorb,
+        Commit,
+        Mask,
+        X25519,
+    },
     io,
+    trace,
     types::{
         ArrayLength,
         NBytes,
@@ -27,10 +27,14 @@ use crate::{
 use iota_streams_core::sponge::prp::PRP;
 use iota_streams_core_edsig::key_exchange::x25519;
 
-impl<'a, F: PRP, OS: io::OStream> X25519<&'a x25519::StaticSecret, &'a x25519::PublicKey> for Context<F, OS> {
-    fn x25519(&mut self, sk: &x25519::StaticSecret, pk: &x25519::PublicKey) -> Result<&mut Self> {
-        let shared = sk.diffie_hellman(pk);
-        self.spongos.absorb(shared.as_bytes());
+impl<'a, F: PRP, OS: io::OStream, K: x25519::KeyAgreement + ?Sized> X25519<&'a K, &'a x25519::PublicKey>
+    for Context<F, OS>
+{
+    fn x25519(&mut self, sk: &K, pk: &x25519::PublicKey) -> Result<&mut Self> {
+        let shared = sk.diffie_hellman(pk)?;
+        let before = self.trace_begin();
+        self.spongos.absorb(&shared[..]);
+        self.trace_end(trace::Command::X25519, shared.len(), before);
         Ok(self)
     }
 }
@@ -38,15 +42,22 @@ impl<'a, F: PRP, OS: io::OStream> X25519<&'a x25519::StaticSecret, &'a x25519::P
 impl<'a, F: PRP, OS: io::OStream> X25519<x25519::EphemeralSecret, &'a x25519::PublicKey> for Context<F, OS> {
     fn x25519(&mut self, sk: x25519::EphemeralSecret, pk: &x25519::PublicKey) -> Result<&mut Self> {
         let shared = sk.diffie_hellman(pk);
+        let before = self.trace_begin();
         self.spongos.absorb(shared.as_bytes());
+        self.trace_end(trace::Command::X25519, shared.as_bytes().len(), before);
         Ok(self)
     }
 }
 
-#[cfg(feature = "std")]
-impl<'a, F: PRP, N: ArrayLength<u8>, OS: io::OStream> X25519<&'a x25519::PublicKey, &'a NBytes<N>> for Context<F, OS> {
-    fn x25519(&mut self, pk: &x25519::PublicKey, key: &NBytes<N>) -> Result<&mut Self> {
-        let ephemeral_ke_sk = x25519::EphemeralSecret::new(&mut rng());
+/// Encrypt `key` for `pk` using the given ephemeral secret.
+impl<'a, F: PRP, N: ArrayLength<u8>, OS: io::OStream>
+    X25519<(x25519::EphemeralSecret, &'a x25519::PublicKey), &'a NBytes<N>> for Context<F, OS>
+{
+    fn x25519(
+        &mut self,
+        (ephemeral_ke_sk, pk): (x25519::EphemeralSecret, &x25519::PublicKey),
+        key: &NBytes<N>,
+    ) -> Result<&mut Self> {
         let ephemeral_ke_pk = x25519::PublicKey::from(&ephemeral_ke_sk);
         self.absorb(&ephemeral_ke_pk)?
             .x25519(ephemeral_ke_sk, pk)?
@@ -55,6 +66,14 @@ impl<'a, F: PRP, N: ArrayLength<u8>, OS: io::OStream> X25519<&'a x25519::PublicK
     }
 }
 
+#[cfg(feature = "std")]
+impl<'a, F: PRP, N: ArrayLength<u8>, OS: io::OStream> X25519<&'a x25519::PublicKey, &'a NBytes<N>> for Context<F, OS> {
+    fn x25519(&mut self, pk: &x25519::PublicKey, key: &NBytes<N>) -> Result<&mut Self> {
+        let ephemeral_ke_sk = x25519::EphemeralSecret::new(&mut rng());
+        self.x25519((ephemeral_ke_sk, pk), key)
+    }
+}
+
 #[cfg(not(feature = "std"))]
 impl<'a, F: PRP, N: ArrayLength<u8>, OS: io::OStream> X25519<&'a x25519::PublicKey, &'a NBytes<N>> for Context<F, OS> {
     fn x25519(&mut self, _pk: &x25519::PublicKey, _key: &NBytes<N>) -> Result<&mut Self> {
@@ -1,4 +1,10 @@
 //! Lightweight abstraction, a trinary equivalent of `Write` trait allowing access to trinary slices.
+//!
+//! Besides contiguous slices, streams can be backed by segmented buffers ([`SegmentedOStream`],
+//! [`SegmentedIStream`]) and, with `std`, by `std::io::Write` and `std::io::Read` ([`WriteStream`],
+//! [`ReadStream`]). [`SharedIStream`] reads from a [`SharedBuffer`] and lets `Bytes` share it instead
+//! of copying. Commands processing variable-size data advance the stream by at most [`CHUNK_SIZE`]
+//! bytes at a time, so such streams only ever buffer a bounded part of a message.
 
 use iota_streams_core::{
     prelude::{
@@ -13,6 +19,30 @@ use iota_streams_core::{
     Result,
 };
 
+use crate::types::SharedBuffer;
+
+mod segmented;
+pub use segmented::{
+    SegmentedIStream,
+    SegmentedOStream,
+};
+
+mod shared;
+pub use shared::SharedIStream;
+
+#[cfg(feature = "std")]
+mod read_write;
+#[cfg(feature = "std")]
+pub use read_write::{
+    ReadStream,
+    WriteStream,
+};
+
+/// Maximum number of bytes a command advances a stream by at once.
+///
+/// Larger fields (eg. `Bytes`) are written and read in several advances of at most this size.
+pub const CHUNK_SIZE: usize = 1024;
+
 /// Write
 pub trait OStream {
     /// Try advance and panic in case of error.
@@ -46,6 +76,18 @@ pub trait IStream {
     /// Try get n tbits from the stream, returning a slice to the buffer.
     fn try_advance<'a>(&'a mut self, n: usize) -> Result<&'a [u8]>;
 
+    /// Try get n bytes from the stream without copying them.
+    ///
+    /// Returns `None` without advancing the stream if it is not backed by a `SharedBuffer`.
+    fn try_advance_shared(&mut self, _n: usize) -> Result<Option<SharedBuffer>> {
+        Ok(None)
+    }
+
+    /// Number of bytes left in the stream, `None` if the stream can't tell.
+    fn remaining(&self) -> Option<usize> {
+        None
+    }
+
     /// Commit advanced buffers from the internal sources.
     fn commit(&mut self);
 
@@ -79,6 +121,9 @@ impl<'b> IStream for &'b [u8] {
         }
         Ok(head)
     }
+    fn remaining(&self) -> Option<usize> {
+        Some(self.len())
+    }
     fn commit(&mut self) {}
     fn dump(&self) -> String {
         format!("{}", hex::encode(self))
@@ -0,0 +1,132 @@
+use std::io::{
+    Read,
+    Write,
+};
+
+use iota_streams_core::{
+    prelude::Vec,
+    wrapped_err,
+    Errors::{
+        StreamReadFailure,
+        StreamWriteFailure,
+    },
+    Result,
+    WrappedError,
+};
+
+use super::{
+    IStream,
+    OStream,
+};
+
+/// Output stream over a `std::io::Write`.
+///
+/// Each advance is buffered and written out on the next advance, on [`WriteStream::flush`] or when the stream
+/// is dropped, so that at most one advance (see [`CHUNK_SIZE`](super::CHUNK_SIZE)) is held in memory. Errors are
+/// ignored when dropping, `flush` or `into_inner` should be called to handle them.
+pub struct WriteStream<W: Write> {
+    writer: Option<W>,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> WriteStream<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Some(writer),
+            buf: Vec::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        self.writer.as_ref().unwrap()
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        self.writer.as_mut().unwrap()
+    }
+
+    /// Write out the last advance and flush the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        self.write_buf()?;
+        self.get_mut()
+            .flush()
+            .map_err(|e| wrapped_err!(StreamWriteFailure, WrappedError(e)))
+    }
+
+    /// Flush the stream and return the underlying writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.flush()?;
+        Ok(self.writer.take().unwrap())
+    }
+
+    fn write_buf(&mut self) -> Result<()> {
+        if !self.buf.is_empty() {
+            let writer = self.writer.as_mut().unwrap();
+            writer
+                .write_all(&self.buf)
+                .map_err(|e| wrapped_err!(StreamWriteFailure, WrappedError(e)))?;
+            self.buf.clear();
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> OStream for WriteStream<W> {
+    fn try_advance<'a>(&'a mut self, n: usize) -> Result<&'a mut [u8]> {
+        self.write_buf()?;
+        self.buf.resize(n, 0);
+        Ok(&mut self.buf[..])
+    }
+
+    fn commit(&mut self) {}
+}
+
+impl<W: Write> Drop for WriteStream<W> {
+    fn drop(&mut self) {
+        if self.writer.is_some() {
+            let _ = self.flush();
+        }
+    }
+}
+
+/// Input stream over a `std::io::Read`.
+///
+/// Each advance reads exactly the requested number of bytes into an internal buffer. Wrapping the reader into
+/// a `std::io::BufReader` avoids small reads when unwrapping fields of fixed size.
+pub struct ReadStream<R: Read> {
+    reader: R,
+    buf: Vec<u8>,
+}
+
+impl<R: Read> ReadStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> IStream for ReadStream<R> {
+    fn try_advance<'a>(&'a mut self, n: usize) -> Result<&'a [u8]> {
+        self.buf.resize(n, 0);
+        self.reader
+            .read_exact(&mut self.buf)
+            .map_err(|e| wrapped_err!(StreamReadFailure, WrappedError(e)))?;
+        Ok(&self.buf[..])
+    }
+
+    fn commit(&mut self) {}
+}
@@ -0,0 +1,184 @@
+use core::mem;
+
+use iota_streams_core::{
+    err,
+    prelude::Vec,
+    Errors::{
+        StreamAllocationExceededIn,
+        StreamAllocationExceededOut,
+    },
+    Result,
+};
+
+use super::{
+    IStream,
+    OStream,
+};
+
+/// Output stream writing into a sequence of non-contiguous buffers.
+///
+/// Advances fitting into the current segment borrow it directly. An advance spanning several segments is
+/// served from an internal buffer which is copied into the segments on the next advance, on `commit` or when
+/// the stream is dropped.
+pub struct SegmentedOStream<'a, I> {
+    segments: I,
+    current: &'a mut [u8],
+    buf: Vec<u8>,
+    targets: Vec<&'a mut [u8]>,
+}
+
+impl<'a, I> SegmentedOStream<'a, I> {
+    /// Copy the buffered advance into the segments it spans.
+    fn scatter(&mut self) {
+        let mut buf = &self.buf[..];
+        for target in self.targets.drain(..) {
+            let (head, tail) = buf.split_at(target.len());
+            target.copy_from_slice(head);
+            buf = tail;
+        }
+    }
+}
+
+impl<'a, I> SegmentedOStream<'a, I>
+where
+    I: Iterator<Item = &'a mut [u8]>,
+{
+    pub fn new(segments: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            segments: segments.into_iter(),
+            current: &mut [],
+            buf: Vec::new(),
+            targets: Vec::new(),
+        }
+    }
+
+    /// Whether all the segments have been filled.
+    pub fn is_empty(&mut self) -> bool {
+        !self.next_segment()
+    }
+
+    /// Skip to the next non-empty segment, if the current one is full.
+    fn next_segment(&mut self) -> bool {
+        while self.current.is_empty() {
+            match self.segments.next() {
+                Some(segment) => self.current = segment,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    fn split_current(&mut self, n: usize) -> &'a mut [u8] {
+        let (head, tail) = mem::take(&mut self.current).split_at_mut(n);
+        self.current = tail;
+        head
+    }
+}
+
+impl<'a, I> OStream for SegmentedOStream<'a, I>
+where
+    I: Iterator<Item = &'a mut [u8]>,
+{
+    fn try_advance<'b>(&'b mut self, n: usize) -> Result<&'b mut [u8]> {
+        self.scatter();
+        self.next_segment();
+        if n <= self.current.len() {
+            return Ok(self.split_current(n));
+        }
+
+        let mut remaining = n;
+        while remaining != 0 {
+            if !self.next_segment() {
+                self.targets.clear();
+                return err!(StreamAllocationExceededOut(n, n - remaining));
+            }
+            let k = remaining.min(self.current.len());
+            let target = self.split_current(k);
+            self.targets.push(target);
+            remaining -= k;
+        }
+        self.buf.clear();
+        self.buf.resize(n, 0);
+        Ok(&mut self.buf[..])
+    }
+
+    fn commit(&mut self) {
+        self.scatter();
+    }
+}
+
+impl<'a, I> Drop for SegmentedOStream<'a, I> {
+    fn drop(&mut self) {
+        self.scatter();
+    }
+}
+
+/// Input stream reading from a sequence of non-contiguous buffers.
+///
+/// Advances within the current segment borrow it directly, advances spanning several segments are copied into
+/// an internal buffer.
+pub struct SegmentedIStream<'a, I> {
+    segments: I,
+    current: &'a [u8],
+    buf: Vec<u8>,
+}
+
+impl<'a, I> SegmentedIStream<'a, I>
+where
+    I: Iterator<Item = &'a [u8]>,
+{
+    pub fn new(segments: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            segments: segments.into_iter(),
+            current: &[],
+            buf: Vec::new(),
+        }
+    }
+
+    /// Whether all the segments have been consumed.
+    pub fn is_empty(&mut self) -> bool {
+        !self.next_segment()
+    }
+
+    /// Skip to the next non-empty segment, if the current one is consumed.
+    fn next_segment(&mut self) -> bool {
+        while self.current.is_empty() {
+            match self.segments.next() {
+                Some(segment) => self.current = segment,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    fn split_current(&mut self, n: usize) -> &'a [u8] {
+        let (head, tail) = self.current.split_at(n);
+        self.current = tail;
+        head
+    }
+}
+
+impl<'a, I> IStream for SegmentedIStream<'a, I>
+where
+    I: Iterator<Item = &'a [u8]>,
+{
+    fn try_advance<'b>(&'b mut self, n: usize) -> Result<&'b [u8]> {
+        self.next_segment();
+        if n <= self.current.len() {
+            return Ok(self.split_current(n));
+        }
+
+        self.buf.clear();
+        while self.buf.len() != n {
+            if !self.next_segment() {
+                return err!(StreamAllocationExceededIn(n, self.buf.len()));
+            }
+            let k = (n - self.buf.len()).min(self.current.len());
+            let head = self.split_current(k);
+            self.buf.extend_from_slice(head);
+        }
+        Ok(&self.buf[..])
+    }
+
+    fn commit(&mut self) {}
+}
@@ -0,0 +1,61 @@
+use iota_streams_core::{
+    prelude::{
+        hex,
+        String,
+    },
+    try_or,
+    Errors::StreamAllocationExceededIn,
+    Result,
+};
+
+use super::IStream;
+use crate::types::SharedBuffer;
+
+/// Input stream reading from a `SharedBuffer`.
+///
+/// `try_advance_shared` returns parts of the buffer without copying them, so unwrapped `Bytes`
+/// share the buffer of the message.
+#[derive(Clone, Default, Debug)]
+pub struct SharedIStream {
+    buf: SharedBuffer,
+    pos: usize,
+}
+
+impl SharedIStream {
+    pub fn new(buf: SharedBuffer) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Number of bytes left in the stream.
+    pub fn len(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl IStream for SharedIStream {
+    fn try_advance<'a>(&'a mut self, n: usize) -> Result<&'a [u8]> {
+        try_or!(n <= self.len(), StreamAllocationExceededIn(n, self.len()))?;
+        self.pos += n;
+        Ok(&self.buf[self.pos - n..self.pos])
+    }
+
+    fn try_advance_shared(&mut self, n: usize) -> Result<Option<SharedBuffer>> {
+        try_or!(n <= self.len(), StreamAllocationExceededIn(n, self.len()))?;
+        self.pos += n;
+        Ok(Some(self.buf.slice(self.pos - n..self.pos)))
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn commit(&mut self) {}
+
+    fn dump(&self) -> String {
+        hex::encode(&self.buf[self.pos..])
+    }
+}
@@ -15,7 +15,7 @@ extern crate std;
 #[allow(clippy::all)]
 pub mod command;
 
-/// Abstractions for input/output buffers. It does not support the actual IO.
+/// Abstractions for input/output buffers, with adapters over segmented buffers and `std::io`.
 #[allow(clippy::all)]
 pub mod io;
 
@@ -23,6 +23,18 @@ pub mod io;
 #[allow(clippy::all)]
 pub mod types;
 
+/// Opt-in structured trace of processed commands.
+pub mod trace;
+
 /// LinkStore trait and impls.
 #[allow(clippy::all)]
 pub mod link_store;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use iota_streams_core::{
+        err,
+        Errors,
+        Result,
+    };
+}
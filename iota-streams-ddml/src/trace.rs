@@ -0,0 +1,251 @@
+//! Structured trace of processed DDML commands.
+//!
+//! A trace is opt-in: wrap and unwrap contexts created with `Context::new_traced` record
+//! every command into a `Trace` instead of doing nothing (or printing, in case of `dump`).
+//! Each entry records the field label (as set by the last `label` or `dump` command), the kind of
+//! command, the offset and length of the encoded field in the stream and a fingerprint of
+//! the Spongos state before and after the command.
+//!
+//! Traces of a wrap and an unwrap of the same message are expected to be equal, comparing
+//! them with `Trace::diverges_at` helps to locate the first field where two implementations
+//! disagree. Traces can be exported as JSON with `Trace::to_json`.
+
+use core::fmt;
+
+use iota_streams_core::{
+    prelude::{
+        hex,
+        String,
+        ToString,
+        Vec,
+    },
+    sponge::{
+        prp::PRP,
+        spongos::Spongos,
+    },
+};
+
+/// Size of Spongos state fingerprint in bytes.
+pub const FINGERPRINT_SIZE: usize = 16;
+
+/// Fingerprint of Spongos state.
+pub type Fingerprint = [u8; FINGERPRINT_SIZE];
+
+/// Compute fingerprint of Spongos state.
+///
+/// The fingerprint is squeezed from a modified copy of the state so that it never reveals
+/// keystream used by subsequent `mask` commands.
+pub fn fingerprint<F: PRP>(spongos: &Spongos<F>) -> Fingerprint {
+    let mut s = spongos.clone();
+    s.absorb(b"trace");
+    s.commit();
+    let mut f = [0_u8; FINGERPRINT_SIZE];
+    s.squeeze(&mut f[..]);
+    f
+}
+
+/// Kind of a traced command.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Command {
+    Absorb,
+    AbsorbExternal,
+    Mask,
+    Skip,
+    Squeeze,
+    SqueezeExternal,
+    Commit,
+    Ed25519,
+    X25519,
+    Join,
+    Drop,
+}
+
+impl Command {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Command::Absorb => "absorb",
+            Command::AbsorbExternal => "absorb_external",
+            Command::Mask => "mask",
+            Command::Skip => "skip",
+            Command::Squeeze => "squeeze",
+            Command::SqueezeExternal => "squeeze_external",
+            Command::Commit => "commit",
+            Command::Ed25519 => "ed25519",
+            Command::X25519 => "x25519",
+            Command::Join => "join",
+            Command::Drop => "drop",
+        }
+    }
+}
+
+impl Command {
+    /// Whether fields of the command are encoded in the stream.
+    pub fn is_encoded(&self) -> bool {
+        !matches!(
+            self,
+            Command::AbsorbExternal | Command::SqueezeExternal | Command::Commit | Command::X25519 | Command::Join
+        )
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single traced command.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Entry {
+    /// Label of the field as set by the last `label` or `dump` command, empty if none.
+    pub field: String,
+    /// Kind of the command.
+    pub command: Command,
+    /// Offset of the encoded field relative to the start of the trace.
+    pub offset: usize,
+    /// Length of the field in bytes, external fields are not encoded and do not advance the offset.
+    pub length: usize,
+    /// Fingerprint of Spongos state before the command.
+    pub spongos_before: Fingerprint,
+    /// Fingerprint of Spongos state after the command.
+    pub spongos_after: Fingerprint,
+}
+
+/// Trace of DDML commands.
+///
+/// Commands are traced at the level of primitive encodings, consecutive commands of the same
+/// kind and for the same field are merged into a single entry. Use `label` to label fields and
+/// separate entries of adjacent fields of the same kind.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Trace {
+    entries: Vec<Entry>,
+    field: String,
+    sealed: bool,
+    offset: usize,
+    state: Fingerprint,
+}
+
+impl Trace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a trace starting with the given Spongos state.
+    pub fn with_state<F: PRP>(spongos: &Spongos<F>) -> Self {
+        Self {
+            state: fingerprint(spongos),
+            ..Self::default()
+        }
+    }
+
+    /// Synchronize with Spongos state replaced outside of traced commands, eg. after `fork`.
+    pub fn sync<F: PRP>(&mut self, spongos: &Spongos<F>) {
+        self.state = fingerprint(spongos);
+    }
+
+    /// Recorded entries.
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Total number of bytes encoded in the stream since the start of the trace.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Label subsequent commands with `field`.
+    pub fn label(&mut self, field: impl ToString) {
+        self.field = field.to_string();
+        self.sealed = true;
+    }
+
+    /// Record a command which processed `length` bytes.
+    pub fn record(&mut self, command: Command, length: usize, before: Fingerprint, after: Fingerprint) {
+        let offset = self.offset;
+        if command.is_encoded() {
+            self.offset += length;
+        }
+        self.state = after;
+
+        if !self.sealed {
+            if let Some(last) = self.entries.last_mut() {
+                let end = if command.is_encoded() {
+                    last.offset + last.length
+                } else {
+                    last.offset
+                };
+                if last.command == command && last.spongos_after == before && end == offset {
+                    last.length += length;
+                    last.spongos_after = after;
+                    return;
+                }
+            }
+        }
+
+        self.sealed = false;
+        self.entries.push(Entry {
+            field: self.field.clone(),
+            command,
+            offset,
+            length,
+            spongos_before: before,
+            spongos_after: after,
+        });
+    }
+
+    /// Record a command which processed `length` bytes and did not touch Spongos state.
+    pub fn record_skip(&mut self, command: Command, length: usize) {
+        let state = self.state;
+        self.record(command, length, state, state);
+    }
+
+    /// Index of the first entry where `self` and `other` disagree, `None` if traces are equal.
+    pub fn diverges_at(&self, other: &Self) -> Option<usize> {
+        let n = core::cmp::min(self.entries.len(), other.entries.len());
+        (0..n)
+            .find(|&i| self.entries[i] != other.entries[i])
+            .or(if self.entries.len() == other.entries.len() {
+                None
+            } else {
+                Some(n)
+            })
+    }
+
+    /// Export trace as JSON array of entries.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, e) in self.entries.iter().enumerate() {
+            if i != 0 {
+                json.push(',');
+            }
+            json.push_str("{\"field\":");
+            push_json_str(&mut json, &e.field);
+            json.push_str(&format!(
+                ",\"command\":\"{}\",\"offset\":{},\"length\":{},\"spongos_before\":\"{}\",\"spongos_after\":\"{}\"}}",
+                e.command,
+                e.offset,
+                e.length,
+                hex::encode(e.spongos_before),
+                hex::encode(e.spongos_after)
+            ));
+        }
+        json.push(']');
+        json
+    }
+}
+
+fn push_json_str(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
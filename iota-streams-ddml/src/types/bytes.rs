@@ -12,13 +12,40 @@ use iota_streams_core::{
     rustversion,
 };
 
+use super::SharedBuffer;
+
 /// Variable-size array of bytes, the size is not known at compile time and is encoded in trinary representation.
+///
+/// Bytes are kept in a `SharedBuffer`, clones share the contents and unwrapping a message
+/// may return bytes sharing the buffer of the message.
+///
+/// The buffer is not exposed as a public field, `Bytes` are created with `From` conversions and
+/// read with `as_slice`, `into_vec` or `shared`.
 #[derive(Eq, Clone, Debug, Default)]
-pub struct Bytes(pub Vec<u8>);
+pub struct Bytes(pub(crate) SharedBuffer);
 
 impl Bytes {
     pub fn new() -> Self {
-        Self(Vec::new())
+        Self(SharedBuffer::new())
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    /// The buffer holding the contents, possibly shared with other `Bytes` or a message body.
+    pub fn shared(&self) -> &SharedBuffer {
+        &self.0
+    }
+
+    /// Mutable access to the contents, copying them first if they are shared.
+    pub fn to_mut(&mut self) -> &mut Vec<u8> {
+        self.0.to_mut()
+    }
+
+    /// Take the contents out, copying them if they are shared.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0.into_vec()
     }
 
     /// Attempts to convert the Bytes into a str
@@ -34,7 +61,7 @@ impl Bytes {
     /// If the Bytes are valid UTF8, this method returns `Some(String)`, otherwise returns `None`
     /// This is the owned alternative of the borrowed [`Bytes::as_str()`].
     pub fn into_string(self) -> Option<String> {
-        String::from_utf8(self.0).ok()
+        String::from_utf8(self.0.into_vec()).ok()
     }
 }
 
@@ -56,33 +83,27 @@ impl hash::Hash for Bytes {
     }
 }
 
-impl<'a> From<&'a Vec<u8>> for &'a Bytes {
-    fn from(v: &Vec<u8>) -> &Bytes {
-        unsafe { &*(v as *const Vec<u8> as *const Bytes) }
-    }
-}
-
-impl<'a> From<&'a mut Vec<u8>> for &'a mut Bytes {
-    fn from(v: &mut Vec<u8>) -> &mut Bytes {
-        unsafe { &mut *(v as *mut Vec<u8> as *mut Bytes) }
+impl From<SharedBuffer> for Bytes {
+    fn from(b: SharedBuffer) -> Self {
+        Self(b)
     }
 }
 
 impl From<Vec<u8>> for Bytes {
     fn from(v: Vec<u8>) -> Self {
-        Self(v)
+        Self(v.into())
     }
 }
 
 impl From<Bytes> for Vec<u8> {
     fn from(b: Bytes) -> Self {
-        b.0
+        b.0.into_vec()
     }
 }
 
 impl From<&[u8]> for Bytes {
     fn from(s: &[u8]) -> Self {
-        Self(s.to_vec())
+        Self(s.into())
     }
 }
 
@@ -90,7 +111,7 @@ impl From<&[u8]> for Bytes {
 #[rustversion::since(1.51)]
 impl<const N: usize> From<[u8; N]> for Bytes {
     fn from(v: [u8; N]) -> Self {
-        Self(v.to_vec())
+        Self(v.to_vec().into())
     }
 }
 
@@ -98,12 +119,12 @@ impl<const N: usize> From<[u8; N]> for Bytes {
 #[rustversion::since(1.51)]
 impl<const N: usize> From<&[u8; N]> for Bytes {
     fn from(v: &[u8; N]) -> Self {
-        Self(v.to_vec())
+        Self(v.to_vec().into())
     }
 }
 
 impl AsRef<[u8]> for Bytes {
     fn as_ref(&self) -> &[u8] {
-        self.0.as_slice()
+        self.as_slice()
     }
 }
@@ -0,0 +1,32 @@
+use iota_streams_core::prelude::BTreeMap;
+
+/// Length-prefixed map.
+///
+/// Encoded as the number of entries (`size_t`) followed by the key and the value of each entry, in ascending
+/// key order so that the encoding of a map is unique. Keys out of order are rejected when unwrapping.
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct Map<K, V>(pub BTreeMap<K, V>);
+
+impl<K: Ord, V> Map<K, V> {
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+}
+
+impl<K: Ord, V> Default for Map<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> From<BTreeMap<K, V>> for Map<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        Self(map)
+    }
+}
+
+impl<K, V> From<Map<K, V>> for BTreeMap<K, V> {
+    fn from(map: Map<K, V>) -> Self {
+        map.0
+    }
+}
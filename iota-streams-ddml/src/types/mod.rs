@@ -21,13 +21,23 @@ mod fallback;
 pub use fallback::*;
 mod hashsig;
 pub use hashsig::*;
+mod int;
+pub use int::*;
 mod mac;
 pub use mac::*;
+mod map;
+pub use map::*;
 mod nbytes;
 pub use nbytes::*;
-mod prehashed;
-pub(crate) use prehashed::*;
+mod oneof;
+pub(crate) use iota_streams_core_edsig::signature::ed25519::Prehashed;
+mod shared;
+pub use shared::*;
 mod size;
 pub use size::*;
+mod string;
+pub use string::*;
 mod uint;
 pub use uint::*;
+mod varint;
+pub use varint::*;
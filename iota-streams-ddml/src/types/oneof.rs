@@ -0,0 +1,126 @@
+/// Declare an enum encoded as a DDML `oneof`.
+///
+/// Every variant holds a single field and is identified by a `Uint8` tag. The tag is processed first, followed
+/// by the field, both with the same command (absorb, mask or skip). An unknown tag is rejected with `BadOneof`
+/// when unwrapping; the variant fields must implement `Default`. The enum can be nested into `Option` and `Map`.
+///
+/// ```
+/// use iota_streams_ddml::{
+///     oneof,
+///     types::{
+///         Int32,
+///         Str,
+///     },
+/// };
+///
+/// oneof! {
+///     #[derive(Clone, PartialEq, Debug)]
+///     pub enum Reading {
+///         0 => Text(Str),
+///         1 => Value(Int32),
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! oneof {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($tag:literal => $variant:ident($field:ty)),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant($field)),+
+        }
+
+        $crate::oneof!(@impl $name, Absorb, absorb, $($tag => $variant($field)),+);
+        $crate::oneof!(@impl $name, Mask, mask, $($tag => $variant($field)),+);
+        $crate::oneof!(@impl $name, Skip, skip, $($tag => $variant($field)),+);
+
+        impl<'a, C> $crate::command::Element<C> for &'a $name
+        where
+            C: $crate::command::Absorb<&'a $name> + $crate::command::Mask<&'a $name> + $crate::command::Skip<&'a $name>,
+        {
+            fn absorb(self, ctx: &mut C) -> $crate::__private::Result<&mut C> {
+                $crate::command::Absorb::absorb(ctx, self)
+            }
+            fn mask(self, ctx: &mut C) -> $crate::__private::Result<&mut C> {
+                $crate::command::Mask::mask(ctx, self)
+            }
+            fn skip(self, ctx: &mut C) -> $crate::__private::Result<&mut C> {
+                $crate::command::Skip::skip(ctx, self)
+            }
+        }
+
+        impl<'a, C> $crate::command::Element<C> for &'a mut $name
+        where
+            C: $crate::command::Absorb<&'a mut $name>
+                + $crate::command::Mask<&'a mut $name>
+                + $crate::command::Skip<&'a mut $name>,
+        {
+            fn absorb(self, ctx: &mut C) -> $crate::__private::Result<&mut C> {
+                $crate::command::Absorb::absorb(ctx, self)
+            }
+            fn mask(self, ctx: &mut C) -> $crate::__private::Result<&mut C> {
+                $crate::command::Mask::mask(ctx, self)
+            }
+            fn skip(self, ctx: &mut C) -> $crate::__private::Result<&mut C> {
+                $crate::command::Skip::skip(ctx, self)
+            }
+        }
+    };
+
+    (@impl $name:ident, $trait:ident, $method:ident, $($tag:literal => $variant:ident($field:ty)),+) => {
+        impl<'a, F> $crate::command::$trait<&'a $name> for $crate::command::sizeof::Context<F>
+        where
+            Self: for<'b> $crate::command::$trait<&'b $crate::types::Uint8>,
+            $(Self: $crate::command::$trait<&'a $field>,)+
+        {
+            fn $method(&mut self, oneof: &'a $name) -> $crate::__private::Result<&mut Self> {
+                match oneof {
+                    $($name::$variant(field) => {
+                        let ctx = $crate::command::$trait::$method(self, &$crate::types::Uint8($tag))?;
+                        $crate::command::$trait::$method(ctx, field)
+                    })+
+                }
+            }
+        }
+
+        impl<'a, F, OS: $crate::io::OStream> $crate::command::$trait<&'a $name> for $crate::command::wrap::Context<F, OS>
+        where
+            Self: for<'b> $crate::command::$trait<&'b $crate::types::Uint8>,
+            $(Self: $crate::command::$trait<&'a $field>,)+
+        {
+            fn $method(&mut self, oneof: &'a $name) -> $crate::__private::Result<&mut Self> {
+                match oneof {
+                    $($name::$variant(field) => {
+                        let ctx = $crate::command::$trait::$method(self, &$crate::types::Uint8($tag))?;
+                        $crate::command::$trait::$method(ctx, field)
+                    })+
+                }
+            }
+        }
+
+        impl<'a, F, IS: $crate::io::IStream> $crate::command::$trait<&'a mut $name>
+            for $crate::command::unwrap::Context<F, IS>
+        where
+            Self: for<'b> $crate::command::$trait<&'b mut $crate::types::Uint8>,
+            $(Self: for<'b> $crate::command::$trait<&'b mut $field>,)+
+        {
+            fn $method(&mut self, oneof: &'a mut $name) -> $crate::__private::Result<&mut Self> {
+                let mut tag = $crate::types::Uint8(0);
+                $crate::command::$trait::$method(self, &mut tag)?;
+                match tag.0 {
+                    $($tag => {
+                        let mut field = <$field as ::core::default::Default>::default();
+                        $crate::command::$trait::$method(self, &mut field)?;
+                        *oneof = $name::$variant(field);
+                    })+
+                    _ => return $crate::__private::err($crate::__private::Errors::BadOneof),
+                }
+                Ok(self)
+            }
+        }
+    };
+}
@@ -0,0 +1,186 @@
+use core::{
+    borrow::Borrow,
+    cmp,
+    fmt,
+    hash,
+    ops::{
+        Bound,
+        Deref,
+        RangeBounds,
+    },
+};
+
+use iota_streams_core::prelude::{
+    Arc,
+    Vec,
+};
+
+/// Reference-counted byte buffer.
+///
+/// Clones and slices share the underlying allocation, the contents are only copied when
+/// modified through `to_mut` while shared.
+#[derive(Clone, Default)]
+pub struct SharedBuffer {
+    buf: Arc<Vec<u8>>,
+    // `None` covers the whole buffer, `to_mut` may change its length.
+    range: Option<(usize, usize)>,
+}
+
+impl SharedBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        match self.range {
+            None => &self.buf[..],
+            Some((start, end)) => &self.buf[start..end],
+        }
+    }
+
+    /// Part of the buffer sharing the allocation with `self`.
+    ///
+    /// Panics if `range` is out of bounds, as slicing does.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+        assert!(
+            start <= end && end <= len,
+            "range {}..{} out of bounds of {}",
+            start,
+            end,
+            len
+        );
+        let offset = self.range.map_or(0, |(start, _)| start);
+        Self {
+            buf: self.buf.clone(),
+            range: Some((offset + start, offset + end)),
+        }
+    }
+
+    /// Whether `self` and `other` share the allocation.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.buf, &other.buf)
+    }
+
+    /// Mutable access to the contents, copying them first if the buffer is shared.
+    pub fn to_mut(&mut self) -> &mut Vec<u8> {
+        if self.range.is_some() || Arc::get_mut(&mut self.buf).is_none() {
+            *self = Self::from(self.as_slice().to_vec());
+        }
+        Arc::get_mut(&mut self.buf).expect("buffer is not shared")
+    }
+
+    /// Take the contents out of the buffer, copying them if the buffer is shared.
+    pub fn into_vec(self) -> Vec<u8> {
+        match self.range {
+            None => Arc::try_unwrap(self.buf).unwrap_or_else(|buf| buf.to_vec()),
+            Some((start, end)) => self.buf[start..end].to_vec(),
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.as_slice().to_vec()
+    }
+}
+
+impl Deref for SharedBuffer {
+    type Target = [u8];
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for SharedBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Borrow<[u8]> for SharedBuffer {
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl fmt::Debug for SharedBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl PartialEq for SharedBuffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for SharedBuffer {}
+
+impl PartialEq<[u8]> for SharedBuffer {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl PartialEq<Vec<u8>> for SharedBuffer {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        self.as_slice() == &other[..]
+    }
+}
+
+impl PartialOrd for SharedBuffer {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SharedBuffer {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl hash::Hash for SharedBuffer {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl From<Vec<u8>> for SharedBuffer {
+    fn from(v: Vec<u8>) -> Self {
+        Self {
+            buf: Arc::new(v),
+            range: None,
+        }
+    }
+}
+
+impl From<&[u8]> for SharedBuffer {
+    fn from(s: &[u8]) -> Self {
+        Self::from(s.to_vec())
+    }
+}
+
+impl From<SharedBuffer> for Vec<u8> {
+    fn from(b: SharedBuffer) -> Self {
+        b.into_vec()
+    }
+}
@@ -0,0 +1,47 @@
+use core::fmt;
+
+use iota_streams_core::prelude::String;
+
+/// UTF-8 string, encoded as `Bytes`. Invalid UTF-8 is rejected when unwrapping.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Default, Hash)]
+pub struct Str(pub String);
+
+impl Str {
+    pub fn new() -> Self {
+        Self(String::new())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Str {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for Str {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl From<&str> for Str {
+    fn from(s: &str) -> Self {
+        Self(s.into())
+    }
+}
+
+impl From<Str> for String {
+    fn from(s: Str) -> Self {
+        s.0
+    }
+}
+
+impl AsRef<str> for Str {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
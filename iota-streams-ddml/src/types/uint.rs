@@ -1,6 +1,6 @@
 use core::fmt;
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
 pub struct Uint8(pub u8);
 
 impl fmt::Display for Uint8 {
@@ -9,7 +9,7 @@ impl fmt::Display for Uint8 {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
 pub struct Uint16(pub u16);
 
 impl fmt::Display for Uint16 {
@@ -18,7 +18,7 @@ impl fmt::Display for Uint16 {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
 pub struct Uint32(pub u32);
 
 impl fmt::Display for Uint32 {
@@ -27,7 +27,7 @@ impl fmt::Display for Uint32 {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
 pub struct Uint64(pub u64);
 
 impl fmt::Display for Uint64 {
@@ -0,0 +1,43 @@
+use core::fmt;
+
+/// Unsigned variable-size integer.
+///
+/// Encoded as `size_t`: the number of significant bytes as `Uint8` followed by these bytes in big-endian order.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug, Default, Hash)]
+pub struct Varuint(pub u64);
+
+/// Signed variable-size integer.
+///
+/// The value is zigzag-encoded (`0, -1, 1, -2, ...` map to `0, 1, 2, 3, ...`) and then encoded as `Varuint`, so
+/// that values of small magnitude take few bytes regardless of their sign.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug, Default, Hash)]
+pub struct Varint(pub i64);
+
+/// Number of bytes needed to encode the value of a `Varuint`, not including the leading size byte.
+pub fn varuint_bytes(n: u64) -> usize {
+    8 - n.leading_zeros() as usize / 8
+}
+
+impl From<Varint> for Varuint {
+    fn from(i: Varint) -> Self {
+        Self(((i.0 << 1) ^ (i.0 >> 63)) as u64)
+    }
+}
+
+impl From<Varuint> for Varint {
+    fn from(u: Varuint) -> Self {
+        Self((u.0 >> 1) as i64 ^ -((u.0 & 1) as i64))
+    }
+}
+
+impl fmt::Display for Varuint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Varint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
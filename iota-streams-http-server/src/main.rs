@@ -0,0 +1,29 @@
+//! Reference server of the Streams HTTP message protocol, storing messages in memory.
+//!
+//! ```text
+//! iota-streams-http-server [ADDRESS]
+//! ```
+//!
+//! The server listens on `127.0.0.1:8080` unless another address is given.
+
+use std::net::SocketAddr;
+
+use iota_streams_app::transport::tangle::http::server::{
+    bind,
+    MessageStore,
+};
+use iota_streams_core::{
+    anyhow,
+    Result,
+};
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let addr = std::env::args().nth(1).unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+    let addr: SocketAddr = addr.parse().map_err(|e| anyhow!("invalid address '{}': {}", addr, e))?;
+    let (addr, server) = bind(&addr, MessageStore::new())?;
+    println!("Serving Streams messages on http://{}", addr);
+    server.await
+}